@@ -27,6 +27,12 @@ fn main() {
         println!("cargo:rustc-cfg=no_relaxed_trait_bounds");
     }
 
+    // Min const generics stabilized in Rust 1.51:
+    // https://blog.rust-lang.org/2021/03/25/Rust-1.51.0.html#const-generics-mvp
+    if minor < 51 {
+        println!("cargo:rustc-cfg=no_min_const_generics");
+    }
+
     // Current minimum supported version of serde_derive crate is Rust 1.56.
     if minor < 56 {
         println!("cargo:rustc-cfg=no_serde_derive");
@@ -58,6 +64,30 @@ fn main() {
     if minor < 64 {
         println!("cargo:rustc-cfg=no_core_cstr");
     }
+
+    // std::backtrace::Backtrace stabilized in Rust 1.65.
+    // https://blog.rust-lang.org/2022/11/03/Rust-1.65.0.html#stabilizations
+    if minor < 65 {
+        println!("cargo:rustc-cfg=no_std_backtrace");
+    }
+
+    // core::cell::OnceCell and std::sync::OnceLock stabilized in Rust 1.70.
+    // https://blog.rust-lang.org/2023/06/01/Rust-1.70.0.html
+    if minor < 70 {
+        println!("cargo:rustc-cfg=no_once_cell");
+    }
+
+    // core::num::Saturating stabilized in Rust 1.74.
+    // https://blog.rust-lang.org/2023/11/16/Rust-1.74.0.html#stabilized-apis
+    if minor < 74 {
+        println!("cargo:rustc-cfg=no_num_saturating");
+    }
+
+    // IpAddr, SocketAddr and friends stabilized in core::net in Rust 1.77.
+    // https://blog.rust-lang.org/2024/03/21/Rust-1.77.0.html#stabilized-apis
+    if minor < 77 {
+        println!("cargo:rustc-cfg=no_core_net");
+    }
 }
 
 fn rustc_minor_version() -> Option<u32> {
@@ -0,0 +1,270 @@
+//! Wrapper types which serialize byte data using `serialize_bytes` and
+//! `deserialize_bytes` / `deserialize_byte_buf` rather than as a sequence of
+//! individual integers.
+//!
+//! Without a wrapper, `&[u8]` and `Vec<u8>` go through the generic slice and
+//! sequence impls, which self-describing formats render as an array of
+//! numbers. Binary formats can store a [`Bytes`] or [`ByteBuf`] as one
+//! contiguous byte string instead.
+//!
+//! ```edition2021
+//! use serde::bytes::{ByteBuf, Bytes};
+//! use serde_derive::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Packet<'a> {
+//!     #[serde(borrow)]
+//!     header: &'a Bytes,
+//!     payload: ByteBuf,
+//! }
+//! ```
+
+use crate::lib::*;
+
+use crate::de::{Deserialize, Deserializer, Error, Visitor};
+use crate::ser::{Serialize, Serializer};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::de::{size_hint, SeqAccess};
+
+/// Wrapper around `[u8]` to serialize and deserialize as a compact byte
+/// string.
+#[derive(Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[repr(transparent)]
+pub struct Bytes {
+    bytes: [u8],
+}
+
+impl Bytes {
+    /// Wrap an existing `&[u8]`.
+    pub fn new(bytes: &[u8]) -> &Bytes {
+        unsafe { &*(bytes as *const [u8] as *const Bytes) }
+    }
+}
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.bytes, formatter)
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<'a> From<&'a [u8]> for &'a Bytes {
+    fn from(bytes: &'a [u8]) -> Self {
+        Bytes::new(bytes)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a Bytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = &'de Bytes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a borrowed byte array")
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(Bytes::new(v))
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(Bytes::new(v.as_bytes()))
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+/// Wrapper around `Vec<u8>` to serialize and deserialize as a compact byte
+/// string.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Clone, Default, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub struct ByteBuf {
+    bytes: Vec<u8>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl ByteBuf {
+    /// Construct a new, empty `ByteBuf`.
+    pub fn new() -> Self {
+        ByteBuf { bytes: Vec::new() }
+    }
+
+    /// Wrap existing bytes in a `ByteBuf`.
+    pub fn from<T: Into<Vec<u8>>>(bytes: T) -> Self {
+        ByteBuf {
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Unwrap the vector of bytes underlying this `ByteBuf`.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl fmt::Debug for ByteBuf {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.bytes, formatter)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl AsRef<[u8]> for ByteBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl AsMut<[u8]> for ByteBuf {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Deref for ByteBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl DerefMut for ByteBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl From<Vec<u8>> for ByteBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        ByteBuf { bytes }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl From<ByteBuf> for Vec<u8> {
+    fn from(buf: ByteBuf) -> Self {
+        buf.bytes
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Borrow<Bytes> for ByteBuf {
+    fn borrow(&self) -> &Bytes {
+        Bytes::new(&self.bytes)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+struct ByteBufVisitor;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let capacity = size_hint::cautious::<u8>(seq.size_hint());
+        let mut bytes = Vec::with_capacity(capacity);
+
+        tri!(seq.next_elements(&mut bytes));
+
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(v.as_bytes().to_vec())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(v.into_bytes())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = tri!(deserializer.deserialize_byte_buf(ByteBufVisitor));
+        Ok(ByteBuf { bytes })
+    }
+}
@@ -0,0 +1,105 @@
+//! Capability flags describing what a data format can represent natively.
+
+/// What a data format can represent natively.
+///
+/// `is_human_readable` alone is too coarse for impls that want to pick the
+/// best representation: a binary format may still lack raw byte support, and
+/// a human-readable one may allow arbitrary map keys. `Capabilities` carries
+/// one flag per representational question so that `Serialize` and
+/// `Deserialize` implementations can ask precisely.
+///
+/// Formats report their capabilities from [`Serializer::capabilities`] and
+/// [`Deserializer::capabilities`], typically by starting from the defaults
+/// and overriding individual flags:
+///
+/// ```edition2021
+/// use serde::ser::Capabilities;
+///
+/// // A binary format with first-class byte strings but string-only keys.
+/// let caps = Capabilities::default()
+///     .with_raw_bytes(true)
+///     .with_non_string_keys(false);
+/// assert!(caps.raw_bytes());
+/// ```
+///
+/// The two sides of a format must report the same capabilities, since a value
+/// serialized under one set of flags is not required to deserialize under
+/// another.
+///
+/// [`Serializer::capabilities`]: crate::Serializer::capabilities
+/// [`Deserializer::capabilities`]: crate::Deserializer::capabilities
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    raw_bytes: bool,
+    non_string_keys: bool,
+    preserves_integer_width: bool,
+    native_enums: bool,
+}
+
+impl Default for Capabilities {
+    /// The profile of a typical self-describing format like JSON: no raw
+    /// bytes, string-only map keys, integer widths collapsed, no native enum
+    /// representation.
+    fn default() -> Self {
+        Capabilities {
+            raw_bytes: false,
+            non_string_keys: false,
+            preserves_integer_width: false,
+            native_enums: false,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Whether byte arrays are a first-class type, rather than being encoded
+    /// as sequences of integers or base64 strings.
+    pub fn raw_bytes(&self) -> bool {
+        self.raw_bytes
+    }
+
+    /// Returns a copy with the [`raw_bytes`](Capabilities::raw_bytes) flag
+    /// replaced.
+    pub fn with_raw_bytes(mut self, raw_bytes: bool) -> Self {
+        self.raw_bytes = raw_bytes;
+        self
+    }
+
+    /// Whether map keys may be values other than strings.
+    pub fn non_string_keys(&self) -> bool {
+        self.non_string_keys
+    }
+
+    /// Returns a copy with the
+    /// [`non_string_keys`](Capabilities::non_string_keys) flag replaced.
+    pub fn with_non_string_keys(mut self, non_string_keys: bool) -> Self {
+        self.non_string_keys = non_string_keys;
+        self
+    }
+
+    /// Whether the width and signedness of integers survive a roundtrip, as
+    /// opposed to every number collapsing into one numeric type.
+    pub fn preserves_integer_width(&self) -> bool {
+        self.preserves_integer_width
+    }
+
+    /// Returns a copy with the
+    /// [`preserves_integer_width`](Capabilities::preserves_integer_width)
+    /// flag replaced.
+    pub fn with_preserves_integer_width(mut self, preserves_integer_width: bool) -> Self {
+        self.preserves_integer_width = preserves_integer_width;
+        self
+    }
+
+    /// Whether enums have a native representation, so that variants do not
+    /// need to be encoded through the externally tagged map convention.
+    pub fn native_enums(&self) -> bool {
+        self.native_enums
+    }
+
+    /// Returns a copy with the
+    /// [`native_enums`](Capabilities::native_enums) flag replaced.
+    pub fn with_native_enums(mut self, native_enums: bool) -> Self {
+        self.native_enums = native_enums;
+        self
+    }
+}
@@ -0,0 +1,203 @@
+//! Deserialization directly into a caller-chosen allocator.
+//!
+//! The [`Deserialize`] trait offers no way to thread an allocator through to
+//! the collections it builds, so they always come from the global heap. The
+//! seeds in this module carry an allocator handle instead, mirroring the
+//! `new_in` constructors in std, which lets high-throughput parsers
+//! deserialize strings, vecs, and maps straight into a bump arena and free
+//! them all at once.
+//!
+//! The element and value types are still deserialized through their ordinary
+//! [`Deserialize`] impls, so nested collections allocate from the global heap
+//! unless their allocator parameter is named explicitly and implements
+//! `Default`.
+//!
+//! Requires the `unstable` feature and a nightly compiler, since the
+//! `Allocator` trait is not stable yet.
+//!
+//! ```edition2021
+//! #![feature(allocator_api)]
+//!
+//! use serde::de::arena::VecSeed;
+//! use serde::de::value::{Error, SeqDeserializer};
+//! use serde::de::DeserializeSeed;
+//! use std::alloc::Global;
+//!
+//! let deserializer = SeqDeserializer::<_, Error>::new([1u32, 2, 3].into_iter());
+//! let values: Vec<u32, Global> = VecSeed::new_in(Global).deserialize(deserializer).unwrap();
+//! assert_eq!(values, [1, 2, 3]);
+//! ```
+
+use crate::lib::*;
+
+use crate::de::size_hint;
+use crate::de::{Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+/// `DeserializeSeed` that produces a `Vec` allocated inside the given
+/// allocator.
+pub struct VecSeed<T, A: Allocator> {
+    alloc: A,
+    marker: PhantomData<T>,
+}
+
+impl<T, A: Allocator> VecSeed<T, A> {
+    /// Creates a seed that deserializes a sequence of `T` into `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        VecSeed {
+            alloc,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, A> DeserializeSeed<'de> for VecSeed<T, A>
+where
+    T: Deserialize<'de>,
+    A: Allocator,
+{
+    type Value = Vec<T, A>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T, A> Visitor<'de> for VecSeed<T, A>
+where
+    T: Deserialize<'de>,
+    A: Allocator,
+{
+    type Value = Vec<T, A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let capacity = size_hint::cautious::<T>(seq.size_hint());
+        let mut values = Vec::with_capacity_in(capacity, self.alloc);
+
+        while let Some(value) = tri!(seq.next_element()) {
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+}
+
+/// `DeserializeSeed` that produces a `BTreeMap` allocated inside the given
+/// allocator.
+pub struct BTreeMapSeed<K, V, A: Allocator + Clone> {
+    alloc: A,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, A: Allocator + Clone> BTreeMapSeed<K, V, A> {
+    /// Creates a seed that deserializes a map of `K` to `V` into `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        BTreeMapSeed {
+            alloc,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, K, V, A> DeserializeSeed<'de> for BTreeMapSeed<K, V, A>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+    A: Allocator + Clone,
+{
+    type Value = BTreeMap<K, V, A>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K, V, A> Visitor<'de> for BTreeMapSeed<K, V, A>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+    A: Allocator + Clone,
+{
+    type Value = BTreeMap<K, V, A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut values = BTreeMap::new_in(self.alloc);
+
+        while let Some((key, value)) = tri!(map.next_entry()) {
+            values.insert(key, value);
+        }
+
+        Ok(values)
+    }
+}
+
+/// `DeserializeSeed` that copies a string into the given allocator, producing
+/// a `Box<str, A>`.
+///
+/// `String` has no allocator parameter yet, so a boxed str is the closest
+/// owned string representation a custom allocator can hold.
+pub struct BoxedStrSeed<A: Allocator> {
+    alloc: A,
+}
+
+impl<A: Allocator> BoxedStrSeed<A> {
+    /// Creates a seed that deserializes a string into `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        BoxedStrSeed { alloc }
+    }
+}
+
+impl<'de, A> DeserializeSeed<'de> for BoxedStrSeed<A>
+where
+    A: Allocator,
+{
+    type Value = Box<str, A>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, A> Visitor<'de> for BoxedStrSeed<A>
+where
+    A: Allocator,
+{
+    type Value = Box<str, A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        let mut bytes = Vec::with_capacity_in(value.len(), self.alloc);
+        bytes.extend_from_slice(value.as_bytes());
+        let (ptr, alloc) = Box::into_raw_with_allocator(bytes.into_boxed_slice());
+        // Sound: the bytes are an exact copy of `value`, which is valid UTF-8.
+        Ok(unsafe { Box::from_raw_in(ptr as *mut str, alloc) })
+    }
+}
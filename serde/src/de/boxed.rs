@@ -0,0 +1,67 @@
+use crate::lib::convert::TryFrom;
+use crate::lib::*;
+
+use crate::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+
+/// Deserializes a `Box<[T; N]>` directly on the heap.
+///
+/// The blanket `Box<T>` impl deserializes the inner value on the stack and
+/// then moves it into the box, which overflows the stack for boxed
+/// multi-megabyte arrays. This function instead collects the elements into a
+/// heap allocation and converts it in place, so the array never exists on the
+/// stack. Use it through `deserialize_with`:
+///
+/// ```edition2021
+/// # use serde_derive::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Image {
+///     #[serde(deserialize_with = "serde::de::boxed_array")]
+///     pixels: Box<[u8; 1 << 20]>,
+/// }
+/// ```
+///
+/// The wire format is the same as for `[T; N]`.
+pub fn boxed_array<'de, D, T, const N: usize>(deserializer: D) -> Result<Box<[T; N]>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct BoxedArrayVisitor<T, const N: usize> {
+        marker: PhantomData<T>,
+    }
+
+    impl<'de, T, const N: usize> Visitor<'de> for BoxedArrayVisitor<T, N>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = Box<[T; N]>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an array of length {}", N)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut elements = Vec::with_capacity(N);
+            for idx in 0..N {
+                match tri!(seq.next_element()) {
+                    Some(element) => elements.push(element),
+                    None => return Err(Error::invalid_length(idx, &self)),
+                }
+            }
+            match <Box<[T; N]>>::try_from(elements.into_boxed_slice()) {
+                Ok(array) => Ok(array),
+                Err(_) => Err(Error::invalid_length(N, &self)),
+            }
+        }
+    }
+
+    deserializer.deserialize_tuple(
+        N,
+        BoxedArrayVisitor {
+            marker: PhantomData,
+        },
+    )
+}
@@ -0,0 +1,163 @@
+//! The collection protocol behind `#[serde(flatten)]` catch-all fields.
+//!
+//! When a struct contains a flattened map field, every entry that was not
+//! claimed by one of the other fields is handed to that field in the form of
+//! a self-describing map. Any type whose `Deserialize` impl accepts a map —
+//! `HashMap`, `BTreeMap`, `serde_json::Map` and the like — therefore works as
+//! a catch-all out of the box; there is no other hidden contract between the
+//! derive and the field type.
+//!
+//! Deserializing into such a map drops the order of the entries and keeps
+//! only one value per key. To capture the leftovers losslessly, target
+//! `Vec<(K, V)>` through the [`collect`] entry point instead; it records
+//! every entry in input order, duplicates included:
+//!
+//! ```edition2021
+//! # use serde_derive::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Packet {
+//!     id: u64,
+//!     #[serde(flatten, deserialize_with = "serde::de::flatten::collect")]
+//!     headers: Vec<(String, String)>,
+//! }
+//! ```
+//!
+//! Types that cannot reasonably implement `Deserialize` that way, such as
+//! ordered or small-vec maps with bespoke wire formats, can implement
+//! [`FlattenCollect`] instead and opt in through `deserialize_with`:
+//!
+//! ```edition2021
+//! # use serde_derive::Deserialize;
+//! use serde::de::flatten::FlattenCollect;
+//! use serde::de::MapAccess;
+//!
+//! struct OrderedExtras(Vec<(String, u32)>);
+//!
+//! impl<'de> FlattenCollect<'de> for OrderedExtras {
+//!     fn flatten_collect<A>(mut map: A) -> Result<Self, A::Error>
+//!     where
+//!         A: MapAccess<'de>,
+//!     {
+//!         let mut entries = Vec::new();
+//!         while let Some(entry) = map.next_entry()? {
+//!             entries.push(entry);
+//!         }
+//!         Ok(OrderedExtras(entries))
+//!     }
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Record {
+//!     id: u64,
+//!     #[serde(flatten, deserialize_with = "serde::de::flatten::collect")]
+//!     extra: OrderedExtras,
+//! }
+//! ```
+
+use crate::lib::*;
+
+use crate::de::{Deserialize, Deserializer, MapAccess, Visitor};
+
+/// A collection that can be built from the leftover entries of a flattened
+/// map.
+///
+/// The entries arrive in the order the format produced them, minus the keys
+/// claimed by named fields. Keys are usually strings but can be any type the
+/// format supports as a map key.
+pub trait FlattenCollect<'de>: Sized {
+    /// Builds the collection by draining the remaining map entries.
+    fn flatten_collect<A>(map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>;
+}
+
+/// Deserializes a [`FlattenCollect`] collection from a map.
+///
+/// This is the `deserialize_with` entry point pairing a flattened field with
+/// its `FlattenCollect` impl, as in the module-level example.
+pub fn collect<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FlattenCollect<'de>,
+{
+    struct CollectVisitor<T> {
+        marker: PhantomData<T>,
+    }
+
+    impl<'de, T> Visitor<'de> for CollectVisitor<T>
+    where
+        T: FlattenCollect<'de>,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map of the remaining fields")
+        }
+
+        fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            T::flatten_collect(map)
+        }
+    }
+
+    deserializer.deserialize_map(CollectVisitor {
+        marker: PhantomData,
+    })
+}
+
+/// Keeps every leftover entry in input order, including entries whose key
+/// occurred more than once.
+impl<'de, K, V> FlattenCollect<'de> for Vec<(K, V)>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn flatten_collect<A>(mut map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(entry) = tri!(map.next_entry()) {
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+impl<'de, K, V> FlattenCollect<'de> for BTreeMap<K, V>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+{
+    fn flatten_collect<A>(mut map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = BTreeMap::new();
+        while let Some((key, value)) = tri!(map.next_entry()) {
+            entries.insert(key, value);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, K, V, S> FlattenCollect<'de> for HashMap<K, V, S>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn flatten_collect<A>(mut map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = HashMap::with_hasher(S::default());
+        while let Some((key, value)) = tri!(map.next_entry()) {
+            entries.insert(key, value);
+        }
+        Ok(entries)
+    }
+}
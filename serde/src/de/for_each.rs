@@ -0,0 +1,161 @@
+use crate::lib::*;
+
+use crate::de::{Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+/// `DeserializeSeed` that invokes a callback for each element of a sequence
+/// as it is parsed, without collecting the sequence into memory.
+///
+/// This makes it possible to process huge arrays in constant memory, even
+/// when they are embedded inside an otherwise-derived struct: wire the seed
+/// up through a `deserialize_with` function that folds the elements into
+/// whatever summary the field actually stores.
+///
+/// The value produced by the seed is the number of elements processed.
+///
+/// ```edition2021
+/// use serde::de::value::{Error, SeqDeserializer};
+/// use serde::de::{DeserializeSeed, ForEach};
+///
+/// let deserializer = SeqDeserializer::<_, Error>::new([1u64, 2, 3].into_iter());
+///
+/// let mut sum = 0;
+/// let count = ForEach::new(|value: u64| sum += value)
+///     .deserialize(deserializer)
+///     .unwrap();
+/// assert_eq!(sum, 6);
+/// assert_eq!(count, 3);
+/// ```
+pub struct ForEach<T, F> {
+    f: F,
+    marker: PhantomData<T>,
+}
+
+impl<T, F> ForEach<T, F> {
+    /// Creates a seed that calls `f` on every element of a sequence.
+    pub fn new(f: F) -> Self
+    where
+        F: FnMut(T),
+    {
+        ForEach {
+            f,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, F> DeserializeSeed<'de> for ForEach<T, F>
+where
+    T: Deserialize<'de>,
+    F: FnMut(T),
+{
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T, F> Visitor<'de> for ForEach<T, F>
+where
+    T: Deserialize<'de>,
+    F: FnMut(T),
+{
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut count = 0;
+        while let Some(value) = tri!(seq.next_element()) {
+            (self.f)(value);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// `DeserializeSeed` that invokes a callback for each entry of a map as it is
+/// parsed, without collecting the map into memory.
+///
+/// The map-entry counterpart of [`ForEach`]. The value produced by the seed
+/// is the number of entries processed.
+///
+/// ```edition2021
+/// use serde::de::value::{Error, MapDeserializer};
+/// use serde::de::{DeserializeSeed, ForEachEntry};
+///
+/// let entries = [("one", 1u64), ("three", 3)];
+/// let deserializer = MapDeserializer::<_, Error>::new(entries.into_iter());
+///
+/// let mut total = 0;
+/// ForEachEntry::new(|key: String, value: u64| total += key.len() as u64 * value)
+///     .deserialize(deserializer)
+///     .unwrap();
+/// assert_eq!(total, 18);
+/// ```
+pub struct ForEachEntry<K, V, F> {
+    f: F,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, F> ForEachEntry<K, V, F> {
+    /// Creates a seed that calls `f` on every entry of a map.
+    pub fn new(f: F) -> Self
+    where
+        F: FnMut(K, V),
+    {
+        ForEachEntry {
+            f,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, K, V, F> DeserializeSeed<'de> for ForEachEntry<K, V, F>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V),
+{
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, K, V, F> Visitor<'de> for ForEachEntry<K, V, F>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V),
+{
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut count = 0;
+        while let Some((key, value)) = tri!(map.next_entry()) {
+            (self.f)(key, value);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
@@ -364,6 +364,24 @@ impl_deserialize_num! {
     num_as_self!(u8:visit_u8 u16:visit_u16 u32:visit_u32 u64:visit_u64);
 }
 
+#[cfg(feature = "unstable")]
+impl_deserialize_num! {
+    f16, deserialize_f16
+    num_self!(f16:visit_f16);
+    num_as_self!(f32:visit_f32 f64:visit_f64);
+    num_as_self!(i8:visit_i8 i16:visit_i16 i32:visit_i32 i64:visit_i64);
+    num_as_self!(u8:visit_u8 u16:visit_u16 u32:visit_u32 u64:visit_u64);
+}
+
+#[cfg(feature = "unstable")]
+impl_deserialize_num! {
+    f128, deserialize_f128
+    num_self!(f128:visit_f128);
+    num_as_self!(f32:visit_f32 f64:visit_f64);
+    num_as_self!(i8:visit_i8 i16:visit_i16 i32:visit_i32 i64:visit_i64);
+    num_as_self!(u8:visit_u8 u16:visit_u16 u32:visit_u32 u64:visit_u64);
+}
+
 macro_rules! num_128 {
     ($ty:ident : $visit:ident) => {
         fn $visit<E>(self, v: $ty) -> Result<Self::Value, E>
@@ -441,6 +459,29 @@ impl<'de> Visitor<'de> for CharVisitor {
         Ok(v)
     }
 
+    #[inline]
+    fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => Err(Error::invalid_value(Unexpected::Unsigned(v as u64), &self)),
+        }
+    }
+
+    #[inline]
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        if v <= u32::max_value() as u64 {
+            self.visit_u32(v as u32)
+        } else {
+            Err(Error::invalid_value(Unexpected::Unsigned(v), &self))
+        }
+    }
+
     #[inline]
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
@@ -483,7 +524,9 @@ impl<'de> Visitor<'de> for StringVisitor {
     where
         E: Error,
     {
-        Ok(v.to_owned())
+        let mut string = tri!(with_capacity::<String, E>(v.len()));
+        string.push_str(v);
+        Ok(string)
     }
 
     fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
@@ -498,7 +541,7 @@ impl<'de> Visitor<'de> for StringVisitor {
         E: Error,
     {
         match str::from_utf8(v) {
-            Ok(s) => Ok(s.to_owned()),
+            Ok(s) => self.visit_str(s),
             Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
         }
     }
@@ -530,6 +573,7 @@ impl<'a, 'de> Visitor<'de> for StringInPlaceVisitor<'a> {
         E: Error,
     {
         self.0.clear();
+        tri!(reserve(self.0, v.len()));
         self.0.push_str(v);
         Ok(())
     }
@@ -549,6 +593,7 @@ impl<'a, 'de> Visitor<'de> for StringInPlaceVisitor<'a> {
         match str::from_utf8(v) {
             Ok(s) => {
                 self.0.clear();
+                tri!(reserve(self.0, s.len()));
                 self.0.push_str(s);
                 Ok(())
             }
@@ -678,7 +723,7 @@ impl<'de> Visitor<'de> for CStringVisitor {
         A: SeqAccess<'de>,
     {
         let capacity = size_hint::cautious::<u8>(seq.size_hint());
-        let mut values = Vec::<u8>::with_capacity(capacity);
+        let mut values: Vec<u8> = tri!(with_capacity(capacity));
 
         while let Some(value) = tri!(seq.next_element()) {
             values.push(value);
@@ -928,7 +973,7 @@ macro_rules! seq_impl {
                         A: SeqAccess<'de>,
                     {
                         $clear(&mut self.0);
-                        $reserve(&mut self.0, size_hint::cautious::<T>($access.size_hint()));
+                        tri!($reserve(&mut *self.0, size_hint::cautious::<T>($access.size_hint())));
 
                         // FIXME: try to overwrite old values here? (Vec, VecDeque, LinkedList)
                         while let Some(value) = tri!($access.next_element()) {
@@ -945,17 +990,105 @@ macro_rules! seq_impl {
     }
 }
 
+// Capacity helpers for the String and collection impls. With the
+// `fallible-alloc` feature enabled, growth goes through `try_reserve` and
+// allocation failure surfaces as `Error::custom` instead of aborting the
+// process.
+#[cfg(any(feature = "std", feature = "alloc"))]
+trait Reserve {
+    #[cfg(not(feature = "fallible-alloc"))]
+    fn reserve(&mut self, additional: usize);
+    #[cfg(feature = "fallible-alloc")]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>;
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+macro_rules! reserve_impl {
+    ($($ty:ident <$($typaram:ident $(: $bound1:ident $(+ $bound2:ident)*)*),*>,)+) => {
+        $(
+            impl<$($typaram $(: $bound1 $(+ $bound2)*)*),*> Reserve for $ty<$($typaram),*> {
+                #[cfg(not(feature = "fallible-alloc"))]
+                fn reserve(&mut self, additional: usize) {
+                    $ty::reserve(self, additional);
+                }
+
+                #[cfg(feature = "fallible-alloc")]
+                fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+                    $ty::try_reserve(self, additional)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Reserve for String {
+    #[cfg(not(feature = "fallible-alloc"))]
+    fn reserve(&mut self, additional: usize) {
+        String::reserve(self, additional);
+    }
+
+    #[cfg(feature = "fallible-alloc")]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        String::try_reserve(self, additional)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+reserve_impl! {
+    Vec<T>,
+    VecDeque<T>,
+    BinaryHeap<T: Ord>,
+}
+
+#[cfg(feature = "std")]
+reserve_impl! {
+    HashSet<T: Eq + Hash, S: BuildHasher>,
+    HashMap<K: Eq + Hash, V, S: BuildHasher>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn reserve<C, E>(values: &mut C, additional: usize) -> Result<(), E>
+where
+    C: Reserve,
+    E: Error,
+{
+    #[cfg(not(feature = "fallible-alloc"))]
+    {
+        values.reserve(additional);
+        Ok(())
+    }
+
+    #[cfg(feature = "fallible-alloc")]
+    {
+        values.try_reserve(additional).map_err(Error::custom)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn with_capacity<C, E>(capacity: usize) -> Result<C, E>
+where
+    C: Default + Reserve,
+    E: Error,
+{
+    let mut values = C::default();
+    tri!(reserve(&mut values, capacity));
+    Ok(values)
+}
+
 // Dummy impl of reserve
 #[cfg(any(feature = "std", feature = "alloc"))]
-fn nop_reserve<T>(_seq: T, _n: usize) {}
+fn nop_reserve<T, E: Error>(_seq: T, _n: usize) -> Result<(), E> {
+    Ok(())
+}
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 seq_impl!(
     BinaryHeap<T: Ord>,
     seq,
     BinaryHeap::clear,
-    BinaryHeap::with_capacity(size_hint::cautious::<T>(seq.size_hint())),
-    BinaryHeap::reserve,
+    tri!(with_capacity(size_hint::cautious::<T>(seq.size_hint()))),
+    reserve,
     BinaryHeap::push
 );
 
@@ -984,8 +1117,8 @@ seq_impl!(
     HashSet<T: Eq + Hash, S: BuildHasher + Default>,
     seq,
     HashSet::clear,
-    HashSet::with_capacity_and_hasher(size_hint::cautious::<T>(seq.size_hint()), S::default()),
-    HashSet::reserve,
+    tri!(with_capacity(size_hint::cautious::<T>(seq.size_hint()))),
+    reserve,
     HashSet::insert
 );
 
@@ -994,14 +1127,14 @@ seq_impl!(
     VecDeque<T>,
     seq,
     VecDeque::clear,
-    VecDeque::with_capacity(size_hint::cautious::<T>(seq.size_hint())),
-    VecDeque::reserve,
+    tri!(with_capacity(size_hint::cautious::<T>(seq.size_hint()))),
+    reserve,
     VecDeque::push_back
 );
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg(all(any(feature = "std", feature = "alloc"), not(feature = "unstable")))]
 impl<'de, T> Deserialize<'de> for Vec<T>
 where
     T: Deserialize<'de>,
@@ -1029,11 +1162,9 @@ where
                 A: SeqAccess<'de>,
             {
                 let capacity = size_hint::cautious::<T>(seq.size_hint());
-                let mut values = Vec::<T>::with_capacity(capacity);
+                let mut values: Vec<T> = tri!(with_capacity(capacity));
 
-                while let Some(value) = tri!(seq.next_element()) {
-                    values.push(value);
-                }
+                tri!(seq.next_elements(&mut values));
 
                 Ok(values)
             }
@@ -1064,6 +1195,97 @@ where
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
             where
                 A: SeqAccess<'de>,
+            {
+                let hint = size_hint::cautious::<T>(seq.size_hint());
+                if let Some(additional) = hint.checked_sub(self.0.len()) {
+                    tri!(reserve(self.0, additional));
+                }
+
+                let filled = tri!(seq.next_slice(self.0));
+                if filled < self.0.len() {
+                    self.0.truncate(filled);
+                    return Ok(());
+                }
+
+                tri!(seq.next_elements(self.0));
+
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_seq(VecInPlaceVisitor(place))
+    }
+}
+
+// Allocator-generic version of the impl above. A separate impl because the
+// default-allocator one must keep compiling on stable, where `Vec` cannot be
+// named with its allocator parameter.
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "unstable"))]
+impl<'de, T, A> Deserialize<'de> for Vec<T, A>
+where
+    T: Deserialize<'de>,
+    A: Allocator + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VecVisitor<T, A: Allocator> {
+            marker: PhantomData<Vec<T, A>>,
+        }
+
+        impl<'de, T, A> Visitor<'de> for VecVisitor<T, A>
+        where
+            T: Deserialize<'de>,
+            A: Allocator + Default,
+        {
+            type Value = Vec<T, A>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: SeqAccess<'de>,
+            {
+                let capacity = size_hint::cautious::<T>(seq.size_hint());
+                let mut values = Vec::with_capacity_in(capacity, A::default());
+
+                while let Some(value) = tri!(seq.next_element()) {
+                    values.push(value);
+                }
+
+                Ok(values)
+            }
+        }
+
+        let visitor = VecVisitor {
+            marker: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VecInPlaceVisitor<'a, T: 'a, A: Allocator>(&'a mut Vec<T, A>);
+
+        impl<'a, 'de, T, A> Visitor<'de> for VecInPlaceVisitor<'a, T, A>
+        where
+            T: Deserialize<'de>,
+            A: Allocator,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: SeqAccess<'de>,
             {
                 let hint = size_hint::cautious::<T>(seq.size_hint());
                 if let Some(additional) = hint.checked_sub(self.0.len()) {
@@ -1176,15 +1398,9 @@ macro_rules! array_impls {
                 where
                     A: SeqAccess<'de>,
                 {
-                    let mut fail_idx = None;
-                    for (idx, dest) in self.0[..].iter_mut().enumerate() {
-                        if tri!(seq.next_element_seed(InPlaceSeed(dest))).is_none() {
-                            fail_idx = Some(idx);
-                            break;
-                        }
-                    }
-                    if let Some(idx) = fail_idx {
-                        return Err(Error::invalid_length(idx, &self));
+                    let filled = tri!(seq.next_slice(&mut self.0[..]));
+                    if filled < $len {
+                        return Err(Error::invalid_length(filled, &self));
                     }
                     Ok(())
                 }
@@ -1352,7 +1568,9 @@ macro_rules! map_impl {
     (
         $ty:ident <K $(: $kbound1:ident $(+ $kbound2:ident)*)*, V $(, $typaram:ident : $bound1:ident $(+ $bound2:ident)*)*>,
         $access:ident,
-        $with_capacity:expr
+        $clear:expr,
+        $with_capacity:expr,
+        $reserve:expr
     ) => {
         impl<'de, K, V $(, $typaram)*> Deserialize<'de> for $ty<K, V $(, $typaram)*>
         where
@@ -1398,23 +1616,153 @@ macro_rules! map_impl {
                 let visitor = MapVisitor { marker: PhantomData };
                 deserializer.deserialize_map(visitor)
             }
+
+            fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct MapInPlaceVisitor<'a, K: 'a, V: 'a $(, $typaram: 'a)*>(&'a mut $ty<K, V $(, $typaram)*>);
+
+                impl<'a, 'de, K, V $(, $typaram)*> Visitor<'de> for MapInPlaceVisitor<'a, K, V $(, $typaram)*>
+                where
+                    K: Deserialize<'de> $(+ $kbound1 $(+ $kbound2)*)*,
+                    V: Deserialize<'de>,
+                    $($typaram: $bound1 $(+ $bound2)*),*
+                {
+                    type Value = ();
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("a map")
+                    }
+
+                    #[inline]
+                    fn visit_map<A>(mut self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        $clear(&mut self.0);
+                        tri!($reserve(&mut *self.0, size_hint::cautious::<(K, V)>($access.size_hint())));
+
+                        while let Some((key, value)) = tri!($access.next_entry()) {
+                            self.0.insert(key, value);
+                        }
+
+                        Ok(())
+                    }
+                }
+
+                deserializer.deserialize_map(MapInPlaceVisitor(place))
+            }
         }
     }
 }
 
-#[cfg(any(feature = "std", feature = "alloc"))]
-map_impl!(BTreeMap<K: Ord, V>, map, BTreeMap::new());
+#[cfg(all(any(feature = "std", feature = "alloc"), not(feature = "unstable")))]
+map_impl!(BTreeMap<K: Ord, V>, map, BTreeMap::clear, BTreeMap::new(), nop_reserve);
+
+// Allocator-generic version of the impl above. Written out rather than going
+// through `map_impl!` because `BTreeMap` carries bounds on its allocator
+// parameter that the macro's visitor struct cannot express.
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "unstable"))]
+impl<'de, K, V, A> Deserialize<'de> for BTreeMap<K, V, A>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+    A: Allocator + Default + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor<K, V, A: Allocator + Clone> {
+            marker: PhantomData<BTreeMap<K, V, A>>,
+        }
+
+        impl<'de, K, V, A> Visitor<'de> for MapVisitor<K, V, A>
+        where
+            K: Deserialize<'de> + Ord,
+            V: Deserialize<'de>,
+            A: Allocator + Default + Clone,
+        {
+            type Value = BTreeMap<K, V, A>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            #[inline]
+            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let mut values = BTreeMap::new_in(A::default());
+
+                while let Some((key, value)) = tri!(map.next_entry()) {
+                    values.insert(key, value);
+                }
+
+                Ok(values)
+            }
+        }
+
+        let visitor = MapVisitor {
+            marker: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
+    }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MapInPlaceVisitor<'a, K: 'a, V: 'a, A: Allocator + Clone>(&'a mut BTreeMap<K, V, A>);
+
+        impl<'a, 'de, K, V, A> Visitor<'de> for MapInPlaceVisitor<'a, K, V, A>
+        where
+            K: Deserialize<'de> + Ord,
+            V: Deserialize<'de>,
+            A: Allocator + Default + Clone,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            #[inline]
+            fn visit_map<M>(mut self, mut map: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                self.0.clear();
+
+                while let Some((key, value)) = tri!(map.next_entry()) {
+                    self.0.insert(key, value);
+                }
+
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_map(MapInPlaceVisitor(place))
+    }
+}
 
 #[cfg(feature = "std")]
 map_impl!(
     HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
     map,
-    HashMap::with_capacity_and_hasher(size_hint::cautious::<(K, V)>(map.size_hint()), S::default())
+    HashMap::clear,
+    tri!(with_capacity::<HashMap<K, V, S>, _>(size_hint::cautious::<(
+        K,
+        V
+    )>(map.size_hint()))),
+    reserve
 );
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 macro_rules! parse_ip_impl {
     ($expecting:tt $ty:ty; $size:tt) => {
         impl<'de> Deserialize<'de> for $ty {
@@ -1432,7 +1780,7 @@ macro_rules! parse_ip_impl {
     };
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 macro_rules! variant_identifier {
     (
         $name_kind:ident ($($variant:ident; $bytes:expr; $index:expr),*)
@@ -1507,7 +1855,7 @@ macro_rules! variant_identifier {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 macro_rules! deserialize_enum {
     (
         $name:ident $name_kind:ident ($($variant:ident; $bytes:expr; $index:expr),*)
@@ -1544,7 +1892,7 @@ macro_rules! deserialize_enum {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl<'de> Deserialize<'de> for net::IpAddr {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1563,13 +1911,13 @@ impl<'de> Deserialize<'de> for net::IpAddr {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 parse_ip_impl!("IPv4 address" net::Ipv4Addr; 4);
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 parse_ip_impl!("IPv6 address" net::Ipv6Addr; 16);
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 macro_rules! parse_socket_impl {
     ($expecting:tt $ty:ty, $new:expr) => {
         impl<'de> Deserialize<'de> for $ty {
@@ -1587,7 +1935,7 @@ macro_rules! parse_socket_impl {
     };
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl<'de> Deserialize<'de> for net::SocketAddr {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1606,10 +1954,10 @@ impl<'de> Deserialize<'de> for net::SocketAddr {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 parse_socket_impl!("IPv4 socket address" net::SocketAddrV4, |(ip, port)| net::SocketAddrV4::new(ip, port));
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 parse_socket_impl!("IPv6 socket address" net::SocketAddrV6, |(ip, port)| net::SocketAddrV6::new(ip, port, 0, 0));
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -1677,6 +2025,17 @@ impl<'de> Visitor<'de> for PathBufVisitor {
         Ok(From::from(v))
     }
 
+    // Platform bytes cover the human-readable encoding that a non-UTF-8 path
+    // serializes as.
+    #[cfg(any(unix, windows))]
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        OsStringVisitor.visit_bytes(v).map(PathBuf::from)
+    }
+
+    #[cfg(not(any(unix, windows)))]
     fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
     where
         E: Error,
@@ -1686,6 +2045,15 @@ impl<'de> Visitor<'de> for PathBufVisitor {
             .map_err(|_| Error::invalid_value(Unexpected::Bytes(v), &self))
     }
 
+    #[cfg(any(unix, windows))]
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        OsStringVisitor.visit_byte_buf(v).map(PathBuf::from)
+    }
+
+    #[cfg(not(any(unix, windows)))]
     fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
     where
         E: Error,
@@ -1702,8 +2070,14 @@ impl<'de> Deserialize<'de> for PathBuf {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_string(PathBufVisitor)
-    }
+        if deserializer.is_human_readable() {
+            // Self-describing formats can hand over either the string form or
+            // the platform bytes that a non-UTF-8 path serializes as.
+            deserializer.deserialize_any(PathBufVisitor)
+        } else {
+            deserializer.deserialize_string(PathBufVisitor)
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -1711,6 +2085,87 @@ forwarded_impl!((), Box<Path>, PathBuf::into_boxed_path);
 
 ////////////////////////////////////////////////////////////////////////////////
 
+// If this were outside of the serde crate, it would just use:
+//
+//    #[derive(Deserialize)]
+//    #[serde(variant_identifier)]
+#[cfg(feature = "std")]
+variant_identifier! {
+    ComponentKind (
+        Prefix; b"Prefix"; 0,
+        RootDir; b"RootDir"; 1,
+        CurDir; b"CurDir"; 2,
+        ParentDir; b"ParentDir"; 3,
+        Normal; b"Normal"; 4
+    )
+    "`Prefix`, `RootDir`, `CurDir`, `ParentDir` or `Normal`",
+    COMPONENT_VARIANTS
+}
+
+#[cfg(feature = "std")]
+struct ComponentVisitor;
+
+#[cfg(feature = "std")]
+impl<'de> Visitor<'de> for ComponentVisitor {
+    type Value = Component<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a path component")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match tri!(data.variant()) {
+            (ComponentKind::Prefix, v) => {
+                // A prefix component cannot be constructed directly because
+                // std::path::PrefixComponent has no public constructor, but
+                // parsing the prefix back out of the borrowed string produces
+                // a component with the same lifetime. On non-Windows
+                // platforms no string parses as a prefix, so this errors
+                // there like deserializing a Windows OsString does.
+                let s = tri!(v.newtype_variant::<&str>());
+                match Path::new(s).components().next() {
+                    Some(component @ Component::Prefix(_)) => Ok(component),
+                    _ => Err(Error::invalid_value(
+                        Unexpected::Str(s),
+                        &"a Windows path prefix",
+                    )),
+                }
+            }
+            (ComponentKind::RootDir, v) => {
+                tri!(v.unit_variant());
+                Ok(Component::RootDir)
+            }
+            (ComponentKind::CurDir, v) => {
+                tri!(v.unit_variant());
+                Ok(Component::CurDir)
+            }
+            (ComponentKind::ParentDir, v) => {
+                tri!(v.unit_variant());
+                Ok(Component::ParentDir)
+            }
+            (ComponentKind::Normal, v) => {
+                let s = tri!(v.newtype_variant::<&str>());
+                Ok(Component::Normal(OsStr::new(s)))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Component<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("Component", COMPONENT_VARIANTS, ComponentVisitor)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 // If this were outside of the serde crate, it would just use:
 //
 //    #[derive(Deserialize)]
@@ -1733,6 +2188,58 @@ impl<'de> Visitor<'de> for OsStringVisitor {
         formatter.write_str("os string")
     }
 
+    // Strings and bytes cover the cross-platform encoding used by
+    // human-readable formats.
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(OsString::from(v))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(OsString::from(v))
+    }
+
+    #[cfg(unix)]
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        Ok(OsString::from_vec(v.to_vec()))
+    }
+
+    #[cfg(windows)]
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        use std::os::windows::ffi::OsStringExt;
+
+        if v.len() % 2 != 0 {
+            return Err(Error::invalid_value(Unexpected::Bytes(v), &self));
+        }
+        let wide: Vec<u16> = v
+            .chunks(2)
+            .map(|pair| pair[0] as u16 | (pair[1] as u16) << 8)
+            .collect();
+        Ok(OsString::from_wide(&wide))
+    }
+
+    // Self-describing formats hand the tagged platform encoding to
+    // deserialize_any as a single-entry map.
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.visit_enum(crate::de::value::MapAccessDeserializer::new(map))
+    }
+
     #[cfg(unix)]
     fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
     where
@@ -1772,18 +2279,55 @@ impl<'de> Deserialize<'de> for OsString {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_enum("OsString", OSSTR_VARIANTS, OsStringVisitor)
+        if deserializer.is_human_readable() {
+            // Human-readable formats are self-describing, so this accepts the
+            // plain string and bytes encodings as well as the tagged platform
+            // encoding written by older versions.
+            deserializer.deserialize_any(OsStringVisitor)
+        } else {
+            deserializer.deserialize_enum("OsString", OSSTR_VARIANTS, OsStringVisitor)
+        }
     }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg(all(any(feature = "std", feature = "alloc"), not(feature = "unstable")))]
 forwarded_impl!((T), Box<T>, Box::new);
 
-#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg(all(any(feature = "std", feature = "alloc"), not(feature = "unstable")))]
 forwarded_impl!((T), Box<[T]>, Vec::into_boxed_slice);
 
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "unstable"))]
+impl<'de, T, A> Deserialize<'de> for Box<T, A>
+where
+    T: Deserialize<'de>,
+    A: Allocator + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = tri!(Deserialize::deserialize(deserializer));
+        Ok(Box::new_in(value, A::default()))
+    }
+}
+
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "unstable"))]
+impl<'de, T, A> Deserialize<'de> for Box<[T], A>
+where
+    T: Deserialize<'de>,
+    A: Allocator + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let vec: Vec<T, A> = tri!(Deserialize::deserialize(deserializer));
+        Ok(vec.into_boxed_slice())
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 forwarded_impl!((), Box<str>, String::into_boxed_str);
 
@@ -1807,8 +2351,10 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
-/// This impl requires the [`"rc"`] Cargo feature of Serde. The resulting
-/// `Weak<T>` has a reference count of 0 and cannot be upgraded.
+/// This impl requires the [`"rc"`] Cargo feature of Serde. Accepts the
+/// `Option<T>` layout written by the corresponding `Serialize` impl and
+/// discards the value; the resulting `Weak<T>` has a reference count of 0 and
+/// cannot be upgraded.
 ///
 /// [`"rc"`]: https://serde.rs/feature-flags.html#-features-rc
 #[cfg(all(feature = "rc", any(feature = "std", feature = "alloc")))]
@@ -1825,8 +2371,10 @@ where
     }
 }
 
-/// This impl requires the [`"rc"`] Cargo feature of Serde. The resulting
-/// `Weak<T>` has a reference count of 0 and cannot be upgraded.
+/// This impl requires the [`"rc"`] Cargo feature of Serde. Accepts the
+/// `Option<T>` layout written by the corresponding `Serialize` impl and
+/// discards the value; the resulting `Weak<T>` has a reference count of 0 and
+/// cannot be upgraded.
 ///
 /// [`"rc"`]: https://serde.rs/feature-flags.html#-features-rc
 #[cfg(all(feature = "rc", any(feature = "std", feature = "alloc")))]
@@ -1912,6 +2460,42 @@ forwarded_impl!((T), Mutex<T>, Mutex::new);
 #[cfg(feature = "std")]
 forwarded_impl!((T), RwLock<T>, RwLock::new);
 
+// `None` deserializes to an uninitialized cell, `Some` to an initialized one.
+#[cfg(not(no_once_cell))]
+impl<'de, T> Deserialize<'de> for OnceCell<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let cell = OnceCell::new();
+        if let Some(value) = tri!(Option::deserialize(deserializer)) {
+            let _ = cell.set(value);
+        }
+        Ok(cell)
+    }
+}
+
+// `None` deserializes to an uninitialized lock, `Some` to an initialized one.
+#[cfg(all(feature = "std", not(no_once_cell)))]
+impl<'de, T> Deserialize<'de> for OnceLock<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let lock = OnceLock::new();
+        if let Some(value) = tri!(Option::deserialize(deserializer)) {
+            let _ = lock.set(value);
+        }
+        Ok(lock)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 // This is a cleaned-up version of the impl generated by:
@@ -1991,6 +2575,40 @@ impl<'de> Deserialize<'de> for Duration {
             }
         }
 
+        // Parses the human-readable form written by the Serialize impl, for
+        // example "1s" or "1.000000002s".
+        fn parse_duration_str(value: &str) -> Option<Duration> {
+            if !value.ends_with('s') {
+                return None;
+            }
+            let value = &value[..value.len() - 1];
+            let (secs, frac) = match value.find('.') {
+                Some(pos) => (&value[..pos], Some(&value[pos + 1..])),
+                None => (value, None),
+            };
+            if secs.is_empty() || !secs.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let secs: u64 = match secs.parse() {
+                Ok(secs) => secs,
+                Err(_) => return None,
+            };
+            let mut nanos: u32 = 0;
+            if let Some(frac) = frac {
+                if frac.is_empty() || frac.len() > 9 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                nanos = match frac.parse() {
+                    Ok(nanos) => nanos,
+                    Err(_) => return None,
+                };
+                for _ in frac.len()..9 {
+                    nanos *= 10;
+                }
+            }
+            Some(Duration::new(secs, nanos))
+        }
+
         struct DurationVisitor;
 
         impl<'de> Visitor<'de> for DurationVisitor {
@@ -2053,10 +2671,208 @@ impl<'de> Deserialize<'de> for Duration {
                 tri!(check_overflow(secs, nanos));
                 Ok(Duration::new(secs, nanos))
             }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                match parse_duration_str(value) {
+                    Some(duration) => Ok(duration),
+                    None => Err(Error::invalid_value(
+                        Unexpected::Str(value),
+                        &"a duration string such as \"1.000000002s\"",
+                    )),
+                }
+            }
         }
 
         const FIELDS: &[&str] = &["secs", "nanos"];
-        deserializer.deserialize_struct("Duration", FIELDS, DurationVisitor)
+        if deserializer.is_human_readable() {
+            // Human-readable formats are self-describing, so this accepts the
+            // string form as well as data written by older versions in the
+            // struct form.
+            deserializer.deserialize_any(DurationVisitor)
+        } else {
+            deserializer.deserialize_struct("Duration", FIELDS, DurationVisitor)
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Reconstructs the instant by applying the serialized signed offset to the
+/// moment of deserialization. See the `Serialize` impl for the encoding and
+/// the caveats that make this opt-in.
+#[cfg(feature = "instant")]
+impl<'de> Deserialize<'de> for Instant {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Secs,
+            Nanos,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`secs` or `nanos`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        match value {
+                            "secs" => Ok(Field::Secs),
+                            "nanos" => Ok(Field::Nanos),
+                            _ => Err(Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+
+                    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        match value {
+                            b"secs" => Ok(Field::Secs),
+                            b"nanos" => Ok(Field::Nanos),
+                            _ => {
+                                let value = crate::__private::from_utf8_lossy(value);
+                                Err(Error::unknown_field(&*value, FIELDS))
+                            }
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        fn reconstruct<E>(secs: i64, nanos: i32) -> Result<Instant, E>
+        where
+            E: Error,
+        {
+            let now = Instant::now();
+            let reconstructed = if secs >= 0 && nanos >= 0 {
+                now.checked_add(Duration::new(secs as u64, nanos as u32))
+            } else if secs <= 0 && nanos <= 0 {
+                // Negating i64::MIN or i32::MIN directly would overflow.
+                now.checked_sub(Duration::new(secs.unsigned_abs(), nanos.unsigned_abs()))
+            } else {
+                return Err(E::custom("Instant offset fields differ in sign"));
+            };
+            match reconstructed {
+                Some(instant) => Ok(instant),
+                None => Err(E::custom("overflow deserializing Instant")),
+            }
+        }
+
+        struct InstantVisitor;
+
+        impl<'de> Visitor<'de> for InstantVisitor {
+            type Value = Instant;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Instant")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let secs: i64 = match tri!(seq.next_element()) {
+                    Some(value) => value,
+                    None => {
+                        return Err(Error::invalid_length(0, &self));
+                    }
+                };
+                let nanos: i32 = match tri!(seq.next_element()) {
+                    Some(value) => value,
+                    None => {
+                        return Err(Error::invalid_length(1, &self));
+                    }
+                };
+                reconstruct(secs, nanos)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut secs: Option<i64> = None;
+                let mut nanos: Option<i32> = None;
+                while let Some(key) = tri!(map.next_key()) {
+                    match key {
+                        Field::Secs => {
+                            if secs.is_some() {
+                                return Err(<A::Error as Error>::duplicate_field("secs"));
+                            }
+                            secs = Some(tri!(map.next_value()));
+                        }
+                        Field::Nanos => {
+                            if nanos.is_some() {
+                                return Err(<A::Error as Error>::duplicate_field("nanos"));
+                            }
+                            nanos = Some(tri!(map.next_value()));
+                        }
+                    }
+                }
+                let secs = match secs {
+                    Some(secs) => secs,
+                    None => return Err(<A::Error as Error>::missing_field("secs")),
+                };
+                let nanos = match nanos {
+                    Some(nanos) => nanos,
+                    None => return Err(<A::Error as Error>::missing_field("nanos")),
+                };
+                reconstruct(secs, nanos)
+            }
+        }
+
+        const FIELDS: &[&str] = &["secs", "nanos"];
+        deserializer.deserialize_struct("Instant", FIELDS, InstantVisitor)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// A backtrace's frames cannot be reconstructed from its stringified form, so
+/// deserialization accepts the string produced during serialization and
+/// discards it, returning a disabled placeholder backtrace.
+#[cfg(all(feature = "std", not(no_std_backtrace)))]
+impl<'de> Deserialize<'de> for Backtrace {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BacktraceVisitor;
+
+        impl<'de> Visitor<'de> for BacktraceVisitor {
+            type Value = Backtrace;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a stringified backtrace")
+            }
+
+            fn visit_str<E>(self, _v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(Backtrace::disabled())
+            }
+        }
+
+        deserializer.deserialize_str(BacktraceVisitor)
     }
 }
 
@@ -2129,6 +2945,125 @@ impl<'de> Deserialize<'de> for SystemTime {
             }
         }
 
+        // Parses an RFC 3339 timestamp such as "2001-09-09T01:46:40.000000002Z"
+        // into its offset from the UNIX epoch. Timestamps before 1970 are
+        // rejected, matching the restriction on the Serialize side.
+        fn parse_rfc3339(value: &str) -> Option<Duration> {
+            fn digits(bytes: &[u8]) -> Option<u64> {
+                let mut n: u64 = 0;
+                for &b in bytes {
+                    if !b.is_ascii_digit() {
+                        return None;
+                    }
+                    n = n * 10 + (b - b'0') as u64;
+                }
+                Some(n)
+            }
+
+            // Days since 1970-01-01, following
+            // http://howardhinnant.github.io/date_algorithms.html.
+            fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+                let year = if month <= 2 { year - 1 } else { year };
+                let era = if year >= 0 { year } else { year - 399 } / 400;
+                let yoe = year - era * 400;
+                let mp = (if month > 2 { month - 3 } else { month + 9 }) as i64;
+                let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+                let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+                era * 146097 + doe - 719468
+            }
+
+            let bytes = value.as_bytes();
+            if bytes.len() < 20
+                || bytes[4] != b'-'
+                || bytes[7] != b'-'
+                || !(bytes[10] == b'T' || bytes[10] == b't' || bytes[10] == b' ')
+                || bytes[13] != b':'
+                || bytes[16] != b':'
+            {
+                return None;
+            }
+            let year = match digits(&bytes[0..4]) {
+                Some(year) => year,
+                None => return None,
+            };
+            let month = match digits(&bytes[5..7]) {
+                Some(month) => month as u32,
+                None => return None,
+            };
+            let day = match digits(&bytes[8..10]) {
+                Some(day) => day as u32,
+                None => return None,
+            };
+            let hour = match digits(&bytes[11..13]) {
+                Some(hour) => hour,
+                None => return None,
+            };
+            let minute = match digits(&bytes[14..16]) {
+                Some(minute) => minute,
+                None => return None,
+            };
+            let second = match digits(&bytes[17..19]) {
+                Some(second) => second,
+                None => return None,
+            };
+            if month < 1 || month > 12 || day < 1 || day > 31 || hour > 23 || minute > 59 {
+                return None;
+            }
+            let mut pos = 19;
+            let mut nanos: u32 = 0;
+            if bytes[pos] == b'.' {
+                let start = pos + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    end += 1;
+                }
+                if end == start || end - start > 9 {
+                    return None;
+                }
+                nanos = match digits(&bytes[start..end]) {
+                    Some(nanos) => nanos as u32,
+                    None => return None,
+                };
+                for _ in end - start..9 {
+                    nanos *= 10;
+                }
+                pos = end;
+            }
+            let offset_secs: i64 = match bytes.get(pos) {
+                Some(&b'Z') | Some(&b'z') if pos + 1 == bytes.len() => 0,
+                Some(&sign)
+                    if (sign == b'+' || sign == b'-')
+                        && pos + 6 == bytes.len()
+                        && bytes[pos + 3] == b':' =>
+                {
+                    let hours = match digits(&bytes[pos + 1..pos + 3]) {
+                        Some(hours) => hours,
+                        None => return None,
+                    };
+                    let minutes = match digits(&bytes[pos + 4..pos + 6]) {
+                        Some(minutes) => minutes,
+                        None => return None,
+                    };
+                    if hours > 23 || minutes > 59 {
+                        return None;
+                    }
+                    let offset = (hours * 3600 + minutes * 60) as i64;
+                    if sign == b'-' {
+                        -offset
+                    } else {
+                        offset
+                    }
+                }
+                _ => return None,
+            };
+            let days = days_from_civil(year as i64, month, day);
+            let secs = days * 86400 + (hour * 3600 + minute * 60 + second) as i64 - offset_secs;
+            if secs < 0 {
+                return None;
+            }
+            Some(Duration::new(secs as u64, nanos))
+        }
+
         struct DurationVisitor;
 
         impl<'de> Visitor<'de> for DurationVisitor {
@@ -2195,10 +3130,30 @@ impl<'de> Deserialize<'de> for SystemTime {
                 tri!(check_overflow(secs, nanos));
                 Ok(Duration::new(secs, nanos))
             }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                match parse_rfc3339(value) {
+                    Some(duration) => Ok(duration),
+                    None => Err(Error::invalid_value(
+                        Unexpected::Str(value),
+                        &"an RFC 3339 timestamp",
+                    )),
+                }
+            }
         }
 
         const FIELDS: &[&str] = &["secs_since_epoch", "nanos_since_epoch"];
-        let duration = tri!(deserializer.deserialize_struct("SystemTime", FIELDS, DurationVisitor));
+        let duration = if deserializer.is_human_readable() {
+            // Human-readable formats are self-describing, so this accepts the
+            // RFC 3339 form as well as data written by older versions in the
+            // struct form.
+            tri!(deserializer.deserialize_any(DurationVisitor))
+        } else {
+            tri!(deserializer.deserialize_struct("SystemTime", FIELDS, DurationVisitor))
+        };
         #[cfg(not(no_systemtime_checked_add))]
         let ret = UNIX_EPOCH
             .checked_add(duration)
@@ -2773,6 +3728,109 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+impl<'de> Deserialize<'de> for cmp::Ordering {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Less,
+            Equal,
+            Greater,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`Less`, `Equal` or `Greater`")
+                    }
+
+                    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        match value {
+                            0 => Ok(Field::Less),
+                            1 => Ok(Field::Equal),
+                            2 => Ok(Field::Greater),
+                            _ => Err(Error::invalid_value(Unexpected::Unsigned(value), &self)),
+                        }
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        match value {
+                            "Less" => Ok(Field::Less),
+                            "Equal" => Ok(Field::Equal),
+                            "Greater" => Ok(Field::Greater),
+                            _ => Err(Error::unknown_variant(value, VARIANTS)),
+                        }
+                    }
+
+                    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        match value {
+                            b"Less" => Ok(Field::Less),
+                            b"Equal" => Ok(Field::Equal),
+                            b"Greater" => Ok(Field::Greater),
+                            _ => match str::from_utf8(value) {
+                                Ok(value) => Err(Error::unknown_variant(value, VARIANTS)),
+                                Err(_) => {
+                                    Err(Error::invalid_value(Unexpected::Bytes(value), &self))
+                                }
+                            },
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct OrderingVisitor;
+
+        impl<'de> Visitor<'de> for OrderingVisitor {
+            type Value = cmp::Ordering;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("enum Ordering")
+            }
+
+            fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+            where
+                A: EnumAccess<'de>,
+            {
+                let (field, v) = tri!(data.variant());
+                tri!(v.unit_variant());
+                match field {
+                    Field::Less => Ok(cmp::Ordering::Less),
+                    Field::Equal => Ok(cmp::Ordering::Equal),
+                    Field::Greater => Ok(cmp::Ordering::Greater),
+                }
+            }
+        }
+
+        const VARIANTS: &[&str] = &["Less", "Equal", "Greater"];
+
+        deserializer.deserialize_enum("Ordering", VARIANTS, OrderingVisitor)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 impl<'de, T, E> Deserialize<'de> for Result<T, E>
 where
     T: Deserialize<'de>,
@@ -2893,6 +3951,19 @@ where
     }
 }
 
+#[cfg(not(no_num_saturating))]
+impl<'de, T> Deserialize<'de> for Saturating<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(Saturating)
+    }
+}
+
 #[cfg(all(feature = "std", not(no_std_atomic)))]
 macro_rules! atomic_impl {
     ($($ty:ident $size:expr)*) => {
@@ -2929,13 +4000,13 @@ atomic_impl! {
     AtomicU64 "64"
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 struct FromStrVisitor<T> {
     expecting: &'static str,
     ty: PhantomData<T>,
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl<T> FromStrVisitor<T> {
     fn new(expecting: &'static str) -> Self {
         FromStrVisitor {
@@ -2945,7 +4016,7 @@ impl<T> FromStrVisitor<T> {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl<'de, T> Visitor<'de> for FromStrVisitor<T>
 where
     T: str::FromStr,
@@ -0,0 +1,129 @@
+use crate::lib::*;
+
+use crate::de::Error;
+
+/// A buffer for deserializing from input that arrives in pieces.
+///
+/// Async readers typically receive a document in chunks and cannot know in
+/// advance where a value ends. `Incremental` accumulates those chunks and
+/// repeatedly attempts a parse: if the format's error reports
+/// [`is_incomplete`], the attempt is treated as "need more data" rather than
+/// a failure, and the caller feeds the next chunk and tries again. Only
+/// errors on complete input are surfaced.
+///
+/// The parse closure receives the buffered bytes and, on success, returns
+/// the value together with the number of bytes it consumed. Consumed bytes
+/// are drained from the buffer so several consecutive values can be read
+/// from the same stream.
+///
+/// ```edition2021
+/// use serde::de::value::StrDeserializer;
+/// use serde::de::{Error as _, Incremental};
+/// use serde::Deserialize;
+/// use std::fmt;
+///
+/// // A toy line-delimited format: a value is not complete until its
+/// // terminating newline has arrived.
+/// #[derive(Debug)]
+/// enum LineError {
+///     Incomplete,
+///     Message(String),
+/// }
+/// #
+/// # impl fmt::Display for LineError {
+/// #     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+/// #         match self {
+/// #             LineError::Incomplete => formatter.write_str("unexpected end of input"),
+/// #             LineError::Message(msg) => formatter.write_str(msg),
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # impl std::error::Error for LineError {}
+///
+/// impl serde::de::Error for LineError {
+///     fn custom<T: fmt::Display>(msg: T) -> Self {
+///         LineError::Message(msg.to_string())
+///     }
+///
+///     fn is_incomplete(&self) -> bool {
+///         matches!(self, LineError::Incomplete)
+///     }
+/// }
+///
+/// fn parse_line(input: &[u8]) -> Result<(String, usize), LineError> {
+///     let line_end = match input.iter().position(|b| *b == b'\n') {
+///         Some(position) => position,
+///         None => return Err(LineError::Incomplete),
+///     };
+///     let line = std::str::from_utf8(&input[..line_end]).map_err(LineError::custom)?;
+///     let value = String::deserialize(StrDeserializer::new(line))?;
+///     Ok((value, line_end + 1))
+/// }
+///
+/// let mut incremental = Incremental::new();
+///
+/// // The first chunk ends mid-value: not an error, just "need more data".
+/// incremental.feed(b"hel");
+/// assert!(incremental.next_value(parse_line).unwrap().is_none());
+///
+/// // Once the rest arrives, the value parses.
+/// incremental.feed(b"lo\n");
+/// let value = incremental.next_value(parse_line).unwrap();
+/// assert_eq!(value.as_deref(), Some("hello"));
+/// ```
+///
+/// [`is_incomplete`]: Error::is_incomplete
+#[derive(Clone, Debug, Default)]
+pub struct Incremental {
+    buffer: Vec<u8>,
+}
+
+impl Incremental {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Incremental { buffer: Vec::new() }
+    }
+
+    /// Appends a chunk of input to the buffer.
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// The input buffered so far.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns true if no unconsumed input is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Attempts to deserialize the next value from the buffered input.
+    ///
+    /// `parse` is given the buffered bytes and returns the value along with
+    /// the number of bytes it consumed; the consumed prefix is removed from
+    /// the buffer. `Ok(None)` means the buffered input is a valid but
+    /// incomplete prefix of a value — feed more input and call again. Errors
+    /// for which [`Error::is_incomplete`] returns false are passed through.
+    pub fn next_value<T, E, F>(&mut self, parse: F) -> Result<Option<T>, E>
+    where
+        F: FnOnce(&[u8]) -> Result<(T, usize), E>,
+        E: Error,
+    {
+        match parse(&self.buffer) {
+            Ok((value, consumed)) => {
+                self.buffer.drain(..consumed);
+                Ok(Some(value))
+            }
+            Err(err) => {
+                if err.is_incomplete() {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::lib::*;
+
+use crate::__private::de::{Content, ContentDeserializer, ContentRefDeserializer};
+use crate::de::value::Error;
+use crate::de::{Deserialize, Deserializer};
+
+/// A value whose deserialization is deferred until it is first accessed.
+///
+/// During deserialization of the surrounding document, a `Lazy<T>` captures
+/// the raw value into an internal buffer without interpreting it as a `T`.
+/// The buffered value is only decoded by [`get`], [`get_mut`], or
+/// [`into_inner`], and the decoded result is cached so repeated accesses do
+/// not re-decode. This lets large optional sections of a document be skipped
+/// entirely unless they are actually needed.
+///
+/// Because decoding happens after the original `Deserializer` is gone,
+/// decode failures are reported as [`serde::de::value::Error`].
+///
+/// ```edition2021
+/// # use serde_derive::Deserialize;
+/// use serde::de::Lazy;
+///
+/// #[derive(Deserialize)]
+/// struct Document<'a> {
+///     summary: String,
+///     // Potentially megabytes of data that most callers never look at.
+///     #[serde(borrow)]
+///     details: Lazy<'a, Vec<String>>,
+/// }
+///
+/// fn summarize(doc: &mut Document) -> Result<usize, serde::de::value::Error> {
+///     let details = doc.details.get()?;
+///     Ok(details.len())
+/// }
+/// ```
+///
+/// [`get`]: Lazy::get
+/// [`get_mut`]: Lazy::get_mut
+/// [`into_inner`]: Lazy::into_inner
+/// [`serde::de::value::Error`]: crate::de::value::Error
+pub struct Lazy<'de, T> {
+    state: State<'de, T>,
+}
+
+enum State<'de, T> {
+    Raw(Content<'de>),
+    Decoded(T),
+}
+
+impl<'de, T> Lazy<'de, T>
+where
+    T: Deserialize<'de>,
+{
+    /// Decodes the buffered value if it has not been decoded yet, and returns
+    /// a reference to it.
+    pub fn get(&mut self) -> Result<&T, Error> {
+        tri!(self.decode());
+        match &self.state {
+            State::Decoded(value) => Ok(value),
+            State::Raw(_) => unreachable!(),
+        }
+    }
+
+    /// Decodes the buffered value if it has not been decoded yet, and returns
+    /// a mutable reference to it.
+    pub fn get_mut(&mut self) -> Result<&mut T, Error> {
+        tri!(self.decode());
+        match &mut self.state {
+            State::Decoded(value) => Ok(value),
+            State::Raw(_) => unreachable!(),
+        }
+    }
+
+    /// Decodes the buffered value if it has not been decoded yet, and returns
+    /// it by value.
+    pub fn into_inner(self) -> Result<T, Error> {
+        match self.state {
+            State::Raw(content) => T::deserialize(ContentDeserializer::new(content)),
+            State::Decoded(value) => Ok(value),
+        }
+    }
+
+    /// Whether the value has already been decoded.
+    pub fn is_decoded(&self) -> bool {
+        match self.state {
+            State::Raw(_) => false,
+            State::Decoded(_) => true,
+        }
+    }
+
+    fn decode(&mut self) -> Result<(), Error> {
+        if let State::Raw(content) = &self.state {
+            let value = tri!(T::deserialize(ContentRefDeserializer::new(content)));
+            self.state = State::Decoded(value);
+        }
+        Ok(())
+    }
+}
+
+impl<'de, T> From<T> for Lazy<'de, T> {
+    fn from(value: T) -> Self {
+        Lazy {
+            state: State::Decoded(value),
+        }
+    }
+}
+
+impl<'de, T> Debug for Lazy<'de, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match &self.state {
+            State::Raw(_) => formatter.write_str("Lazy(<buffered>)"),
+            State::Decoded(value) => formatter.debug_tuple("Lazy").field(value).finish(),
+        }
+    }
+}
+
+impl<'de: 'a, 'a, T> Deserialize<'de> for Lazy<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = tri!(Content::deserialize(deserializer));
+        Ok(Lazy {
+            state: State::Raw(content),
+        })
+    }
+}
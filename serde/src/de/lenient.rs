@@ -0,0 +1,211 @@
+use crate::lib::*;
+
+use crate::__private::de::{Content, ContentDeserializer};
+use crate::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+/// A sequence that tolerates elements which fail to deserialize.
+///
+/// Where `Vec<T>` fails as soon as any single element is malformed,
+/// `LenientVec<T>` buffers each element, attempts to deserialize it as `T`,
+/// and collects the ones that succeed. Elements that fail are skipped and
+/// their error messages are retained so that the caller can report or log
+/// them. This is useful for ingestion pipelines that want to salvage the
+/// valid portion of a partially corrupt array without hand-writing a
+/// `SeqAccess` loop.
+///
+/// Errors in the underlying format itself, for example a syntax error in the
+/// middle of the sequence, are still fatal and propagate to the caller.
+///
+/// ```edition2021
+/// # use serde_derive::Deserialize;
+/// use serde::de::{Deserialize, Deserializer, LenientVec};
+///
+/// #[derive(Deserialize)]
+/// struct Record {
+///     id: u32,
+/// }
+///
+/// # fn example<'de, D>(deserializer: D) -> Result<(), D::Error>
+/// # where
+/// #     D: Deserializer<'de>,
+/// # {
+/// // Given input like `[{"id": 1}, {"id": "oops"}, {"id": 3}]`, this
+/// // produces the two valid records and one error message rather than
+/// // failing the whole deserialization.
+/// let records = LenientVec::<Record>::deserialize(deserializer)?;
+/// for err in records.errors() {
+///     eprintln!("skipped element: {}", err);
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LenientVec<T> {
+    elements: Vec<T>,
+    errors: Vec<String>,
+}
+
+impl<T> LenientVec<T> {
+    /// The elements that deserialized successfully, in their original order.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Error messages for the elements that were skipped, in the order they
+    /// were encountered.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Consumes the `LenientVec`, returning the successfully deserialized
+    /// elements.
+    pub fn into_elements(self) -> Vec<T> {
+        self.elements
+    }
+
+    /// Consumes the `LenientVec`, returning both the successfully
+    /// deserialized elements and the errors for the skipped ones.
+    pub fn into_parts(self) -> (Vec<T>, Vec<String>) {
+        (self.elements, self.errors)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LenientVec<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LenientVecVisitor<T> {
+            marker: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for LenientVecVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = LenientVec<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut elements = Vec::new();
+                let mut errors = Vec::new();
+                while let Some(content) = tri!(seq.next_element::<Content>()) {
+                    match T::deserialize(ContentDeserializer::<A::Error>::new(content)) {
+                        Ok(element) => elements.push(element),
+                        Err(err) => errors.push(err.to_string()),
+                    }
+                }
+                Ok(LenientVec { elements, errors })
+            }
+        }
+
+        deserializer.deserialize_seq(LenientVecVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+/// A map that tolerates entries which fail to deserialize.
+///
+/// The map counterpart of [`LenientVec`]. Each entry is buffered and an
+/// attempt is made to deserialize its key as `K` and its value as `V`.
+/// Entries for which either half fails are skipped and their error messages
+/// retained; the rest are collected into a `BTreeMap`.
+///
+/// As with `LenientVec`, errors in the underlying format itself remain
+/// fatal.
+#[derive(Clone, Debug, Default)]
+pub struct LenientMap<K, V> {
+    entries: BTreeMap<K, V>,
+    errors: Vec<String>,
+}
+
+impl<K, V> LenientMap<K, V> {
+    /// The entries that deserialized successfully.
+    pub fn entries(&self) -> &BTreeMap<K, V> {
+        &self.entries
+    }
+
+    /// Error messages for the entries that were skipped, in the order they
+    /// were encountered.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Consumes the `LenientMap`, returning the successfully deserialized
+    /// entries.
+    pub fn into_entries(self) -> BTreeMap<K, V> {
+        self.entries
+    }
+
+    /// Consumes the `LenientMap`, returning both the successfully
+    /// deserialized entries and the errors for the skipped ones.
+    pub fn into_parts(self) -> (BTreeMap<K, V>, Vec<String>) {
+        (self.entries, self.errors)
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for LenientMap<K, V>
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LenientMapVisitor<K, V> {
+            marker: PhantomData<BTreeMap<K, V>>,
+        }
+
+        impl<'de, K, V> Visitor<'de> for LenientMapVisitor<K, V>
+        where
+            K: Deserialize<'de> + Ord,
+            V: Deserialize<'de>,
+        {
+            type Value = LenientMap<K, V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = BTreeMap::new();
+                let mut errors = Vec::new();
+                while let Some(key) = tri!(map.next_key::<Content>()) {
+                    let value = tri!(map.next_value::<Content>());
+                    let key = match K::deserialize(ContentDeserializer::<A::Error>::new(key)) {
+                        Ok(key) => key,
+                        Err(err) => {
+                            errors.push(err.to_string());
+                            continue;
+                        }
+                    };
+                    match V::deserialize(ContentDeserializer::<A::Error>::new(value)) {
+                        Ok(value) => {
+                            entries.insert(key, value);
+                        }
+                        Err(err) => errors.push(err.to_string()),
+                    }
+                }
+                Ok(LenientMap { entries, errors })
+            }
+        }
+
+        deserializer.deserialize_map(LenientMapVisitor {
+            marker: PhantomData,
+        })
+    }
+}
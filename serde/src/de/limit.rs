@@ -0,0 +1,38 @@
+use crate::de::size_hint;
+use core::sync::atomic::Ordering;
+
+/// Process-wide budget for how much memory collection types may preallocate
+/// on the strength of a length hint.
+///
+/// Length hints come straight from the input, so a malicious document can
+/// claim an enormous length for a tiny payload. Collection `Deserialize`
+/// impls therefore cap their `with_capacity` calls at this many bytes — 1 MiB
+/// by default — and grow normally beyond it. Applications reading trusted
+/// input can raise the budget to avoid reallocation, and hardened ones can
+/// lower it. Both handwritten impls and derive-generated code consult the
+/// same budget.
+///
+/// ```edition2021
+/// use serde::de::SizeLimit;
+///
+/// // Deserializing gigabytes of trusted telemetry; skip the reallocations.
+/// SizeLimit::set_max_preallocation_bytes(64 * 1024 * 1024);
+/// assert_eq!(SizeLimit::max_preallocation_bytes(), 64 * 1024 * 1024);
+/// # SizeLimit::set_max_preallocation_bytes(1024 * 1024);
+/// ```
+pub struct SizeLimit;
+
+impl SizeLimit {
+    /// The current preallocation budget in bytes.
+    pub fn max_preallocation_bytes() -> usize {
+        size_hint::MAX_PREALLOC_BYTES.load(Ordering::Relaxed)
+    }
+
+    /// Replaces the preallocation budget, returning the previous value.
+    ///
+    /// The budget applies to every deserialization in the process from this
+    /// point on.
+    pub fn set_max_preallocation_bytes(bytes: usize) -> usize {
+        size_hint::MAX_PREALLOC_BYTES.swap(bytes, Ordering::Relaxed)
+    }
+}
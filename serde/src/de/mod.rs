@@ -90,6 +90,7 @@
 //!    - Range\<T\>
 //!    - RangeInclusive\<T\>
 //!    - Bound\<T\>
+//!    - cmp::Ordering
 //!    - num::NonZero*
 //!    - `!` *(unstable)*
 //!  - **Net types**:
@@ -116,14 +117,61 @@ use crate::lib::*;
 
 ////////////////////////////////////////////////////////////////////////////////
 
+#[cfg(all(feature = "unstable", any(feature = "std", feature = "alloc")))]
+pub mod arena;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod flatten;
+#[cfg(not(no_core_try_from))]
+pub mod num;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod schema;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod track;
 pub mod value;
 
+#[cfg(all(not(no_min_const_generics), any(feature = "std", feature = "alloc")))]
+mod boxed;
+mod for_each;
 mod format;
 mod ignored_any;
 mod impls;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod incremental;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod lazy;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod lenient;
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    any(
+        all(not(no_target_has_atomic), target_has_atomic = "ptr"),
+        all(no_target_has_atomic, not(no_std_atomic)),
+    )
+))]
+mod limit;
+mod seq_iter;
 pub(crate) mod size_hint;
 
+#[cfg(all(not(no_min_const_generics), any(feature = "std", feature = "alloc")))]
+pub use self::boxed::boxed_array;
+pub use self::for_each::{ForEach, ForEachEntry};
 pub use self::ignored_any::IgnoredAny;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use self::incremental::Incremental;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use self::lazy::Lazy;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use self::lenient::{LenientMap, LenientVec};
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    any(
+        all(not(no_target_has_atomic), target_has_atomic = "ptr"),
+        all(no_target_has_atomic, not(no_std_atomic)),
+    )
+))]
+pub use self::limit::SizeLimit;
+pub use self::seq_iter::SeqIter;
+pub use crate::capabilities::Capabilities;
 
 #[cfg(not(any(feature = "std", feature = "unstable")))]
 #[doc(no_inline)]
@@ -208,7 +256,18 @@ macro_rules! declare_error_trait {
             /// expected type is the string.
             #[cold]
             fn invalid_type(unexp: Unexpected, exp: &Expected) -> Self {
-                Error::custom(format_args!("invalid type: {}, expected {}", unexp, exp))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::InvalidType {
+                        unexpected: unexp.to_string(),
+                        expected: exp.to_string(),
+                    })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    Error::custom(format_args!("invalid type: {}, expected {}", unexp, exp))
+                }
             }
 
             /// Raised when a `Deserialize` receives a value of the right type but that
@@ -226,7 +285,49 @@ macro_rules! declare_error_trait {
             /// expected value is a string.
             #[cold]
             fn invalid_value(unexp: Unexpected, exp: &Expected) -> Self {
-                Error::custom(format_args!("invalid value: {}, expected {}", unexp, exp))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::InvalidValue {
+                        unexpected: unexp.to_string(),
+                        expected: exp.to_string(),
+                    })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    Error::custom(format_args!("invalid value: {}, expected {}", unexp, exp))
+                }
+            }
+
+            /// Like `invalid_type`, but derives the [`Unexpected`] variant from a
+            /// plain Rust value instead of requiring the caller to spell it out.
+            ///
+            /// ```edition2021
+            /// # use serde::de::{self, Unexpected};
+            /// # fn example<E>(v: bool) -> Result<String, E>
+            /// # where
+            /// #     E: de::Error,
+            /// # {
+            /// // Equivalent to de::Error::invalid_type(Unexpected::Bool(v), &"a string"):
+            /// Err(de::Error::invalid_type_of(&v, &"a string"))
+            /// # }
+            /// ```
+            #[cold]
+            fn invalid_type_of<V>(value: &V, exp: &Expected) -> Self
+            where
+                V: ?Sized + AsUnexpected,
+            {
+                Error::invalid_type(value.as_unexpected(), exp)
+            }
+
+            /// Like `invalid_value`, but derives the [`Unexpected`] variant from a
+            /// plain Rust value instead of requiring the caller to spell it out.
+            #[cold]
+            fn invalid_value_of<V>(value: &V, exp: &Expected) -> Self
+            where
+                V: ?Sized + AsUnexpected,
+            {
+                Error::invalid_value(value.as_unexpected(), exp)
             }
 
             /// Raised when deserializing a sequence or map and the input data contains
@@ -240,24 +341,53 @@ macro_rules! declare_error_trait {
             /// expected.
             #[cold]
             fn invalid_length(len: usize, exp: &Expected) -> Self {
-                Error::custom(format_args!("invalid length {}, expected {}", len, exp))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::InvalidLength {
+                        length: len,
+                        expected: exp.to_string(),
+                    })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    Error::custom(format_args!("invalid length {}, expected {}", len, exp))
+                }
             }
 
             /// Raised when a `Deserialize` enum type received a variant with an
             /// unrecognized name.
             #[cold]
             fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
-                if expected.is_empty() {
-                    Error::custom(format_args!(
-                        "unknown variant `{}`, there are no variants",
-                        variant
-                    ))
-                } else {
-                    Error::custom(format_args!(
-                        "unknown variant `{}`, expected {}",
-                        variant,
-                        OneOf { names: expected }
-                    ))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::UnknownVariant {
+                        variant: variant.to_owned(),
+                        expected,
+                    })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    if expected.is_empty() {
+                        Error::custom(format_args!(
+                            "unknown variant `{}`, there are no variants",
+                            variant
+                        ))
+                    } else if let Some(suggestion) = did_you_mean(variant, expected) {
+                        Error::custom(format_args!(
+                            "unknown variant `{}`, expected {}, did you mean `{}`?",
+                            variant,
+                            OneOf { names: expected },
+                            suggestion
+                        ))
+                    } else {
+                        Error::custom(format_args!(
+                            "unknown variant `{}`, expected {}",
+                            variant,
+                            OneOf { names: expected }
+                        ))
+                    }
                 }
             }
 
@@ -265,17 +395,35 @@ macro_rules! declare_error_trait {
             /// unrecognized name.
             #[cold]
             fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
-                if expected.is_empty() {
-                    Error::custom(format_args!(
-                        "unknown field `{}`, there are no fields",
-                        field
-                    ))
-                } else {
-                    Error::custom(format_args!(
-                        "unknown field `{}`, expected {}",
-                        field,
-                        OneOf { names: expected }
-                    ))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::UnknownField {
+                        field: field.to_owned(),
+                        expected,
+                    })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    if expected.is_empty() {
+                        Error::custom(format_args!(
+                            "unknown field `{}`, there are no fields",
+                            field
+                        ))
+                    } else if let Some(suggestion) = did_you_mean(field, expected) {
+                        Error::custom(format_args!(
+                            "unknown field `{}`, expected {}, did you mean `{}`?",
+                            field,
+                            OneOf { names: expected },
+                            suggestion
+                        ))
+                    } else {
+                        Error::custom(format_args!(
+                            "unknown field `{}`, expected {}",
+                            field,
+                            OneOf { names: expected }
+                        ))
+                    }
                 }
             }
 
@@ -284,14 +432,74 @@ macro_rules! declare_error_trait {
             /// input.
             #[cold]
             fn missing_field(field: &'static str) -> Self {
-                Error::custom(format_args!("missing field `{}`", field))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::MissingField { field })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    Error::custom(format_args!("missing field `{}`", field))
+                }
             }
 
             /// Raised when a `Deserialize` struct type received more than one of the
             /// same field.
             #[cold]
             fn duplicate_field(field: &'static str) -> Self {
-                Error::custom(format_args!("duplicate field `{}`", field))
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                {
+                    Error::from_kind(ErrorKind::DuplicateField { field })
+                }
+
+                #[cfg(not(any(feature = "std", feature = "alloc")))]
+                {
+                    Error::custom(format_args!("duplicate field `{}`", field))
+                }
+            }
+
+            /// Constructs an error carrying a structured [`ErrorKind`].
+            ///
+            /// The provided constructors `invalid_type`, `missing_field` and
+            /// friends all route through this method, so a format error type
+            /// that overrides it (storing the kind and reporting it back from
+            /// [`kind`]) gets machine-inspectable errors from derive-generated
+            /// code without any further changes. The default implementation
+            /// discards the structure and produces the same message as
+            /// `Error::custom`, since `ErrorKind`'s `Display` output matches
+            /// the historical error strings.
+            ///
+            /// [`kind`]: Error::kind
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            #[cold]
+            fn from_kind(kind: ErrorKind) -> Self {
+                Error::custom(kind)
+            }
+
+            /// The structured kind of this error, if it carries one.
+            ///
+            /// Returns None for error types that do not record kinds, and for
+            /// errors built through `Error::custom` rather than one of the
+            /// structured constructors.
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            fn kind(&self) -> Option<&ErrorKind> {
+                None
+            }
+
+            /// Returns true if this error means the input ended before the
+            /// value was complete, so retrying with more input may succeed.
+            ///
+            /// Self-describing formats whose error type can distinguish
+            /// truncated input from malformed input should override this to
+            /// return true for the truncated case. Callers such as
+            /// [`Incremental`] use it to decide between requesting more data
+            /// and reporting a hard failure. The default implementation
+            /// returns false, which is always sound: it only means resumable
+            /// readers cannot tell the two cases apart.
+            ///
+            /// [`Incremental`]: crate::de::Incremental
+            fn is_incomplete(&self) -> bool {
+                false
             }
         }
     }
@@ -303,6 +511,139 @@ declare_error_trait!(Error: Sized + StdError);
 #[cfg(not(feature = "std"))]
 declare_error_trait!(Error: Sized + Debug + Display);
 
+/// Structured form of the errors built by the provided [`Error`]
+/// constructors.
+///
+/// Each variant corresponds to one of the constructors on the `Error` trait
+/// and carries the same information, so callers can answer questions like
+/// "was this a missing field, and which one?" without parsing the display
+/// string. Format error types opt in by overriding [`Error::from_kind`] and
+/// [`Error::kind`]; the `Display` impl of `ErrorKind` produces exactly the
+/// message the corresponding constructor has always produced.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Produced by [`Error::invalid_type`].
+    InvalidType {
+        /// Description of the type encountered in the input.
+        unexpected: String,
+        /// Description of the type the `Deserialize` expected.
+        expected: String,
+    },
+    /// Produced by [`Error::invalid_value`].
+    InvalidValue {
+        /// Description of the value encountered in the input.
+        unexpected: String,
+        /// Description of the value the `Deserialize` expected.
+        expected: String,
+    },
+    /// Produced by [`Error::invalid_length`].
+    InvalidLength {
+        /// The number of elements encountered in the input.
+        length: usize,
+        /// Description of the length the `Deserialize` expected.
+        expected: String,
+    },
+    /// Produced by [`Error::unknown_variant`].
+    UnknownVariant {
+        /// The unrecognized variant name encountered in the input.
+        variant: String,
+        /// The variant names accepted by the enum.
+        expected: &'static [&'static str],
+    },
+    /// Produced by [`Error::unknown_field`].
+    UnknownField {
+        /// The unrecognized field name encountered in the input.
+        field: String,
+        /// The field names accepted by the struct.
+        expected: &'static [&'static str],
+    },
+    /// Produced by [`Error::missing_field`].
+    MissingField {
+        /// The name of the field that was not present in the input.
+        field: &'static str,
+    },
+    /// Produced by [`Error::duplicate_field`].
+    DuplicateField {
+        /// The name of the field that occurred more than once.
+        field: &'static str,
+    },
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Display for ErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidType {
+                unexpected,
+                expected,
+            } => write!(
+                formatter,
+                "invalid type: {}, expected {}",
+                unexpected, expected
+            ),
+            ErrorKind::InvalidValue {
+                unexpected,
+                expected,
+            } => write!(
+                formatter,
+                "invalid value: {}, expected {}",
+                unexpected, expected
+            ),
+            ErrorKind::InvalidLength { length, expected } => {
+                write!(formatter, "invalid length {}, expected {}", length, expected)
+            }
+            ErrorKind::UnknownVariant { variant, expected } => {
+                if expected.is_empty() {
+                    write!(
+                        formatter,
+                        "unknown variant `{}`, there are no variants",
+                        variant
+                    )
+                } else if let Some(suggestion) = did_you_mean(variant, expected) {
+                    write!(
+                        formatter,
+                        "unknown variant `{}`, expected {}, did you mean `{}`?",
+                        variant,
+                        OneOf { names: expected },
+                        suggestion
+                    )
+                } else {
+                    write!(
+                        formatter,
+                        "unknown variant `{}`, expected {}",
+                        variant,
+                        OneOf { names: expected }
+                    )
+                }
+            }
+            ErrorKind::UnknownField { field, expected } => {
+                if expected.is_empty() {
+                    write!(formatter, "unknown field `{}`, there are no fields", field)
+                } else if let Some(suggestion) = did_you_mean(field, expected) {
+                    write!(
+                        formatter,
+                        "unknown field `{}`, expected {}, did you mean `{}`?",
+                        field,
+                        OneOf { names: expected },
+                        suggestion
+                    )
+                } else {
+                    write!(
+                        formatter,
+                        "unknown field `{}`, expected {}",
+                        field,
+                        OneOf { names: expected }
+                    )
+                }
+            }
+            ErrorKind::MissingField { field } => write!(formatter, "missing field `{}`", field),
+            ErrorKind::DuplicateField { field } => write!(formatter, "duplicate field `{}`", field),
+        }
+    }
+}
+
 /// `Unexpected` represents an unexpected invocation of any one of the `Visitor`
 /// trait methods.
 ///
@@ -421,6 +762,73 @@ impl<'a> fmt::Display for Unexpected<'a> {
     }
 }
 
+/// Conversion of a plain Rust value into the [`Unexpected`] variant that
+/// describes it.
+///
+/// This powers the [`Error::invalid_type_of`] and [`Error::invalid_value_of`]
+/// convenience constructors, so custom `Deserialize` impls can produce
+/// high-quality errors without matching out the right `Unexpected` variant by
+/// hand. It is implemented for the primitive types as well as strings and
+/// byte slices.
+///
+/// [`Error::invalid_type_of`]: Error::invalid_type_of
+/// [`Error::invalid_value_of`]: Error::invalid_value_of
+pub trait AsUnexpected {
+    /// Returns the `Unexpected` variant describing this value.
+    fn as_unexpected(&self) -> Unexpected<'_>;
+}
+
+macro_rules! impl_as_unexpected {
+    ($($ty:ty => |$value:ident| $unexpected:expr,)*) => {
+        $(
+            impl AsUnexpected for $ty {
+                fn as_unexpected(&self) -> Unexpected<'_> {
+                    let $value = self;
+                    $unexpected
+                }
+            }
+        )*
+    };
+}
+
+impl_as_unexpected! {
+    bool => |v| Unexpected::Bool(*v),
+    u8 => |v| Unexpected::Unsigned(u64::from(*v)),
+    u16 => |v| Unexpected::Unsigned(u64::from(*v)),
+    u32 => |v| Unexpected::Unsigned(u64::from(*v)),
+    u64 => |v| Unexpected::Unsigned(*v),
+    i8 => |v| Unexpected::Signed(i64::from(*v)),
+    i16 => |v| Unexpected::Signed(i64::from(*v)),
+    i32 => |v| Unexpected::Signed(i64::from(*v)),
+    i64 => |v| Unexpected::Signed(*v),
+    f32 => |v| Unexpected::Float(f64::from(*v)),
+    f64 => |v| Unexpected::Float(*v),
+    char => |v| Unexpected::Char(*v),
+    str => |v| Unexpected::Str(v),
+    [u8] => |v| Unexpected::Bytes(v),
+    () => |_v| Unexpected::Unit,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl AsUnexpected for String {
+    fn as_unexpected(&self) -> Unexpected<'_> {
+        Unexpected::Str(self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl AsUnexpected for Vec<u8> {
+    fn as_unexpected(&self) -> Unexpected<'_> {
+        Unexpected::Bytes(self)
+    }
+}
+
+impl<T> AsUnexpected for Option<T> {
+    fn as_unexpected(&self) -> Unexpected<'_> {
+        Unexpected::Option
+    }
+}
+
 /// `Expected` represents an explanation of what data a `Visitor` was expecting
 /// to receive.
 ///
@@ -558,11 +966,12 @@ pub trait Deserialize<'de>: Sized {
     /// If you manually implement this, your recursive deserializations should
     /// use `deserialize_in_place`.
     ///
-    /// This method is stable and an official public API, but hidden from the
-    /// documentation because it is almost never what newbies are looking for.
-    /// Showing it in rustdoc would cause it to be featured more prominently
-    /// than it deserves.
-    #[doc(hidden)]
+    /// The impls provided by this crate reuse existing allocations where
+    /// possible, for example `Vec`, `String`, `HashMap`, and `BTreeMap` clear
+    /// themselves and refill their existing buffers rather than allocating
+    /// fresh ones. Derived impls deserialize each field into the
+    /// corresponding field of `place`. The default implementation just
+    /// delegates to `deserialize` and overwrites `place` with the result.
     fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
     where
         D: Deserializer<'de>,
@@ -990,6 +1399,19 @@ pub trait Deserializer<'de>: Sized {
         Err(Error::custom("u128 is not supported"))
     }
 
+    /// Hint that the `Deserialize` type is expecting an `f16` value.
+    ///
+    /// The default behavior forwards to [`deserialize_f32`].
+    ///
+    /// [`deserialize_f32`]: #tymethod.deserialize_f32
+    #[cfg(feature = "unstable")]
+    fn deserialize_f16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f32(visitor)
+    }
+
     /// Hint that the `Deserialize` type is expecting a `f32` value.
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -1000,6 +1422,18 @@ pub trait Deserializer<'de>: Sized {
     where
         V: Visitor<'de>;
 
+    /// Hint that the `Deserialize` type is expecting an `f128` value.
+    ///
+    /// The default behavior unconditionally returns an error.
+    #[cfg(feature = "unstable")]
+    fn deserialize_f128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let _ = visitor;
+        Err(Error::custom("f128 is not supported"))
+    }
+
     /// Hint that the `Deserialize` type is expecting a `char` value.
     fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -1049,6 +1483,34 @@ pub trait Deserializer<'de>: Sized {
     where
         V: Visitor<'de>;
 
+    /// Hint that the `Deserialize` type is expecting a string value and is
+    /// able to consume it in pieces through [`Visitor::visit_str_chunked`].
+    ///
+    /// Formats that buffer entire values may treat this exactly like
+    /// `deserialize_str`, which is what the default implementation does.
+    /// Streaming formats can instead hand the visitor a [`StrChunkAccess`]
+    /// and avoid materializing very large strings in one allocation.
+    fn deserialize_str_chunked<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    /// Hint that the `Deserialize` type is expecting a byte array and is able
+    /// to consume it in pieces through [`Visitor::visit_bytes_chunked`].
+    ///
+    /// Formats that buffer entire values may treat this exactly like
+    /// `deserialize_bytes`, which is what the default implementation does.
+    /// Streaming formats can instead hand the visitor a [`BytesChunkAccess`]
+    /// and avoid materializing very large byte arrays in one allocation.
+    fn deserialize_bytes_chunked<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
     /// Hint that the `Deserialize` type is expecting an optional value.
     ///
     /// This allows deserializers that encode an optional value as a nullable
@@ -1217,6 +1679,46 @@ pub trait Deserializer<'de>: Sized {
         true
     }
 
+    /// Determine whether the format records the structure of the data, so
+    /// that `Deserialize` implementations may rely on [`deserialize_any`].
+    ///
+    /// Representations that must inspect the data before deciding how to
+    /// interpret it — such as untagged and internally tagged enums — only
+    /// work against self-describing formats. A `Deserialize` implementation
+    /// may consult this flag to fall back to an encoding that does not
+    /// require introspection.
+    ///
+    /// The default implementation of this method returns `true`.
+    /// Non-self-describing data formats should override it to return `false`.
+    /// The value must agree with [`Serializer::is_self_describing`] for the
+    /// same format, since a value serialized in one mode is not required to
+    /// deserialize from the other.
+    ///
+    /// [`deserialize_any`]: Deserializer::deserialize_any
+    /// [`Serializer::is_self_describing`]: crate::Serializer::is_self_describing
+    #[inline]
+    fn is_self_describing(&self) -> bool {
+        true
+    }
+
+    /// Determine what this data format can represent natively, at a finer
+    /// grain than [`is_human_readable`](Deserializer::is_human_readable).
+    ///
+    /// `Deserialize` implementations that accept several encodings can
+    /// consult individual [`Capabilities`] flags to know which one the
+    /// matching `Serialize` implementation will have chosen.
+    ///
+    /// The default implementation returns [`Capabilities::default`]. The
+    /// value must agree with [`Serializer::capabilities`] for the same
+    /// format, since a value serialized under one set of flags is not
+    /// required to deserialize under another.
+    ///
+    /// [`Serializer::capabilities`]: crate::Serializer::capabilities
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+
     // Not public API.
     #[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
     #[doc(hidden)]
@@ -1437,6 +1939,19 @@ pub trait Visitor<'de>: Sized {
         ))
     }
 
+    /// The input contains an `f16`.
+    ///
+    /// The default implementation forwards to [`visit_f32`].
+    ///
+    /// [`visit_f32`]: #method.visit_f32
+    #[cfg(feature = "unstable")]
+    fn visit_f16<E>(self, v: f16) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_f32(v as f32)
+    }
+
     /// The input contains an `f32`.
     ///
     /// The default implementation forwards to [`visit_f64`].
@@ -1459,6 +1974,21 @@ pub trait Visitor<'de>: Sized {
         Err(Error::invalid_type(Unexpected::Float(v), &self))
     }
 
+    /// The input contains an `f128`.
+    ///
+    /// The default implementation fails with a type error.
+    #[cfg(feature = "unstable")]
+    fn visit_f128<E>(self, v: f128) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let _ = v;
+        Err(Error::invalid_type(
+            Unexpected::Other("floating point `f128`"),
+            &self,
+        ))
+    }
+
     /// The input contains a `char`.
     ///
     /// The default implementation forwards to [`visit_str`] as a one-character
@@ -1590,6 +2120,51 @@ pub trait Visitor<'de>: Sized {
         self.visit_bytes(&v)
     }
 
+    /// The input contains a string that the `Deserializer` streams in pieces.
+    ///
+    /// This is called by formats that support
+    /// [`Deserializer::deserialize_str_chunked`] when a value is large enough
+    /// to be worth streaming. Visitors that can consume the string
+    /// incrementally — hashing it, writing it through, counting — should
+    /// override this method and drain the [`StrChunkAccess`] chunk by chunk.
+    ///
+    /// The default implementation accumulates the chunks into a `String` and
+    /// forwards to `visit_string`.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn visit_str_chunked<A>(self, mut chunks: A) -> Result<Self::Value, A::Error>
+    where
+        A: StrChunkAccess<'de>,
+    {
+        let mut string = String::with_capacity(size_hint::cautious::<u8>(chunks.size_hint()));
+        while let Some(chunk) = tri!(chunks.next_chunk()) {
+            string.push_str(chunk);
+        }
+        self.visit_string(string)
+    }
+
+    /// The input contains a byte array that the `Deserializer` streams in
+    /// pieces.
+    ///
+    /// This is called by formats that support
+    /// [`Deserializer::deserialize_bytes_chunked`] when a value is large
+    /// enough to be worth streaming. Visitors that can consume the bytes
+    /// incrementally should override this method and drain the
+    /// [`BytesChunkAccess`] chunk by chunk.
+    ///
+    /// The default implementation accumulates the chunks into a `Vec<u8>` and
+    /// forwards to `visit_byte_buf`.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn visit_bytes_chunked<A>(self, mut chunks: A) -> Result<Self::Value, A::Error>
+    where
+        A: BytesChunkAccess<'de>,
+    {
+        let mut buf = Vec::with_capacity(size_hint::cautious::<u8>(chunks.size_hint()));
+        while let Some(chunk) = tri!(chunks.next_chunk()) {
+            buf.extend_from_slice(chunk);
+        }
+        self.visit_byte_buf(buf)
+    }
+
     /// The input contains an optional that is absent.
     ///
     /// The default implementation fails with a type error.
@@ -1726,6 +2301,44 @@ pub trait SeqAccess<'de> {
         self.next_element_seed(PhantomData)
     }
 
+    /// Deserializes all remaining elements of the sequence, appending them to
+    /// `out`.
+    ///
+    /// This is an optional bulk counterpart to `next_element`. The default
+    /// implementation pulls elements one at a time, but formats that lay out
+    /// contiguous runs of primitives (a binary format holding a `Vec<u8>` or
+    /// `Vec<f32>`, say) can override it to copy whole runs at once.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn next_elements<T>(&mut self, out: &mut Vec<T>) -> Result<(), Self::Error>
+    where
+        T: Deserialize<'de>,
+    {
+        while let Some(value) = tri!(self.next_element()) {
+            out.push(value);
+        }
+        Ok(())
+    }
+
+    /// Deserializes elements into the initialized buffer `buf`, overwriting
+    /// its contents in order. Returns the number of elements written, which is
+    /// less than `buf.len()` only if the sequence ended first.
+    ///
+    /// The default implementation deserializes each element in place so that
+    /// existing allocations are reused; like `next_elements` it may be
+    /// overridden by formats that can fill contiguous primitive runs with a
+    /// memcpy.
+    fn next_slice<T>(&mut self, buf: &mut [T]) -> Result<usize, Self::Error>
+    where
+        T: Deserialize<'de>,
+    {
+        for (filled, dest) in buf.iter_mut().enumerate() {
+            if tri!(self.next_element_seed(crate::seed::InPlaceSeed(dest))).is_none() {
+                return Ok(filled);
+            }
+        }
+        Ok(buf.len())
+    }
+
     /// Returns the number of elements remaining in the sequence, if known.
     #[inline]
     fn size_hint(&self) -> Option<usize> {
@@ -1755,6 +2368,23 @@ where
         (**self).next_element()
     }
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[inline]
+    fn next_elements<T>(&mut self, out: &mut Vec<T>) -> Result<(), Self::Error>
+    where
+        T: Deserialize<'de>,
+    {
+        (**self).next_elements(out)
+    }
+
+    #[inline]
+    fn next_slice<T>(&mut self, buf: &mut [T]) -> Result<usize, Self::Error>
+    where
+        T: Deserialize<'de>,
+    {
+        (**self).next_slice(buf)
+    }
+
     #[inline]
     fn size_hint(&self) -> Option<usize> {
         (**self).size_hint()
@@ -1954,6 +2584,102 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Provides a `Visitor` access to a string that the `Deserializer` streams in
+/// pieces.
+///
+/// Formats hand an implementation of this trait to
+/// [`Visitor::visit_str_chunked`] so that multi-hundred-megabyte strings can
+/// be consumed without a single giant allocation. Chunks may split the string
+/// at arbitrary character boundaries but never inside a UTF-8 code point.
+///
+/// # Lifetime
+///
+/// The `'de` lifetime of this trait is the lifetime of data that may be
+/// borrowed from the input. See the page [Understanding deserializer
+/// lifetimes] for a more detailed explanation of these lifetimes.
+///
+/// [Understanding deserializer lifetimes]: https://serde.rs/lifetimes.html
+pub trait StrChunkAccess<'de> {
+    /// The error type that can be returned if some error occurs during
+    /// deserialization.
+    type Error: Error;
+
+    /// Returns the next chunk of the string, or `None` when the string is
+    /// complete. The chunk is only valid until the next call.
+    fn next_chunk(&mut self) -> Result<Option<&str>, Self::Error>;
+
+    /// Returns the total length of the string in bytes, if known.
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<'de, 'a, A: ?Sized> StrChunkAccess<'de> for &'a mut A
+where
+    A: StrChunkAccess<'de>,
+{
+    type Error = A::Error;
+
+    #[inline]
+    fn next_chunk(&mut self) -> Result<Option<&str>, Self::Error> {
+        (**self).next_chunk()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        (**self).size_hint()
+    }
+}
+
+/// Provides a `Visitor` access to a byte array that the `Deserializer`
+/// streams in pieces.
+///
+/// Formats hand an implementation of this trait to
+/// [`Visitor::visit_bytes_chunked`] so that very large byte arrays can be
+/// consumed without a single giant allocation. Chunks may split the data at
+/// arbitrary positions.
+///
+/// # Lifetime
+///
+/// The `'de` lifetime of this trait is the lifetime of data that may be
+/// borrowed from the input. See the page [Understanding deserializer
+/// lifetimes] for a more detailed explanation of these lifetimes.
+///
+/// [Understanding deserializer lifetimes]: https://serde.rs/lifetimes.html
+pub trait BytesChunkAccess<'de> {
+    /// The error type that can be returned if some error occurs during
+    /// deserialization.
+    type Error: Error;
+
+    /// Returns the next chunk of the byte array, or `None` when the array is
+    /// complete. The chunk is only valid until the next call.
+    fn next_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error>;
+
+    /// Returns the total length of the byte array, if known.
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<'de, 'a, A: ?Sized> BytesChunkAccess<'de> for &'a mut A
+where
+    A: BytesChunkAccess<'de>,
+{
+    type Error = A::Error;
+
+    #[inline]
+    fn next_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        (**self).next_chunk()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        (**self).size_hint()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Provides a `Visitor` access to the data of an enum in the input.
 ///
 /// `EnumAccess` is created by the `Deserializer` and passed to the
@@ -2269,6 +2995,61 @@ struct OneOf {
     names: &'static [&'static str],
 }
 
+/// Finds the expected name closest to the unrecognized input, if any is close
+/// enough to plausibly be a typo, for use in "did you mean" suggestions.
+///
+/// Comparisons are capped at 64 bytes so that the edit distance can be
+/// computed without allocating; longer names are never suggested.
+fn did_you_mean(input: &str, names: &'static [&'static str]) -> Option<&'static str> {
+    // Arbitrary cutoffs, in the spirit of rustc's suggestions: very short
+    // inputs produce too many false positives to be worth suggesting for,
+    // and the more of the name the user got right, the more edits we are
+    // willing to attribute to a typo.
+    if input.len() < 3 {
+        return None;
+    }
+    // With a single expected name the message already spells it out, so
+    // repeating it as a suggestion adds nothing.
+    if names.len() <= 1 {
+        return None;
+    }
+    let max_distance = cmp::max(1, input.len() / 3);
+
+    let mut best: Option<(usize, &'static str)> = None;
+    for name in names {
+        if let Some(distance) = levenshtein(input.as_bytes(), name.as_bytes()) {
+            if distance <= max_distance && best.map_or(true, |(b, _)| distance < b) {
+                best = Some((distance, name));
+            }
+        }
+    }
+    best.map(|(_, name)| name)
+}
+
+/// Levenshtein edit distance between two byte strings, or `None` if either is
+/// longer than the 64-byte cap.
+fn levenshtein(a: &[u8], b: &[u8]) -> Option<usize> {
+    const CAP: usize = 64;
+    if a.len() > CAP || b.len() > CAP {
+        return None;
+    }
+
+    let mut row = [0usize; CAP + 1];
+    for (j, entry) in row.iter_mut().enumerate().take(b.len() + 1) {
+        *entry = j;
+    }
+    for (i, &byte_a) in a.iter().enumerate() {
+        let mut prev_diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &byte_b) in b.iter().enumerate() {
+            let substitution = prev_diagonal + (byte_a != byte_b) as usize;
+            prev_diagonal = row[j + 1];
+            row[j + 1] = cmp::min(substitution, cmp::min(row[j], prev_diagonal) + 1);
+        }
+    }
+    Some(row[b.len()])
+}
+
 impl Display for OneOf {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self.names.len() {
@@ -0,0 +1,136 @@
+//! Checked numeric conversions for `Visitor` implementations.
+//!
+//! Format deserializers and custom visitors frequently need to accept an
+//! integer visited as one width into a value of another width, for example a
+//! `u8` visited through [`Visitor::visit_u64`]. Re-implementing the range
+//! checks is easy to get subtly wrong, so this module exposes the same
+//! conversion rules that serde's own `Deserialize` impls for the primitive
+//! integer types use, producing an `invalid_value` error when the input does
+//! not fit.
+//!
+//! ```edition2021
+//! use serde::de::{self, Visitor};
+//! use std::fmt;
+//!
+//! struct PortVisitor;
+//!
+//! impl<'de> Visitor<'de> for PortVisitor {
+//!     type Value = u16;
+//!
+//!     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+//!         formatter.write_str("a port number")
+//!     }
+//!
+//!     fn visit_u64<E>(self, value: u64) -> Result<u16, E>
+//!     where
+//!         E: de::Error,
+//!     {
+//!         de::num::checked_from_u64(value, &self)
+//!     }
+//!
+//!     fn visit_i64<E>(self, value: i64) -> Result<u16, E>
+//!     where
+//!         E: de::Error,
+//!     {
+//!         de::num::checked_from_i64(value, &self)
+//!     }
+//! }
+//! ```
+//!
+//! [`Visitor::visit_u64`]: crate::de::Visitor::visit_u64
+
+use crate::lib::convert::TryFrom;
+use crate::lib::*;
+
+use crate::de::{Error, Expected, Unexpected};
+
+/// Converts a signed integer into any primitive integer type, producing an
+/// `invalid_value` error if the value is out of range for the target.
+pub fn checked_from_i64<T, E>(value: i64, expected: &dyn Expected) -> Result<T, E>
+where
+    T: TryFrom<i64>,
+    E: Error,
+{
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => Err(Error::invalid_value(Unexpected::Signed(value), expected)),
+    }
+}
+
+/// Converts an unsigned integer into any primitive integer type, producing an
+/// `invalid_value` error if the value is out of range for the target.
+pub fn checked_from_u64<T, E>(value: u64, expected: &dyn Expected) -> Result<T, E>
+where
+    T: TryFrom<u64>,
+    E: Error,
+{
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => Err(Error::invalid_value(Unexpected::Unsigned(value), expected)),
+    }
+}
+
+/// Converts a 128-bit signed integer into any primitive integer type,
+/// producing an `invalid_value` error if the value is out of range for the
+/// target.
+///
+/// `Unexpected` has no 128-bit variant, so the error reports the value as
+/// `Unexpected::Other`.
+pub fn checked_from_i128<T, E>(value: i128, expected: &dyn Expected) -> Result<T, E>
+where
+    T: TryFrom<i128>,
+    E: Error,
+{
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => Err(Error::invalid_value(Unexpected::Other("i128"), expected)),
+    }
+}
+
+/// Converts a 128-bit unsigned integer into any primitive integer type,
+/// producing an `invalid_value` error if the value is out of range for the
+/// target.
+///
+/// `Unexpected` has no 128-bit variant, so the error reports the value as
+/// `Unexpected::Other`.
+pub fn checked_from_u128<T, E>(value: u128, expected: &dyn Expected) -> Result<T, E>
+where
+    T: TryFrom<u128>,
+    E: Error,
+{
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => Err(Error::invalid_value(Unexpected::Other("u128"), expected)),
+    }
+}
+
+/// Converts a float into any primitive integer type, producing an
+/// `invalid_value` error if the value has a fractional part or is out of
+/// range for the target.
+///
+/// This is the conventional bridging rule for formats that do not distinguish
+/// integers from floats: `1.0` deserializes as the integer `1` while `1.5`
+/// and out-of-range values are rejected.
+pub fn checked_from_f64<T, E>(value: f64, expected: &dyn Expected) -> Result<T, E>
+where
+    T: TryFrom<i64> + TryFrom<u64>,
+    E: Error,
+{
+    // `%` rather than `f64::fract` so that this works without std.
+    if value % 1.0 != 0.0 {
+        return Err(Error::invalid_value(Unexpected::Float(value), expected));
+    }
+    if value >= 0.0 && value <= u64::MAX as f64 {
+        match T::try_from(value as u64) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(Error::invalid_value(Unexpected::Float(value), expected)),
+        }
+    } else if value < 0.0 && value >= i64::MIN as f64 {
+        match T::try_from(value as i64) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(Error::invalid_value(Unexpected::Float(value), expected)),
+        }
+    } else {
+        Err(Error::invalid_value(Unexpected::Float(value), expected))
+    }
+}
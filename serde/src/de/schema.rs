@@ -0,0 +1,458 @@
+//! Schema-driven adapter for non-self-describing input.
+//!
+//! Compact binary formats typically encode values positionally, without any
+//! type information in the stream. Deserializers for such formats cannot
+//! support [`deserialize_any`], which rules out untagged enums,
+//! `#[serde(flatten)]`, and other features that need the input to describe
+//! itself.
+//!
+//! [`SchemaDeserializer`] bridges that gap: it pairs a positional
+//! `Deserializer` with a runtime [`Schema`] describing what the stream
+//! contains, and exposes a self-describing `Deserializer` on top. Every type
+//! hint from the caller is ignored in favor of the schema, so `deserialize_any`
+//! and everything built on it work over a schemaless stream.
+//!
+//! ```edition2021
+//! # use serde_derive::Deserialize;
+//! use serde::de::schema::{Schema, SchemaDeserializer};
+//! use serde::Deserialize as _;
+//!
+//! # fn example<'de, D>(positional: D) -> Result<(), D::Error>
+//! # where
+//! #     D: serde::Deserializer<'de>,
+//! # {
+//! // The stream contains two unsigned integers, in this order.
+//! let schema = Schema::Struct {
+//!     name: "Rect".to_owned(),
+//!     fields: vec![
+//!         ("width".to_owned(), Schema::U64),
+//!         ("height".to_owned(), Schema::U64),
+//!     ],
+//! };
+//!
+//! #[derive(Deserialize)]
+//! struct Rect {
+//!     width: u64,
+//!     height: u64,
+//! }
+//!
+//! let rect = Rect::deserialize(SchemaDeserializer::new(positional, &schema))?;
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`deserialize_any`]: crate::Deserializer::deserialize_any
+
+use crate::lib::*;
+
+use crate::de::value::StrDeserializer;
+use crate::de::{DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+
+/// Runtime description of the shape of a positionally encoded value.
+///
+/// A schema mirrors the serde data model: leaves are primitive types and
+/// interior nodes describe how child values are laid out in the stream.
+/// Structs are encoded as their field values in declaration order, which is
+/// how non-self-describing formats conventionally serialize them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Schema {
+    /// A boolean.
+    Bool,
+    /// A signed integer, read as `i64`.
+    I64,
+    /// An unsigned integer, read as `u64`.
+    U64,
+    /// A floating point number, read as `f64`.
+    F64,
+    /// A character.
+    Char,
+    /// A string.
+    Str,
+    /// A byte array.
+    Bytes,
+    /// The unit value.
+    Unit,
+    /// An optional value.
+    Option(Box<Schema>),
+    /// A variable-length sequence of values sharing one schema.
+    Seq(Box<Schema>),
+    /// A fixed-length sequence of heterogeneous values.
+    Tuple(Vec<Schema>),
+    /// A map with uniform key and value schemas.
+    Map(Box<Schema>, Box<Schema>),
+    /// A struct, encoded as its field values in order.
+    Struct {
+        /// The name of the struct.
+        name: String,
+        /// The fields in the order they appear in the stream.
+        fields: Vec<(String, Schema)>,
+    },
+}
+
+/// A self-describing `Deserializer` layered over a positional one.
+///
+/// See the [module documentation](self) for details.
+pub struct SchemaDeserializer<'s, D> {
+    de: D,
+    schema: &'s Schema,
+}
+
+impl<'s, D> SchemaDeserializer<'s, D> {
+    /// Pairs a positional deserializer with the schema describing its input.
+    pub fn new(de: D, schema: &'s Schema) -> Self {
+        SchemaDeserializer { de, schema }
+    }
+}
+
+impl<'de, 's, D> Deserializer<'de> for SchemaDeserializer<'s, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.schema {
+            Schema::Bool => self.de.deserialize_bool(visitor),
+            Schema::I64 => self.de.deserialize_i64(visitor),
+            Schema::U64 => self.de.deserialize_u64(visitor),
+            Schema::F64 => self.de.deserialize_f64(visitor),
+            Schema::Char => self.de.deserialize_char(visitor),
+            Schema::Str => self.de.deserialize_str(visitor),
+            Schema::Bytes => self.de.deserialize_bytes(visitor),
+            Schema::Unit => self.de.deserialize_unit(visitor),
+            Schema::Option(inner) => self.de.deserialize_option(SchemaOptionVisitor {
+                delegate: visitor,
+                schema: inner,
+            }),
+            Schema::Seq(element) => self.de.deserialize_seq(SchemaSeqVisitor {
+                delegate: visitor,
+                element,
+            }),
+            Schema::Tuple(elements) => self.de.deserialize_tuple(
+                elements.len(),
+                SchemaTupleVisitor {
+                    delegate: visitor,
+                    elements,
+                },
+            ),
+            Schema::Map(key, value) => self.de.deserialize_map(SchemaMapVisitor {
+                delegate: visitor,
+                key,
+                value,
+            }),
+            Schema::Struct { fields, .. } => self.de.deserialize_tuple(
+                fields.len(),
+                SchemaStructVisitor {
+                    delegate: visitor,
+                    fields,
+                },
+            ),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// A `DeserializeSeed` that wraps the deserializer it is given in a
+/// `SchemaDeserializer`, so that nested values are also driven by the schema.
+struct SchemaSeed<'s, S> {
+    seed: S,
+    schema: &'s Schema,
+}
+
+impl<'de, 's, S> DeserializeSeed<'de> for SchemaSeed<'s, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.seed
+            .deserialize(SchemaDeserializer::new(deserializer, self.schema))
+    }
+}
+
+struct SchemaOptionVisitor<'s, V> {
+    delegate: V,
+    schema: &'s Schema,
+}
+
+impl<'de, 's, V> Visitor<'de> for SchemaOptionVisitor<'s, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.delegate.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.delegate
+            .visit_some(SchemaDeserializer::new(deserializer, self.schema))
+    }
+}
+
+struct SchemaSeqVisitor<'s, V> {
+    delegate: V,
+    element: &'s Schema,
+}
+
+impl<'de, 's, V> Visitor<'de> for SchemaSeqVisitor<'s, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.delegate.visit_seq(SchemaSeqAccess {
+            seq,
+            element: self.element,
+        })
+    }
+}
+
+struct SchemaSeqAccess<'s, A> {
+    seq: A,
+    element: &'s Schema,
+}
+
+impl<'de, 's, A> SeqAccess<'de> for SchemaSeqAccess<'s, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.seq.next_element_seed(SchemaSeed {
+            seed,
+            schema: self.element,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.seq.size_hint()
+    }
+}
+
+struct SchemaTupleVisitor<'s, V> {
+    delegate: V,
+    elements: &'s [Schema],
+}
+
+impl<'de, 's, V> Visitor<'de> for SchemaTupleVisitor<'s, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.delegate.visit_seq(SchemaTupleAccess {
+            seq,
+            elements: self.elements.iter(),
+        })
+    }
+}
+
+struct SchemaTupleAccess<'s, A> {
+    seq: A,
+    elements: slice::Iter<'s, Schema>,
+}
+
+impl<'de, 's, A> SeqAccess<'de> for SchemaTupleAccess<'s, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.elements.next() {
+            Some(schema) => self.seq.next_element_seed(SchemaSeed { seed, schema }),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+struct SchemaMapVisitor<'s, V> {
+    delegate: V,
+    key: &'s Schema,
+    value: &'s Schema,
+}
+
+impl<'de, 's, V> Visitor<'de> for SchemaMapVisitor<'s, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.delegate.visit_map(SchemaMapAccess {
+            map,
+            key: self.key,
+            value: self.value,
+        })
+    }
+}
+
+struct SchemaMapAccess<'s, A> {
+    map: A,
+    key: &'s Schema,
+    value: &'s Schema,
+}
+
+impl<'de, 's, A> MapAccess<'de> for SchemaMapAccess<'s, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.map.next_key_seed(SchemaSeed {
+            seed,
+            schema: self.key,
+        })
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.map.next_value_seed(SchemaSeed {
+            seed,
+            schema: self.value,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+struct SchemaStructVisitor<'s, V> {
+    delegate: V,
+    fields: &'s [(String, Schema)],
+}
+
+impl<'de, 's, V> Visitor<'de> for SchemaStructVisitor<'s, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.delegate.visit_map(SchemaStructAccess {
+            seq,
+            fields: self.fields.iter(),
+            value_schema: None,
+        })
+    }
+}
+
+/// Presents the positional field values of a struct as a map whose keys are
+/// the field names from the schema.
+struct SchemaStructAccess<'s, A> {
+    seq: A,
+    fields: slice::Iter<'s, (String, Schema)>,
+    value_schema: Option<&'s Schema>,
+}
+
+impl<'de, 's, A> MapAccess<'de> for SchemaStructAccess<'s, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.fields.next() {
+            Some((name, schema)) => {
+                self.value_schema = Some(schema);
+                seed.deserialize(StrDeserializer::new(name)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let schema = self
+            .value_schema
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        match tri!(self.seq.next_element_seed(SchemaSeed { seed, schema })) {
+            Some(value) => Ok(value),
+            None => Err(Error::custom(
+                "positional input ended before all fields of the struct were read",
+            )),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::lib::*;
+
+use crate::de::{Deserialize, SeqAccess};
+
+/// An iterator over the elements of a [`SeqAccess`].
+///
+/// `SeqIter` adapts the pull-style `next_element` interface into a regular
+/// `Iterator` yielding `Result<T, A::Error>`, so `Visitor::visit_seq`
+/// implementations can use iterator combinators instead of an explicit loop.
+///
+/// ```edition2021
+/// use serde::de::{SeqAccess, SeqIter, Visitor};
+/// use std::fmt;
+///
+/// struct SumVisitor;
+///
+/// impl<'de> Visitor<'de> for SumVisitor {
+///     type Value = u64;
+///
+///     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+///         formatter.write_str("a sequence of integers")
+///     }
+///
+///     fn visit_seq<A>(self, seq: A) -> Result<u64, A::Error>
+///     where
+///         A: SeqAccess<'de>,
+///     {
+///         SeqIter::<A, u64>::new(seq).sum()
+///     }
+/// }
+/// ```
+pub struct SeqIter<'de, A, T> {
+    access: A,
+    marker: PhantomData<(&'de (), T)>,
+}
+
+impl<'de, A, T> SeqIter<'de, A, T> {
+    /// Creates an iterator over the elements of the given `SeqAccess`.
+    pub fn new(access: A) -> Self
+    where
+        A: SeqAccess<'de>,
+        T: Deserialize<'de>,
+    {
+        SeqIter {
+            access,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, A, T> Iterator for SeqIter<'de, A, T>
+where
+    A: SeqAccess<'de>,
+    T: Deserialize<'de>,
+{
+    type Item = Result<T, A::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.access.next_element() {
+            Ok(Some(element)) => Some(Ok(element)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.access.size_hint() {
+            Some(size) => (size, Some(size)),
+            None => (0, None),
+        }
+    }
+}
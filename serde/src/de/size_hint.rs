@@ -1,5 +1,14 @@
 use crate::lib::*;
 
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    any(
+        all(not(no_target_has_atomic), target_has_atomic = "ptr"),
+        all(no_target_has_atomic, not(no_std_atomic)),
+    )
+))]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 pub fn from_bounds<I>(iter: &I) -> Option<usize>
 where
     I: Iterator,
@@ -8,15 +17,40 @@ where
 }
 
 #[cfg(any(feature = "std", feature = "alloc"))]
-pub fn cautious<Element>(hint: Option<usize>) -> usize {
-    const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+pub(crate) const DEFAULT_MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    any(
+        all(not(no_target_has_atomic), target_has_atomic = "ptr"),
+        all(no_target_has_atomic, not(no_std_atomic)),
+    )
+))]
+pub(crate) static MAX_PREALLOC_BYTES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_PREALLOC_BYTES);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub(crate) fn max_prealloc_bytes() -> usize {
+    #[cfg(any(
+        all(not(no_target_has_atomic), target_has_atomic = "ptr"),
+        all(no_target_has_atomic, not(no_std_atomic)),
+    ))]
+    return MAX_PREALLOC_BYTES.load(Ordering::Relaxed);
 
+    #[cfg(not(any(
+        all(not(no_target_has_atomic), target_has_atomic = "ptr"),
+        all(no_target_has_atomic, not(no_std_atomic)),
+    )))]
+    DEFAULT_MAX_PREALLOC_BYTES
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn cautious<Element>(hint: Option<usize>) -> usize {
     if mem::size_of::<Element>() == 0 {
         0
     } else {
         cmp::min(
             hint.unwrap_or(0),
-            MAX_PREALLOC_BYTES / mem::size_of::<Element>(),
+            max_prealloc_bytes() / mem::size_of::<Element>(),
         )
     }
 }
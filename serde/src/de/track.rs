@@ -0,0 +1,1044 @@
+//! Field-path context for deserialization errors.
+//!
+//! A plain deserialization error reports only the leaf failure, such as
+//! "invalid type: string, expected u32", which is of little help inside a
+//! deeply nested structure. [`deserialize`] runs a `Deserialize` impl with
+//! path tracking and, on failure, reports where in the structure the error
+//! occurred, like `config.servers[3].port`.
+//!
+//! ```edition2021
+//! use serde::de::track;
+//! use serde::de::value::{Error, MapDeserializer};
+//!
+//! # use serde_derive::Deserialize;
+//! #[derive(Deserialize, Debug)]
+//! struct Outer {
+//!     inner: Vec<u16>,
+//! }
+//!
+//! let deserializer = MapDeserializer::<_, Error>::new(
+//!     [("inner", vec![3_u64, 70_000])].into_iter(),
+//! );
+//!
+//! let err = track::deserialize::<_, Outer>(deserializer).unwrap_err();
+//! assert_eq!(err.path(), "inner[1]");
+//! ```
+//!
+//! Tracking wraps every level of the deserialization, so it costs a string
+//! clone per map key; use it where error quality matters more than peak
+//! throughput, or re-run a failed deserialization with tracking enabled just
+//! to produce the report.
+
+use crate::lib::*;
+
+use crate::de::{
+    Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+
+/// Deserializes `T`, attaching the path of the failing field to any error.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, PathError<D::Error>>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let track = Track {
+        path: RefCell::new(None),
+    };
+    match T::deserialize(TrackedDeserializer {
+        de: deserializer,
+        chain: &Chain::Root,
+        track: &track,
+    }) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(PathError {
+            path: track.path.into_inner().unwrap_or_else(String::new),
+            error,
+        }),
+    }
+}
+
+/// A deserialization error together with the path at which it occurred.
+///
+/// The path is rendered with `.` between struct fields and map keys and
+/// `[index]` for sequence elements, e.g. `config.servers[3].port`. Segments
+/// whose name could not be determined render as `?`. The path is empty when
+/// the error occurred at the top level.
+#[derive(Debug)]
+pub struct PathError<E> {
+    path: String,
+    error: E,
+}
+
+impl<E> PathError<E> {
+    /// The path at which the error occurred.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// A reference to the underlying error.
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// The underlying error, discarding the path.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> Display for PathError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            Display::fmt(&self.error, formatter)
+        } else {
+            write!(formatter, "{}: {}", self.path, self.error)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> std::error::Error for PathError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Records the path of the deepest failure. Only the first trigger wins,
+/// because errors bubble outward through shallower wrappers afterwards.
+struct Track {
+    path: RefCell<Option<String>>,
+}
+
+impl Track {
+    fn trigger(&self, chain: &Chain) {
+        let mut path = self.path.borrow_mut();
+        if path.is_none() {
+            *path = Some(chain.render());
+        }
+    }
+}
+
+/// The path to the value currently being deserialized, as a parent-linked
+/// list on the call stack.
+enum Chain<'a> {
+    Root,
+    Field {
+        parent: &'a Chain<'a>,
+        name: String,
+    },
+    Index {
+        parent: &'a Chain<'a>,
+        index: usize,
+    },
+    Unknown {
+        parent: &'a Chain<'a>,
+    },
+}
+
+impl<'a> Chain<'a> {
+    fn render(&self) -> String {
+        let mut rendered = String::new();
+        self.write(&mut rendered);
+        rendered
+    }
+
+    fn write(&self, out: &mut String) {
+        match self {
+            Chain::Root => {}
+            Chain::Field { parent, name } => {
+                parent.write(out);
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(name);
+            }
+            Chain::Index { parent, index } => {
+                parent.write(out);
+                let _ = fmt::write(out, format_args!("[{}]", index));
+            }
+            Chain::Unknown { parent } => {
+                parent.write(out);
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push('?');
+            }
+        }
+    }
+}
+
+/// Forwards to the wrapped deserializer, recording the current path when an
+/// error passes through.
+struct TrackedDeserializer<'a, D> {
+    de: D,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+}
+
+macro_rules! forward_tracked {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let result = self.de.$method(TrackedVisitor {
+                    visitor,
+                    chain: self.chain,
+                    track: self.track,
+                });
+                match result {
+                    Ok(value) => Ok(value),
+                    Err(err) => {
+                        self.track.trigger(self.chain);
+                        Err(err)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, D> Deserializer<'de> for TrackedDeserializer<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_tracked! {
+        deserialize_any deserialize_bool
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+        deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit
+        deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.de.deserialize_unit_struct(
+            name,
+            TrackedVisitor {
+                visitor,
+                chain: self.chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, self.chain)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.de.deserialize_newtype_struct(
+            name,
+            TrackedVisitor {
+                visitor,
+                chain: self.chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, self.chain)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.de.deserialize_tuple(
+            len,
+            TrackedVisitor {
+                visitor,
+                chain: self.chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, self.chain)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.de.deserialize_tuple_struct(
+            name,
+            len,
+            TrackedVisitor {
+                visitor,
+                chain: self.chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, self.chain)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.de.deserialize_struct(
+            name,
+            fields,
+            TrackedVisitor {
+                visitor,
+                chain: self.chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, self.chain)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.de.deserialize_enum(
+            name,
+            variants,
+            TrackedVisitor {
+                visitor,
+                chain: self.chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, self.chain)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+}
+
+fn track_result<T, E>(result: Result<T, E>, track: &Track, chain: &Chain) -> Result<T, E> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(err) => {
+            track.trigger(chain);
+            Err(err)
+        }
+    }
+}
+
+struct TrackedVisitor<'a, V> {
+    visitor: V,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+}
+
+macro_rules! forward_tracked_visit {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: crate::de::Error,
+            {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, V> Visitor<'de> for TrackedVisitor<'a, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_tracked_visit! {
+        visit_bool: bool,
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+        visit_str: &str,
+        visit_borrowed_str: &'de str,
+        visit_string: String,
+        visit_bytes: &[u8],
+        visit_borrowed_bytes: &'de [u8],
+        visit_byte_buf: Vec<u8>,
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        self.visitor.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor.visit_some(TrackedDeserializer {
+            de: deserializer,
+            chain: self.chain,
+            track: self.track,
+        })
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor.visit_newtype_struct(TrackedDeserializer {
+            de: deserializer,
+            chain: self.chain,
+            track: self.track,
+        })
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.visitor.visit_seq(TrackedSeqAccess {
+            access: seq,
+            chain: self.chain,
+            track: self.track,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.visitor.visit_map(TrackedMapAccess {
+            access: map,
+            chain: self.chain,
+            track: self.track,
+            key: None,
+        })
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        self.visitor.visit_enum(TrackedEnumAccess {
+            access: data,
+            chain: self.chain,
+            track: self.track,
+        })
+    }
+}
+
+struct TrackedSeed<'a, S> {
+    seed: S,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+}
+
+impl<'de, 'a, S> DeserializeSeed<'de> for TrackedSeed<'a, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.seed.deserialize(TrackedDeserializer {
+            de: deserializer,
+            chain: self.chain,
+            track: self.track,
+        })
+    }
+}
+
+struct TrackedSeqAccess<'a, A> {
+    access: A,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+    index: usize,
+}
+
+impl<'de, 'a, A> SeqAccess<'de> for TrackedSeqAccess<'a, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let chain = Chain::Index {
+            parent: self.chain,
+            index: self.index,
+        };
+        self.index += 1;
+        let result = self.access.next_element_seed(TrackedSeed {
+            seed,
+            chain: &chain,
+            track: self.track,
+        });
+        track_result(result, self.track, &chain)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.access.size_hint()
+    }
+}
+
+struct TrackedMapAccess<'a, A> {
+    access: A,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+    key: Option<String>,
+}
+
+impl<'de, 'a, A> MapAccess<'de> for TrackedMapAccess<'a, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        self.key = None;
+        let result = self.access.next_key_seed(CaptureKey {
+            seed,
+            key: &mut self.key,
+        });
+        track_result(result, self.track, self.chain)
+    }
+
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let chain = match self.key.take() {
+            Some(name) => Chain::Field {
+                parent: self.chain,
+                name,
+            },
+            None => Chain::Unknown { parent: self.chain },
+        };
+        let result = self.access.next_value_seed(TrackedSeed {
+            seed,
+            chain: &chain,
+            track: self.track,
+        });
+        track_result(result, self.track, &chain)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.access.size_hint()
+    }
+}
+
+struct TrackedEnumAccess<'a, A> {
+    access: A,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+}
+
+impl<'de, 'a, A> EnumAccess<'de> for TrackedEnumAccess<'a, A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = TrackedVariantAccess<'a, A::Variant>;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let mut name = None;
+        let result = self.access.variant_seed(CaptureKey {
+            seed,
+            key: &mut name,
+        });
+        match track_result(result, self.track, self.chain) {
+            Ok((value, variant)) => Ok((
+                value,
+                TrackedVariantAccess {
+                    variant,
+                    chain: self.chain,
+                    track: self.track,
+                    name,
+                },
+            )),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+struct TrackedVariantAccess<'a, A> {
+    variant: A,
+    chain: &'a Chain<'a>,
+    track: &'a Track,
+    name: Option<String>,
+}
+
+impl<'a, A> TrackedVariantAccess<'a, A> {
+    fn chain(name: Option<String>, parent: &'a Chain<'a>) -> Chain<'a> {
+        match name {
+            Some(name) => Chain::Field { parent, name },
+            None => Chain::Unknown { parent },
+        }
+    }
+}
+
+impl<'de, 'a, A> VariantAccess<'de> for TrackedVariantAccess<'a, A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        let chain = Self::chain(self.name, self.chain);
+        track_result(self.variant.unit_variant(), self.track, &chain)
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let chain = Self::chain(self.name, self.chain);
+        let result = self.variant.newtype_variant_seed(TrackedSeed {
+            seed,
+            chain: &chain,
+            track: self.track,
+        });
+        track_result(result, self.track, &chain)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let chain = Self::chain(self.name, self.chain);
+        let result = self.variant.tuple_variant(
+            len,
+            TrackedVisitor {
+                visitor,
+                chain: &chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, &chain)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let chain = Self::chain(self.name, self.chain);
+        let result = self.variant.struct_variant(
+            fields,
+            TrackedVisitor {
+                visitor,
+                chain: &chain,
+                track: self.track,
+            },
+        );
+        track_result(result, self.track, &chain)
+    }
+}
+
+/// Captures the textual form of a map key or variant name while the wrapped
+/// seed deserializes it.
+struct CaptureKey<'a, S> {
+    seed: S,
+    key: &'a mut Option<String>,
+}
+
+impl<'de, 'a, S> DeserializeSeed<'de> for CaptureKey<'a, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.seed.deserialize(CaptureKeyDeserializer {
+            de: deserializer,
+            key: self.key,
+        })
+    }
+}
+
+struct CaptureKeyDeserializer<'a, D> {
+    de: D,
+    key: &'a mut Option<String>,
+}
+
+macro_rules! forward_capture {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.de.$method(CaptureKeyVisitor {
+                    visitor,
+                    key: self.key,
+                })
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, D> Deserializer<'de> for CaptureKeyDeserializer<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_capture! {
+        deserialize_any deserialize_bool
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+        deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit
+        deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_unit_struct(
+            name,
+            CaptureKeyVisitor {
+                visitor,
+                key: self.key,
+            },
+        )
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_newtype_struct(
+            name,
+            CaptureKeyVisitor {
+                visitor,
+                key: self.key,
+            },
+        )
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_tuple(
+            len,
+            CaptureKeyVisitor {
+                visitor,
+                key: self.key,
+            },
+        )
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_tuple_struct(
+            name,
+            len,
+            CaptureKeyVisitor {
+                visitor,
+                key: self.key,
+            },
+        )
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_struct(
+            name,
+            fields,
+            CaptureKeyVisitor {
+                visitor,
+                key: self.key,
+            },
+        )
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_enum(
+            name,
+            variants,
+            CaptureKeyVisitor {
+                visitor,
+                key: self.key,
+            },
+        )
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+}
+
+struct CaptureKeyVisitor<'a, V> {
+    visitor: V,
+    key: &'a mut Option<String>,
+}
+
+macro_rules! forward_uncaptured_visit {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: crate::de::Error,
+            {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+macro_rules! capture_int_visit {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: crate::de::Error,
+            {
+                *self.key = Some({
+                    let mut key = String::new();
+                    let _ = fmt::write(&mut key, format_args!("{}", v));
+                    key
+                });
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, V> Visitor<'de> for CaptureKeyVisitor<'a, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_uncaptured_visit! {
+        visit_bool: bool,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+    }
+
+    capture_int_visit! {
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        *self.key = Some(v.to_owned());
+        self.visitor.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        *self.key = Some(v.to_owned());
+        self.visitor.visit_borrowed_str(v)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        *self.key = Some(v.clone());
+        self.visitor.visit_string(v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        if let Ok(s) = str::from_utf8(v) {
+            *self.key = Some(s.to_owned());
+        }
+        self.visitor.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        if let Ok(s) = str::from_utf8(v) {
+            *self.key = Some(s.to_owned());
+        }
+        self.visitor.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        if let Ok(s) = str::from_utf8(&v) {
+            *self.key = Some(s.to_owned());
+        }
+        self.visitor.visit_byte_buf(v)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        self.visitor.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor.visit_some(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: crate::de::Error,
+    {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.visitor.visit_seq(seq)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.visitor.visit_map(map)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        self.visitor.visit_enum(data)
+    }
+}
@@ -182,6 +182,94 @@ impl<E> Debug for UnitDeserializer<E> {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+impl<'de, T, E> IntoDeserializer<'de, E> for Option<T>
+where
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Deserializer = OptionDeserializer<T, E>;
+
+    fn into_deserializer(self) -> OptionDeserializer<T, E> {
+        OptionDeserializer::new(self)
+    }
+}
+
+/// A deserializer holding an `Option<T>`.
+pub struct OptionDeserializer<T, E> {
+    value: Option<T>,
+    marker: PhantomData<E>,
+}
+
+impl<T, E> OptionDeserializer<T, E> {
+    #[allow(missing_docs)]
+    pub fn new(value: Option<T>) -> Self {
+        OptionDeserializer {
+            value,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, E> de::Deserializer<'de> for OptionDeserializer<T, E>
+where
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Error = E;
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier ignored_any
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => visitor.visit_some(value.into_deserializer()),
+            None => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => visitor.visit_some(value.into_deserializer()),
+            None => visitor.visit_none(),
+        }
+    }
+}
+
+impl<T, E> Clone for OptionDeserializer<T, E>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        OptionDeserializer {
+            value: self.value.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, E> Debug for OptionDeserializer<T, E>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("OptionDeserializer")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// A deserializer that cannot be instantiated.
 #[cfg(feature = "unstable")]
 pub struct NeverDeserializer<E> {
@@ -878,6 +966,9 @@ impl<'de, E> Debug for BorrowedBytesDeserializer<'de, E> {
 ////////////////////////////////////////////////////////////////////////////////
 
 /// A deserializer that iterates over a sequence.
+///
+/// The iterator may be owned, and elements of mixed types can be represented
+/// by buffering each one into a [`BufferedValue`].
 #[derive(Clone)]
 pub struct SeqDeserializer<I, E> {
     iter: iter::Fuse<I>,
@@ -1040,6 +1131,194 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// A deserializer that presents the elements of a tuple as a sequence.
+///
+/// Unlike [`SeqDeserializer`], the elements do not need to share a common
+/// type; each one only needs its own [`IntoDeserializer`] impl.
+pub struct TupleDeserializer<T, E> {
+    value: T,
+    marker: PhantomData<E>,
+}
+
+impl<T, E> TupleDeserializer<T, E> {
+    #[allow(missing_docs)]
+    pub fn new(value: T) -> Self {
+        TupleDeserializer {
+            value,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, E> Clone for TupleDeserializer<T, E>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        TupleDeserializer {
+            value: self.value.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, E> Debug for TupleDeserializer<T, E>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("TupleDeserializer")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+macro_rules! tuple_deserializer {
+    ($($len:expr => $access:ident => ($($name:ident $field:ident)+))+) => {
+        $(
+            impl<'de, E, $($name),+> IntoDeserializer<'de, E> for ($($name,)+)
+            where
+                $($name: IntoDeserializer<'de, E>,)+
+                E: de::Error,
+            {
+                type Deserializer = TupleDeserializer<($($name,)+), E>;
+
+                fn into_deserializer(self) -> Self::Deserializer {
+                    TupleDeserializer::new(self)
+                }
+            }
+
+            impl<'de, E, $($name),+> de::Deserializer<'de> for TupleDeserializer<($($name,)+), E>
+            where
+                $($name: IntoDeserializer<'de, E>,)+
+                E: de::Error,
+            {
+                type Error = E;
+
+                forward_to_deserialize_any! {
+                    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str
+                    string bytes byte_buf option unit unit_struct newtype_struct
+                    map struct enum identifier ignored_any
+                }
+
+                fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+                where
+                    V: de::Visitor<'de>,
+                {
+                    let ($($field,)+) = self.value;
+                    let mut access = $access {
+                        $($field: Some($field),)+
+                        marker: PhantomData,
+                    };
+                    let value = tri!(visitor.visit_seq(&mut access));
+                    let remaining = access.remaining();
+                    if remaining == 0 {
+                        Ok(value)
+                    } else {
+                        Err(de::Error::invalid_length(
+                            $len - remaining,
+                            &ExpectedInSeq($len),
+                        ))
+                    }
+                }
+
+                fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+                where
+                    V: de::Visitor<'de>,
+                {
+                    self.deserialize_any(visitor)
+                }
+
+                fn deserialize_tuple<V>(
+                    self,
+                    _len: usize,
+                    visitor: V,
+                ) -> Result<V::Value, Self::Error>
+                where
+                    V: de::Visitor<'de>,
+                {
+                    self.deserialize_any(visitor)
+                }
+
+                fn deserialize_tuple_struct<V>(
+                    self,
+                    _name: &'static str,
+                    _len: usize,
+                    visitor: V,
+                ) -> Result<V::Value, Self::Error>
+                where
+                    V: de::Visitor<'de>,
+                {
+                    self.deserialize_any(visitor)
+                }
+            }
+
+            struct $access<$($name,)+ E> {
+                $($field: Option<$name>,)+
+                marker: PhantomData<E>,
+            }
+
+            impl<$($name,)+ E> $access<$($name,)+ E> {
+                fn remaining(&self) -> usize {
+                    let mut len = 0;
+                    $(
+                        if self.$field.is_some() {
+                            len += 1;
+                        }
+                    )+
+                    len
+                }
+            }
+
+            impl<'de, E, $($name),+> de::SeqAccess<'de> for $access<$($name,)+ E>
+            where
+                $($name: IntoDeserializer<'de, E>,)+
+                E: de::Error,
+            {
+                type Error = E;
+
+                fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+                where
+                    T: de::DeserializeSeed<'de>,
+                {
+                    $(
+                        if let Some(value) = self.$field.take() {
+                            return seed.deserialize(value.into_deserializer()).map(Some);
+                        }
+                    )+
+                    Ok(None)
+                }
+
+                fn size_hint(&self) -> Option<usize> {
+                    Some(self.remaining())
+                }
+            }
+        )+
+    };
+}
+
+tuple_deserializer! {
+    1 => TupleAccess1 => (T0 t0)
+    2 => TupleAccess2 => (T0 t0 T1 t1)
+    3 => TupleAccess3 => (T0 t0 T1 t1 T2 t2)
+    4 => TupleAccess4 => (T0 t0 T1 t1 T2 t2 T3 t3)
+    5 => TupleAccess5 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4)
+    6 => TupleAccess6 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5)
+    7 => TupleAccess7 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6)
+    8 => TupleAccess8 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7)
+    9 => TupleAccess9 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8)
+    10 => TupleAccess10 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9)
+    11 => TupleAccess11 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9 T10 t10)
+    12 => TupleAccess12 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9 T10 t10 T11 t11)
+    13 => TupleAccess13 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9 T10 t10 T11 t11 T12 t12)
+    14 => TupleAccess14 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9 T10 t10 T11 t11 T12 t12 T13 t13)
+    15 => TupleAccess15 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9 T10 t10 T11 t11 T12 t12 T13 t13 T14 t14)
+    16 => TupleAccess16 => (T0 t0 T1 t1 T2 t2 T3 t3 T4 t4 T5 t5 T6 t6 T7 t7 T8 t8 T9 t9 T10 t10 T11 t11 T12 t12 T13 t13 T14 t14 T15 t15)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// A deserializer holding a `SeqAccess`.
 #[derive(Clone, Debug)]
 pub struct SeqAccessDeserializer<A> {
@@ -1076,6 +1355,9 @@ where
 ////////////////////////////////////////////////////////////////////////////////
 
 /// A deserializer that iterates over a map.
+///
+/// The iterator may be owned, and entries of mixed key or value types can be
+/// represented by buffering them into [`BufferedValue`]s.
 pub struct MapDeserializer<'de, I, E>
 where
     I: Iterator,
@@ -1541,92 +1823,374 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
-mod private {
-    use crate::lib::*;
+/// A deserializer that presents `(&'static str, value)` pairs as a struct.
+///
+/// The fields are visited as a map, which is the layout derived `Deserialize`
+/// impls accept, so this is the building block for `deserialize_with` helpers
+/// and config mergers that assemble a struct out of values obtained
+/// elsewhere. Requesting a sequence visits the field values alone in order,
+/// matching how structs deserialize from sequences.
+///
+/// Struct fields rarely share one type; [`BufferedValue`] erases the value
+/// types so that heterogeneous fields fit in one iterator:
+///
+/// ```edition2021
+/// use serde::de::value::{self, BufferedValue, StructDeserializer};
+/// use serde::Deserialize;
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize, PartialEq, Debug)]
+/// struct Config {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// # fn main() -> Result<(), value::Error> {
+/// let fields = vec![
+///     ("name", BufferedValue::from_serialize("alpha")?),
+///     ("retries", BufferedValue::from_serialize(&3u32)?),
+/// ];
+///
+/// let config = Config::deserialize(StructDeserializer::<_, value::Error>::new(
+///     fields.into_iter(),
+/// ))?;
+/// assert_eq!(
+///     config,
+///     Config {
+///         name: "alpha".to_owned(),
+///         retries: 3,
+///     },
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct StructDeserializer<'de, I, E> {
+    iter: I,
+    lifetime: PhantomData<&'de ()>,
+    error: PhantomData<E>,
+}
 
-    use crate::de::{
-        self, DeserializeSeed, Deserializer, MapAccess, Unexpected, VariantAccess, Visitor,
-    };
+impl<'de, I, E> StructDeserializer<'de, I, E> {
+    /// Construct a new `StructDeserializer<I, E>`.
+    pub fn new(iter: I) -> Self {
+        StructDeserializer {
+            iter,
+            lifetime: PhantomData,
+            error: PhantomData,
+        }
+    }
+}
 
-    pub struct UnitOnly<E> {
-        marker: PhantomData<E>,
+impl<'de, I, T, E> de::Deserializer<'de> for StructDeserializer<'de, I, E>
+where
+    I: Iterator<Item = (&'static str, T)>,
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<W>(self, visitor: W) -> Result<W::Value, Self::Error>
+    where
+        W: de::Visitor<'de>,
+    {
+        MapDeserializer::new(self.iter).deserialize_any(visitor)
     }
 
-    pub fn unit_only<T, E>(t: T) -> (T, UnitOnly<E>) {
-        (
-            t,
-            UnitOnly {
-                marker: PhantomData,
-            },
-        )
+    fn deserialize_seq<W>(self, visitor: W) -> Result<W::Value, Self::Error>
+    where
+        W: de::Visitor<'de>,
+    {
+        SeqDeserializer::new(self.iter.map(|(_, value)| value)).deserialize_any(visitor)
     }
 
-    impl<'de, E> de::VariantAccess<'de> for UnitOnly<E>
+    fn deserialize_tuple<W>(self, _len: usize, visitor: W) -> Result<W::Value, Self::Error>
     where
-        E: de::Error,
+        W: de::Visitor<'de>,
     {
-        type Error = E;
+        self.deserialize_seq(visitor)
+    }
 
-        fn unit_variant(self) -> Result<(), Self::Error> {
-            Ok(())
-        }
+    fn deserialize_tuple_struct<W>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: W,
+    ) -> Result<W::Value, Self::Error>
+    where
+        W: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
 
-        fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
-        where
-            T: de::DeserializeSeed<'de>,
-        {
-            Err(de::Error::invalid_type(
-                Unexpected::UnitVariant,
-                &"newtype variant",
-            ))
-        }
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct map struct enum
+        identifier ignored_any
+    }
+}
 
-        fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: de::Visitor<'de>,
-        {
-            Err(de::Error::invalid_type(
-                Unexpected::UnitVariant,
-                &"tuple variant",
-            ))
-        }
+impl<'de, I, T, E> IntoDeserializer<'de, E> for StructDeserializer<'de, I, E>
+where
+    I: Iterator<Item = (&'static str, T)>,
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Deserializer = Self;
 
-        fn struct_variant<V>(
-            self,
-            _fields: &'static [&'static str],
-            _visitor: V,
-        ) -> Result<V::Value, Self::Error>
-        where
-            V: de::Visitor<'de>,
-        {
-            Err(de::Error::invalid_type(
-                Unexpected::UnitVariant,
-                &"struct variant",
-            ))
-        }
+    fn into_deserializer(self) -> Self {
+        self
     }
+}
 
-    pub struct MapAsEnum<A> {
-        map: A,
-    }
+////////////////////////////////////////////////////////////////////////////////
 
-    pub fn map_as_enum<A>(map: A) -> MapAsEnum<A> {
-        MapAsEnum { map }
+/// A deserializer for an enum variant named up front, with the payload
+/// supplied by any [`IntoDeserializer`] value.
+///
+/// All four variant shapes are covered: pass `None` for a unit variant, and
+/// for newtype, tuple, and struct variants a payload whose deserializer
+/// produces the corresponding value, sequence, or map. [`BufferedValue`] is
+/// a convenient payload type when the variant contents are heterogeneous.
+///
+/// ```edition2021
+/// use serde::de::value::{self, EnumDeserializer};
+/// use serde::Deserialize;
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize, PartialEq, Debug)]
+/// enum Shape {
+///     Point,
+///     Circle(u32),
+/// }
+///
+/// # fn main() -> Result<(), value::Error> {
+/// let point = Shape::deserialize(EnumDeserializer::<u32, value::Error>::new("Point", None))?;
+/// assert_eq!(point, Shape::Point);
+///
+/// let circle = Shape::deserialize(EnumDeserializer::<_, value::Error>::new(
+///     "Circle",
+///     Some(7u32),
+/// ))?;
+/// assert_eq!(circle, Shape::Circle(7));
+/// # Ok(())
+/// # }
+/// ```
+pub struct EnumDeserializer<T, E> {
+    variant: &'static str,
+    value: Option<T>,
+    error: PhantomData<E>,
+}
+
+impl<T, E> EnumDeserializer<T, E> {
+    /// Construct a new `EnumDeserializer<T, E>`.
+    pub fn new(variant: &'static str, value: Option<T>) -> Self {
+        EnumDeserializer {
+            variant,
+            value,
+            error: PhantomData,
+        }
     }
+}
 
-    impl<'de, A> VariantAccess<'de> for MapAsEnum<A>
+impl<'de, T, E> de::Deserializer<'de> for EnumDeserializer<T, E>
+where
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<W>(self, visitor: W) -> Result<W::Value, Self::Error>
     where
-        A: MapAccess<'de>,
+        W: de::Visitor<'de>,
     {
-        type Error = A::Error;
+        visitor.visit_enum(self)
+    }
 
-        fn unit_variant(mut self) -> Result<(), Self::Error> {
-            self.map.next_value()
-        }
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
 
-        fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value, Self::Error>
-        where
-            T: DeserializeSeed<'de>,
+impl<'de, T, E> de::EnumAccess<'de> for EnumDeserializer<T, E>
+where
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Error = E;
+    type Variant = Self;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        let variant = tri!(seed.deserialize(StrDeserializer::new(self.variant)));
+        Ok((variant, self))
+    }
+}
+
+impl<'de, T, E> de::VariantAccess<'de> for EnumDeserializer<T, E>
+where
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            None => Ok(()),
+            Some(_) => Err(de::Error::invalid_type(
+                de::Unexpected::NewtypeVariant,
+                &"unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value.into_deserializer()),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<W>(self, _len: usize, visitor: W) -> Result<W::Value, Self::Error>
+    where
+        W: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => value.into_deserializer().deserialize_seq(visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<W>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: W,
+    ) -> Result<W::Value, Self::Error>
+    where
+        W: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => value.into_deserializer().deserialize_map(visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+impl<'de, T, E> IntoDeserializer<'de, E> for EnumDeserializer<T, E>
+where
+    T: IntoDeserializer<'de, E>,
+    E: de::Error,
+{
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+mod private {
+    use crate::lib::*;
+
+    use crate::de::{
+        self, DeserializeSeed, Deserializer, MapAccess, Unexpected, VariantAccess, Visitor,
+    };
+
+    pub struct UnitOnly<E> {
+        marker: PhantomData<E>,
+    }
+
+    pub fn unit_only<T, E>(t: T) -> (T, UnitOnly<E>) {
+        (
+            t,
+            UnitOnly {
+                marker: PhantomData,
+            },
+        )
+    }
+
+    impl<'de, E> de::VariantAccess<'de> for UnitOnly<E>
+    where
+        E: de::Error,
+    {
+        type Error = E;
+
+        fn unit_variant(self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+        where
+            T: de::DeserializeSeed<'de>,
+        {
+            Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            ))
+        }
+
+        fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            ))
+        }
+
+        fn struct_variant<V>(
+            self,
+            _fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            ))
+        }
+    }
+
+    pub struct MapAsEnum<A> {
+        map: A,
+    }
+
+    pub fn map_as_enum<A>(map: A) -> MapAsEnum<A> {
+        MapAsEnum { map }
+    }
+
+    impl<'de, A> VariantAccess<'de> for MapAsEnum<A>
+    where
+        A: MapAccess<'de>,
+    {
+        type Error = A::Error;
+
+        fn unit_variant(mut self) -> Result<(), Self::Error> {
+            self.map.next_value()
+        }
+
+        fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value, Self::Error>
+        where
+            T: DeserializeSeed<'de>,
         {
             self.map.next_value_seed(seed)
         }
@@ -1706,3 +2270,722 @@ mod private {
     pub type First<T> = <T as Pair>::First;
     pub type Second<T> = <T as Pair>::Second;
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Serializes a value into an in-memory representation and returns a
+/// deserializer that replays it.
+///
+/// This bridges `Serialize` directly to `Deserialize` without going through a
+/// concrete data format, so any serializable value can be re-deserialized into
+/// another type. Enums are buffered in their externally tagged representation;
+/// the usual enum representation attributes apply on both sides of the bridge.
+///
+/// ```edition2021
+/// use serde::de::value;
+/// use serde::Deserialize;
+/// use serde_derive::{Deserialize, Serialize};
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Deserialize, PartialEq, Debug)]
+/// struct Endpoint {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// fn main() -> Result<(), value::Error> {
+///     let config = Config {
+///         host: "localhost".to_owned(),
+///         port: 8080,
+///     };
+///
+///     let endpoint = Endpoint::deserialize(value::to_deserializer(&config)?)?;
+///     assert_eq!(
+///         endpoint,
+///         Endpoint {
+///             host: "localhost".to_owned(),
+///             port: 8080,
+///         }
+///     );
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+pub fn to_deserializer<T>(value: &T) -> Result<impl Deserializer<'static, Error = Error>, Error>
+where
+    T: ?Sized + ser::Serialize,
+{
+    let content = tri!(value.serialize(crate::__private::ser::ContentSerializer::<Error>::new()));
+    Ok(crate::__private::de::ContentDeserializer::new(buffer(
+        content,
+    )))
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+fn buffer(content: crate::__private::ser::Content) -> crate::__private::de::Content<'static> {
+    use crate::__private::de::Content as De;
+    use crate::__private::ser::Content as Ser;
+
+    match content {
+        Ser::Bool(v) => De::Bool(v),
+        Ser::U8(v) => De::U8(v),
+        Ser::U16(v) => De::U16(v),
+        Ser::U32(v) => De::U32(v),
+        Ser::U64(v) => De::U64(v),
+        Ser::I8(v) => De::I8(v),
+        Ser::I16(v) => De::I16(v),
+        Ser::I32(v) => De::I32(v),
+        Ser::I64(v) => De::I64(v),
+        Ser::F32(v) => De::F32(v),
+        Ser::F64(v) => De::F64(v),
+        Ser::Char(v) => De::Char(v),
+        Ser::String(v) => De::String(v),
+        Ser::Bytes(v) => De::ByteBuf(v),
+        Ser::None => De::None,
+        Ser::Some(v) => De::Some(Box::new(buffer(*v))),
+        Ser::Unit | Ser::UnitStruct(_) => De::Unit,
+        Ser::UnitVariant(_, _, variant) => De::Str(variant),
+        Ser::NewtypeStruct(_, v) => De::Newtype(Box::new(buffer(*v))),
+        Ser::NewtypeVariant(_, _, variant, v) => {
+            De::Map(iter::once((De::Str(variant), buffer(*v))).collect())
+        }
+        Ser::Seq(v) | Ser::Tuple(v) | Ser::TupleStruct(_, v) => {
+            De::Seq(v.into_iter().map(buffer).collect())
+        }
+        Ser::TupleVariant(_, _, variant, v) => De::Map(
+            iter::once((
+                De::Str(variant),
+                De::Seq(v.into_iter().map(buffer).collect()),
+            ))
+            .collect(),
+        ),
+        Ser::Map(v) => De::Map(v.into_iter().map(|(k, v)| (buffer(k), buffer(v))).collect()),
+        Ser::Struct(_, fields) => De::Map(
+            fields
+                .into_iter()
+                .map(|(name, v)| (De::Str(name), buffer(v)))
+                .collect(),
+        ),
+        Ser::StructVariant(_, _, variant, fields) => De::Map(
+            iter::once((
+                De::Str(variant),
+                De::Map(
+                    fields
+                        .into_iter()
+                        .map(|(name, v)| (De::Str(name), buffer(v)))
+                        .collect(),
+                ),
+            ))
+            .collect(),
+        ),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// A format-agnostic buffered value.
+///
+/// `BufferedValue` captures arbitrary self-describing input — scalars,
+/// strings, bytes, options, sequences, and maps — in memory, without tying it
+/// to any particular data format or Rust type. The captured value can be
+/// inspected, replayed into a `Deserialize` implementation through
+/// [`IntoDeserializer`], or serialized back out. This is the buffering that
+/// untagged and internally tagged enums use internally, promoted to a public
+/// type.
+///
+/// ```edition2021
+/// use serde::de::value::{BufferedValue, Error, MapDeserializer};
+/// use serde::de::{Deserialize, IntoDeserializer};
+/// use std::collections::BTreeMap;
+///
+/// fn main() -> Result<(), Error> {
+///     let de = MapDeserializer::<_, Error>::new(vec![("alpha", 1_u32), ("beta", 2)].into_iter());
+///
+///     // Capture the input without committing to a Rust type yet.
+///     let value = BufferedValue::deserialize(de)?;
+///
+///     // Replay it once a type has been chosen.
+///     let map = BTreeMap::<String, u32>::deserialize(value.into_deserializer())?;
+///     assert_eq!(map["beta"], 2);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+#[derive(Clone, Debug)]
+pub struct BufferedValue<'de>(crate::__private::de::Content<'de>);
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl<'de> BufferedValue<'de> {
+    /// The string representation of this value, if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.as_str()
+    }
+
+    /// Captures the output of a `Serialize` implementation.
+    ///
+    /// The recorded value owns all of its data and can be replayed into any
+    /// `Deserialize` implementation through [`IntoDeserializer`], which makes
+    /// it possible to drive Serialize→Deserialize roundtrips, snapshots, and
+    /// other tooling without going through a concrete data format. Enum
+    /// variants are captured in the externally tagged layout, matching how
+    /// [`BufferedValue`] buffers them on the deserialization side.
+    pub fn from_serialize<T>(value: &T) -> Result<BufferedValue<'static>, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(CaptureSerializer).map(BufferedValue)
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl<'de> de::Deserialize<'de> for BufferedValue<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::__private::de::Content::deserialize(deserializer).map(BufferedValue)
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl<'de> ser::Serialize for BufferedValue<'de> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        ser::Serialize::serialize(&SerializeContent(&self.0), serializer)
+    }
+}
+
+// Private adapter serializing a borrowed Content tree. Newtypes are buffered
+// without their name, so they re-serialize transparently as the inner value.
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+struct SerializeContent<'a, 'de>(&'a crate::__private::de::Content<'de>);
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl<'a, 'de> ser::Serialize for SerializeContent<'a, 'de> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use crate::__private::de::Content;
+
+        match *self.0 {
+            Content::Bool(v) => serializer.serialize_bool(v),
+            Content::U8(v) => serializer.serialize_u8(v),
+            Content::U16(v) => serializer.serialize_u16(v),
+            Content::U32(v) => serializer.serialize_u32(v),
+            Content::U64(v) => serializer.serialize_u64(v),
+            Content::I8(v) => serializer.serialize_i8(v),
+            Content::I16(v) => serializer.serialize_i16(v),
+            Content::I32(v) => serializer.serialize_i32(v),
+            Content::I64(v) => serializer.serialize_i64(v),
+            Content::F32(v) => serializer.serialize_f32(v),
+            Content::F64(v) => serializer.serialize_f64(v),
+            Content::Char(v) => serializer.serialize_char(v),
+            Content::String(ref v) => serializer.serialize_str(v),
+            Content::Str(v) => serializer.serialize_str(v),
+            Content::ByteBuf(ref v) => serializer.serialize_bytes(v),
+            Content::Bytes(v) => serializer.serialize_bytes(v),
+            Content::None => serializer.serialize_none(),
+            Content::Some(ref v) => serializer.serialize_some(&SerializeContent(v)),
+            Content::Unit => serializer.serialize_unit(),
+            Content::Newtype(ref v) => ser::Serialize::serialize(&SerializeContent(v), serializer),
+            Content::Seq(ref v) => serializer.collect_seq(v.iter().map(SerializeContent)),
+            Content::Map(ref v) => serializer.collect_map(
+                v.iter()
+                    .map(|(key, value)| (SerializeContent(key), SerializeContent(value))),
+            ),
+        }
+    }
+}
+
+// Private serializer capturing any Serialize output as an owned Content
+// tree, for `BufferedValue::from_serialize`.
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+struct CaptureSerializer;
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::Serializer for CaptureSerializer {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    type SerializeSeq = CaptureSeq;
+    type SerializeTuple = CaptureSeq;
+    type SerializeTupleStruct = CaptureSeq;
+    type SerializeTupleVariant = CaptureSeqVariant;
+    type SerializeMap = CaptureMap;
+    type SerializeStruct = CaptureMap;
+    type SerializeStructVariant = CaptureMapVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::I8(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::I16(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::I32(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::I64(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::U8(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::U16(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::U32(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::U64(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::F32(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::F64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::ByteBuf(v.to_owned()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let inner = tri!(value.serialize(CaptureSerializer));
+        Ok(crate::__private::de::Content::Some(Box::new(inner)))
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Str(variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let inner = tri!(value.serialize(CaptureSerializer));
+        Ok(crate::__private::de::Content::Newtype(Box::new(inner)))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let inner = tri!(value.serialize(CaptureSerializer));
+        let mut entries = Vec::with_capacity(1);
+        entries.push((crate::__private::de::Content::Str(variant), inner));
+        Ok(crate::__private::de::Content::Map(entries))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<CaptureSeq, Error> {
+        Ok(CaptureSeq {
+            elements: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<CaptureSeq, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<CaptureSeq, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<CaptureSeqVariant, Error> {
+        Ok(CaptureSeqVariant {
+            variant,
+            elements: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<CaptureMap, Error> {
+        Ok(CaptureMap {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<CaptureMap, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<CaptureMapVariant, Error> {
+        Ok(CaptureMapVariant {
+            variant,
+            fields: Vec::with_capacity(len),
+        })
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+struct CaptureSeq {
+    elements: Vec<crate::__private::de::Content<'static>>,
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeSeq for CaptureSeq {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.elements.push(tri!(value.serialize(CaptureSerializer)));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Seq(self.elements))
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeTuple for CaptureSeq {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeTupleStruct for CaptureSeq {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+struct CaptureSeqVariant {
+    variant: &'static str,
+    elements: Vec<crate::__private::de::Content<'static>>,
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeTupleVariant for CaptureSeqVariant {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.elements.push(tri!(value.serialize(CaptureSerializer)));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        let mut entries = Vec::with_capacity(1);
+        entries.push((
+            crate::__private::de::Content::Str(self.variant),
+            crate::__private::de::Content::Seq(self.elements),
+        ));
+        Ok(crate::__private::de::Content::Map(entries))
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+struct CaptureMap {
+    entries: Vec<(
+        crate::__private::de::Content<'static>,
+        crate::__private::de::Content<'static>,
+    )>,
+    key: Option<crate::__private::de::Content<'static>>,
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeMap for CaptureMap {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.key = Some(tri!(key.serialize(CaptureSerializer)));
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(ser::Error::custom("serialize_value called before serialize_key")),
+        };
+        self.entries.push((key, tri!(value.serialize(CaptureSerializer))));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Map(self.entries))
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeStruct for CaptureMap {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.entries.push((
+            crate::__private::de::Content::Str(key),
+            tri!(value.serialize(CaptureSerializer)),
+        ));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(crate::__private::de::Content::Map(self.entries))
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+struct CaptureMapVariant {
+    variant: &'static str,
+    fields: Vec<(
+        crate::__private::de::Content<'static>,
+        crate::__private::de::Content<'static>,
+    )>,
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl ser::SerializeStructVariant for CaptureMapVariant {
+    type Ok = crate::__private::de::Content<'static>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.fields.push((
+            crate::__private::de::Content::Str(key),
+            tri!(value.serialize(CaptureSerializer)),
+        ));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        let mut entries = Vec::with_capacity(1);
+        entries.push((
+            crate::__private::de::Content::Str(self.variant),
+            crate::__private::de::Content::Map(self.fields),
+        ));
+        Ok(crate::__private::de::Content::Map(entries))
+    }
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl<'de, E> IntoDeserializer<'de, E> for BufferedValue<'de>
+where
+    E: de::Error,
+{
+    type Deserializer = BufferedValueDeserializer<'de, E>;
+
+    fn into_deserializer(self) -> BufferedValueDeserializer<'de, E> {
+        BufferedValueDeserializer {
+            value: self.0,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A deserializer that replays a [`BufferedValue`].
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+pub struct BufferedValueDeserializer<'de, E> {
+    value: crate::__private::de::Content<'de>,
+    marker: PhantomData<E>,
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+macro_rules! forward_to_content_deserializer {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                crate::__private::de::ContentDeserializer::new(self.value).$method(visitor)
+            }
+        )*
+    };
+}
+
+#[cfg(all(not(no_serde_derive), any(feature = "std", feature = "alloc")))]
+impl<'de, E> Deserializer<'de> for BufferedValueDeserializer<'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    forward_to_content_deserializer! {
+        deserialize_any deserialize_bool
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        crate::__private::de::ContentDeserializer::new(self.value)
+            .deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        crate::__private::de::ContentDeserializer::new(self.value)
+            .deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        crate::__private::de::ContentDeserializer::new(self.value).deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        crate::__private::de::ContentDeserializer::new(self.value)
+            .deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        crate::__private::de::ContentDeserializer::new(self.value)
+            .deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        crate::__private::de::ContentDeserializer::new(self.value)
+            .deserialize_enum(name, variants, visitor)
+    }
+}
@@ -0,0 +1,33 @@
+use crate::lib::*;
+
+/// A fixed-size buffer that `Display` impls can be formatted into through
+/// `fmt::Write`, without requiring `std::io`.
+pub(crate) struct Buf<'a> {
+    bytes: &'a mut [u8],
+    offset: usize,
+}
+
+impl<'a> Buf<'a> {
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        Buf { bytes, offset: 0 }
+    }
+
+    pub fn as_str(&self) -> &str {
+        let slice = &self.bytes[..self.offset];
+        // fmt::Write only receives strings, so every byte written is part of
+        // valid UTF-8.
+        str::from_utf8(slice).expect("must be valid UTF-8")
+    }
+}
+
+impl<'a> fmt::Write for Buf<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.offset + s.len() > self.bytes.len() {
+            Err(fmt::Error)
+        } else {
+            self.bytes[self.offset..self.offset + s.len()].copy_from_slice(s.as_bytes());
+            self.offset += s.len();
+            Ok(())
+        }
+    }
+}
@@ -102,7 +102,10 @@
 // discussion of these features please refer to this issue:
 //
 //    https://github.com/serde-rs/serde/issues/812
-#![cfg_attr(feature = "unstable", feature(error_in_core, never_type))]
+#![cfg_attr(
+    feature = "unstable",
+    feature(allocator_api, btreemap_alloc, error_in_core, never_type, f16, f128)
+)]
 #![allow(unknown_lints, bare_trait_objects, deprecated)]
 // Ignored clippy and clippy_pedantic lints
 #![allow(
@@ -168,12 +171,16 @@ mod lib {
         pub use std::*;
     }
 
-    pub use self::core::{cmp, iter, mem, num, ptr, slice, str};
+    pub use self::core::{cmp, iter, num, ptr, str};
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub use self::core::{mem, slice};
     pub use self::core::{f32, f64};
     pub use self::core::{i16, i32, i64, i8, isize};
     pub use self::core::{u16, u32, u64, u8, usize};
 
     pub use self::core::cell::{Cell, RefCell};
+    #[cfg(not(no_once_cell))]
+    pub use self::core::cell::OnceCell;
     pub use self::core::clone::{self, Clone};
     pub use self::core::cmp::Reverse;
     pub use self::core::convert::{self, From, Into};
@@ -181,7 +188,10 @@ mod lib {
     pub use self::core::fmt::{self, Debug, Display};
     pub use self::core::marker::{self, PhantomData};
     pub use self::core::num::Wrapping;
-    pub use self::core::ops::{Bound, Range, RangeFrom, RangeInclusive, RangeTo};
+    #[cfg(not(no_num_saturating))]
+    pub use self::core::num::Saturating;
+    pub use self::core::borrow::Borrow;
+    pub use self::core::ops::{Bound, Deref, DerefMut, Range, RangeFrom, RangeInclusive, RangeTo};
     pub use self::core::option::{self, Option};
     pub use self::core::result::{self, Result};
     pub use self::core::time::Duration;
@@ -196,6 +206,11 @@ mod lib {
     #[cfg(feature = "std")]
     pub use std::string::{String, ToString};
 
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    pub use alloc::format;
+    #[cfg(feature = "std")]
+    pub use std::format;
+
     #[cfg(all(feature = "alloc", not(feature = "std")))]
     pub use alloc::vec::Vec;
     #[cfg(feature = "std")]
@@ -221,6 +236,14 @@ mod lib {
     #[cfg(feature = "std")]
     pub use std::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
 
+    #[cfg(all(feature = "fallible-alloc", feature = "alloc", not(feature = "std")))]
+    pub use alloc::collections::TryReserveError;
+    #[cfg(all(feature = "fallible-alloc", feature = "std"))]
+    pub use std::collections::TryReserveError;
+
+    #[cfg(all(feature = "unstable", any(feature = "std", feature = "alloc")))]
+    pub use self::core::alloc::Allocator;
+
     #[cfg(all(not(no_core_cstr), not(feature = "std")))]
     pub use self::core::ffi::CStr;
     #[cfg(feature = "std")]
@@ -232,7 +255,15 @@ mod lib {
     pub use std::ffi::CString;
 
     #[cfg(feature = "std")]
-    pub use std::{error, net};
+    pub use std::error;
+
+    #[cfg(all(not(no_core_net), not(feature = "std")))]
+    pub use self::core::net;
+    #[cfg(feature = "std")]
+    pub use std::net;
+
+    #[cfg(all(feature = "std", not(no_std_backtrace)))]
+    pub use std::backtrace::Backtrace;
 
     #[cfg(feature = "std")]
     pub use std::collections::{HashMap, HashSet};
@@ -243,12 +274,17 @@ mod lib {
     #[cfg(feature = "std")]
     pub use std::io::Write;
     #[cfg(feature = "std")]
-    pub use std::path::{Path, PathBuf};
+    pub use std::path::{Component, Components, Path, PathBuf};
     #[cfg(feature = "std")]
     pub use std::sync::{Mutex, RwLock};
+    #[cfg(all(feature = "std", not(no_once_cell)))]
+    pub use std::sync::OnceLock;
     #[cfg(feature = "std")]
     pub use std::time::{SystemTime, UNIX_EPOCH};
 
+    #[cfg(feature = "instant")]
+    pub use std::time::Instant;
+
     #[cfg(all(feature = "std", no_target_has_atomic, not(no_std_atomic)))]
     pub use std::sync::atomic::{
         AtomicBool, AtomicI16, AtomicI32, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU8,
@@ -292,7 +328,15 @@ mod macros;
 #[macro_use]
 mod integer128;
 
+mod capabilities;
+#[cfg(any(feature = "std", not(no_core_net)))]
+mod format;
+
+pub mod bytes;
 pub mod de;
+pub mod meta;
+#[cfg(all(feature = "rc", feature = "std"))]
+pub mod rc;
 pub mod ser;
 
 #[doc(inline)]
@@ -0,0 +1,55 @@
+//! Type-level metadata declared through derive attributes.
+//!
+//! Format crates sometimes need extra information about a type that the serde
+//! data model does not carry — XML namespaces, database column types,
+//! protobuf options and the like. Historically each format crate invented its
+//! own attribute macro for this. The [`Metadata`] trait provides a common
+//! channel instead: metadata is declared on the type with
+//! `#[serde(meta(key = "value"))]` and a format that cares about it opts in
+//! by bounding on `Metadata`.
+//!
+//! ```edition2021
+//! # use serde_derive::Serialize;
+//! use serde::meta::Metadata;
+//!
+//! #[derive(Serialize)]
+//! #[serde(meta(xmlns = "http://example.com/ns"))]
+//! struct Document {
+//!     #[serde(meta(column = "TEXT"))]
+//!     title: String,
+//! }
+//!
+//! assert_eq!(
+//!     Document::container_metadata(),
+//!     [("xmlns", "http://example.com/ns")]
+//! );
+//! assert_eq!(Document::field_metadata("title"), [("column", "TEXT")]);
+//! ```
+
+/// Compile-time key-value metadata attached to a type.
+///
+/// The `Serialize` derive implements this trait for a type whenever at least
+/// one `#[serde(meta(...))]` attribute is present on the container, a field,
+/// or a variant. All methods have empty defaults so the trait can also be
+/// implemented by hand with only the relevant lookup filled in.
+///
+/// Field and variant lookups use serialized names, so `rename` and
+/// `rename_all` are already applied.
+pub trait Metadata {
+    /// Metadata declared on the container itself.
+    fn container_metadata() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Metadata declared on the field with the given serialized name.
+    fn field_metadata(field: &str) -> &'static [(&'static str, &'static str)] {
+        let _ = field;
+        &[]
+    }
+
+    /// Metadata declared on the variant with the given serialized name.
+    fn variant_metadata(variant: &str) -> &'static [(&'static str, &'static str)] {
+        let _ = variant;
+        &[]
+    }
+}
@@ -11,9 +11,12 @@ use crate::de::{MapAccess, Unexpected};
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 pub use self::content::{
-    Content, ContentDeserializer, ContentRefDeserializer, EnumDeserializer,
-    InternallyTaggedUnitVisitor, TagContentOtherField, TagContentOtherFieldVisitor,
-    TagOrContentField, TagOrContentFieldVisitor, TaggedContentVisitor, UntaggedUnitVisitor,
+    internally_tagged_tuple_content, Content, ContentDeserializer, ContentRefDeserializer,
+    EnumDeserializer, InternallyTaggedUnitVisitor, KeyedByDeserialize, KeyedBySeed,
+    MapFromPairsSeed,
+    TagContentOtherField,
+    TagContentOtherFieldVisitor, TagOrContentField, TagOrContentFieldVisitor,
+    TaggedContentVisitor, UntaggedUnitVisitor,
 };
 
 pub use crate::seed::InPlaceSeed;
@@ -58,6 +61,140 @@ where
     Deserialize::deserialize(deserializer)
 }
 
+/// Like `missing_field`, but reports the message given in
+/// `#[serde(missing_field_error = "...")]` instead of the generic
+/// "missing field" one.
+pub fn missing_field_custom<'de, V, E>(msg: &'static str) -> Result<V, E>
+where
+    V: Deserialize<'de>,
+    E: Error,
+{
+    struct MissingFieldDeserializer<E>(&'static str, PhantomData<E>);
+
+    impl<'de, E> Deserializer<'de> for MissingFieldDeserializer<E>
+    where
+        E: Error,
+    {
+        type Error = E;
+
+        fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, E>
+        where
+            V: Visitor<'de>,
+        {
+            Err(Error::custom(self.0))
+        }
+
+        fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+        where
+            V: Visitor<'de>,
+        {
+            visitor.visit_none()
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    let deserializer = MissingFieldDeserializer(msg, PhantomData);
+    Deserialize::deserialize(deserializer)
+}
+
+/// Resolves a field identifier against static name tables, used by
+/// `#[serde(compact_codegen)]` deserializers in place of a per-container
+/// identifier visitor. `names` holds the primary field names in declaration
+/// order and `aliases` one row of accepted spellings per field; the result is
+/// the index of the matched field, or `None` for an unknown identifier when
+/// `deny_unknown` is false.
+pub fn deserialize_field_index<'de, D>(
+    deserializer: D,
+    names: &'static [&'static str],
+    aliases: &'static [&'static [&'static str]],
+    deny_unknown: bool,
+) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct FieldIndexVisitor {
+        names: &'static [&'static str],
+        aliases: &'static [&'static [&'static str]],
+        deny_unknown: bool,
+    }
+
+    impl FieldIndexVisitor {
+        fn find(&self, field: &[u8]) -> Option<u64> {
+            for (index, name) in self.names.iter().enumerate() {
+                if name.as_bytes() == field {
+                    return Some(index as u64);
+                }
+            }
+            for (index, row) in self.aliases.iter().enumerate() {
+                if row.iter().any(|alias| alias.as_bytes() == field) {
+                    return Some(index as u64);
+                }
+            }
+            None
+        }
+    }
+
+    impl<'de> Visitor<'de> for FieldIndexVisitor {
+        type Value = Option<u64>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("field identifier")
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            if value < self.names.len() as u64 {
+                Ok(Some(value))
+            } else if self.deny_unknown {
+                Err(Error::invalid_value(
+                    crate::de::Unexpected::Unsigned(value),
+                    &self,
+                ))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            match self.find(value.as_bytes()) {
+                Some(index) => Ok(Some(index)),
+                None if self.deny_unknown => Err(Error::unknown_field(value, self.names)),
+                None => Ok(None),
+            }
+        }
+
+        fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            match self.find(value) {
+                Some(index) => Ok(Some(index)),
+                None if self.deny_unknown => {
+                    let value = crate::__private::from_utf8_lossy(value);
+                    Err(Error::unknown_field(&value, self.names))
+                }
+                None => Ok(None),
+            }
+        }
+    }
+
+    deserializer.deserialize_identifier(FieldIndexVisitor {
+        names,
+        aliases,
+        deny_unknown,
+    })
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 pub fn borrow_cow_str<'de: 'a, 'a, D, R>(deserializer: D) -> Result<R, D::Error>
 where
@@ -194,6 +331,263 @@ where
         .map(From::from)
 }
 
+#[cfg(feature = "std")]
+pub fn borrow_cow_path<'de: 'a, 'a, D, R>(deserializer: D) -> Result<R, D::Error>
+where
+    D: Deserializer<'de>,
+    R: From<Cow<'a, Path>>,
+{
+    struct CowPathVisitor;
+
+    impl<'a> Visitor<'a> for CowPathVisitor {
+        type Value = Cow<'a, Path>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a path")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(Cow::Owned(PathBuf::from(v)))
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'a str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(Cow::Borrowed(Path::new(v)))
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(Cow::Owned(PathBuf::from(v)))
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            match str::from_utf8(v) {
+                Ok(s) => Ok(Cow::Owned(PathBuf::from(s))),
+                Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+            }
+        }
+
+        fn visit_borrowed_bytes<E>(self, v: &'a [u8]) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            match str::from_utf8(v) {
+                Ok(s) => Ok(Cow::Borrowed(Path::new(s))),
+                Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+            }
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            match String::from_utf8(v) {
+                Ok(s) => Ok(Cow::Owned(PathBuf::from(s))),
+                Err(e) => Err(Error::invalid_value(
+                    Unexpected::Bytes(&e.into_bytes()),
+                    &self,
+                )),
+            }
+        }
+    }
+
+    deserializer.deserialize_str(CowPathVisitor).map(From::from)
+}
+
+#[cfg(feature = "std")]
+pub fn borrow_cow_os_str<'de: 'a, 'a, D, R>(deserializer: D) -> Result<R, D::Error>
+where
+    D: Deserializer<'de>,
+    R: From<Cow<'a, OsStr>>,
+{
+    struct CowOsStrVisitor;
+
+    impl<'a> Visitor<'a> for CowOsStrVisitor {
+        type Value = Cow<'a, OsStr>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an OS string")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(Cow::Owned(OsString::from(v.to_owned())))
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'a str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(Cow::Borrowed(OsStr::new(v)))
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(Cow::Owned(OsString::from(v)))
+        }
+
+        #[cfg(unix)]
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Ok(Cow::Owned(OsStr::from_bytes(v).to_os_string()))
+        }
+
+        #[cfg(unix)]
+        fn visit_borrowed_bytes<E>(self, v: &'a [u8]) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Ok(Cow::Borrowed(OsStr::from_bytes(v)))
+        }
+
+        #[cfg(unix)]
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            use std::os::unix::ffi::OsStringExt;
+            Ok(Cow::Owned(OsString::from_vec(v)))
+        }
+    }
+
+    deserializer
+        .deserialize_str(CowOsStrVisitor)
+        .map(From::from)
+}
+
+// Wrappers carrying the borrowing Cow behavior into containers, so that
+// #[serde(borrow)] keeps working when a Cow field is nested in an Option,
+// Vec, or map.
+#[cfg(any(feature = "std", feature = "alloc"))]
+struct BorrowedCowStr<'a>(Cow<'a, str>);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedCowStr<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let cow: Cow<'a, str> = tri!(borrow_cow_str(deserializer));
+        Ok(BorrowedCowStr(cow))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+struct BorrowedCowBytes<'a>(Cow<'a, [u8]>);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedCowBytes<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let cow: Cow<'a, [u8]> = tri!(borrow_cow_bytes(deserializer));
+        Ok(BorrowedCowBytes(cow))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn borrow_cow_str_option<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<BorrowedCowStr<'a>>::deserialize(deserializer)
+        .map(|option| option.map(|wrapper| wrapper.0))
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn borrow_cow_bytes_option<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, [u8]>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<BorrowedCowBytes<'a>>::deserialize(deserializer)
+        .map(|option| option.map(|wrapper| wrapper.0))
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn borrow_cow_str_vec<'de: 'a, 'a, D>(deserializer: D) -> Result<Vec<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<BorrowedCowStr<'a>>::deserialize(deserializer)
+        .map(|vec| vec.into_iter().map(|wrapper| wrapper.0).collect())
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn borrow_cow_bytes_vec<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Vec<Cow<'a, [u8]>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<BorrowedCowBytes<'a>>::deserialize(deserializer)
+        .map(|vec| vec.into_iter().map(|wrapper| wrapper.0).collect())
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn borrow_cow_str_map<'de: 'a, 'a, D, V, M>(deserializer: D) -> Result<M, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+    M: iter::FromIterator<(Cow<'a, str>, V)>,
+{
+    struct CowStrMapVisitor<'a, V, M> {
+        marker: PhantomData<(Cow<'a, str>, V, M)>,
+    }
+
+    impl<'de: 'a, 'a, V, M> Visitor<'de> for CowStrMapVisitor<'a, V, M>
+    where
+        V: Deserialize<'de>,
+        M: iter::FromIterator<(Cow<'a, str>, V)>,
+    {
+        type Value = M;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut entries = Vec::<(Cow<'a, str>, V)>::with_capacity(
+                crate::de::size_hint::cautious::<(Cow<str>, V)>(map.size_hint()),
+            );
+            while let Some(key) = tri!(map.next_key::<BorrowedCowStr<'a>>()) {
+                let value = tri!(map.next_value());
+                entries.push((key.0, value));
+            }
+            Ok(entries.into_iter().collect())
+        }
+    }
+
+    deserializer.deserialize_map(CowStrMapVisitor {
+        marker: PhantomData,
+    })
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 mod content {
     // This module is private and nothing here should be used outside of
@@ -297,7 +691,7 @@ mod content {
         {
             // Untagged and internally tagged enums are only supported in
             // self-describing formats.
-            let visitor = ContentVisitor { value: PhantomData };
+            let visitor = ContentVisitor::new();
             deserializer.__deserialize_content(actually_private::T, visitor)
         }
     }
@@ -313,13 +707,48 @@ mod content {
         }
     }
 
+    // Nesting deeper than this is refused while buffering so that adversarial
+    // input cannot overflow the stack; the buffering recursion happens inside
+    // serde where format-level limits cannot see it. The `unbounded_depth`
+    // feature turns the guard off.
+    const RECURSION_LIMIT: usize = 128;
+
     struct ContentVisitor<'de> {
         value: PhantomData<Content<'de>>,
+        depth: usize,
     }
 
     impl<'de> ContentVisitor<'de> {
         fn new() -> Self {
-            ContentVisitor { value: PhantomData }
+            ContentVisitor {
+                value: PhantomData,
+                depth: 0,
+            }
+        }
+    }
+
+    // Deserializes one nested `Content` value, enforcing the recursion limit
+    // before going a level deeper.
+    struct ContentSeed<'de> {
+        value: PhantomData<Content<'de>>,
+        depth: usize,
+    }
+
+    impl<'de> DeserializeSeed<'de> for ContentSeed<'de> {
+        type Value = Content<'de>;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if !cfg!(feature = "unbounded_depth") && self.depth >= RECURSION_LIMIT {
+                return Err(de::Error::custom("recursion limit exceeded"));
+            }
+            let visitor = ContentVisitor {
+                value: PhantomData,
+                depth: self.depth,
+            };
+            deserializer.__deserialize_content(actually_private::T, visitor)
         }
     }
 
@@ -474,14 +903,24 @@ mod content {
         where
             D: Deserializer<'de>,
         {
-            Deserialize::deserialize(deserializer).map(|v| Content::Some(Box::new(v)))
+            let seed = ContentSeed {
+                value: PhantomData,
+                depth: self.depth + 1,
+            };
+            seed.deserialize(deserializer)
+                .map(|v| Content::Some(Box::new(v)))
         }
 
         fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
         where
             D: Deserializer<'de>,
         {
-            Deserialize::deserialize(deserializer).map(|v| Content::Newtype(Box::new(v)))
+            let seed = ContentSeed {
+                value: PhantomData,
+                depth: self.depth + 1,
+            };
+            seed.deserialize(deserializer)
+                .map(|v| Content::Newtype(Box::new(v)))
         }
 
         fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
@@ -490,7 +929,10 @@ mod content {
         {
             let mut vec =
                 Vec::<Content>::with_capacity(size_hint::cautious::<Content>(visitor.size_hint()));
-            while let Some(e) = tri!(visitor.next_element()) {
+            while let Some(e) = tri!(visitor.next_element_seed(ContentSeed {
+                value: PhantomData,
+                depth: self.depth + 1,
+            })) {
                 vec.push(e);
             }
             Ok(Content::Seq(vec))
@@ -504,8 +946,20 @@ mod content {
                 Vec::<(Content, Content)>::with_capacity(
                     size_hint::cautious::<(Content, Content)>(visitor.size_hint()),
                 );
-            while let Some(kv) = tri!(visitor.next_entry()) {
-                vec.push(kv);
+            loop {
+                let key = tri!(visitor.next_key_seed(ContentSeed {
+                    value: PhantomData,
+                    depth: self.depth + 1,
+                }));
+                let key = match key {
+                    Some(key) => key,
+                    None => break,
+                };
+                let value = tri!(visitor.next_value_seed(ContentSeed {
+                    value: PhantomData,
+                    depth: self.depth + 1,
+                }));
+                vec.push((key, value));
             }
             Ok(Content::Map(vec))
         }
@@ -530,16 +984,27 @@ mod content {
 
     struct TagOrContentVisitor<'de> {
         name: &'static str,
+        aliases: &'static [&'static str],
         value: PhantomData<TagOrContent<'de>>,
     }
 
     impl<'de> TagOrContentVisitor<'de> {
-        fn new(name: &'static str) -> Self {
+        fn new(name: &'static str, aliases: &'static [&'static str]) -> Self {
             TagOrContentVisitor {
                 name,
+                aliases,
                 value: PhantomData,
             }
         }
+
+        fn is_tag_str(&self, value: &str) -> bool {
+            value == self.name || self.aliases.contains(&value)
+        }
+
+        fn is_tag_bytes(&self, value: &[u8]) -> bool {
+            value == self.name.as_bytes()
+                || self.aliases.iter().any(|alias| value == alias.as_bytes())
+        }
     }
 
     impl<'de> DeserializeSeed<'de> for TagOrContentVisitor<'de> {
@@ -674,7 +1139,7 @@ mod content {
         where
             F: de::Error,
         {
-            if value == self.name {
+            if self.is_tag_str(value) {
                 Ok(TagOrContent::Tag)
             } else {
                 ContentVisitor::new()
@@ -687,7 +1152,7 @@ mod content {
         where
             F: de::Error,
         {
-            if value == self.name {
+            if self.is_tag_str(value) {
                 Ok(TagOrContent::Tag)
             } else {
                 ContentVisitor::new()
@@ -700,7 +1165,7 @@ mod content {
         where
             F: de::Error,
         {
-            if value == self.name {
+            if self.is_tag_str(&value) {
                 Ok(TagOrContent::Tag)
             } else {
                 ContentVisitor::new()
@@ -713,7 +1178,7 @@ mod content {
         where
             F: de::Error,
         {
-            if value == self.name.as_bytes() {
+            if self.is_tag_bytes(value) {
                 Ok(TagOrContent::Tag)
             } else {
                 ContentVisitor::new()
@@ -726,7 +1191,7 @@ mod content {
         where
             F: de::Error,
         {
-            if value == self.name.as_bytes() {
+            if self.is_tag_bytes(value) {
                 Ok(TagOrContent::Tag)
             } else {
                 ContentVisitor::new()
@@ -739,7 +1204,7 @@ mod content {
         where
             F: de::Error,
         {
-            if value == self.name.as_bytes() {
+            if self.is_tag_bytes(&value) {
                 Ok(TagOrContent::Tag)
             } else {
                 ContentVisitor::new()
@@ -817,16 +1282,22 @@ mod content {
     /// Not public API.
     pub struct TaggedContentVisitor<T> {
         tag_name: &'static str,
+        tag_aliases: &'static [&'static str],
         expecting: &'static str,
         value: PhantomData<T>,
     }
 
     impl<T> TaggedContentVisitor<T> {
         /// Visitor for the content of an internally tagged enum with the given
-        /// tag name.
-        pub fn new(name: &'static str, expecting: &'static str) -> Self {
+        /// tag name and any alternative names the tag key may appear under.
+        pub fn new(
+            name: &'static str,
+            aliases: &'static [&'static str],
+            expecting: &'static str,
+        ) -> Self {
             TaggedContentVisitor {
                 tag_name: name,
+                tag_aliases: aliases,
                 expecting,
                 value: PhantomData,
             }
@@ -866,7 +1337,10 @@ mod content {
                 Content,
                 Content,
             )>(map.size_hint()));
-            while let Some(k) = tri!(map.next_key_seed(TagOrContentVisitor::new(self.tag_name))) {
+            while let Some(k) = tri!(map.next_key_seed(TagOrContentVisitor::new(
+                self.tag_name,
+                self.tag_aliases
+            ))) {
                 match k {
                     TagOrContent::Tag => {
                         if tag.is_some() {
@@ -898,6 +1372,7 @@ mod content {
     /// Not public API.
     pub struct TagOrContentFieldVisitor {
         pub tag: &'static str,
+        pub tag_aliases: &'static [&'static str],
         pub content: &'static str,
     }
 
@@ -937,7 +1412,7 @@ mod content {
         where
             E: de::Error,
         {
-            if field == self.tag {
+            if field == self.tag || self.tag_aliases.contains(&field) {
                 Ok(TagOrContentField::Tag)
             } else if field == self.content {
                 Ok(TagOrContentField::Content)
@@ -950,7 +1425,9 @@ mod content {
         where
             E: de::Error,
         {
-            if field == self.tag.as_bytes() {
+            if field == self.tag.as_bytes()
+                || self.tag_aliases.iter().any(|alias| field == alias.as_bytes())
+            {
                 Ok(TagOrContentField::Tag)
             } else if field == self.content.as_bytes() {
                 Ok(TagOrContentField::Content)
@@ -973,6 +1450,7 @@ mod content {
     /// Not public API.
     pub struct TagContentOtherFieldVisitor {
         pub tag: &'static str,
+        pub tag_aliases: &'static [&'static str],
         pub content: &'static str,
     }
 
@@ -1020,7 +1498,9 @@ mod content {
         where
             E: de::Error,
         {
-            if field == self.tag.as_bytes() {
+            if field == self.tag.as_bytes()
+                || self.tag_aliases.iter().any(|alias| field == alias.as_bytes())
+            {
                 Ok(TagContentOtherField::Tag)
             } else if field == self.content.as_bytes() {
                 Ok(TagContentOtherField::Content)
@@ -1465,6 +1945,8 @@ mod content {
                 Content::ByteBuf(v) => visitor.visit_byte_buf(v),
                 Content::Bytes(v) => visitor.visit_borrowed_bytes(v),
                 Content::U8(v) => visitor.visit_u8(v),
+                Content::U16(v) => visitor.visit_u16(v),
+                Content::U32(v) => visitor.visit_u32(v),
                 Content::U64(v) => visitor.visit_u64(v),
                 _ => Err(self.invalid_type(&visitor)),
             }
@@ -2039,6 +2521,8 @@ mod content {
                 Content::ByteBuf(ref v) => visitor.visit_bytes(v),
                 Content::Bytes(v) => visitor.visit_borrowed_bytes(v),
                 Content::U8(v) => visitor.visit_u8(v),
+                Content::U16(v) => visitor.visit_u16(v),
+                Content::U32(v) => visitor.visit_u32(v),
                 Content::U64(v) => visitor.visit_u64(v),
                 _ => Err(self.invalid_type(&visitor)),
             }
@@ -2365,6 +2849,31 @@ mod content {
         }
     }
 
+    /// Extracts the content of an internally tagged tuple variant. Map forms
+    /// keep the tuple under the conventional "content" key adjacent to the
+    /// tag; seq forms carry the tuple elements inline after the tag and pass
+    /// through unchanged.
+    ///
+    /// Not public API.
+    pub fn internally_tagged_tuple_content<'de, E>(
+        deserializer: ContentDeserializer<'de, E>,
+    ) -> Result<ContentDeserializer<'de, E>, E>
+    where
+        E: de::Error,
+    {
+        match deserializer.content {
+            Content::Map(entries) => {
+                for (key, value) in entries {
+                    if key.as_str() == Some("content") {
+                        return Ok(ContentDeserializer::new(value));
+                    }
+                }
+                Err(de::Error::missing_field("content"))
+            }
+            content => Ok(ContentDeserializer::new(content)),
+        }
+    }
+
     /// Visitor for deserializing an internally tagged unit variant.
     ///
     /// Not public API.
@@ -2453,6 +2962,280 @@ mod content {
             Ok(())
         }
     }
+
+    /// Used from generated code for `#[serde(map_from_pairs)]` fields: the
+    /// field deserializes from its usual map representation or from a
+    /// sequence of `[key, value]` pairs.
+    ///
+    /// Not public API.
+    pub struct MapFromPairsSeed<T> {
+        marker: PhantomData<T>,
+    }
+
+    impl<T> MapFromPairsSeed<T> {
+        pub fn new() -> Self {
+            MapFromPairsSeed {
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, T> DeserializeSeed<'de> for MapFromPairsSeed<T>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = T;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let content = match tri!(Content::deserialize(deserializer)) {
+                Content::Seq(pairs) => {
+                    let mut entries = Vec::with_capacity(pairs.len());
+                    for pair in pairs {
+                        let kv = match pair {
+                            Content::Seq(kv) => kv,
+                            other => {
+                                return Err(de::Error::invalid_type(
+                                    other.unexpected(),
+                                    &"a [key, value] pair",
+                                ));
+                            }
+                        };
+                        let len = kv.len();
+                        let mut kv = kv.into_iter();
+                        match (kv.next(), kv.next(), kv.next()) {
+                            (Some(key), Some(value), None) => entries.push((key, value)),
+                            _ => {
+                                return Err(de::Error::invalid_length(
+                                    len,
+                                    &"a [key, value] pair",
+                                ));
+                            }
+                        }
+                    }
+                    Content::Map(entries)
+                }
+                other => other,
+            };
+            T::deserialize(ContentDeserializer::new(content))
+        }
+    }
+
+    /// Used from generated code for `#[serde(keyed_by = "...")]` fields: the
+    /// collection deserializes from a map, with each entry's key injected into
+    /// the named field of its value before the item is deserialized.
+    ///
+    /// Not public API.
+    pub struct KeyedBySeed<T> {
+        key: &'static str,
+        marker: PhantomData<T>,
+    }
+
+    impl<T> KeyedBySeed<T> {
+        pub fn new(key: &'static str) -> Self {
+            KeyedBySeed {
+                key,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, T> DeserializeSeed<'de> for KeyedBySeed<T>
+    where
+        T: KeyedByDeserialize<'de>,
+    {
+        type Value = T;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            T::deserialize_keyed_by(self.key, deserializer)
+        }
+    }
+
+    /// A collection that can deserialize from a map keyed by a field of its
+    /// items. Not public API.
+    pub trait KeyedByDeserialize<'de>: Sized {
+        fn deserialize_keyed_by<D>(key: &'static str, deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>;
+    }
+
+    fn inject_key<'de, E>(
+        key: &'static str,
+        map_key: Content<'de>,
+        value: Content<'de>,
+    ) -> Result<Content<'de>, E>
+    where
+        E: de::Error,
+    {
+        match value {
+            Content::Map(mut entries) => {
+                entries.push((Content::Str(key), map_key));
+                Ok(Content::Map(entries))
+            }
+            other => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"a map to inject the key into",
+            )),
+        }
+    }
+
+    impl<'de, T> KeyedByDeserialize<'de> for Vec<T>
+    where
+        T: Deserialize<'de>,
+    {
+        fn deserialize_keyed_by<D>(key: &'static str, deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct KeyedVecVisitor<T> {
+                key: &'static str,
+                marker: PhantomData<T>,
+            }
+
+            impl<'de, T> Visitor<'de> for KeyedVecVisitor<T>
+            where
+                T: Deserialize<'de>,
+            {
+                type Value = Vec<T>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a map keyed by an item field")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: MapAccess<'de>,
+                {
+                    let mut items = Vec::with_capacity(size_hint::cautious::<(
+                        Content,
+                        Content,
+                    )>(map.size_hint()));
+                    while let Some((map_key, value)) =
+                        tri!(map.next_entry::<Content, Content>())
+                    {
+                        let content = tri!(inject_key(self.key, map_key, value));
+                        items.push(tri!(T::deserialize(ContentDeserializer::new(content))));
+                    }
+                    Ok(items)
+                }
+            }
+
+            deserializer.deserialize_map(KeyedVecVisitor {
+                key,
+                marker: PhantomData,
+            })
+        }
+    }
+
+    impl<'de, K, V> KeyedByDeserialize<'de> for BTreeMap<K, V>
+    where
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+    {
+        fn deserialize_keyed_by<D>(key: &'static str, deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct KeyedMapVisitor<K, V> {
+                key: &'static str,
+                marker: PhantomData<(K, V)>,
+            }
+
+            impl<'de, K, V> Visitor<'de> for KeyedMapVisitor<K, V>
+            where
+                K: Deserialize<'de> + Ord,
+                V: Deserialize<'de>,
+            {
+                type Value = BTreeMap<K, V>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a map keyed by an item field")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: MapAccess<'de>,
+                {
+                    let mut items = BTreeMap::new();
+                    while let Some((map_key, value)) =
+                        tri!(map.next_entry::<Content, Content>())
+                    {
+                        let k = tri!(K::deserialize(ContentRefDeserializer::new(&map_key)));
+                        let content = tri!(inject_key(self.key, map_key, value));
+                        let v = tri!(V::deserialize(ContentDeserializer::new(content)));
+                        items.insert(k, v);
+                    }
+                    Ok(items)
+                }
+            }
+
+            deserializer.deserialize_map(KeyedMapVisitor {
+                key,
+                marker: PhantomData,
+            })
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl<'de, K, V, S> KeyedByDeserialize<'de> for HashMap<K, V, S>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        S: BuildHasher + Default,
+    {
+        fn deserialize_keyed_by<D>(key: &'static str, deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct KeyedMapVisitor<K, V, S> {
+                key: &'static str,
+                marker: PhantomData<(K, V, S)>,
+            }
+
+            impl<'de, K, V, S> Visitor<'de> for KeyedMapVisitor<K, V, S>
+            where
+                K: Deserialize<'de> + Eq + Hash,
+                V: Deserialize<'de>,
+                S: BuildHasher + Default,
+            {
+                type Value = HashMap<K, V, S>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a map keyed by an item field")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: MapAccess<'de>,
+                {
+                    let mut items = HashMap::with_capacity_and_hasher(
+                        size_hint::cautious::<(Content, Content)>(map.size_hint()),
+                        S::default(),
+                    );
+                    while let Some((map_key, value)) =
+                        tri!(map.next_entry::<Content, Content>())
+                    {
+                        let k = tri!(K::deserialize(ContentRefDeserializer::new(&map_key)));
+                        let content = tri!(inject_key(self.key, map_key, value));
+                        let v = tri!(V::deserialize(ContentDeserializer::new(content)));
+                        items.insert(k, v);
+                    }
+                    Ok(items)
+                }
+            }
+
+            deserializer.deserialize_map(KeyedMapVisitor {
+                key,
+                marker: PhantomData,
+            })
+        }
+    }
+
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -2586,9 +3369,43 @@ where
     }
 }
 
+/// One key-value pair buffered for `#[serde(flatten)]` fields.
+///
+/// Entries claimed by a flattened struct are nulled out of the buffer, while
+/// entries read by a flattened map are borrowed so that a later flattened
+/// field can still see them. The `used` flag records that somebody read the
+/// value so that `deny_unknown_fields` only reports keys nobody claimed.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct FlatMapEntry<'de> {
+    key: Content<'de>,
+    value: Content<'de>,
+    used: Cell<bool>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'de> FlatMapEntry<'de> {
+    pub fn new(key: Content<'de>, value: Content<'de>) -> Self {
+        FlatMapEntry {
+            key,
+            value,
+            used: Cell::new(false),
+        }
+    }
+
+    /// The key of this entry if no flattened field has deserialized its
+    /// value, for reporting in an unknown-field error.
+    pub fn unused_key(&self) -> Option<&Content<'de>> {
+        if self.used.get() {
+            None
+        } else {
+            Some(&self.key)
+        }
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 pub struct FlatMapDeserializer<'a, 'de: 'a, E>(
-    pub &'a mut Vec<Option<(Content<'de>, Content<'de>)>>,
+    pub &'a mut Vec<Option<FlatMapEntry<'de>>>,
     pub PhantomData<E>,
 );
 
@@ -2657,7 +3474,7 @@ where
     {
         visitor.visit_map(FlatMapAccess {
             iter: self.0.iter(),
-            pending_content: None,
+            pending: None,
             _marker: PhantomData,
         })
     }
@@ -2737,8 +3554,8 @@ where
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 struct FlatMapAccess<'a, 'de: 'a, E> {
-    iter: slice::Iter<'a, Option<(Content<'de>, Content<'de>)>>,
-    pending_content: Option<&'a Content<'de>>,
+    iter: slice::Iter<'a, Option<FlatMapEntry<'de>>>,
+    pending: Option<&'a FlatMapEntry<'de>>,
     _marker: PhantomData<E>,
 }
 
@@ -2755,13 +3572,15 @@ where
     {
         for item in &mut self.iter {
             // Items in the vector are nulled out when used by a struct.
-            if let Some((ref key, ref content)) = *item {
+            if let Some(entry) = item {
                 // Do not take(), instead borrow this entry. The internally tagged
                 // enum does its own buffering so we can't tell whether this entry
                 // is going to be consumed. Borrowing here leaves the entry
                 // available for later flattened fields.
-                self.pending_content = Some(content);
-                return seed.deserialize(ContentRefDeserializer::new(key)).map(Some);
+                self.pending = Some(entry);
+                return seed
+                    .deserialize(ContentRefDeserializer::new(&entry.key))
+                    .map(Some);
             }
         }
         Ok(None)
@@ -2771,8 +3590,12 @@ where
     where
         T: DeserializeSeed<'de>,
     {
-        match self.pending_content.take() {
-            Some(value) => seed.deserialize(ContentRefDeserializer::new(value)),
+        match self.pending.take() {
+            Some(entry) => {
+                let value = tri!(seed.deserialize(ContentRefDeserializer::new(&entry.value)));
+                entry.used.set(true);
+                Ok(value)
+            }
             None => Err(Error::custom("value is missing")),
         }
     }
@@ -2780,7 +3603,7 @@ where
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 struct FlatStructAccess<'a, 'de: 'a, E> {
-    iter: slice::IterMut<'a, Option<(Content<'de>, Content<'de>)>>,
+    iter: slice::IterMut<'a, Option<FlatMapEntry<'de>>>,
     pending_content: Option<Content<'de>>,
     fields: &'static [&'static str],
     _marker: PhantomData<E>,
@@ -2821,7 +3644,7 @@ where
 /// field name matches any of the recognized ones.
 #[cfg(any(feature = "std", feature = "alloc"))]
 fn flat_map_take_entry<'de>(
-    entry: &mut Option<(Content<'de>, Content<'de>)>,
+    entry: &mut Option<FlatMapEntry<'de>>,
     recognized: &[&str],
 ) -> Option<(Content<'de>, Content<'de>)> {
     // Entries in the FlatMapDeserializer buffer are nulled out as they get
@@ -2829,11 +3652,11 @@ fn flat_map_take_entry<'de>(
     // and if the field is one recognized by the current data structure.
     let is_recognized = match entry {
         None => false,
-        Some((k, _v)) => k.as_str().map_or(false, |name| recognized.contains(&name)),
+        Some(e) => e.key.as_str().map_or(false, |name| recognized.contains(&name)),
     };
 
     if is_recognized {
-        entry.take()
+        entry.take().map(|e| (e.key, e.value))
     } else {
         None
     }
@@ -2868,6 +3691,35 @@ where
         tri!(variant_access.unit_variant());
         Ok(variant)
     }
+
+    // Formats that treat the tag as a plain value rather than an enum hand
+    // the string straight to the visitor.
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        F::deserialize(v.into_deserializer())
+    }
+
+    // Integer tags, as written by `#[serde(tag_repr = "...")]`, identify the
+    // variant by index.
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        F::deserialize(v.into_deserializer())
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        if v < 0 {
+            Err(Error::invalid_value(crate::de::Unexpected::Signed(v), &self))
+        } else {
+            self.visit_u64(v as u64)
+        }
+    }
 }
 
 impl<'de, F> DeserializeSeed<'de> for AdjacentlyTaggedEnumVariantSeed<F>
@@ -10,6 +10,7 @@ pub use crate::lib::clone::Clone;
 pub use crate::lib::convert::{From, Into};
 pub use crate::lib::default::Default;
 pub use crate::lib::fmt::{self, Formatter};
+pub use crate::lib::iter::{Extend, FromIterator, IntoIterator, Iterator};
 pub use crate::lib::marker::PhantomData;
 pub use crate::lib::option::Option::{self, None, Some};
 pub use crate::lib::ptr;
@@ -18,10 +19,10 @@ pub use crate::lib::result::Result::{self, Err, Ok};
 pub use self::string::from_utf8_lossy;
 
 #[cfg(any(feature = "alloc", feature = "std"))]
-pub use crate::lib::{ToString, Vec};
+pub use crate::lib::{String, ToString, Vec};
 
 #[cfg(not(no_core_try_from))]
-pub use crate::lib::convert::TryFrom;
+pub use crate::lib::convert::{TryFrom, TryInto};
 
 mod string {
     use crate::lib::*;
@@ -3,7 +3,7 @@ use crate::lib::*;
 use crate::ser::{self, Impossible, Serialize, SerializeMap, SerializeStruct, Serializer};
 
 #[cfg(any(feature = "std", feature = "alloc"))]
-use self::content::{
+pub use self::content::{
     Content, ContentSerializer, SerializeStructVariantAsMapValue, SerializeTupleVariantAsMapValue,
 };
 
@@ -14,16 +14,17 @@ pub fn constrain<T: ?Sized>(t: &T) -> &T {
 }
 
 /// Not public API.
-pub fn serialize_tagged_newtype<S, T>(
+pub fn serialize_tagged_newtype<S, V, T>(
     serializer: S,
     type_ident: &'static str,
     variant_ident: &'static str,
     tag: &'static str,
-    variant_name: &'static str,
+    variant_name: V,
     value: &T,
 ) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
+    V: Serialize,
     T: Serialize,
 {
     value.serialize(TaggedSerializer {
@@ -35,11 +36,11 @@ where
     })
 }
 
-struct TaggedSerializer<S> {
+struct TaggedSerializer<S, V> {
     type_ident: &'static str,
     variant_ident: &'static str,
     tag: &'static str,
-    variant_name: &'static str,
+    variant_name: V,
     delegate: S,
 }
 
@@ -79,7 +80,7 @@ impl Display for Unsupported {
     }
 }
 
-impl<S> TaggedSerializer<S>
+impl<S, V> TaggedSerializer<S, V>
 where
     S: Serializer,
 {
@@ -91,9 +92,10 @@ where
     }
 }
 
-impl<S> Serializer for TaggedSerializer<S>
+impl<S, V> Serializer for TaggedSerializer<S, V>
 where
     S: Serializer,
+    V: Serialize,
 {
     type Ok = S::Ok;
     type Error = S::Error;
@@ -183,13 +185,13 @@ where
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
         let mut map = tri!(self.delegate.serialize_map(Some(1)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         map.end()
     }
 
     fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
         let mut map = tri!(self.delegate.serialize_map(Some(1)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         map.end()
     }
 
@@ -200,7 +202,7 @@ where
         inner_variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
         let mut map = tri!(self.delegate.serialize_map(Some(2)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         tri!(map.serialize_entry(inner_variant, &()));
         map.end()
     }
@@ -227,7 +229,7 @@ where
         T: Serialize,
     {
         let mut map = tri!(self.delegate.serialize_map(Some(2)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         tri!(map.serialize_entry(inner_variant, inner_value));
         map.end()
     }
@@ -270,7 +272,7 @@ where
         len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
         let mut map = tri!(self.delegate.serialize_map(Some(2)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         tri!(map.serialize_key(inner_variant));
         Ok(SerializeTupleVariantAsMapValue::new(
             map,
@@ -281,7 +283,7 @@ where
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         let mut map = tri!(self.delegate.serialize_map(len.map(|len| len + 1)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         Ok(map)
     }
 
@@ -291,7 +293,7 @@ where
         len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
         let mut state = tri!(self.delegate.serialize_struct(name, len + 1));
-        tri!(state.serialize_field(self.tag, self.variant_name));
+        tri!(state.serialize_field(self.tag, &self.variant_name));
         Ok(state)
     }
 
@@ -317,7 +319,7 @@ where
         len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         let mut map = tri!(self.delegate.serialize_map(Some(2)));
-        tri!(map.serialize_entry(self.tag, self.variant_name));
+        tri!(map.serialize_entry(self.tag, &self.variant_name));
         tri!(map.serialize_key(inner_variant));
         Ok(SerializeStructVariantAsMapValue::new(
             map,
@@ -551,12 +553,23 @@ mod content {
     }
 
     pub struct ContentSerializer<E> {
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
     impl<E> ContentSerializer<E> {
         pub fn new() -> Self {
-            ContentSerializer { error: PhantomData }
+            ContentSerializer::with_is_human_readable(true)
+        }
+
+        /// Buffers for a target serializer whose `is_human_readable` returns
+        /// the given value, so that readability-sensitive types are captured
+        /// in the representation the target expects.
+        pub fn with_is_human_readable(human_readable: bool) -> Self {
+            ContentSerializer {
+                human_readable,
+                error: PhantomData,
+            }
         }
     }
 
@@ -694,6 +707,7 @@ mod content {
         fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, E> {
             Ok(SerializeSeq {
                 elements: Vec::with_capacity(len.unwrap_or(0)),
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
@@ -701,6 +715,7 @@ mod content {
         fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, E> {
             Ok(SerializeTuple {
                 elements: Vec::with_capacity(len),
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
@@ -713,6 +728,7 @@ mod content {
             Ok(SerializeTupleStruct {
                 name,
                 fields: Vec::with_capacity(len),
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
@@ -729,6 +745,7 @@ mod content {
                 variant_index,
                 variant,
                 fields: Vec::with_capacity(len),
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
@@ -737,6 +754,7 @@ mod content {
             Ok(SerializeMap {
                 entries: Vec::with_capacity(len.unwrap_or(0)),
                 key: None,
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
@@ -749,6 +767,7 @@ mod content {
             Ok(SerializeStruct {
                 name,
                 fields: Vec::with_capacity(len),
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
@@ -765,13 +784,19 @@ mod content {
                 variant_index,
                 variant,
                 fields: Vec::with_capacity(len),
+                human_readable: self.human_readable,
                 error: PhantomData,
             })
         }
+
+        fn is_human_readable(&self) -> bool {
+            self.human_readable
+        }
     }
 
     pub struct SerializeSeq<E> {
         elements: Vec<Content>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -786,7 +811,7 @@ mod content {
         where
             T: Serialize,
         {
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.elements.push(value);
             Ok(())
         }
@@ -798,6 +823,7 @@ mod content {
 
     pub struct SerializeTuple<E> {
         elements: Vec<Content>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -812,7 +838,7 @@ mod content {
         where
             T: Serialize,
         {
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.elements.push(value);
             Ok(())
         }
@@ -825,6 +851,7 @@ mod content {
     pub struct SerializeTupleStruct<E> {
         name: &'static str,
         fields: Vec<Content>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -839,7 +866,7 @@ mod content {
         where
             T: Serialize,
         {
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.fields.push(value);
             Ok(())
         }
@@ -854,6 +881,7 @@ mod content {
         variant_index: u32,
         variant: &'static str,
         fields: Vec<Content>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -868,7 +896,7 @@ mod content {
         where
             T: Serialize,
         {
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.fields.push(value);
             Ok(())
         }
@@ -886,6 +914,7 @@ mod content {
     pub struct SerializeMap<E> {
         entries: Vec<(Content, Content)>,
         key: Option<Content>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -900,7 +929,7 @@ mod content {
         where
             T: Serialize,
         {
-            let key = tri!(key.serialize(ContentSerializer::<E>::new()));
+            let key = tri!(key.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.key = Some(key);
             Ok(())
         }
@@ -913,7 +942,7 @@ mod content {
                 .key
                 .take()
                 .expect("serialize_value called before serialize_key");
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.entries.push((key, value));
             Ok(())
         }
@@ -927,8 +956,8 @@ mod content {
             K: Serialize,
             V: Serialize,
         {
-            let key = tri!(key.serialize(ContentSerializer::<E>::new()));
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let key = tri!(key.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.entries.push((key, value));
             Ok(())
         }
@@ -937,6 +966,7 @@ mod content {
     pub struct SerializeStruct<E> {
         name: &'static str,
         fields: Vec<(&'static str, Content)>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -951,7 +981,7 @@ mod content {
         where
             T: Serialize,
         {
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.fields.push((key, value));
             Ok(())
         }
@@ -966,6 +996,7 @@ mod content {
         variant_index: u32,
         variant: &'static str,
         fields: Vec<(&'static str, Content)>,
+        human_readable: bool,
         error: PhantomData<E>,
     }
 
@@ -980,7 +1011,7 @@ mod content {
         where
             T: Serialize,
         {
-            let value = tri!(value.serialize(ContentSerializer::<E>::new()));
+            let value = tri!(value.serialize(ContentSerializer::<E>::with_is_human_readable(self.human_readable)));
             self.fields.push((key, value));
             Ok(())
         }
@@ -1383,3 +1414,137 @@ where
         write!(formatter, "enum variant cannot be serialized: {:?}", self.0)
     }
 }
+
+/// Used from generated code for `#[serde(keyed_by = "...")]` fields: the
+/// collection serializes as a map keyed by the named field of each item, with
+/// that field removed from the map values.
+///
+/// Not public API.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct KeyedBy<'a, T: ?Sized> {
+    pub key: &'static str,
+    pub collection: &'a T,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T> Serialize for KeyedBy<'a, T>
+where
+    T: ?Sized + KeyedBySerialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.collection.serialize_keyed_by(self.key, serializer)
+    }
+}
+
+/// A collection that can serialize as a map keyed by a field of its items.
+/// Not public API.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub trait KeyedBySerialize {
+    fn serialize_keyed_by<S>(&self, key: &'static str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn serialize_keyed_entry<M, T>(map: &mut M, key: &'static str, item: &T) -> Result<(), M::Error>
+where
+    M: SerializeMap,
+    T: Serialize,
+{
+    let content = tri!(item.serialize(ContentSerializer::<M::Error>::new()));
+    let mut id = None;
+    let value = match content {
+        Content::Struct(name, fields) => {
+            let mut rest = Vec::with_capacity(fields.len());
+            for (k, v) in fields {
+                if k == key && id.is_none() {
+                    id = Some(v);
+                } else {
+                    rest.push((k, v));
+                }
+            }
+            Content::Struct(name, rest)
+        }
+        Content::Map(entries) => {
+            let mut rest = Vec::with_capacity(entries.len());
+            for (k, v) in entries {
+                let is_key = match k {
+                    Content::String(ref s) => s == key,
+                    _ => false,
+                };
+                if is_key && id.is_none() {
+                    id = Some(v);
+                } else {
+                    rest.push((k, v));
+                }
+            }
+            Content::Map(rest)
+        }
+        _ => {
+            return Err(ser::Error::custom(
+                "keyed_by requires items that serialize as a struct or map",
+            ));
+        }
+    };
+    match id {
+        Some(id) => map.serialize_entry(&id, &value),
+        None => Err(ser::Error::custom(format_args!(
+            "item is missing keyed_by field `{}`",
+            key
+        ))),
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<T> KeyedBySerialize for Vec<T>
+where
+    T: Serialize,
+{
+    fn serialize_keyed_by<S>(&self, key: &'static str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = tri!(serializer.serialize_map(Some(self.len())));
+        for item in self {
+            tri!(serialize_keyed_entry(&mut map, key, item));
+        }
+        map.end()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<K, T> KeyedBySerialize for BTreeMap<K, T>
+where
+    T: Serialize,
+{
+    fn serialize_keyed_by<S>(&self, key: &'static str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = tri!(serializer.serialize_map(Some(self.len())));
+        for item in self.values() {
+            tri!(serialize_keyed_entry(&mut map, key, item));
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, T, H> KeyedBySerialize for HashMap<K, T, H>
+where
+    T: Serialize,
+{
+    fn serialize_keyed_by<S>(&self, key: &'static str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = tri!(serializer.serialize_map(Some(self.len())));
+        for item in self.values() {
+            tri!(serialize_keyed_entry(&mut map, key, item));
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,296 @@
+//! Identity-preserving serialization for `Rc` and `Arc`.
+//!
+//! The plain impls behind the `rc` feature clone out the pointee, so a graph
+//! with many shared nodes balloons on serialization and loses its sharing on
+//! deserialization. The [`shared`] module serializes each unique pointer once
+//! and emits back-references for later occurrences, reconstructing the
+//! sharing when deserializing.
+//!
+//! Occurrences are matched up through a thread-local registry which is only
+//! active inside a [`SharedScope`]. Wrap each complete serialization or
+//! deserialization in a scope; without one, every occurrence is written in
+//! full and sharing is lost (but the output remains valid).
+//!
+//! ```edition2021
+//! use serde::rc::SharedScope;
+//! use serde_derive::{Deserialize, Serialize};
+//! use std::rc::Rc;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Doc {
+//!     #[serde(with = "serde::rc::shared")]
+//!     head: Rc<String>,
+//!     #[serde(with = "serde::rc::shared")]
+//!     tail: Rc<String>,
+//! }
+//!
+//! # fn main() {
+//! let node = Rc::new(String::from("node"));
+//! let doc = Doc {
+//!     head: Rc::clone(&node),
+//!     tail: node,
+//! };
+//!
+//! let _scope = SharedScope::new();
+//! // serialize `doc` here; the string is written once.
+//! # }
+//! ```
+//!
+//! Back-references can only point at pointers that were fully deserialized
+//! earlier in the same scope, so cyclic graphs cannot be reconstructed.
+
+use crate::lib::*;
+
+use crate::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use crate::ser::{Serialize, SerializeStruct, Serializer};
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A reference-counted pointer which [`shared`] can serialize by identity.
+pub trait SharedPointer: Clone {
+    /// The pointed-to type.
+    type Target;
+
+    /// Constructs a new pointer owning `value`.
+    fn new(value: Self::Target) -> Self;
+
+    /// The address of the pointee, used as its identity.
+    fn address(&self) -> usize;
+
+    /// Borrows the pointee.
+    fn get(&self) -> &Self::Target;
+}
+
+impl<T> SharedPointer for Rc<T> {
+    type Target = T;
+
+    fn new(value: T) -> Self {
+        Rc::new(value)
+    }
+
+    fn address(&self) -> usize {
+        &**self as *const T as usize
+    }
+
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<T> SharedPointer for Arc<T> {
+    type Target = T;
+
+    fn new(value: T) -> Self {
+        Arc::new(value)
+    }
+
+    fn address(&self) -> usize {
+        &**self as *const T as usize
+    }
+
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+struct Registry {
+    // Pointer address -> id, for serialization.
+    ids: HashMap<usize, u64>,
+    next_id: u64,
+    // Id -> boxed Rc<T> / Arc<T>, for deserialization.
+    pointers: HashMap<u64, Box<dyn Any>>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        Registry {
+            ids: HashMap::new(),
+            next_id: 0,
+            pointers: HashMap::new(),
+        }
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Option<Registry>> = RefCell::new(None);
+}
+
+/// Activates the shared-pointer registry on the current thread for as long as
+/// it is alive.
+///
+/// Scopes nest; each one starts with an empty registry and restores the
+/// enclosing one when dropped. See the [module documentation](self) for an
+/// example.
+pub struct SharedScope {
+    previous: Option<Registry>,
+}
+
+impl SharedScope {
+    /// Begins a scope in which occurrences of the same `Rc` or `Arc` are
+    /// matched up by identity.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let previous = REGISTRY.with(|registry| registry.borrow_mut().replace(Registry::new()));
+        SharedScope { previous }
+    }
+}
+
+impl Drop for SharedScope {
+    fn drop(&mut self) {
+        REGISTRY.with(|registry| *registry.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Serialize and deserialize a shared pointer by identity, through
+/// `#[serde(with = "serde::rc::shared")]`.
+///
+/// Each unique pointer is serialized as an id together with its value; later
+/// occurrences within the same [`SharedScope`] carry only the id.
+pub mod shared {
+    use super::*;
+
+    const FIELDS: &[&str] = &["id", "value"];
+
+    /// Serializes `ptr` in full on its first occurrence in the current
+    /// [`SharedScope`], or as a back-reference on later ones.
+    pub fn serialize<P, S>(ptr: &P, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: SharedPointer,
+        P::Target: Serialize,
+        S: Serializer,
+    {
+        let (id, back_ref) = REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            match *registry {
+                Some(ref mut registry) => match registry.ids.get(&ptr.address()) {
+                    Some(&id) => (id, true),
+                    None => {
+                        let id = registry.next_id;
+                        registry.next_id += 1;
+                        registry.ids.insert(ptr.address(), id);
+                        (id, false)
+                    }
+                },
+                // No active scope: emit every occurrence in full.
+                None => (0, false),
+            }
+        });
+
+        let mut state = tri!(serializer.serialize_struct("Shared", 2));
+        tri!(state.serialize_field("id", &id));
+        if back_ref {
+            tri!(state.serialize_field("value", &None::<&P::Target>));
+        } else {
+            tri!(state.serialize_field("value", &Some(ptr.get())));
+        }
+        state.end()
+    }
+
+    /// Reconstructs a shared pointer, resolving back-references against the
+    /// current [`SharedScope`].
+    pub fn deserialize<'de, P, D>(deserializer: D) -> Result<P, D::Error>
+    where
+        P: SharedPointer + 'static,
+        P::Target: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct SharedVisitor<P>(PhantomData<P>);
+
+        impl<'de, P> Visitor<'de> for SharedVisitor<P>
+        where
+            P: SharedPointer + 'static,
+            P::Target: Deserialize<'de>,
+        {
+            type Value = P;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a shared pointer")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let id: u64 = match tri!(seq.next_element()) {
+                    Some(id) => id,
+                    None => return Err(de::Error::invalid_length(0, &self)),
+                };
+                let value: Option<P::Target> = match tri!(seq.next_element()) {
+                    Some(value) => value,
+                    None => return Err(de::Error::invalid_length(1, &self)),
+                };
+                resolve(id, value)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut id: Option<u64> = None;
+                let mut value: Option<Option<P::Target>> = None;
+                while let Some(key) = tri!(map.next_key::<Cow<str>>()) {
+                    match &*key {
+                        "id" => {
+                            if id.is_some() {
+                                return Err(de::Error::duplicate_field("id"));
+                            }
+                            id = Some(tri!(map.next_value()));
+                        }
+                        "value" => {
+                            if value.is_some() {
+                                return Err(de::Error::duplicate_field("value"));
+                            }
+                            value = Some(tri!(map.next_value()));
+                        }
+                        other => return Err(de::Error::unknown_field(other, FIELDS)),
+                    }
+                }
+                let id = match id {
+                    Some(id) => id,
+                    None => return Err(de::Error::missing_field("id")),
+                };
+                let value = match value {
+                    Some(value) => value,
+                    None => return Err(de::Error::missing_field("value")),
+                };
+                resolve(id, value)
+            }
+        }
+
+        fn resolve<P, E>(id: u64, value: Option<P::Target>) -> Result<P, E>
+        where
+            P: SharedPointer + 'static,
+            E: de::Error,
+        {
+            REGISTRY.with(|registry| {
+                let mut registry = registry.borrow_mut();
+                match value {
+                    Some(value) => {
+                        let ptr = P::new(value);
+                        if let Some(ref mut registry) = *registry {
+                            registry.pointers.insert(id, Box::new(ptr.clone()));
+                        }
+                        Ok(ptr)
+                    }
+                    None => match registry
+                        .as_ref()
+                        .and_then(|registry| registry.pointers.get(&id))
+                        .and_then(|any| any.downcast_ref::<P>())
+                    {
+                        Some(ptr) => Ok(ptr.clone()),
+                        None => Err(de::Error::custom(format_args!(
+                            "unresolved shared pointer back-reference {}",
+                            id
+                        ))),
+                    },
+                }
+            })
+        }
+
+        deserializer.deserialize_struct("Shared", FIELDS, SharedVisitor(PhantomData))
+    }
+}
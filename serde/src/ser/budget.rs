@@ -0,0 +1,670 @@
+use crate::lib::*;
+
+use crate::ser::{
+    Error, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+/// Limits enforced while serializing untrusted or recursive data.
+///
+/// A budget caps the nesting depth, the total number of elements, entries,
+/// and fields, and the total number of string and byte-string bytes written
+/// during one serialization. Applying a budget to a value with [`apply`]
+/// produces a `Serialize` adapter that fails with an error the moment any
+/// limit is exceeded, so services serializing user-controlled structures
+/// cannot be driven into unbounded output or a stack overflow.
+///
+/// Limits start out unlimited; configure only the ones that matter:
+///
+/// ```edition2021
+/// use serde::ser::Budget;
+///
+/// # use serde_derive::Serialize;
+/// # #[derive(Serialize)]
+/// # struct Untrusted { items: Vec<String> }
+/// # fn example<S>(serializer: S, value: &Untrusted) -> Result<S::Ok, S::Error>
+/// # where
+/// #     S: serde::Serializer,
+/// # {
+/// let budget = Budget::new()
+///     .max_depth(32)
+///     .max_elements(10_000)
+///     .max_string_bytes(1 << 20);
+///
+/// serde::Serialize::serialize(&budget.apply(value), serializer)
+/// # }
+/// ```
+///
+/// [`apply`]: Budget::apply
+#[derive(Clone, Debug)]
+pub struct Budget {
+    max_depth: usize,
+    max_elements: usize,
+    max_string_bytes: usize,
+}
+
+impl Budget {
+    /// Creates a budget with all limits unlimited.
+    pub fn new() -> Self {
+        Budget {
+            max_depth: usize::max_value(),
+            max_elements: usize::max_value(),
+            max_string_bytes: usize::max_value(),
+        }
+    }
+
+    /// Limits how deeply sequences, maps, and structs may nest.
+    pub fn max_depth(mut self, limit: usize) -> Self {
+        self.max_depth = limit;
+        self
+    }
+
+    /// Limits the total number of sequence elements, map entries, and struct
+    /// fields across the whole value.
+    pub fn max_elements(mut self, limit: usize) -> Self {
+        self.max_elements = limit;
+        self
+    }
+
+    /// Limits the cumulative length in bytes of all strings and byte strings
+    /// across the whole value.
+    pub fn max_string_bytes(mut self, limit: usize) -> Self {
+        self.max_string_bytes = limit;
+        self
+    }
+
+    /// Wraps a value so that serializing the wrapper enforces this budget.
+    pub fn apply<'b, T>(&'b self, value: &'b T) -> Budgeted<'b, T>
+    where
+        T: ?Sized + Serialize,
+    {
+        Budgeted {
+            value,
+            budget: self,
+        }
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::new()
+    }
+}
+
+/// A value paired with a [`Budget`], created by [`Budget::apply`].
+///
+/// Serializing a `Budgeted` serializes the underlying value, erroring as
+/// soon as the budget is exceeded.
+pub struct Budgeted<'b, T>
+where
+    T: ?Sized,
+{
+    value: &'b T,
+    budget: &'b Budget,
+}
+
+impl<'b, T> Serialize for Budgeted<'b, T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let spent = Spent {
+            elements: Cell::new(0),
+            string_bytes: Cell::new(0),
+        };
+        self.value.serialize(BudgetedSerializer {
+            ser: serializer,
+            meter: Meter {
+                budget: self.budget,
+                spent: &spent,
+            },
+            depth: 0,
+        })
+    }
+}
+
+/// Counters shared by every level of one budgeted serialization.
+struct Spent {
+    elements: Cell<usize>,
+    string_bytes: Cell<usize>,
+}
+
+/// The budget together with what has been spent against it so far. Depth is
+/// not part of the meter because it is per-branch rather than cumulative;
+/// each wrapper carries its own depth by value.
+struct Meter<'m> {
+    budget: &'m Budget,
+    spent: &'m Spent,
+}
+
+impl<'m> Copy for Meter<'m> {}
+
+impl<'m> Clone for Meter<'m> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m> Meter<'m> {
+    fn check_depth<E>(&self, depth: usize) -> Result<(), E>
+    where
+        E: Error,
+    {
+        if depth > self.budget.max_depth {
+            Err(Error::custom("nesting depth budget exceeded"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn spend_element<E>(&self) -> Result<(), E>
+    where
+        E: Error,
+    {
+        let spent = self.spent.elements.get().saturating_add(1);
+        self.spent.elements.set(spent);
+        if spent > self.budget.max_elements {
+            Err(Error::custom("element budget exceeded"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn spend_string_bytes<E>(&self, len: usize) -> Result<(), E>
+    where
+        E: Error,
+    {
+        let spent = self.spent.string_bytes.get().saturating_add(len);
+        self.spent.string_bytes.set(spent);
+        if spent > self.budget.max_string_bytes {
+            Err(Error::custom("string byte budget exceeded"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A nested value wrapper carrying the shared meter and the depth at which
+/// the value sits, used to re-enter budgeted serialization from compound
+/// serializers.
+struct BudgetedValue<'m, T>
+where
+    T: ?Sized,
+{
+    value: &'m T,
+    meter: Meter<'m>,
+    depth: usize,
+}
+
+impl<'m, T> Serialize for BudgetedValue<'m, T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(BudgetedSerializer {
+            ser: serializer,
+            meter: self.meter,
+            depth: self.depth,
+        })
+    }
+}
+
+/// The wrapper `Serializer` that enforces a [`Budget`].
+struct BudgetedSerializer<'m, S> {
+    ser: S,
+    meter: Meter<'m>,
+    depth: usize,
+}
+
+impl<'m, S> Serializer for BudgetedSerializer<'m, S>
+where
+    S: Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    type SerializeSeq = BudgetedCompound<'m, S::SerializeSeq>;
+    type SerializeTuple = BudgetedCompound<'m, S::SerializeTuple>;
+    type SerializeTupleStruct = BudgetedCompound<'m, S::SerializeTupleStruct>;
+    type SerializeTupleVariant = BudgetedCompound<'m, S::SerializeTupleVariant>;
+    type SerializeMap = BudgetedCompound<'m, S::SerializeMap>;
+    type SerializeStruct = BudgetedCompound<'m, S::SerializeStruct>;
+    type SerializeStructVariant = BudgetedCompound<'m, S::SerializeStructVariant>;
+
+    fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i8(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i16(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i32(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i64(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i128(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u64(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u128(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_f32(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_f64(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_char(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        tri!(self.meter.spend_string_bytes(v.len()));
+        self.ser.serialize_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+        tri!(self.meter.spend_string_bytes(v.len()));
+        self.ser.serialize_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_none()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = BudgetedValue {
+            value,
+            meter: self.meter,
+            depth: self.depth,
+        };
+        self.ser.serialize_some(&value)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.ser
+            .serialize_unit_variant(name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = BudgetedValue {
+            value,
+            meter: self.meter,
+            depth: self.depth,
+        };
+        self.ser.serialize_newtype_struct(name, &value)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = BudgetedValue {
+            value,
+            meter: self.meter,
+            depth: self.depth,
+        };
+        self.ser
+            .serialize_newtype_variant(name, variant_index, variant, &value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self.ser.serialize_seq(len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self.ser.serialize_tuple(len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self.ser.serialize_tuple_struct(name, len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self
+                .ser
+                .serialize_tuple_variant(name, variant_index, variant, len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self.ser.serialize_map(len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self.ser.serialize_struct(name, len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        let depth = self.depth + 1;
+        tri!(self.meter.check_depth(depth));
+        Ok(BudgetedCompound {
+            inner: tri!(self
+                .ser
+                .serialize_struct_variant(name, variant_index, variant, len)),
+            meter: self.meter,
+            depth,
+        })
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Display,
+    {
+        // Measure the formatted length without allocating so the budget also
+        // covers strings produced through `collect_str`.
+        struct LenCounter(usize);
+
+        impl fmt::Write for LenCounter {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 += s.len();
+                Ok(())
+            }
+        }
+
+        let mut counter = LenCounter(0);
+        let _ = fmt::write(&mut counter, format_args!("{}", value));
+        tri!(self.meter.spend_string_bytes(counter.0));
+        self.ser.collect_str(value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.ser.is_human_readable()
+    }
+
+    fn is_self_describing(&self) -> bool {
+        self.ser.is_self_describing()
+    }
+}
+
+/// Compound serializer that charges one element for every element, entry, or
+/// field and keeps nested values under the same budget.
+struct BudgetedCompound<'m, S> {
+    inner: S,
+    meter: Meter<'m>,
+    depth: usize,
+}
+
+impl<'m, S> BudgetedCompound<'m, S> {
+    fn nested<'v, T>(&self, value: &'v T) -> BudgetedValue<'v, T>
+    where
+        T: ?Sized,
+        'm: 'v,
+    {
+        BudgetedValue {
+            value,
+            meter: self.meter,
+            depth: self.depth,
+        }
+    }
+}
+
+impl<'m, S> SerializeSeq for BudgetedCompound<'m, S>
+where
+    S: SerializeSeq,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_element(&self.nested(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'m, S> SerializeTuple for BudgetedCompound<'m, S>
+where
+    S: SerializeTuple,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_element(&self.nested(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'m, S> SerializeTupleStruct for BudgetedCompound<'m, S>
+where
+    S: SerializeTupleStruct,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_field(&self.nested(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'m, S> SerializeTupleVariant for BudgetedCompound<'m, S>
+where
+    S: SerializeTupleVariant,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_field(&self.nested(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'m, S> SerializeMap for BudgetedCompound<'m, S>
+where
+    S: SerializeMap,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_key(&self.nested(key))
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.inner.serialize_value(&self.nested(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'m, S> SerializeStruct for BudgetedCompound<'m, S>
+where
+    S: SerializeStruct,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_field(key, &self.nested(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'m, S> SerializeStructVariant for BudgetedCompound<'m, S>
+where
+    S: SerializeStructVariant,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        tri!(self.meter.spend_element());
+        self.inner.serialize_field(key, &self.nested(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
@@ -0,0 +1,386 @@
+use crate::lib::*;
+
+use crate::de::value;
+use crate::ser::Serialize;
+use crate::__private::ser::{Content, ContentSerializer};
+
+/// One location where the serialized forms of two values differ.
+///
+/// Produced by [`diff`] and [`diff_first`]. The [`path`] identifies where in
+/// the data model the mismatch sits using the same dot-separated syntax as
+/// [`FieldFilter`]: struct field names, map keys, and sequence indices, with
+/// the empty path meaning the values differ at the top level. [`left`] and
+/// [`right`] are short human-readable renderings of what each side holds at
+/// that location.
+///
+/// [`path`]: Difference::path
+/// [`left`]: Difference::left
+/// [`right`]: Difference::right
+/// [`FieldFilter`]: crate::ser::FieldFilter
+#[derive(Clone, Debug)]
+pub struct Difference {
+    path: String,
+    left: String,
+    right: String,
+}
+
+impl Difference {
+    /// Dot-separated path to the mismatch; empty for the top level.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Short rendering of what the left value holds at the path.
+    pub fn left(&self) -> &str {
+        &self.left
+    }
+
+    /// Short rendering of what the right value holds at the path.
+    pub fn right(&self) -> &str {
+        &self.right
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(formatter, "{} != {}", self.left, self.right)
+        } else {
+            write!(
+                formatter,
+                "`{}`: {} != {}",
+                self.path, self.left, self.right
+            )
+        }
+    }
+}
+
+/// Reports every path at which two values serialize differently.
+///
+/// Both values are driven through recording serializers and the captured data
+/// models are compared structurally, so the result is independent of any wire
+/// format and requires no conversion to a concrete `Value` type. An empty
+/// vector means the two values serialize identically.
+///
+/// What is compared is what a data format sees, with the same equivalences as
+/// [`hash`]: struct and tuple names are ignored, newtype structs are
+/// transparent, and integers compare by value regardless of width. Sequences
+/// and maps are compared entry by entry in serialized order; a length
+/// mismatch is reported at the path of the collection itself, followed by
+/// differences in the entries both sides have.
+///
+/// The only errors are those raised by the values' own `Serialize` impls.
+///
+/// ```edition2021
+/// # use serde_derive::Serialize;
+/// #[derive(Serialize)]
+/// struct Config {
+///     threads: usize,
+///     name: String,
+/// }
+///
+/// let old = Config { threads: 4, name: "alpha".to_owned() };
+/// let new = Config { threads: 8, name: "alpha".to_owned() };
+///
+/// let differences = serde::ser::diff(&old, &new).unwrap();
+/// assert_eq!(differences.len(), 1);
+/// assert_eq!(differences[0].path(), "threads");
+/// ```
+///
+/// [`hash`]: crate::ser::hash
+pub fn diff<L, R>(left: &L, right: &R) -> Result<Vec<Difference>, value::Error>
+where
+    L: ?Sized + Serialize,
+    R: ?Sized + Serialize,
+{
+    let left = tri!(left.serialize(ContentSerializer::new()));
+    let right = tri!(right.serialize(ContentSerializer::new()));
+    let mut differences = Vec::new();
+    walk(&left, &right, &mut String::new(), &mut differences, false);
+    Ok(differences)
+}
+
+/// Reports the first path at which two values serialize differently, or
+/// `None` if they serialize identically.
+///
+/// Equivalent to the first element of [`diff`] but stops comparing at the
+/// first mismatch, which is the common case for assertions and change
+/// detection.
+pub fn diff_first<L, R>(left: &L, right: &R) -> Result<Option<Difference>, value::Error>
+where
+    L: ?Sized + Serialize,
+    R: ?Sized + Serialize,
+{
+    let left = tri!(left.serialize(ContentSerializer::new()));
+    let right = tri!(right.serialize(ContentSerializer::new()));
+    let mut differences = Vec::new();
+    walk(&left, &right, &mut String::new(), &mut differences, true);
+    Ok(differences.into_iter().next())
+}
+
+// Unwraps the layers that data formats do not observe: newtype structs are
+// transparent on the Serialize side.
+fn strip(content: &Content) -> &Content {
+    let mut content = content;
+    while let Content::NewtypeStruct(_, inner) = content {
+        content = inner;
+    }
+    content
+}
+
+fn push_segment(path: &mut String, segment: &str) -> usize {
+    let len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(segment);
+    len
+}
+
+fn record(left: &Content, right: &Content, path: &str, out: &mut Vec<Difference>) {
+    out.push(Difference {
+        path: path.to_owned(),
+        left: describe(left),
+        right: describe(right),
+    });
+}
+
+fn walk(
+    left: &Content,
+    right: &Content,
+    path: &mut String,
+    out: &mut Vec<Difference>,
+    first_only: bool,
+) {
+    if first_only && !out.is_empty() {
+        return;
+    }
+
+    let left = strip(left);
+    let right = strip(right);
+
+    match (left, right) {
+        (Content::Bool(l), Content::Bool(r)) if l == r => {}
+        (Content::Char(l), Content::Char(r)) if l == r => {}
+        (Content::String(l), Content::String(r)) if l == r => {}
+        (Content::Bytes(l), Content::Bytes(r)) if l == r => {}
+        (Content::None, Content::None) => {}
+        (Content::Some(l), Content::Some(r)) => walk(l, r, path, out, first_only),
+        (Content::Unit, Content::Unit)
+        | (Content::Unit, Content::UnitStruct(_))
+        | (Content::UnitStruct(_), Content::Unit)
+        | (Content::UnitStruct(_), Content::UnitStruct(_)) => {}
+        (Content::UnitVariant(_, _, l), Content::UnitVariant(_, _, r)) if l == r => {}
+        (
+            Content::NewtypeVariant(_, _, lv, l),
+            Content::NewtypeVariant(_, _, rv, r),
+        ) if lv == rv => walk(l, r, path, out, first_only),
+        (
+            Content::TupleVariant(_, _, lv, l),
+            Content::TupleVariant(_, _, rv, r),
+        ) if lv == rv => walk_elements(l, r, left, right, path, out, first_only),
+        (
+            Content::StructVariant(_, _, lv, l),
+            Content::StructVariant(_, _, rv, r),
+        ) if lv == rv => walk_fields(l, r, left, right, path, out, first_only),
+        (Content::Struct(_, l), Content::Struct(_, r)) => {
+            walk_fields(l, r, left, right, path, out, first_only);
+        }
+        (Content::Map(l), Content::Map(r)) => {
+            walk_entries(l, r, left, right, path, out, first_only);
+        }
+        _ => {
+            // Sequences, tuples, and tuple structs are indistinguishable to
+            // most formats, so they compare against each other; everything
+            // else that falls through here is a genuine mismatch.
+            match (elements(left), elements(right)) {
+                (Some(l), Some(r)) => walk_elements(l, r, left, right, path, out, first_only),
+                _ => {
+                    if !scalar_eq(left, right) {
+                        record(left, right, path, out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn walk_elements(
+    left: &[Content],
+    right: &[Content],
+    left_parent: &Content,
+    right_parent: &Content,
+    path: &mut String,
+    out: &mut Vec<Difference>,
+    first_only: bool,
+) {
+    if left.len() != right.len() {
+        record(left_parent, right_parent, path, out);
+    }
+    for (index, (l, r)) in left.iter().zip(right).enumerate() {
+        if first_only && !out.is_empty() {
+            return;
+        }
+        let truncate_to = push_segment(path, itoa_segment(index).as_str());
+        walk(l, r, path, out, first_only);
+        path.truncate(truncate_to);
+    }
+}
+
+fn walk_fields(
+    left: &[(&'static str, Content)],
+    right: &[(&'static str, Content)],
+    left_parent: &Content,
+    right_parent: &Content,
+    path: &mut String,
+    out: &mut Vec<Difference>,
+    first_only: bool,
+) {
+    if left.len() != right.len() {
+        record(left_parent, right_parent, path, out);
+    }
+    for (&(lk, ref lv), &(rk, ref rv)) in left.iter().zip(right) {
+        if first_only && !out.is_empty() {
+            return;
+        }
+        if lk != rk {
+            record(left_parent, right_parent, path, out);
+            continue;
+        }
+        let truncate_to = push_segment(path, lk);
+        walk(lv, rv, path, out, first_only);
+        path.truncate(truncate_to);
+    }
+}
+
+fn walk_entries(
+    left: &[(Content, Content)],
+    right: &[(Content, Content)],
+    left_parent: &Content,
+    right_parent: &Content,
+    path: &mut String,
+    out: &mut Vec<Difference>,
+    first_only: bool,
+) {
+    if left.len() != right.len() {
+        record(left_parent, right_parent, path, out);
+    }
+    for ((lk, lv), (rk, rv)) in left.iter().zip(right) {
+        if first_only && !out.is_empty() {
+            return;
+        }
+        let mut key_differences = Vec::new();
+        walk(lk, rk, &mut String::new(), &mut key_differences, true);
+        if !key_differences.is_empty() {
+            record(left_parent, right_parent, path, out);
+            continue;
+        }
+        let truncate_to = push_segment(path, key_segment(lk).as_str());
+        walk(lv, rv, path, out, first_only);
+        path.truncate(truncate_to);
+    }
+}
+
+fn elements(content: &Content) -> Option<&[Content]> {
+    match content {
+        Content::Seq(elements) | Content::Tuple(elements) => Some(elements),
+        Content::TupleStruct(_, fields) => Some(fields),
+        _ => None,
+    }
+}
+
+// Numeric equality across integer widths, matching the equivalences used by
+// the structural hash.
+fn scalar_eq(left: &Content, right: &Content) -> bool {
+    match (as_unsigned(left), as_unsigned(right)) {
+        (Some(l), Some(r)) => return l == r,
+        (None, None) => {}
+        _ => return false,
+    }
+    match (as_signed(left), as_signed(right)) {
+        (Some(l), Some(r)) => return l == r,
+        (None, None) => {}
+        _ => return false,
+    }
+    match (as_float(left), as_float(right)) {
+        (Some(l), Some(r)) => l.to_bits() == r.to_bits(),
+        _ => false,
+    }
+}
+
+fn as_unsigned(content: &Content) -> Option<u64> {
+    match *content {
+        Content::U8(v) => Some(u64::from(v)),
+        Content::U16(v) => Some(u64::from(v)),
+        Content::U32(v) => Some(u64::from(v)),
+        Content::U64(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn as_signed(content: &Content) -> Option<i64> {
+    match *content {
+        Content::I8(v) => Some(i64::from(v)),
+        Content::I16(v) => Some(i64::from(v)),
+        Content::I32(v) => Some(i64::from(v)),
+        Content::I64(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn as_float(content: &Content) -> Option<f64> {
+    match *content {
+        Content::F32(v) => Some(f64::from(v)),
+        Content::F64(v) => Some(v),
+        _ => None,
+    }
+}
+
+// Renders a map key as a path segment. Keys that have no natural textual
+// form fall back to a placeholder; the rendered path is for humans, not for
+// addressing back into the value.
+fn key_segment(key: &Content) -> String {
+    match *strip(key) {
+        Content::String(ref s) => s.clone(),
+        Content::Char(c) => c.to_string(),
+        Content::Bool(b) => b.to_string(),
+        ref key => match (as_unsigned(key), as_signed(key)) {
+            (Some(v), _) => v.to_string(),
+            (_, Some(v)) => v.to_string(),
+            _ => String::from("?"),
+        },
+    }
+}
+
+fn itoa_segment(index: usize) -> String {
+    index.to_string()
+}
+
+fn describe(content: &Content) -> String {
+    match *content {
+        Content::Bool(v) => format!("bool `{}`", v),
+        Content::Char(v) => format!("char `{:?}`", v),
+        Content::String(ref v) => format!("string {:?}", v),
+        Content::Bytes(ref v) => format!("{} bytes", v.len()),
+        Content::None => String::from("none"),
+        Content::Some(ref v) => format!("some({})", describe(v)),
+        Content::Unit | Content::UnitStruct(_) => String::from("unit"),
+        Content::UnitVariant(_, _, variant)
+        | Content::NewtypeVariant(_, _, variant, _)
+        | Content::TupleVariant(_, _, variant, _)
+        | Content::StructVariant(_, _, variant, _) => format!("variant `{}`", variant),
+        Content::NewtypeStruct(_, ref inner) => describe(inner),
+        Content::Seq(ref v) | Content::Tuple(ref v) => {
+            format!("sequence of {} elements", v.len())
+        }
+        Content::TupleStruct(_, ref v) => format!("sequence of {} elements", v.len()),
+        Content::Map(ref v) => format!("map of {} entries", v.len()),
+        Content::Struct(_, ref v) => format!("struct with {} fields", v.len()),
+        ref content => match (as_unsigned(content), as_signed(content), as_float(content)) {
+            (Some(v), _, _) => format!("integer `{}`", v),
+            (_, Some(v), _) => format!("integer `{}`", v),
+            (_, _, Some(v)) => format!("float `{}`", v),
+            _ => String::from("?"),
+        },
+    }
+}
@@ -0,0 +1,687 @@
+use crate::lib::*;
+
+use crate::__private::ser::{Content, ContentSerializer};
+use crate::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+/// A runtime selection of fields to keep or drop during serialization.
+///
+/// A filter holds a set of dot-separated field paths such as `"a"` or
+/// `"a.b.c"` and operates in one of two modes: [`include`] keeps only the
+/// named paths (and everything below them), [`exclude`] keeps everything
+/// except the named paths. Applying a filter to a value with [`apply`]
+/// produces a `Serialize` adapter that drops non-matching struct fields and
+/// string-keyed map entries on the way to any `Serializer`, so APIs can
+/// implement sparse field selection (`?fields=a,b.c`) over any serializable
+/// type without bespoke DTOs.
+///
+/// Paths name struct fields and string map keys. Sequences are transparent:
+/// a path into a `Vec` of structs applies to every element. Entries of maps
+/// with non-string keys are never dropped.
+///
+/// ```edition2021
+/// use serde::ser::FieldFilter;
+///
+/// # use serde_derive::Serialize;
+/// # #[derive(Serialize)]
+/// # struct User { name: String, email: String, address: String }
+/// # fn example<S>(serializer: S, user: &User) -> Result<S::Ok, S::Error>
+/// # where
+/// #     S: serde::Serializer,
+/// # {
+/// let filter = FieldFilter::include(["name", "email"]);
+///
+/// // Serializes only the `name` and `email` fields of the user.
+/// serde::Serialize::serialize(&filter.apply(user), serializer)
+/// # }
+/// ```
+///
+/// Because the number of surviving entries is not known up front, filtered
+/// maps are serialized with a length hint of `None` and filtered structs
+/// report dropped fields through [`SerializeStruct::skip_field`].
+///
+/// [`include`]: FieldFilter::include
+/// [`exclude`]: FieldFilter::exclude
+/// [`apply`]: FieldFilter::apply
+/// [`SerializeStruct::skip_field`]: crate::ser::SerializeStruct::skip_field
+#[derive(Clone, Debug)]
+pub struct FieldFilter {
+    paths: Vec<Vec<String>>,
+    mode: Mode,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    Include,
+    Exclude,
+}
+
+enum Decision {
+    /// The subtree at this path is serialized unchanged.
+    Keep,
+    /// Some descendants of this path are dropped; descend and keep filtering.
+    Descend,
+    /// The field or entry at this path is dropped.
+    Drop,
+}
+
+impl FieldFilter {
+    /// Creates a filter that keeps only the given dot-separated field paths.
+    pub fn include<I>(paths: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        FieldFilter {
+            paths: parse_paths(paths),
+            mode: Mode::Include,
+        }
+    }
+
+    /// Creates a filter that keeps everything except the given dot-separated
+    /// field paths.
+    pub fn exclude<I>(paths: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        FieldFilter {
+            paths: parse_paths(paths),
+            mode: Mode::Exclude,
+        }
+    }
+
+    /// Wraps a value so that serializing the wrapper applies this filter.
+    pub fn apply<'f, T>(&'f self, value: &'f T) -> Filtered<'f, T>
+    where
+        T: ?Sized + Serialize,
+    {
+        Filtered {
+            value,
+            filter: self,
+            path: Vec::new(),
+        }
+    }
+
+    fn decide(&self, path: &[String]) -> Decision {
+        let matches =
+            |pattern: &[String]| path.len() >= pattern.len() && path[..pattern.len()] == *pattern;
+        let reaches_below =
+            |pattern: &[String]| pattern.len() > path.len() && pattern[..path.len()] == *path;
+        match self.mode {
+            Mode::Include => {
+                if self.paths.iter().any(|pattern| matches(pattern)) {
+                    Decision::Keep
+                } else if self.paths.iter().any(|pattern| reaches_below(pattern)) {
+                    Decision::Descend
+                } else {
+                    Decision::Drop
+                }
+            }
+            Mode::Exclude => {
+                if self.paths.iter().any(|pattern| matches(pattern)) {
+                    Decision::Drop
+                } else if self.paths.iter().any(|pattern| reaches_below(pattern)) {
+                    Decision::Descend
+                } else {
+                    Decision::Keep
+                }
+            }
+        }
+    }
+}
+
+fn parse_paths<I>(paths: I) -> Vec<Vec<String>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    paths
+        .into_iter()
+        .map(|path| path.as_ref().split('.').map(str::to_owned).collect())
+        .collect()
+}
+
+/// A value paired with a [`FieldFilter`], created by [`FieldFilter::apply`].
+///
+/// Serializing a `Filtered` serializes the underlying value with the
+/// non-matching fields dropped.
+pub struct Filtered<'f, T>
+where
+    T: ?Sized,
+{
+    value: &'f T,
+    filter: &'f FieldFilter,
+    path: Vec<String>,
+}
+
+impl<'f, T> Serialize for Filtered<'f, T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(FilteredSerializer {
+            ser: serializer,
+            filter: self.filter,
+            path: self.path.clone(),
+        })
+    }
+}
+
+/// The wrapper `Serializer` that drives a [`FieldFilter`]. It is only ever
+/// used at paths where the filter still has something to drop; fully kept
+/// subtrees are handed to the underlying serializer unwrapped.
+struct FilteredSerializer<'f, S> {
+    ser: S,
+    filter: &'f FieldFilter,
+    path: Vec<String>,
+}
+
+impl<'f, S> Serializer for FilteredSerializer<'f, S>
+where
+    S: Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    type SerializeSeq = FilteredElements<'f, S::SerializeSeq>;
+    type SerializeTuple = FilteredElements<'f, S::SerializeTuple>;
+    type SerializeTupleStruct = FilteredElements<'f, S::SerializeTupleStruct>;
+    type SerializeTupleVariant = FilteredElements<'f, S::SerializeTupleVariant>;
+    type SerializeMap = FilteredMap<'f, S::SerializeMap>;
+    type SerializeStruct = FilteredFields<'f, S::SerializeStruct>;
+    type SerializeStructVariant = FilteredFields<'f, S::SerializeStructVariant>;
+
+    fn serialize_bool(self, v: bool) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i8(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i16(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i32(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i64(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_i128(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u64(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_u128(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_f32(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_f64(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_char(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_none()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path,
+        };
+        self.ser.serialize_some(&value)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.ser.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.ser
+            .serialize_unit_variant(name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path,
+        };
+        self.ser.serialize_newtype_struct(name, &value)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path,
+        };
+        self.ser
+            .serialize_newtype_variant(name, variant_index, variant, &value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(FilteredElements {
+            inner: tri!(self.ser.serialize_seq(len)),
+            filter: self.filter,
+            path: self.path,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Ok(FilteredElements {
+            inner: tri!(self.ser.serialize_tuple(len)),
+            filter: self.filter,
+            path: self.path,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Ok(FilteredElements {
+            inner: tri!(self.ser.serialize_tuple_struct(name, len)),
+            filter: self.filter,
+            path: self.path,
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Ok(FilteredElements {
+            inner: tri!(self
+                .ser
+                .serialize_tuple_variant(name, variant_index, variant, len)),
+            filter: self.filter,
+            path: self.path,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        // The number of entries surviving the filter is not known up front.
+        Ok(FilteredMap {
+            inner: tri!(self.ser.serialize_map(None)),
+            filter: self.filter,
+            path: self.path,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Ok(FilteredFields {
+            inner: tri!(self.ser.serialize_struct(name, len)),
+            filter: self.filter,
+            path: self.path,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Ok(FilteredFields {
+            inner: tri!(self
+                .ser
+                .serialize_struct_variant(name, variant_index, variant, len)),
+            filter: self.filter,
+            path: self.path,
+        })
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Display,
+    {
+        self.ser.collect_str(value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.ser.is_human_readable()
+    }
+
+    fn is_self_describing(&self) -> bool {
+        self.ser.is_self_describing()
+    }
+}
+
+/// Sequence and tuple elements are not addressable by field paths, so the
+/// filter passes through them unchanged while continuing to apply to any
+/// structs or maps nested inside.
+struct FilteredElements<'f, S> {
+    inner: S,
+    filter: &'f FieldFilter,
+    path: Vec<String>,
+}
+
+impl<'f, S> SerializeSeq for FilteredElements<'f, S>
+where
+    S: SerializeSeq,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path.clone(),
+        };
+        self.inner.serialize_element(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'f, S> SerializeTuple for FilteredElements<'f, S>
+where
+    S: SerializeTuple,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path.clone(),
+        };
+        self.inner.serialize_element(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'f, S> SerializeTupleStruct for FilteredElements<'f, S>
+where
+    S: SerializeTupleStruct,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path.clone(),
+        };
+        self.inner.serialize_field(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'f, S> SerializeTupleVariant for FilteredElements<'f, S>
+where
+    S: SerializeTupleVariant,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = Filtered {
+            value,
+            filter: self.filter,
+            path: self.path.clone(),
+        };
+        self.inner.serialize_field(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+/// Applies the filter to string-keyed map entries. Keys are buffered as
+/// `Content` so that the decision to drop an entry can be made before
+/// anything is written to the underlying serializer.
+struct FilteredMap<'f, S> {
+    inner: S,
+    filter: &'f FieldFilter,
+    path: Vec<String>,
+    key: Option<Content>,
+}
+
+impl<'f, S> SerializeMap for FilteredMap<'f, S>
+where
+    S: SerializeMap,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(tri!(key.serialize(ContentSerializer::new())));
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        let segment = match &key {
+            Content::String(s) => Some(s.clone()),
+            _ => None,
+        };
+        match segment {
+            Some(segment) => {
+                let mut path = self.path.clone();
+                path.push(segment);
+                match self.filter.decide(&path) {
+                    Decision::Keep => {
+                        tri!(self.inner.serialize_key(&key));
+                        self.inner.serialize_value(value)
+                    }
+                    Decision::Descend => {
+                        tri!(self.inner.serialize_key(&key));
+                        self.inner.serialize_value(&Filtered {
+                            value,
+                            filter: self.filter,
+                            path,
+                        })
+                    }
+                    Decision::Drop => Ok(()),
+                }
+            }
+            // Entries with non-string keys cannot be named by a field path
+            // and are kept unchanged.
+            None => {
+                tri!(self.inner.serialize_key(&key));
+                self.inner.serialize_value(value)
+            }
+        }
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+/// Applies the filter to struct fields, reporting dropped fields through
+/// `skip_field`.
+struct FilteredFields<'f, S> {
+    inner: S,
+    filter: &'f FieldFilter,
+    path: Vec<String>,
+}
+
+impl<'f, S> FilteredFields<'f, S> {
+    fn child_path(&self, key: &'static str) -> Vec<String> {
+        let mut path = self.path.clone();
+        path.push(String::from(key));
+        path
+    }
+}
+
+impl<'f, S> SerializeStruct for FilteredFields<'f, S>
+where
+    S: SerializeStruct,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let path = self.child_path(key);
+        match self.filter.decide(&path) {
+            Decision::Keep => self.inner.serialize_field(key, value),
+            Decision::Descend => self.inner.serialize_field(
+                key,
+                &Filtered {
+                    value,
+                    filter: self.filter,
+                    path,
+                },
+            ),
+            Decision::Drop => self.inner.skip_field(key),
+        }
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+impl<'f, S> SerializeStructVariant for FilteredFields<'f, S>
+where
+    S: SerializeStructVariant,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let path = self.child_path(key);
+        match self.filter.decide(&path) {
+            Decision::Keep => self.inner.serialize_field(key, value),
+            Decision::Descend => self.inner.serialize_field(
+                key,
+                &Filtered {
+                    value,
+                    filter: self.filter,
+                    path,
+                },
+            ),
+            Decision::Drop => self.inner.skip_field(key),
+        }
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
@@ -0,0 +1,473 @@
+use crate::lib::*;
+
+use crate::de::value::Error;
+use crate::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+use core::hash::Hasher;
+
+// One domain-separation byte per data model construct, fed to the hasher
+// before the construct's payload so that, for example, an empty string and an
+// empty sequence produce different digests.
+const BOOL: u8 = 0;
+const SIGNED: u8 = 1;
+const UNSIGNED: u8 = 2;
+const FLOAT: u8 = 3;
+const CHAR: u8 = 4;
+const STR: u8 = 5;
+const BYTES: u8 = 6;
+const NONE: u8 = 7;
+const SOME: u8 = 8;
+const UNIT: u8 = 9;
+const VARIANT: u8 = 10;
+const SEQ: u8 = 11;
+const MAP: u8 = 12;
+const END: u8 = 13;
+
+// Terminates strings in the hash stream. Not a valid UTF-8 byte, so it cannot
+// collide with string contents; this is the same trick `Hash for str` uses.
+const STR_END: u8 = 0xff;
+
+/// Hashes the serialized form of `value` into a caller-supplied [`Hasher`].
+///
+/// The value is driven through [`HashSerializer`], which feeds the Serde data
+/// model itself — not any particular wire format — into the hasher. Two
+/// values that serialize identically produce identical digests no matter what
+/// data format they would eventually be written in, which makes the digest
+/// suitable for caching and change detection keyed on serialized content.
+///
+/// What participates in the digest is exactly what a data format sees:
+/// primitive values, string and byte contents, sequence and map structure,
+/// field names, and variant names. Struct and tuple names do not participate,
+/// and newtype structs are transparent, so `#[serde(transparent)]`-style
+/// wrappers hash the same as their contents. Integers hash by value after
+/// widening, so the same number serialized as `u32` or `u64` hashes the same.
+///
+/// The only errors are those raised by the value's own `Serialize` impl;
+/// hashing itself cannot fail.
+///
+/// ```edition2021
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::Hasher;
+///
+/// # use serde_derive::Serialize;
+/// # #[derive(Serialize)]
+/// # struct Config { threads: usize, name: String }
+/// # fn example(config: &Config) -> u64 {
+/// let mut hasher = DefaultHasher::new();
+/// serde::ser::hash(config, &mut hasher).unwrap();
+/// hasher.finish()
+/// # }
+/// ```
+pub fn hash<T, H>(value: &T, hasher: &mut H) -> Result<(), Error>
+where
+    T: ?Sized + Serialize,
+    H: Hasher,
+{
+    value.serialize(HashSerializer::new(hasher))
+}
+
+/// A `Serializer` that feeds the data model into a [`Hasher`].
+///
+/// This is the serializer behind [`hash`]; use it directly when the value is
+/// only available through a `Serialize` trait object or a seed.
+pub struct HashSerializer<'h, H> {
+    hasher: &'h mut H,
+}
+
+impl<'h, H> HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    /// Creates a serializer that writes into `hasher`.
+    pub fn new(hasher: &'h mut H) -> Self {
+        HashSerializer { hasher }
+    }
+
+    fn hash_str(&mut self, v: &str) {
+        self.hasher.write_u8(STR);
+        self.hasher.write(v.as_bytes());
+        self.hasher.write_u8(STR_END);
+    }
+
+    fn reborrow(&mut self) -> HashSerializer<'_, H> {
+        HashSerializer {
+            hasher: self.hasher,
+        }
+    }
+}
+
+impl<'h, H> Serializer for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.hasher.write_u8(BOOL);
+        self.hasher.write_u8(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.serialize_i128(i128::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.hasher.write_u8(SIGNED);
+        self.hasher.write_i128(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.serialize_u128(u128::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.hasher.write_u8(UNSIGNED);
+        self.hasher.write_u128(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.hasher.write_u8(FLOAT);
+        self.hasher.write_u64(v.to_bits());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.hasher.write_u8(CHAR);
+        self.hasher.write_u32(v as u32);
+        Ok(())
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<(), Error> {
+        self.hash_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.hasher.write_u8(BYTES);
+        self.hasher.write_u64(v.len() as u64);
+        self.hasher.write(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.hasher.write_u8(NONE);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.hasher.write_u8(SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.hasher.write_u8(UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.hasher.write_u8(VARIANT);
+        self.hash_str(variant);
+        self.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.hasher.write_u8(VARIANT);
+        self.hash_str(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Error> {
+        // Sequence lengths are hashed implicitly by the END marker, not from
+        // the hint, which a format is free to ignore and which may be absent.
+        self.hasher.write_u8(SEQ);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, Error> {
+        self.hasher.write_u8(VARIANT);
+        self.hash_str(variant);
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, Error> {
+        self.hasher.write_u8(MAP);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, Error> {
+        self.hasher.write_u8(VARIANT);
+        self.hash_str(variant);
+        self.serialize_map(Some(len))
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Display,
+    {
+        struct Adapter<'a, H> {
+            hasher: &'a mut H,
+        }
+
+        impl<'a, H> fmt::Write for Adapter<'a, H>
+        where
+            H: Hasher,
+        {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.hasher.write(s.as_bytes());
+                Ok(())
+            }
+        }
+
+        self.hasher.write_u8(STR);
+        let mut adapter = Adapter {
+            hasher: self.hasher,
+        };
+        // Display impls used with collect_str are expected to be infallible.
+        let _ = fmt::write(&mut adapter, format_args!("{}", value));
+        self.hasher.write_u8(STR_END);
+        Ok(())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'h, H> SerializeSeq for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self.reborrow())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.hasher.write_u8(END);
+        Ok(())
+    }
+}
+
+impl<'h, H> SerializeTuple for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'h, H> SerializeTupleStruct for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'h, H> SerializeTupleVariant for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'h, H> SerializeMap for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(self.reborrow())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self.reborrow())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.hasher.write_u8(END);
+        Ok(())
+    }
+}
+
+impl<'h, H> SerializeStruct for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.hash_str(key);
+        value.serialize(self.reborrow())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.hasher.write_u8(END);
+        Ok(())
+    }
+}
+
+impl<'h, H> SerializeStructVariant for HashSerializer<'h, H>
+where
+    H: Hasher,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeStruct::end(self)
+    }
+}
@@ -31,8 +31,12 @@ primitive_impl!(u16, serialize_u16);
 primitive_impl!(u32, serialize_u32);
 primitive_impl!(u64, serialize_u64);
 primitive_impl!(u128, serialize_u128);
+#[cfg(feature = "unstable")]
+primitive_impl!(f16, serialize_f16);
 primitive_impl!(f32, serialize_f32);
 primitive_impl!(f64, serialize_f64);
+#[cfg(feature = "unstable")]
+primitive_impl!(f128, serialize_f128);
 primitive_impl!(char, serialize_char);
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -328,6 +332,21 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+impl Serialize for cmp::Ordering {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            cmp::Ordering::Less => serializer.serialize_unit_variant("Ordering", 0, "Less"),
+            cmp::Ordering::Equal => serializer.serialize_unit_variant("Ordering", 1, "Equal"),
+            cmp::Ordering::Greater => serializer.serialize_unit_variant("Ordering", 2, "Greater"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 impl Serialize for () {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -496,9 +515,12 @@ deref_impl!(<'a, T: ?Sized> Serialize for Cow<'a, T> where T: Serialize + ToOwne
 
 ////////////////////////////////////////////////////////////////////////////////
 
-/// This impl requires the [`"rc"`] Cargo feature of Serde.
+/// This impl requires the [`"rc"`] Cargo feature of Serde. Serializes as the
+/// `Option<T>` returned by [`upgrade`]: the pointed-to value while any strong
+/// pointer to it remains, `None` once the weak reference is dangling.
 ///
 /// [`"rc"`]: https://serde.rs/feature-flags.html#-features-rc
+/// [`upgrade`]: std::rc::Weak::upgrade
 #[cfg(all(feature = "rc", any(feature = "std", feature = "alloc")))]
 impl<T: ?Sized> Serialize for RcWeak<T>
 where
@@ -512,9 +534,12 @@ where
     }
 }
 
-/// This impl requires the [`"rc"`] Cargo feature of Serde.
+/// This impl requires the [`"rc"`] Cargo feature of Serde. Serializes as the
+/// `Option<T>` returned by [`upgrade`]: the pointed-to value while any strong
+/// pointer to it remains, `None` once the weak reference is dangling.
 ///
 /// [`"rc"`]: https://serde.rs/feature-flags.html#-features-rc
+/// [`upgrade`]: std::sync::Weak::upgrade
 #[cfg(all(feature = "rc", any(feature = "std", feature = "alloc")))]
 impl<T: ?Sized> Serialize for ArcWeak<T>
 where
@@ -530,6 +555,9 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+// On compilers where the NonZero* types are aliases of the generic
+// `NonZero<T>` (Rust 1.79+), these impls cover every possible instantiation of
+// `NonZero<T>` since only the primitive integers implement ZeroablePrimitive.
 macro_rules! nonzero_integers {
     ($($T:ident,)+) => {
         $(
@@ -623,6 +651,42 @@ where
     }
 }
 
+// An uninitialized cell serializes as `None`, an initialized one as `Some` of
+// the inner value.
+#[cfg(not(no_once_cell))]
+impl<T> Serialize for OnceCell<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.get() {
+            Some(value) => serializer.serialize_some(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+// An uninitialized lock serializes as `None`, an initialized one as `Some` of
+// the inner value.
+#[cfg(all(feature = "std", not(no_once_cell)))]
+impl<T> Serialize for OnceLock<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.get() {
+            Some(value) => serializer.serialize_some(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 impl<T, E> Serialize for Result<T, E>
@@ -645,12 +709,25 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Human-readable formats get a string such as `"1.000000002s"`, other
+/// formats the `{secs, nanos}` struct encoding.
 impl Serialize for Duration {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         use super::SerializeStruct;
+        if serializer.is_human_readable() {
+            return if self.subsec_nanos() == 0 {
+                serializer.collect_str(&format_args!("{}s", self.as_secs()))
+            } else {
+                serializer.collect_str(&format_args!(
+                    "{}.{:09}s",
+                    self.as_secs(),
+                    self.subsec_nanos()
+                ))
+            };
+        }
         let mut state = tri!(serializer.serialize_struct("Duration", 2));
         tri!(state.serialize_field("secs", &self.as_secs()));
         tri!(state.serialize_field("nanos", &self.subsec_nanos()));
@@ -660,6 +737,9 @@ impl Serialize for Duration {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Human-readable formats get an RFC 3339 UTC timestamp such as
+/// `"2001-09-09T01:46:40Z"`, other formats the
+/// `{secs_since_epoch, nanos_since_epoch}` struct encoding.
 #[cfg(feature = "std")]
 impl Serialize for SystemTime {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -667,10 +747,43 @@ impl Serialize for SystemTime {
         S: Serializer,
     {
         use super::SerializeStruct;
+
+        // Gregorian calendar date for a number of days since 1970-01-01,
+        // following http://howardhinnant.github.io/date_algorithms.html.
+        fn civil_from_days(days: u64) -> (u64, u32, u32) {
+            let z = days + 719468;
+            let era = z / 146097;
+            let doe = z % 146097;
+            let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+            let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+            let mp = (5 * doy + 2) / 153;
+            let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+            let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+            let year = yoe + era * 400 + (month <= 2) as u64;
+            (year, month, day)
+        }
+
         let duration_since_epoch = match self.duration_since(UNIX_EPOCH) {
             Ok(duration_since_epoch) => duration_since_epoch,
             Err(_) => return Err(S::Error::custom("SystemTime must be later than UNIX_EPOCH")),
         };
+        if serializer.is_human_readable() {
+            let secs = duration_since_epoch.as_secs();
+            let nanos = duration_since_epoch.subsec_nanos();
+            let (year, month, day) = civil_from_days(secs / 86400);
+            let (hour, minute, second) = (secs / 3600 % 24, secs / 60 % 60, secs % 60);
+            return if nanos == 0 {
+                serializer.collect_str(&format_args!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                    year, month, day, hour, minute, second
+                ))
+            } else {
+                serializer.collect_str(&format_args!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+                    year, month, day, hour, minute, second, nanos
+                ))
+            };
+        }
         let mut state = tri!(serializer.serialize_struct("SystemTime", 2));
         tri!(state.serialize_field("secs_since_epoch", &duration_since_epoch.as_secs()));
         tri!(state.serialize_field("nanos_since_epoch", &duration_since_epoch.subsec_nanos()));
@@ -680,31 +793,68 @@ impl Serialize for SystemTime {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Serializes the instant as a signed offset from the moment of
+/// serialization, with negative values denoting instants in the past.
+/// Deserialization applies the offset to the moment of deserialization, so a
+/// round trip shifts the instant by however long serialization, transport,
+/// and deserialization took. Gated behind the "instant" feature because of
+/// this inherent imprecision.
+#[cfg(feature = "instant")]
+impl Serialize for Instant {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use super::SerializeStruct;
+        let now = Instant::now();
+        let (secs, nanos) = if *self >= now {
+            let offset = *self - now;
+            (offset.as_secs() as i64, offset.subsec_nanos() as i32)
+        } else {
+            let offset = now - *self;
+            (-(offset.as_secs() as i64), -(offset.subsec_nanos() as i32))
+        };
+        let mut state = tri!(serializer.serialize_struct("Instant", 2));
+        tri!(state.serialize_field("secs", &secs));
+        tri!(state.serialize_field("nanos", &nanos));
+        state.end()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Serializes the backtrace in its stringified form, the same text that
+/// `Backtrace`'s `Display` impl produces. Frames cannot be reconstructed from
+/// this representation; it is intended for error reports and telemetry.
+#[cfg(all(feature = "std", not(no_std_backtrace)))]
+impl Serialize for Backtrace {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Serialize a value that implements `Display` as a string, when that string is
 /// statically known to never have more than a constant `MAX_LEN` bytes.
 ///
 /// Panics if the `Display` impl tries to write more than `MAX_LEN` bytes.
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 macro_rules! serialize_display_bounded_length {
     ($value:expr, $max:expr, $serializer:expr) => {{
+        use crate::lib::fmt::Write;
+
         let mut buffer = [0u8; $max];
-        let remaining_len = {
-            let mut remaining = &mut buffer[..];
-            write!(remaining, "{}", $value).unwrap();
-            remaining.len()
-        };
-        let written_len = buffer.len() - remaining_len;
-        let written = &buffer[..written_len];
-
-        // write! only provides fmt::Formatter to Display implementations, which
-        // has methods write_str and write_char but no method to write arbitrary
-        // bytes. Therefore `written` must be valid UTF-8.
-        let written_str = str::from_utf8(written).expect("must be valid UTF-8");
-        $serializer.serialize_str(written_str)
+        let mut writer = crate::format::Buf::new(&mut buffer);
+        write!(writer, "{}", $value).unwrap();
+        $serializer.serialize_str(writer.as_str())
     }};
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl Serialize for net::IpAddr {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -728,7 +878,7 @@ impl Serialize for net::IpAddr {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 const DEC_DIGITS_LUT: &[u8] = b"\
       0001020304050607080910111213141516171819\
       2021222324252627282930313233343536373839\
@@ -736,7 +886,7 @@ const DEC_DIGITS_LUT: &[u8] = b"\
       6061626364656667686970717273747576777879\
       8081828384858687888990919293949596979899";
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 #[inline]
 fn format_u8(mut n: u8, out: &mut [u8]) -> usize {
     if n >= 100 {
@@ -774,7 +924,7 @@ fn test_format_u8() {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl Serialize for net::Ipv4Addr {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -798,7 +948,7 @@ impl Serialize for net::Ipv4Addr {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl Serialize for net::Ipv6Addr {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -814,7 +964,7 @@ impl Serialize for net::Ipv6Addr {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl Serialize for net::SocketAddr {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -838,7 +988,7 @@ impl Serialize for net::SocketAddr {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl Serialize for net::SocketAddrV4 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -854,7 +1004,7 @@ impl Serialize for net::SocketAddrV4 {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", not(no_core_net)))]
 impl Serialize for net::SocketAddrV6 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -883,6 +1033,9 @@ impl Serialize for Path {
     {
         match self.to_str() {
             Some(s) => s.serialize(serializer),
+            #[cfg(any(unix, windows))]
+            None => self.as_os_str().serialize(serializer),
+            #[cfg(not(any(unix, windows)))]
             None => Err(Error::custom("path contains invalid UTF-8 characters")),
         }
     }
@@ -898,25 +1051,87 @@ impl Serialize for PathBuf {
     }
 }
 
-#[cfg(all(feature = "std", any(unix, windows)))]
-impl Serialize for OsStr {
-    #[cfg(unix)]
+#[cfg(feature = "std")]
+impl<'a> Serialize for Component<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        fn utf8<E>(os_str: &OsStr) -> Result<&str, E>
+        where
+            E: Error,
+        {
+            os_str
+                .to_str()
+                .ok_or_else(|| Error::custom("path contains invalid UTF-8 characters"))
+        }
+
+        match *self {
+            Component::Prefix(prefix) => {
+                let s = tri!(utf8(prefix.as_os_str()));
+                serializer.serialize_newtype_variant("Component", 0, "Prefix", s)
+            }
+            Component::RootDir => serializer.serialize_unit_variant("Component", 1, "RootDir"),
+            Component::CurDir => serializer.serialize_unit_variant("Component", 2, "CurDir"),
+            Component::ParentDir => serializer.serialize_unit_variant("Component", 3, "ParentDir"),
+            Component::Normal(os_str) => {
+                let s = tri!(utf8(os_str));
+                serializer.serialize_newtype_variant("Component", 4, "Normal", s)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Serialize for Components<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        use std::os::unix::ffi::OsStrExt;
-        serializer.serialize_newtype_variant("OsString", 0, "Unix", self.as_bytes())
+        serializer.collect_seq(self.clone())
     }
+}
 
-    #[cfg(windows)]
+/// Human-readable formats get a cross-platform encoding: a plain string when
+/// the data is valid UTF-8, and the raw platform bytes otherwise. Compact
+/// formats keep the platform-tagged `Unix` / `Windows` enum encoding.
+#[cfg(all(feature = "std", any(unix, windows)))]
+impl Serialize for OsStr {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        use std::os::windows::ffi::OsStrExt;
-        let val = self.encode_wide().collect::<Vec<_>>();
-        serializer.serialize_newtype_variant("OsString", 1, "Windows", &val)
+        if serializer.is_human_readable() {
+            if let Some(s) = self.to_str() {
+                return serializer.serialize_str(s);
+            }
+            #[cfg(unix)]
+            {
+                use std::os::unix::ffi::OsStrExt;
+                return serializer.serialize_bytes(self.as_bytes());
+            }
+            #[cfg(windows)]
+            {
+                use std::os::windows::ffi::OsStrExt;
+                let mut bytes = Vec::new();
+                for unit in self.encode_wide() {
+                    bytes.push(unit as u8);
+                    bytes.push((unit >> 8) as u8);
+                }
+                return serializer.serialize_bytes(&bytes);
+            }
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            serializer.serialize_newtype_variant("OsString", 0, "Unix", self.as_bytes())
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStrExt;
+            let val = self.encode_wide().collect::<Vec<_>>();
+            serializer.serialize_newtype_variant("OsString", 1, "Windows", &val)
+        }
     }
 }
 
@@ -945,6 +1160,20 @@ where
     }
 }
 
+#[cfg(not(no_num_saturating))]
+impl<T> Serialize for Saturating<T>
+where
+    T: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
 impl<T> Serialize for Reverse<T>
 where
     T: Serialize,
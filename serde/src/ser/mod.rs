@@ -85,6 +85,7 @@
 //!    - Range\<T\>
 //!    - RangeInclusive\<T\>
 //!    - Bound\<T\>
+//!    - cmp::Ordering
 //!    - num::NonZero*
 //!    - `!` *(unstable)*
 //!  - **Net types**:
@@ -109,11 +110,26 @@
 
 use crate::lib::*;
 
+mod budget;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod diff;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod filter;
 mod fmt;
+mod hash;
 mod impls;
 mod impossible;
-
+#[cfg(all(feature = "rayon", feature = "std"))]
+pub mod parallel;
+
+pub use self::budget::{Budget, Budgeted};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use self::diff::{diff, diff_first, Difference};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use self::filter::{FieldFilter, Filtered};
+pub use self::hash::{hash, HashSerializer};
 pub use self::impossible::Impossible;
+pub use crate::capabilities::Capabilities;
 
 #[cfg(not(any(feature = "std", feature = "unstable")))]
 #[doc(no_inline)]
@@ -253,6 +269,88 @@ pub trait Serialize {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// A data structure that can be serialized with access to external state.
+///
+/// This is the serialization counterpart of [`DeserializeSeed`]: where a seed
+/// threads state *into* the value being built, `SerializeSeed` threads state
+/// alongside the value being written out, for example a registry that resolves
+/// interned ids back to strings.
+///
+/// Values implementing this trait do not satisfy the `Serialize` bounds of
+/// serializer APIs on their own; bind them to their state with [`Seeded`]
+/// first.
+///
+/// This trait can be derived by combining `#[serde(seed = "State")]` on the
+/// container with `#[serde(serialize_seed_with = "...")]` on the fields that
+/// need the state.
+///
+/// ```edition2021
+/// use serde::ser::{Seeded, SerializeSeed, SerializeStruct, Serializer};
+///
+/// struct Names(Vec<String>);
+///
+/// struct Record {
+///     name: usize, // index into a Names table
+/// }
+///
+/// impl SerializeSeed for Record {
+///     type Seed = Names;
+///
+///     fn serialize_seed<S>(&self, serializer: S, seed: &Names) -> Result<S::Ok, S::Error>
+///     where
+///         S: Serializer,
+///     {
+///         let mut s = serializer.serialize_struct("Record", 1)?;
+///         s.serialize_field("name", &seed.0[self.name])?;
+///         s.end()
+///     }
+/// }
+/// # fn use_it<S: Serializer>(serializer: S, record: Record, names: Names) -> Result<S::Ok, S::Error> {
+/// # serializer.collect_seq(Some(Seeded::new(&record, &names)))
+/// # }
+/// ```
+///
+/// [`DeserializeSeed`]: crate::de::DeserializeSeed
+pub trait SerializeSeed {
+    /// State that is made available during serialization.
+    type Seed: ?Sized;
+
+    /// Serialize this value into the given Serde serializer, with access to
+    /// the provided state.
+    fn serialize_seed<S>(&self, serializer: S, seed: &Self::Seed) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Adapter binding a [`SerializeSeed`] value to its state so that the pair can
+/// be passed to any API expecting `Serialize`.
+pub struct Seeded<'a, T: ?Sized, S: ?Sized> {
+    value: &'a T,
+    seed: &'a S,
+}
+
+impl<'a, T: ?Sized, S: ?Sized> Seeded<'a, T, S> {
+    /// Pairs a value with the state it serializes against.
+    pub fn new(value: &'a T, seed: &'a S) -> Self {
+        Seeded { value, seed }
+    }
+}
+
+impl<'a, T, S> Serialize for Seeded<'a, T, S>
+where
+    T: ?Sized + SerializeSeed<Seed = S>,
+    S: ?Sized,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        self.value.serialize_seed(serializer, self.seed)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// A **data format** that can serialize any data structure supported by Serde.
 ///
 /// The role of this trait is to define the serialization half of the [Serde
@@ -408,7 +506,7 @@ pub trait Serializer: Sized {
     ///
     /// If the format does not differentiate between `i8` and `i64`, a
     /// reasonable implementation would be to cast the value to `i64` and
-    /// forward to `serialize_i64`.
+    /// forward to `serialize_i64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -424,13 +522,15 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error>;
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(i64::from(v))
+    }
 
     /// Serialize an `i16` value.
     ///
     /// If the format does not differentiate between `i16` and `i64`, a
     /// reasonable implementation would be to cast the value to `i64` and
-    /// forward to `serialize_i64`.
+    /// forward to `serialize_i64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -446,13 +546,15 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error>;
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(i64::from(v))
+    }
 
     /// Serialize an `i32` value.
     ///
     /// If the format does not differentiate between `i32` and `i64`, a
     /// reasonable implementation would be to cast the value to `i64` and
-    /// forward to `serialize_i64`.
+    /// forward to `serialize_i64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -468,7 +570,9 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error>;
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(i64::from(v))
+    }
 
     /// Serialize an `i64` value.
     ///
@@ -515,7 +619,7 @@ pub trait Serializer: Sized {
     ///
     /// If the format does not differentiate between `u8` and `u64`, a
     /// reasonable implementation would be to cast the value to `u64` and
-    /// forward to `serialize_u64`.
+    /// forward to `serialize_u64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -531,13 +635,15 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error>;
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(u64::from(v))
+    }
 
     /// Serialize a `u16` value.
     ///
     /// If the format does not differentiate between `u16` and `u64`, a
     /// reasonable implementation would be to cast the value to `u64` and
-    /// forward to `serialize_u64`.
+    /// forward to `serialize_u64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -553,13 +659,15 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error>;
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(u64::from(v))
+    }
 
     /// Serialize a `u32` value.
     ///
     /// If the format does not differentiate between `u32` and `u64`, a
     /// reasonable implementation would be to cast the value to `u64` and
-    /// forward to `serialize_u64`.
+    /// forward to `serialize_u64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -575,7 +683,9 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error>;
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(u64::from(v))
+    }
 
     /// Serialize a `u64` value.
     ///
@@ -618,11 +728,21 @@ pub trait Serializer: Sized {
         Err(Error::custom("u128 is not supported"))
     }
 
+    /// Serialize an `f16` value.
+    ///
+    /// If the format does not differentiate between `f16` and `f32`, a
+    /// reasonable implementation would be to cast the value to `f32` and
+    /// forward to `serialize_f32`. This is the default behavior.
+    #[cfg(feature = "unstable")]
+    fn serialize_f16(self, v: f16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f32(v as f32)
+    }
+
     /// Serialize an `f32` value.
     ///
     /// If the format does not differentiate between `f32` and `f64`, a
     /// reasonable implementation would be to cast the value to `f64` and
-    /// forward to `serialize_f64`.
+    /// forward to `serialize_f64`. This is the default behavior.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -638,7 +758,9 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error>;
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(f64::from(v))
+    }
 
     /// Serialize an `f64` value.
     ///
@@ -658,10 +780,20 @@ pub trait Serializer: Sized {
     /// ```
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error>;
 
+    /// Serialize an `f128` value.
+    ///
+    /// The default behavior unconditionally returns an error.
+    #[cfg(feature = "unstable")]
+    fn serialize_f128(self, v: f128) -> Result<Self::Ok, Self::Error> {
+        let _ = v;
+        Err(Error::custom("f128 is not supported"))
+    }
+
     /// Serialize a character.
     ///
     /// If the format does not support characters, it is reasonable to serialize
-    /// it as a single element `str` or a `u32`.
+    /// it as a single element `str` or a `u32`. The default behavior is to
+    /// serialize it as a single element `str`.
     ///
     /// ```edition2021
     /// # use serde::Serializer;
@@ -677,7 +809,9 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error>;
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(v.encode_utf8(&mut [0u8; 4]))
+    }
 
     /// Serialize a `&str`.
     ///
@@ -821,6 +955,7 @@ pub trait Serializer: Sized {
     /// Serialize a unit struct like `struct Unit` or `PhantomData<T>`.
     ///
     /// A reasonable implementation would be to forward to `serialize_unit`.
+    /// This is the default behavior.
     ///
     /// ```edition2021
     /// use serde::{Serialize, Serializer};
@@ -836,7 +971,10 @@ pub trait Serializer: Sized {
     ///     }
     /// }
     /// ```
-    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error>;
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        let _ = name;
+        self.serialize_unit()
+    }
 
     /// Serialize a unit variant like `E::A` in `enum E { A, B }`.
     ///
@@ -875,7 +1013,7 @@ pub trait Serializer: Sized {
     ///
     /// Serializers are encouraged to treat newtype structs as insignificant
     /// wrappers around the data they contain. A reasonable implementation would
-    /// be to forward to `value.serialize(self)`.
+    /// be to forward to `value.serialize(self)`. This is the default behavior.
     ///
     /// ```edition2021
     /// use serde::{Serialize, Serializer};
@@ -897,7 +1035,11 @@ pub trait Serializer: Sized {
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize;
+        T: Serialize,
+    {
+        let _ = name;
+        value.serialize(self)
+    }
 
     /// Serialize a newtype variant like `E::N` in `enum E { N(u8) }`.
     ///
@@ -1435,6 +1577,51 @@ pub trait Serializer: Sized {
     fn is_human_readable(&self) -> bool {
         true
     }
+
+    /// Determine whether `Serialize` implementations should serialize in a
+    /// form that can be deserialized without knowing the shape of the data up
+    /// front.
+    ///
+    /// Self-describing formats like JSON record the structure of the data in
+    /// the output, so a `Deserializer` for them can answer
+    /// [`deserialize_any`]. Non-self-describing formats like Bincode write
+    /// nothing but the raw values, and representations that need to inspect
+    /// the data before deciding how to interpret it — such as untagged and
+    /// internally tagged enums — cannot be used with them. A `Serialize`
+    /// implementation may consult this flag to pick an encoding that the
+    /// corresponding `Deserializer` will be able to read back.
+    ///
+    /// The default implementation of this method returns `true`.
+    /// Non-self-describing data formats should override it to return `false`.
+    /// The value must agree with [`Deserializer::is_self_describing`] for the
+    /// same format, since a value serialized in one mode is not required to
+    /// deserialize from the other.
+    ///
+    /// [`deserialize_any`]: crate::Deserializer::deserialize_any
+    /// [`Deserializer::is_self_describing`]: crate::Deserializer::is_self_describing
+    #[inline]
+    fn is_self_describing(&self) -> bool {
+        true
+    }
+
+    /// Determine what this data format can represent natively, at a finer
+    /// grain than [`is_human_readable`](Serializer::is_human_readable).
+    ///
+    /// `Serialize` implementations with several possible encodings can
+    /// consult individual [`Capabilities`] flags — raw byte support,
+    /// non-string map keys, integer width preservation, native enums — to
+    /// pick the best one instead of guessing from human-readability alone.
+    ///
+    /// The default implementation returns [`Capabilities::default`]. The
+    /// value must agree with [`Deserializer::capabilities`] for the same
+    /// format, since a value serialized under one set of flags is not
+    /// required to deserialize under another.
+    ///
+    /// [`Deserializer::capabilities`]: crate::Deserializer::capabilities
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
 }
 
 /// Returned from `Serializer::serialize_seq`.
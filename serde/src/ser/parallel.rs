@@ -0,0 +1,99 @@
+//! Parallel pre-serialization of large sequences, behind the `rayon` feature
+//! (which takes effect together with `std`).
+//!
+//! A [`Serializer`] consumes values strictly in order, so serialization itself
+//! cannot be parallelized. What can be parallelized is the per-element work:
+//! the helpers here serialize chunks of a slice into in-memory buffers on
+//! rayon worker threads and then replay the buffers in order through the
+//! `Serializer`. The output is identical to serializing the slice directly.
+//!
+//! The buffering is pure overhead for small inputs, so short slices fall back
+//! to ordinary single-threaded serialization, and nothing changes for callers
+//! that do not use these helpers.
+
+use crate::lib::*;
+
+use crate::de::value;
+use crate::ser::{Error, Serialize, SerializeSeq, Serializer};
+use crate::__private::ser::{Content, ContentSerializer};
+
+use rayon::prelude::*;
+// The prelude's ParallelSlice is shadowed by the adapter type below.
+use rayon::slice::ParallelSlice as _;
+
+/// Number of elements below which parallel buffering is not worth the cost.
+const MIN_PARALLEL_LEN: usize = 2048;
+
+/// Number of elements serialized into each per-thread buffer.
+const CHUNK_LEN: usize = 1024;
+
+/// Serializes `slice` as a sequence, pre-encoding its elements in parallel.
+///
+/// Errors raised by element `Serialize` impls on worker threads are carried
+/// across the thread boundary as their messages and resurface through
+/// [`Error::custom`].
+///
+/// ```edition2021
+/// use serde::ser::parallel;
+/// use serde::Serializer;
+///
+/// fn serialize_samples<S>(samples: &Vec<u64>, serializer: S) -> Result<S::Ok, S::Error>
+/// where
+///     S: Serializer,
+/// {
+///     parallel::serialize_slice(samples, serializer)
+/// }
+/// ```
+pub fn serialize_slice<T, S>(slice: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Sync,
+    S: Serializer,
+{
+    if slice.len() < MIN_PARALLEL_LEN {
+        return serializer.collect_seq(slice);
+    }
+
+    // Buffer with the target serializer's readability so that types whose
+    // encoding depends on is_human_readable are captured in the form the
+    // target expects.
+    let human_readable = serializer.is_human_readable();
+    let chunks: Result<Vec<Vec<Content>>, value::Error> = slice
+        .par_chunks(CHUNK_LEN)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|element| {
+                    element.serialize(ContentSerializer::with_is_human_readable(human_readable))
+                })
+                .collect()
+        })
+        .collect();
+    let chunks = match chunks {
+        Ok(chunks) => chunks,
+        Err(err) => return Err(Error::custom(err)),
+    };
+
+    let mut seq = tri!(serializer.serialize_seq(Some(slice.len())));
+    for element in chunks.iter().flatten() {
+        tri!(seq.serialize_element(element));
+    }
+    seq.end()
+}
+
+/// Adapter that serializes the wrapped slice with [`serialize_slice`].
+///
+/// Useful where a `Serialize` value is required, for example inside another
+/// collection that is serialized normally.
+pub struct ParallelSlice<'a, T>(pub &'a [T]);
+
+impl<'a, T> Serialize for ParallelSlice<'a, T>
+where
+    T: Serialize + Sync,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_slice(self.0, serializer)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::internals::ast::{Container, Data};
+use crate::internals::ast::{self, Container, Data};
 use crate::internals::{attr, ungroup};
 use proc_macro2::Span;
 use std::collections::HashSet;
@@ -110,6 +110,10 @@ pub fn with_bound(
     }
 
     impl<'ast> FindTyParams<'ast> {
+        fn uses_type_param(&self) -> bool {
+            !self.relevant_type_params.is_empty() || !self.associated_type_usage.is_empty()
+        }
+
         fn visit_field(&mut self, field: &'ast syn::Field) {
             if let syn::Type::Path(ty) = ungroup(&field.ty) {
                 if let Some(Pair::Punctuated(t, _)) = ty.path.segments.pairs().next() {
@@ -244,16 +248,41 @@ pub fn with_bound(
         fn visit_macro(&mut self, _mac: &'ast syn::Macro) {}
     }
 
-    let all_type_params = generics
+    let all_type_params: HashSet<syn::Ident> = generics
         .type_params()
         .map(|param| param.ident.clone())
         .collect();
 
     let mut visitor = FindTyParams {
-        all_type_params,
+        all_type_params: all_type_params.clone(),
         relevant_type_params: HashSet::new(),
         associated_type_usage: Vec::new(),
     };
+    // Flattened fields are serialized and deserialized through the whole field
+    // type, not element by element, so the minimal correct bound is on the
+    // field type itself rather than on every type parameter it mentions. This
+    // lets `Inner<T>: Deserialize` impls that do not require `T: Deserialize`
+    // be flattened into generic containers.
+    let mut flattened_type_usage: Vec<syn::Type> = Vec::new();
+    fn visit<'ast>(
+        field: &ast::Field<'ast>,
+        visitor: &mut FindTyParams<'ast>,
+        flattened_type_usage: &mut Vec<syn::Type>,
+    ) {
+        if field.attrs.flatten() {
+            let mut probe = FindTyParams {
+                all_type_params: visitor.all_type_params.clone(),
+                relevant_type_params: HashSet::new(),
+                associated_type_usage: Vec::new(),
+            };
+            probe.visit_field(field.original);
+            if probe.uses_type_param() {
+                flattened_type_usage.push(field.ty.clone());
+            }
+        } else {
+            visitor.visit_field(field.original);
+        }
+    }
     match &cont.data {
         Data::Enum(variants) => {
             for variant in variants {
@@ -262,13 +291,13 @@ pub fn with_bound(
                     .iter()
                     .filter(|field| filter(&field.attrs, Some(&variant.attrs)));
                 for field in relevant_fields {
-                    visitor.visit_field(field.original);
+                    visit(field, &mut visitor, &mut flattened_type_usage);
                 }
             }
         }
         Data::Struct(_, fields) => {
             for field in fields.iter().filter(|field| filter(&field.attrs, None)) {
-                visitor.visit_field(field.original);
+                visit(field, &mut visitor, &mut flattened_type_usage);
             }
         }
     }
@@ -279,16 +308,24 @@ pub fn with_bound(
         .type_params()
         .map(|param| param.ident.clone())
         .filter(|id| relevant_type_params.contains(id))
-        .map(|id| syn::TypePath {
-            qself: None,
-            path: id.into(),
+        .map(|id| {
+            syn::Type::Path(syn::TypePath {
+                qself: None,
+                path: id.into(),
+            })
         })
-        .chain(associated_type_usage.into_iter().cloned())
+        .chain(
+            associated_type_usage
+                .into_iter()
+                .cloned()
+                .map(syn::Type::Path),
+        )
+        .chain(flattened_type_usage)
         .map(|bounded_ty| {
             syn::WherePredicate::Type(syn::PredicateType {
                 lifetimes: None,
                 // the type parameter that is being bounded e.g. T
-                bounded_ty: syn::Type::Path(bounded_ty),
+                bounded_ty,
                 colon_token: <Token![:]>::default(),
                 // the bound e.g. Serialize
                 bounds: vec![syn::TypeParamBound::Trait(syn::TraitBound {
@@ -4,7 +4,7 @@ use crate::internals::{attr, replace_receiver, ungroup, Ctxt, Derive};
 use crate::{bound, dummy, pretend, this};
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ptr;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
@@ -28,7 +28,239 @@ pub fn expand_derive_deserialize(input: &mut syn::DeriveInput) -> syn::Result<To
     let delife = params.borrowed.de_lifetime();
     let serde = cont.attrs.serde_path();
 
-    let impl_block = if let Some(remote) = cont.attrs.remote() {
+    let body = match cont.attrs.finalize() {
+        Some(finalize) => quote! {
+            let __result = { #body };
+            match __result {
+                #serde::__private::Ok(mut __value) => {
+                    #finalize(&mut __value);
+                    #serde::__private::Ok(__value)
+                }
+                #serde::__private::Err(__err) => #serde::__private::Err(__err),
+            }
+        },
+        None => quote!(#body),
+    };
+
+    let body = match cont.attrs.validate() {
+        Some(validate) => quote! {
+            let __result = { #body };
+            match __result {
+                #serde::__private::Ok(__value) => match #validate(&__value) {
+                    #serde::__private::Ok(()) => #serde::__private::Ok(__value),
+                    #serde::__private::Err(__err) => {
+                        #serde::__private::Err(#serde::de::Error::custom(__err))
+                    }
+                },
+                #serde::__private::Err(__err) => #serde::__private::Err(__err),
+            }
+        },
+        None => quote!(#body),
+    };
+
+    let body = if let Some(version) = cont.attrs.version() {
+        // #[serde(version = N)] consumes the `Versioned` wrapper written by
+        // the Serialize expansion. The version number is read before the value
+        // and dispatched on: the current version runs the ordinary
+        // deserialization, and each older version runs its upgrade_from hook.
+        let migrate_arms = cont
+            .attrs
+            .upgrade_from()
+            .iter()
+            .map(|(from, func)| quote!(#from => #func(__deserializer),));
+        let unsupported = format!("unsupported version {{}} of {}", ident);
+        quote! {
+            fn __deserialize_value<'de, __D>(
+                __deserializer: __D,
+                __version: u32,
+            ) -> #serde::__private::Result<#ident, __D::Error>
+            where
+                __D: #serde::Deserializer<'de>,
+            {
+                match __version {
+                    #(#migrate_arms)*
+                    #version => { #body }
+                    _ => #serde::__private::Err(#serde::de::Error::custom(format_args!(
+                        #unsupported,
+                        __version,
+                    ))),
+                }
+            }
+
+            struct __ValueSeed(u32);
+
+            impl<'de> #serde::de::DeserializeSeed<'de> for __ValueSeed {
+                type Value = #ident;
+
+                fn deserialize<__D>(self, __deserializer: __D) -> #serde::__private::Result<Self::Value, __D::Error>
+                where
+                    __D: #serde::Deserializer<'de>,
+                {
+                    __deserialize_value(__deserializer, self.0)
+                }
+            }
+
+            enum __VersionedField {
+                Version,
+                Value,
+            }
+
+            impl<'de> #serde::Deserialize<'de> for __VersionedField {
+                fn deserialize<__D>(__deserializer: __D) -> #serde::__private::Result<Self, __D::Error>
+                where
+                    __D: #serde::Deserializer<'de>,
+                {
+                    struct __FieldVisitor;
+
+                    impl<'de> #serde::de::Visitor<'de> for __FieldVisitor {
+                        type Value = __VersionedField;
+
+                        fn expecting(&self, __formatter: &mut #serde::__private::Formatter) -> #serde::__private::fmt::Result {
+                            #serde::__private::Formatter::write_str(__formatter, "`version` or `value`")
+                        }
+
+                        fn visit_u64<__E>(self, __value: u64) -> #serde::__private::Result<Self::Value, __E>
+                        where
+                            __E: #serde::de::Error,
+                        {
+                            match __value {
+                                0u64 => #serde::__private::Ok(__VersionedField::Version),
+                                1u64 => #serde::__private::Ok(__VersionedField::Value),
+                                _ => #serde::__private::Err(#serde::de::Error::invalid_value(
+                                    #serde::de::Unexpected::Unsigned(__value),
+                                    &"field index 0 <= i < 2",
+                                )),
+                            }
+                        }
+
+                        fn visit_str<__E>(self, __value: &str) -> #serde::__private::Result<Self::Value, __E>
+                        where
+                            __E: #serde::de::Error,
+                        {
+                            match __value {
+                                "version" => #serde::__private::Ok(__VersionedField::Version),
+                                "value" => #serde::__private::Ok(__VersionedField::Value),
+                                _ => #serde::__private::Err(#serde::de::Error::unknown_field(__value, FIELDS)),
+                            }
+                        }
+
+                        fn visit_bytes<__E>(self, __value: &[u8]) -> #serde::__private::Result<Self::Value, __E>
+                        where
+                            __E: #serde::de::Error,
+                        {
+                            match __value {
+                                b"version" => #serde::__private::Ok(__VersionedField::Version),
+                                b"value" => #serde::__private::Ok(__VersionedField::Value),
+                                _ => {
+                                    let __value = &#serde::__private::from_utf8_lossy(__value);
+                                    #serde::__private::Err(#serde::de::Error::unknown_field(__value, FIELDS))
+                                }
+                            }
+                        }
+                    }
+
+                    #serde::Deserializer::deserialize_identifier(__deserializer, __FieldVisitor)
+                }
+            }
+
+            struct __VersionedVisitor;
+
+            impl<'de> #serde::de::Visitor<'de> for __VersionedVisitor {
+                type Value = #ident;
+
+                fn expecting(&self, __formatter: &mut #serde::__private::Formatter) -> #serde::__private::fmt::Result {
+                    #serde::__private::Formatter::write_str(__formatter, "struct Versioned")
+                }
+
+                #[inline]
+                fn visit_seq<__A>(self, mut __seq: __A) -> #serde::__private::Result<Self::Value, __A::Error>
+                where
+                    __A: #serde::de::SeqAccess<'de>,
+                {
+                    let __version = match #serde::de::SeqAccess::next_element::<u32>(&mut __seq)? {
+                        #serde::__private::Some(__version) => __version,
+                        #serde::__private::None => {
+                            return #serde::__private::Err(#serde::de::Error::invalid_length(
+                                0usize,
+                                &"struct Versioned with 2 elements",
+                            ));
+                        }
+                    };
+                    match #serde::de::SeqAccess::next_element_seed(&mut __seq, __ValueSeed(__version))? {
+                        #serde::__private::Some(__value) => #serde::__private::Ok(__value),
+                        #serde::__private::None => #serde::__private::Err(#serde::de::Error::invalid_length(
+                            1usize,
+                            &"struct Versioned with 2 elements",
+                        )),
+                    }
+                }
+
+                #[inline]
+                fn visit_map<__A>(self, mut __map: __A) -> #serde::__private::Result<Self::Value, __A::Error>
+                where
+                    __A: #serde::de::MapAccess<'de>,
+                {
+                    // The version must come first so that the value can be
+                    // deserialized with the right shape in a single pass.
+                    let __version = match #serde::de::MapAccess::next_key::<__VersionedField>(&mut __map)? {
+                        #serde::__private::Some(__VersionedField::Version) => {
+                            #serde::de::MapAccess::next_value::<u32>(&mut __map)?
+                        }
+                        _ => {
+                            return #serde::__private::Err(<__A::Error as #serde::de::Error>::missing_field("version"));
+                        }
+                    };
+                    let mut __value: #serde::__private::Option<Self::Value> = #serde::__private::None;
+                    while let #serde::__private::Some(__key) = #serde::de::MapAccess::next_key::<__VersionedField>(&mut __map)? {
+                        match __key {
+                            __VersionedField::Version => {
+                                return #serde::__private::Err(<__A::Error as #serde::de::Error>::duplicate_field("version"));
+                            }
+                            __VersionedField::Value => {
+                                if #serde::__private::Option::is_some(&__value) {
+                                    return #serde::__private::Err(<__A::Error as #serde::de::Error>::duplicate_field("value"));
+                                }
+                                __value = #serde::__private::Some(
+                                    #serde::de::MapAccess::next_value_seed(&mut __map, __ValueSeed(__version))?,
+                                );
+                            }
+                        }
+                    }
+                    match __value {
+                        #serde::__private::Some(__value) => #serde::__private::Ok(__value),
+                        #serde::__private::None => {
+                            #serde::__private::Err(<__A::Error as #serde::de::Error>::missing_field("value"))
+                        }
+                    }
+                }
+            }
+
+            const FIELDS: &'static [&'static str] = &["version", "value"];
+            #serde::Deserializer::deserialize_struct(__deserializer, "Versioned", FIELDS, __VersionedVisitor)
+        }
+    } else {
+        body
+    };
+
+    let impl_block = if let Some(seed_ty) = cont.attrs.seed() {
+        // #[serde(seed = "State")] generates a DeserializeSeed impl for the
+        // state type instead of a Deserialize impl for the container. The
+        // state is handed to the visitor so that seed_with fields can use it.
+        quote! {
+            #[automatically_derived]
+            impl #de_impl_generics #serde::de::DeserializeSeed<#delife> for #seed_ty #where_clause {
+                type Value = #ident #ty_generics;
+
+                fn deserialize<__D>(self, __deserializer: __D) -> #serde::__private::Result<Self::Value, __D::Error>
+                where
+                    __D: #serde::Deserializer<#delife>,
+                {
+                    let __seed = self;
+                    #body
+                }
+            }
+        }
+    } else if let Some(remote) = cont.attrs.remote() {
         let vis = &input.vis;
         let used = pretend::pretend_used(&cont, params.is_packed);
         quote! {
@@ -69,6 +301,7 @@ pub fn expand_derive_deserialize(input: &mut syn::DeriveInput) -> syn::Result<To
 fn precondition(cx: &Ctxt, cont: &Container) {
     precondition_sized(cx, cont);
     precondition_no_de_lifetime(cx, cont);
+    crate::ser::precondition_version(cx, cont);
 }
 
 fn precondition_sized(cx: &Ctxt, cont: &Container) {
@@ -307,10 +540,7 @@ fn deserialize_in_place_body(cont: &Container, params: &Parameters) -> Option<St
         || cont.attrs.type_from().is_some()
         || cont.attrs.type_try_from().is_some()
         || cont.attrs.identifier().is_some()
-        || cont
-            .data
-            .all_fields()
-            .all(|f| f.attrs.deserialize_with().is_some())
+        || cont.attrs.version().is_some()
     {
         return None;
     }
@@ -330,6 +560,36 @@ fn deserialize_in_place_body(cont: &Container, params: &Parameters) -> Option<St
     let delife = params.borrowed.de_lifetime();
     let stmts = Stmts(code);
 
+    let stmts = match cont.attrs.finalize() {
+        Some(finalize) => quote! {
+            let __result = { #stmts };
+            match __result {
+                _serde::__private::Ok(()) => {
+                    #finalize(__place);
+                    _serde::__private::Ok(())
+                }
+                _serde::__private::Err(__err) => _serde::__private::Err(__err),
+            }
+        },
+        None => quote!(#stmts),
+    };
+
+    let stmts = match cont.attrs.validate() {
+        Some(validate) => quote! {
+            let __result = { #stmts };
+            match __result {
+                _serde::__private::Ok(()) => match #validate(&*__place) {
+                    _serde::__private::Ok(()) => _serde::__private::Ok(()),
+                    _serde::__private::Err(__err) => {
+                        _serde::__private::Err(_serde::de::Error::custom(__err))
+                    }
+                },
+                _serde::__private::Err(__err) => _serde::__private::Err(__err),
+            }
+        },
+        None => quote!(#stmts),
+    };
+
     let fn_deserialize_in_place = quote_block! {
         fn deserialize_in_place<__D>(__deserializer: __D, __place: &mut Self) -> _serde::__private::Result<(), __D::Error>
         where
@@ -404,7 +664,7 @@ fn deserialize_try_from(type_try_from: &syn::Type) -> Fragment {
 fn deserialize_unit_struct(params: &Parameters, cattrs: &attr::Container) -> Fragment {
     let this_type = &params.this_type;
     let this_value = &params.this_value;
-    let type_name = cattrs.name().deserialize_name();
+    let type_name = cattrs.name().deserialize_name_expr();
     let (de_impl_generics, de_ty_generics, ty_generics, where_clause) =
         split_with_de_lifetime(params);
     let delife = params.borrowed.de_lifetime();
@@ -519,13 +779,13 @@ fn deserialize_tuple(
     };
     let dispatch = match form {
         TupleForm::Tuple if nfields == 1 => {
-            let type_name = cattrs.name().deserialize_name();
+            let type_name = cattrs.name().deserialize_name_expr();
             quote! {
                 _serde::Deserializer::deserialize_newtype_struct(__deserializer, #type_name, #visitor_expr)
             }
         }
         TupleForm::Tuple => {
-            let type_name = cattrs.name().deserialize_name();
+            let type_name = cattrs.name().deserialize_name_expr();
             quote! {
                 _serde::Deserializer::deserialize_tuple_struct(__deserializer, #type_name, #field_count, #visitor_expr)
             }
@@ -597,9 +857,21 @@ fn deserialize_tuple_in_place(
     let nfields = fields.len();
 
     let visit_newtype_struct = if nfields == 1 {
-        // We do not generate deserialize_in_place if every field has a
-        // deserialize_with.
-        assert!(fields[0].attrs.deserialize_with().is_none());
+        let write = match (
+            fields[0].attrs.deserialize_with_in_place(),
+            fields[0].attrs.deserialize_with(),
+        ) {
+            (Some(path), _) => quote! {
+                #path(__e, &mut self.place.0)
+            },
+            (None, Some(path)) => quote! {
+                self.place.0 = #path(__e)?;
+                _serde::__private::Ok(())
+            },
+            (None, None) => quote! {
+                _serde::Deserialize::deserialize_in_place(__e, &mut self.place.0)
+            },
+        };
 
         Some(quote! {
             #[inline]
@@ -607,7 +879,7 @@ fn deserialize_tuple_in_place(
             where
                 __E: _serde::Deserializer<#delife>,
             {
-                _serde::Deserialize::deserialize_in_place(__e, &mut self.place.0)
+                #write
             }
         })
     } else {
@@ -623,7 +895,7 @@ fn deserialize_tuple_in_place(
         }
     };
 
-    let type_name = cattrs.name().deserialize_name();
+    let type_name = cattrs.name().deserialize_name_expr();
     let dispatch = if nfields == 1 {
         quote!(_serde::Deserializer::deserialize_newtype_struct(__deserializer, #type_name, #visitor_expr))
     } else {
@@ -698,16 +970,43 @@ fn deserialize_seq(
                 let #var = #default;
             }
         } else {
-            let visit = match field.attrs.deserialize_with() {
+            let wrap = match field.attrs.deserialize_with() {
+                Some(path) => Some(wrap_deserialize_field_with(params, field.ty, path)),
+                None => field
+                    .attrs
+                    .key_with()
+                    .map(|path| wrap_deserialize_key_with(params, field.ty, path)),
+            };
+            let visit = match wrap {
                 None => {
-                    let field_ty = field.ty;
-                    let span = field.original.span();
-                    let func =
-                        quote_spanned!(span=> _serde::de::SeqAccess::next_element::<#field_ty>);
-                    quote!(#func(&mut __seq)?)
+                    if let (Some(seed_with), Some(seed_ty)) =
+                        (field.attrs.seed_with(), cattrs.seed())
+                    {
+                        let span = field.original.span();
+                        let func = quote_spanned!(span=> _serde::de::SeqAccess::next_element_seed);
+                        let (wrapper, wrapper_expr) =
+                            wrap_deserialize_seed_with(params, field.ty, seed_ty, seed_with);
+                        quote!({
+                            #wrapper
+                            #func(&mut __seq, #wrapper_expr)?
+                        })
+                    } else if let Some(keyed_by) = field.attrs.keyed_by() {
+                        let span = field.original.span();
+                        let func = quote_spanned!(span=> _serde::de::SeqAccess::next_element_seed);
+                        quote!(#func(&mut __seq, _serde::__private::de::KeyedBySeed::new(#keyed_by))?)
+                    } else if field.attrs.map_from_pairs() {
+                        let span = field.original.span();
+                        let func = quote_spanned!(span=> _serde::de::SeqAccess::next_element_seed);
+                        quote!(#func(&mut __seq, _serde::__private::de::MapFromPairsSeed::new())?)
+                    } else {
+                        let field_ty = field.ty;
+                        let span = field.original.span();
+                        let func =
+                            quote_spanned!(span=> _serde::de::SeqAccess::next_element::<#field_ty>);
+                        quote!(#func(&mut __seq)?)
+                    }
                 }
-                Some(path) => {
-                    let (wrapper, wrapper_ty) = wrap_deserialize_field_with(params, field.ty, path);
+                Some((wrapper, wrapper_ty)) => {
                     quote!({
                         #wrapper
                         _serde::__private::Option::map(
@@ -717,11 +1016,13 @@ fn deserialize_seq(
                 }
             };
             let value_if_none = expr_is_missing_seq(None, index_in_seq, field, cattrs, expecting);
+            let validate = field_validate_check(field, quote!(#var));
             let assign = quote! {
                 let #var = match #visit {
                     _serde::__private::Some(__value) => __value,
                     _serde::__private::None => #value_if_none,
                 };
+                #validate
             };
             index_in_seq += 1;
             assign
@@ -797,18 +1098,66 @@ fn deserialize_seq_in_place(
             }
         } else {
             let value_if_none = expr_is_missing_seq(Some(quote!(self.place.#member = )), index_in_seq, field, cattrs, expecting);
-            let write = match field.attrs.deserialize_with() {
-                None => {
-                    quote! {
-                        if let _serde::__private::None = _serde::de::SeqAccess::next_element_seed(&mut __seq,
-                            _serde::__private::de::InPlaceSeed(&mut self.place.#member))?
-                        {
+            let wrap = match field.attrs.deserialize_with() {
+                Some(path) => Some(wrap_deserialize_field_with(params, field.ty, path)),
+                None => field
+                    .attrs
+                    .key_with()
+                    .map(|path| wrap_deserialize_key_with(params, field.ty, path)),
+            };
+            let write = match (field.attrs.deserialize_with_in_place(), wrap) {
+                (Some(path), _) => {
+                    let (wrapper, wrapper_expr) = wrap_deserialize_with_in_place(
+                        params,
+                        field.ty,
+                        path,
+                        quote!(&mut self.place.#member),
+                    );
+                    quote!({
+                        #wrapper
+                        if let _serde::__private::None = _serde::de::SeqAccess::next_element_seed(&mut __seq, #wrapper_expr)? {
                             #value_if_none;
                         }
+                    })
+                }
+                (None, None) => {
+                    if let Some(keyed_by) = field.attrs.keyed_by() {
+                        quote! {
+                            match _serde::de::SeqAccess::next_element_seed(&mut __seq,
+                                _serde::__private::de::KeyedBySeed::new(#keyed_by))?
+                            {
+                                _serde::__private::Some(__value) => {
+                                    self.place.#member = __value;
+                                }
+                                _serde::__private::None => {
+                                    #value_if_none;
+                                }
+                            }
+                        }
+                    } else if field.attrs.map_from_pairs() {
+                        quote! {
+                            match _serde::de::SeqAccess::next_element_seed(&mut __seq,
+                                _serde::__private::de::MapFromPairsSeed::new())?
+                            {
+                                _serde::__private::Some(__value) => {
+                                    self.place.#member = __value;
+                                }
+                                _serde::__private::None => {
+                                    #value_if_none;
+                                }
+                            }
+                        }
+                    } else {
+                        quote! {
+                            if let _serde::__private::None = _serde::de::SeqAccess::next_element_seed(&mut __seq,
+                                _serde::__private::de::InPlaceSeed(&mut self.place.#member))?
+                            {
+                                #value_if_none;
+                            }
+                        }
                     }
                 }
-                Some(path) => {
-                    let (wrapper, wrapper_ty) = wrap_deserialize_field_with(params, field.ty, path);
+                (None, Some((wrapper, wrapper_ty))) => {
                     quote!({
                         #wrapper
                         match _serde::de::SeqAccess::next_element::<#wrapper_ty>(&mut __seq)? {
@@ -822,8 +1171,12 @@ fn deserialize_seq_in_place(
                     })
                 }
             };
+            let validate = field_validate_check(field, quote!(self.place.#member));
             index_in_seq += 1;
-            write
+            quote! {
+                #write
+                #validate
+            }
         }
     });
 
@@ -882,6 +1235,8 @@ fn deserialize_newtype_struct(
         };
     }
 
+    let validate = field_validate_check(field, quote!(__field0));
+
     quote! {
         #[inline]
         fn visit_newtype_struct<__E>(self, __e: __E) -> _serde::__private::Result<Self::Value, __E::Error>
@@ -889,6 +1244,7 @@ fn deserialize_newtype_struct(
             __E: _serde::Deserializer<#delife>,
         {
             let __field0: #field_ty = #value;
+            #validate
             _serde::__private::Ok(#result)
         }
     }
@@ -948,11 +1304,16 @@ fn deserialize_struct(
         .iter()
         .enumerate()
         // Skip fields that shouldn't be deserialized or that were flattened,
-        // so they don't appear in the storage in their literal form
-        .filter(|&(_, field)| !field.attrs.skip_deserializing() && !field.attrs.flatten())
+        // so they don't appear in the storage in their literal form. A
+        // collect_unknown field has no key of its own either.
+        .filter(|&(_, field)| {
+            !field.attrs.skip_deserializing()
+                && !field.attrs.flatten()
+                && !field.attrs.collect_unknown()
+        })
         .map(|(i, field)| {
             (
-                field.attrs.name().deserialize_name(),
+                field.attrs.name(),
                 field_i(i),
                 field.attrs.aliases(),
             )
@@ -960,6 +1321,12 @@ fn deserialize_struct(
         .collect();
     let field_visitor = deserialize_field_identifier(&field_names_idents, cattrs);
 
+    // A #[serde(seed)] container moves the state into the visitor so that
+    // seed_with fields can borrow it during visit_seq / visit_map.
+    let seed_field = cattrs.seed().map(|seed_ty| quote!(seed: #seed_ty,));
+    let seed_init = cattrs.seed().map(|_| quote!(seed: __seed,));
+    let seed_prelude = cattrs.seed().map(|_| quote!(let __seed = self.seed;));
+
     // untagged struct variants do not get a visit_seq method. The same applies to
     // structs that only have a map representation.
     let visit_seq = match form {
@@ -982,6 +1349,7 @@ fn deserialize_struct(
                 where
                     __A: _serde::de::SeqAccess<#delife>,
                 {
+                    #seed_prelude
                     #visit_seq
                 }
             })
@@ -1010,7 +1378,7 @@ fn deserialize_struct(
     } else {
         let field_names = field_names_idents
             .iter()
-            .flat_map(|&(_, _, aliases)| aliases);
+            .flat_map(flat_aliases);
 
         Some(quote! {
             #[doc(hidden)]
@@ -1020,6 +1388,7 @@ fn deserialize_struct(
 
     let visitor_expr = quote! {
         __Visitor {
+            #seed_init
             marker: _serde::__private::PhantomData::<#this_type #ty_generics>,
             lifetime: _serde::__private::PhantomData,
         }
@@ -1029,7 +1398,7 @@ fn deserialize_struct(
             _serde::Deserializer::deserialize_map(__deserializer, #visitor_expr)
         },
         StructForm::Struct => {
-            let type_name = cattrs.name().deserialize_name();
+            let type_name = cattrs.name().deserialize_name_expr();
             quote! {
                 _serde::Deserializer::deserialize_struct(__deserializer, #type_name, FIELDS, #visitor_expr)
             }
@@ -1053,6 +1422,7 @@ fn deserialize_struct(
 
         #[doc(hidden)]
         struct __Visitor #de_impl_generics #where_clause {
+            #seed_field
             marker: _serde::__private::PhantomData<#this_type #ty_generics>,
             lifetime: _serde::__private::PhantomData<&#delife ()>,
         }
@@ -1071,6 +1441,7 @@ fn deserialize_struct(
             where
                 __A: _serde::de::MapAccess<#delife>,
             {
+                #seed_prelude
                 #visit_map
             }
         }
@@ -1109,7 +1480,7 @@ fn deserialize_struct_in_place(
         .filter(|&(_, field)| !field.attrs.skip_deserializing())
         .map(|(i, field)| {
             (
-                field.attrs.name().deserialize_name(),
+                field.attrs.name(),
                 field_i(i),
                 field.attrs.aliases(),
             )
@@ -1127,8 +1498,8 @@ fn deserialize_struct_in_place(
     let visit_map = Stmts(deserialize_map_in_place(params, fields, cattrs));
     let field_names = field_names_idents
         .iter()
-        .flat_map(|&(_, _, aliases)| aliases);
-    let type_name = cattrs.name().deserialize_name();
+        .flat_map(flat_aliases);
+    let type_name = cattrs.name().deserialize_name_expr();
 
     let in_place_impl_generics = de_impl_generics.in_place();
     let in_place_ty_generics = de_ty_generics.in_place();
@@ -1198,7 +1569,7 @@ fn deserialize_homogeneous_enum(
     variants: &[Variant],
     cattrs: &attr::Container,
 ) -> Fragment {
-    match cattrs.tag() {
+    let fragment = match cattrs.tag() {
         attr::TagType::External => deserialize_externally_tagged_enum(params, variants, cattrs),
         attr::TagType::Internal { tag } => {
             deserialize_internally_tagged_enum(params, variants, cattrs, tag)
@@ -1207,6 +1578,26 @@ fn deserialize_homogeneous_enum(
             deserialize_adjacently_tagged_enum(params, variants, cattrs, tag, content)
         }
         attr::TagType::None => deserialize_untagged_enum(params, variants, cattrs),
+    };
+
+    // The untagged and internally tagged representations need to buffer the
+    // input through `deserialize_any`, which only self-describing formats
+    // support. With `index_fallback` other formats get the externally tagged
+    // representation instead, mirroring the fallback on the Serialize side.
+    if cattrs.index_fallback() {
+        let self_describing = Expr(fragment);
+        let external = Expr(deserialize_externally_tagged_enum(
+            params, variants, cattrs,
+        ));
+        quote_block! {
+            if _serde::Deserializer::is_self_describing(&__deserializer) {
+                #self_describing
+            } else {
+                #external
+            }
+        }
+    } else {
+        fragment
     }
 }
 
@@ -1219,26 +1610,52 @@ fn prepare_enum_variant_enum(
         .enumerate()
         .filter(|&(_, variant)| !variant.attrs.skip_deserializing());
 
+    // A newtype `other` variant captures the unrecognized tag itself, so it
+    // does not participate in tag matching and its `__Field` representation
+    // carries the captured value.
+    let other_newtype = deserialized_variants
+        .clone()
+        .find(|&(_, variant)| variant.attrs.other() && matches!(variant.style, Style::Newtype));
+
     let variant_names_idents: Vec<_> = deserialized_variants
         .clone()
+        .filter(|&(_, variant)| !(variant.attrs.other() && matches!(variant.style, Style::Newtype)))
         .map(|(i, variant)| {
             (
-                variant.attrs.name().deserialize_name(),
+                variant.attrs.name(),
                 field_i(i),
                 variant.attrs.aliases(),
             )
         })
         .collect();
 
-    let fallthrough = deserialized_variants
-        .position(|(_, variant)| variant.attrs.other())
-        .map(|other_idx| {
-            let ignore_variant = variant_names_idents[other_idx].1.clone();
-            quote!(_serde::__private::Ok(__Field::#ignore_variant))
-        });
+    let (ignore_variant, fallthrough) = if let Some((i, variant)) = other_newtype {
+        let ident = field_i(i);
+        let field_ty = variant.fields[0].ty;
+        let ignore_variant = quote!(#ident(#field_ty),);
+        let fallthrough = quote! {
+            _serde::__private::Result::map(
+                _serde::Deserialize::deserialize(
+                    _serde::__private::de::IdentifierDeserializer::from(__value)
+                ),
+                __Field::#ident,
+            )
+        };
+        (Some(ignore_variant), Some(fallthrough))
+    } else {
+        let fallthrough = deserialized_variants
+            .position(|(_, variant)| variant.attrs.other())
+            .map(|other_idx| {
+                let ignore_variant = variant_names_idents[other_idx].1.clone();
+                quote!(_serde::__private::Ok(__Field::#ignore_variant))
+            });
+        (None, fallthrough)
+    };
 
     let variants_stmt = {
-        let variant_names = variant_names_idents.iter().map(|(name, _, _)| name);
+        let variant_names = variant_names_idents
+            .iter()
+            .map(|(name, _, _)| name.deserialize_name_expr());
         quote! {
             #[doc(hidden)]
             const VARIANTS: &'static [&'static str] = &[ #(#variant_names),* ];
@@ -1249,7 +1666,7 @@ fn prepare_enum_variant_enum(
         &variant_names_idents,
         cattrs,
         true,
-        None,
+        ignore_variant,
         fallthrough,
     ));
 
@@ -1266,7 +1683,7 @@ fn deserialize_externally_tagged_enum(
         split_with_de_lifetime(params);
     let delife = params.borrowed.de_lifetime();
 
-    let type_name = cattrs.name().deserialize_name();
+    let type_name = cattrs.name().deserialize_name_expr();
     let expecting = format!("enum {}", params.type_name());
     let expecting = cattrs.expecting().unwrap_or(&expecting);
 
@@ -1355,6 +1772,7 @@ fn deserialize_internally_tagged_enum(
     cattrs: &attr::Container,
     tag: &str,
 ) -> Fragment {
+    let this_value = &params.this_value;
     let (variants_stmt, variant_visitor) = prepare_enum_variant_enum(variants, cattrs);
 
     // Match arms to extract a variant from a string
@@ -1365,6 +1783,15 @@ fn deserialize_internally_tagged_enum(
         .map(|(i, variant)| {
             let variant_name = field_i(i);
 
+            // A newtype `other` variant receives the captured tag; the
+            // buffered content is dropped.
+            if variant.attrs.other() && matches!(variant.style, Style::Newtype) {
+                let variant_ident = &variant.ident;
+                return quote! {
+                    __Field::#variant_name(__tag) => _serde::__private::Ok(#this_value::#variant_ident(__tag)),
+                };
+            }
+
             let block = Match(deserialize_internally_tagged_variant(
                 params,
                 variant,
@@ -1379,18 +1806,48 @@ fn deserialize_internally_tagged_enum(
 
     let expecting = format!("internally tagged enum {}", params.type_name());
     let expecting = cattrs.expecting().unwrap_or(&expecting);
+    let tag_aliases = cattrs.tag_aliases();
+
+    // With `tag_deserialize_with` the tag value is run through the user's
+    // normalization function and the variant is matched against its output
+    // rather than against the raw tag.
+    let (normalize_item, tag_ty, tag_expr) = match cattrs.tag_deserialize_with() {
+        Some(path) => (
+            Some(quote! {
+                #[doc(hidden)]
+                struct __NormalizedTag(__Field);
+
+                impl<'de> _serde::Deserialize<'de> for __NormalizedTag {
+                    fn deserialize<__D>(__deserializer: __D) -> _serde::__private::Result<Self, __D::Error>
+                    where
+                        __D: _serde::Deserializer<'de>,
+                    {
+                        let __normalized: _serde::__private::String = #path(__deserializer)?;
+                        let __field = _serde::Deserialize::deserialize(
+                            _serde::de::IntoDeserializer::into_deserializer(__normalized.as_str()))?;
+                        _serde::__private::Ok(__NormalizedTag(__field))
+                    }
+                }
+            }),
+            quote!(__NormalizedTag),
+            quote!(__tag.0),
+        ),
+        None => (None, quote!(__Field), quote!(__tag)),
+    };
 
     quote_block! {
         #variant_visitor
 
         #variants_stmt
 
+        #normalize_item
+
         let (__tag, __content) = _serde::Deserializer::deserialize_any(
             __deserializer,
-            _serde::__private::de::TaggedContentVisitor::<__Field>::new(#tag, #expecting))?;
+            _serde::__private::de::TaggedContentVisitor::<#tag_ty>::new(#tag, &[#(#tag_aliases),*], #expecting))?;
         let __deserializer = _serde::__private::de::ContentDeserializer::<__D::Error>::new(__content);
 
-        match __tag {
+        match #tag_expr {
             #(#variant_arms)*
         }
     }
@@ -1418,6 +1875,18 @@ fn deserialize_adjacently_tagged_enum(
         .map(|(i, variant)| {
             let variant_index = field_i(i);
 
+            // A newtype `other` variant receives the captured tag; the
+            // content is read and discarded.
+            if variant.attrs.other() && matches!(variant.style, Style::Newtype) {
+                let variant_ident = &variant.ident;
+                return quote! {
+                    __Field::#variant_index(__tag) => {
+                        let _ = <_serde::de::IgnoredAny as _serde::Deserialize>::deserialize(__deserializer)?;
+                        _serde::__private::Ok(#this_value::#variant_ident(__tag))
+                    }
+                };
+            }
+
             let block = Match(deserialize_untagged_variant(
                 params,
                 variant,
@@ -1434,7 +1903,7 @@ fn deserialize_adjacently_tagged_enum(
     let rust_name = params.type_name();
     let expecting = format!("adjacently tagged enum {}", rust_name);
     let expecting = cattrs.expecting().unwrap_or(&expecting);
-    let type_name = cattrs.name().deserialize_name();
+    let type_name = cattrs.name().deserialize_name_expr();
     let deny_unknown_fields = cattrs.deny_unknown_fields();
 
     // If unknown fields are allowed, we pick the visitor that can step over
@@ -1445,9 +1914,11 @@ fn deserialize_adjacently_tagged_enum(
         quote! { _serde::__private::de::TagContentOtherFieldVisitor }
     };
 
+    let tag_aliases = cattrs.tag_aliases();
     let tag_or_content = quote! {
         #field_visitor_ty {
             tag: #tag,
+            tag_aliases: &[#(#tag_aliases),*],
             content: #content,
         }
     };
@@ -1472,6 +1943,14 @@ fn deserialize_adjacently_tagged_enum(
             let variant_index = field_i(i);
             let variant_ident = &variant.ident;
 
+            // A newtype `other` variant is complete as soon as the tag has
+            // been captured.
+            if variant.attrs.other() && matches!(variant.style, Style::Newtype) {
+                return Some(quote! {
+                    __Field::#variant_index(__tag) => _serde::__private::Ok(#this_value::#variant_ident(__tag)),
+                });
+            }
+
             let arm = match variant.style {
                 Style::Unit => quote! {
                     _serde::__private::Ok(#this_value::#variant_ident)
@@ -1750,16 +2229,37 @@ fn deserialize_untagged_enum_after(
     let fallthrough_msg = cattrs.expecting().unwrap_or(&fallthrough_msg);
 
     // Ignore any error associated with non-untagged deserialization so that we
-    // can fall through to the untagged variants. This may be infallible so we
-    // need to provide the error type.
+    // can fall through to the untagged variants, but hold on to it: if none of
+    // the untagged variants match either, the tagged error names the tag and
+    // the expected variants, which the generic untagged message does not. An
+    // explicit #[serde(expecting = "...")] still takes precedence. This may be
+    // infallible so we need to provide the error type.
+    let preserve_first_err = first_attempt.is_some() && cattrs.expecting().is_none();
     let first_attempt = first_attempt.map(|expr| {
-        quote! {
-            if let _serde::__private::Result::<_, __D::Error>::Ok(__ok) = (|| #expr)() {
-                return _serde::__private::Ok(__ok);
+        if preserve_first_err {
+            quote! {
+                let __first_err = match (|| #expr)() {
+                    _serde::__private::Result::<_, __D::Error>::Ok(__ok) => {
+                        return _serde::__private::Ok(__ok);
+                    }
+                    _serde::__private::Err(__err) => __err,
+                };
+            }
+        } else {
+            quote! {
+                if let _serde::__private::Result::<_, __D::Error>::Ok(__ok) = (|| #expr)() {
+                    return _serde::__private::Ok(__ok);
+                }
             }
         }
     });
 
+    let fallthrough_err = if preserve_first_err {
+        quote!(_serde::__private::Err(__first_err))
+    } else {
+        quote!(_serde::__private::Err(_serde::de::Error::custom(#fallthrough_msg)))
+    };
+
     quote_block! {
         let __content = <_serde::__private::de::Content as _serde::Deserialize>::deserialize(__deserializer)?;
         let __deserializer = _serde::__private::de::ContentRefDeserializer::<__D::Error>::new(&__content);
@@ -1772,7 +2272,7 @@ fn deserialize_untagged_enum_after(
             }
         )*
 
-        _serde::__private::Err(_serde::de::Error::custom(#fallthrough_msg))
+        #fallthrough_err
     }
 }
 
@@ -1861,7 +2361,22 @@ fn deserialize_internally_tagged_variant(
             cattrs,
             StructForm::InternallyTagged(variant_ident, deserializer),
         ),
-        Style::Tuple => unreachable!("checked in serde_derive_internals"),
+        Style::Tuple => {
+            // The tuple sits under the "content" key of the remainder map, or
+            // inline after the tag in seq form; extract it before
+            // deserializing the tuple itself.
+            let tuple = Expr(deserialize_tuple(
+                params,
+                &variant.fields,
+                cattrs,
+                TupleForm::Untagged(variant_ident, quote!(__content_deserializer)),
+            ));
+            quote_block! {
+                let __content_deserializer =
+                    _serde::__private::de::internally_tagged_tuple_content(#deserializer)?;
+                #tuple
+            }
+        }
     }
 }
 
@@ -1983,8 +2498,21 @@ fn deserialize_untagged_newtype_variant(
     }
 }
 
+/// Names contributed by one field or variant to a `FIELDS` or `VARIANTS`
+/// array: a `rename = CONST` path if there is one, then the literal aliases
+/// (which include a literal main name).
+fn flat_aliases(
+    (name, _, aliases): &(&attr::Name, Ident, &BTreeSet<String>),
+) -> Vec<TokenStream> {
+    name.deserialize_name_const()
+        .map(ToTokens::to_token_stream)
+        .into_iter()
+        .chain(aliases.iter().map(ToTokens::to_token_stream))
+        .collect()
+}
+
 fn deserialize_generated_identifier(
-    fields: &[(&str, Ident, &BTreeSet<String>)],
+    fields: &[(&attr::Name, Ident, &BTreeSet<String>)],
     cattrs: &attr::Container,
     is_variant: bool,
     ignore_variant: Option<TokenStream>,
@@ -2000,6 +2528,7 @@ fn deserialize_generated_identifier(
         fallthrough,
         None,
         !is_variant && cattrs.has_flatten(),
+        cattrs,
         None,
     ));
 
@@ -2038,12 +2567,97 @@ fn deserialize_generated_identifier(
     }
 }
 
+// Generates a field identifier for `#[serde(compact_codegen)]` containers.
+// Instead of a per-container visitor with inline name matching in three
+// `visit_*` methods, the expansion is a pair of static name tables and one
+// small index match; the actual identifier matching happens once in the
+// shared `deserialize_field_index` runtime helper.
+fn deserialize_compact_field_identifier(
+    fields: &[(&attr::Name, Ident, &BTreeSet<String>)],
+    cattrs: &attr::Container,
+) -> Fragment {
+    let field_idents = fields.iter().map(|(_, ident, _)| ident);
+    let name_exprs = fields
+        .iter()
+        .map(|(name, _, _)| name.deserialize_name_expr());
+    let alias_rows = fields.iter().map(|(_, _, aliases)| {
+        let aliases = aliases.iter().map(String::as_str);
+        quote!(&[#(#aliases),*])
+    });
+    let index_arms = fields.iter().enumerate().map(|(index, (_, ident, _))| {
+        let index = index as u64;
+        quote!(_serde::__private::Some(#index) => _serde::__private::Ok(__Field::#ident),)
+    });
+
+    let deny_unknown = cattrs.deny_unknown_fields();
+    let (ignore_variant, unknown_arm) = if deny_unknown {
+        // The runtime helper has already rejected unknown identifiers, so
+        // this arm only satisfies exhaustiveness.
+        let unknown_arm = quote! {
+            _ => _serde::__private::Err(_serde::de::Error::custom("unexpected field index")),
+        };
+        (None, unknown_arm)
+    } else {
+        let ignore_variant = quote!(__ignore,);
+        let unknown_arm = quote!(_ => _serde::__private::Ok(__Field::__ignore),);
+        (Some(ignore_variant), unknown_arm)
+    };
+
+    quote_block! {
+        #[allow(non_camel_case_types)]
+        #[doc(hidden)]
+        enum __Field {
+            #(#field_idents,)*
+            #ignore_variant
+        }
+
+        #[doc(hidden)]
+        const __FIELD_NAMES: &[&str] = &[ #(#name_exprs),* ];
+
+        #[doc(hidden)]
+        const __FIELD_ALIASES: &[&[&str]] = &[ #(#alias_rows),* ];
+
+        impl<'de> _serde::Deserialize<'de> for __Field {
+            #[inline]
+            fn deserialize<__D>(__deserializer: __D) -> _serde::__private::Result<Self, __D::Error>
+            where
+                __D: _serde::Deserializer<'de>,
+            {
+                match _serde::__private::de::deserialize_field_index(
+                    __deserializer,
+                    __FIELD_NAMES,
+                    __FIELD_ALIASES,
+                    #deny_unknown,
+                )? {
+                    #(#index_arms)*
+                    #unknown_arm
+                }
+            }
+        }
+    }
+}
+
+// `#[serde(compact_codegen)]` only kicks in for identifier matching that can
+// be expressed as static tables. Flatten needs to capture unknown keys, and
+// `rename_all_with` / `case_insensitive` match through function calls rather
+// than table lookups, so those containers keep the classic expansion.
+fn compact_codegen_applies(cattrs: &attr::Container) -> bool {
+    cattrs.compact_codegen()
+        && !cattrs.has_flatten()
+        && cattrs.rename_all_with().is_none()
+        && !cattrs.case_insensitive()
+}
+
 /// Generates enum and its `Deserialize` implementation that represents each
 /// non-skipped field of the struct
 fn deserialize_field_identifier(
-    fields: &[(&str, Ident, &BTreeSet<String>)],
+    fields: &[(&attr::Name, Ident, &BTreeSet<String>)],
     cattrs: &attr::Container,
 ) -> Stmts {
+    if compact_codegen_applies(cattrs) {
+        return Stmts(deserialize_compact_field_identifier(fields, cattrs));
+    }
+
     let (ignore_variant, fallthrough) = if cattrs.has_flatten() {
         let ignore_variant = quote!(__other(_serde::__private::de::Content<'de>),);
         let fallthrough = quote!(_serde::__private::Ok(__Field::__other(__value)));
@@ -2119,14 +2733,14 @@ fn deserialize_custom_identifier(
         .iter()
         .map(|variant| {
             (
-                variant.attrs.name().deserialize_name(),
+                variant.attrs.name(),
                 variant.ident.clone(),
                 variant.attrs.aliases(),
             )
         })
         .collect();
 
-    let names = names_idents.iter().flat_map(|&(_, _, aliases)| aliases);
+    let names = names_idents.iter().flat_map(flat_aliases);
 
     let names_const = if fallthrough.is_some() {
         None
@@ -2154,6 +2768,7 @@ fn deserialize_custom_identifier(
         fallthrough,
         fallthrough_borrowed,
         false,
+        cattrs,
         cattrs.expecting(),
     ));
 
@@ -2182,24 +2797,157 @@ fn deserialize_custom_identifier(
 
 fn deserialize_identifier(
     this_value: &TokenStream,
-    fields: &[(&str, Ident, &BTreeSet<String>)],
+    fields: &[(&attr::Name, Ident, &BTreeSet<String>)],
     is_variant: bool,
     fallthrough: Option<TokenStream>,
     fallthrough_borrowed: Option<TokenStream>,
     collect_other_fields: bool,
+    cattrs: &attr::Container,
     expecting: Option<&str>,
 ) -> Fragment {
-    let str_mapping = fields.iter().map(|(_, ident, aliases)| {
-        // `aliases` also contains a main name
-        quote!(#(#aliases)|* => _serde::__private::Ok(#this_value::#ident))
+    let rename_all_with = cattrs.rename_all_with();
+    let case_insensitive = cattrs.case_insensitive();
+
+    // Number of fields from which identifier matching dispatches on the name
+    // length before comparing contents. A flat match is cheaper to compile
+    // and fast enough below this.
+    const LENGTH_BUCKET_THRESHOLD: usize = 16;
+
+    // With plain literal names, field lookup can first dispatch on the
+    // identifier length so that each incoming name is only compared against
+    // the handful of fields of the same length, instead of sequentially
+    // against every field. Guarded arms (`rename = CONST`, `rename_all_with`,
+    // `case_insensitive`) cannot be grouped by length and keep the flat
+    // match.
+    let length_buckets: Option<BTreeMap<usize, Vec<(&String, &Ident)>>> =
+        if fields.len() >= LENGTH_BUCKET_THRESHOLD
+            && rename_all_with.is_none()
+            && !case_insensitive
+            && fields
+                .iter()
+                .all(|(name, _, _)| name.deserialize_name_const().is_none())
+        {
+            let mut buckets = BTreeMap::new();
+            for (_, ident, aliases) in fields {
+                for alias in *aliases {
+                    buckets
+                        .entry(alias.len())
+                        .or_insert_with(Vec::new)
+                        .push((alias, ident));
+                }
+            }
+            Some(buckets)
+        } else {
+            None
+        };
+
+    let str_mapping = fields.iter().map(|(name, ident, aliases)| {
+        // `aliases` also contains a main name, except one that comes from
+        // `rename = CONST`, which is matched as a const pattern instead
+        let patterns = name
+            .deserialize_name_const()
+            .map(ToTokens::to_token_stream)
+            .into_iter()
+            .chain(aliases.iter().map(ToTokens::to_token_stream));
+        let value = quote!(_serde::__private::Ok(#this_value::#ident));
+        if let Some(path) = rename_all_with {
+            // `rename_all_with` names cannot be matched as literal patterns,
+            // so each candidate is run through the function in a guard.
+            let mut checks = patterns.map(|pattern| quote!(__value == #path(#pattern)));
+            let first = checks.next();
+            quote!(_ if #first #(|| #checks)* => #value)
+        } else if case_insensitive {
+            let mut checks = patterns.map(|pattern| quote!(__value.eq_ignore_ascii_case(#pattern)));
+            let first = checks.next();
+            quote!(_ if #first #(|| #checks)* => #value)
+        } else {
+            quote!(#(#patterns)|* => #value)
+        }
     });
-    let bytes_mapping = fields.iter().map(|(_, ident, aliases)| {
-        // `aliases` also contains a main name
+    let str_mapping: Vec<_> = str_mapping.collect();
+    let bytes_mapping = fields.iter().map(|(name, ident, aliases)| {
+        // `aliases` also contains a main name. A name that comes from
+        // `rename = CONST` is only known to the compiler, so it is matched
+        // through a guard rather than a byte string pattern.
+        let value = quote!(_serde::__private::Ok(#this_value::#ident));
+        let const_arm = name
+            .deserialize_name_const()
+            .map(|path| quote!(_ if __value == #path.as_bytes() => #value));
         let aliases = aliases
             .iter()
-            .map(|alias| Literal::byte_string(alias.as_bytes()));
-        quote!(#(#aliases)|* => _serde::__private::Ok(#this_value::#ident))
+            .map(|alias| Literal::byte_string(alias.as_bytes()))
+            .collect::<Vec<_>>();
+        match const_arm {
+            Some(const_arm) if aliases.is_empty() => const_arm,
+            Some(const_arm) => quote!(#const_arm, #(#aliases)|* => #value),
+            None => quote!(#(#aliases)|* => #value),
+        }
     });
+    let bytes_mapping: Vec<_> = bytes_mapping.collect();
+
+    let str_match_body = |default: TokenStream| match &length_buckets {
+        Some(buckets) => {
+            let len_arms = buckets.iter().map(|(len, entries)| {
+                let arms = entries.iter().map(|(alias, ident)| {
+                    quote!(#alias => _serde::__private::Ok(#this_value::#ident))
+                });
+                let default = &default;
+                quote! {
+                    #len => match __value {
+                        #(#arms,)*
+                        _ => { #default }
+                    },
+                }
+            });
+            quote! {
+                match __value.len() {
+                    #(#len_arms)*
+                    _ => { #default }
+                }
+            }
+        }
+        None => {
+            let str_mapping = &str_mapping;
+            quote! {
+                match __value {
+                    #(#str_mapping,)*
+                    _ => { #default }
+                }
+            }
+        }
+    };
+    let bytes_match_body = |default: TokenStream| match &length_buckets {
+        Some(buckets) => {
+            let len_arms = buckets.iter().map(|(len, entries)| {
+                let arms = entries.iter().map(|(alias, ident)| {
+                    let alias = Literal::byte_string(alias.as_bytes());
+                    quote!(#alias => _serde::__private::Ok(#this_value::#ident))
+                });
+                let default = &default;
+                quote! {
+                    #len => match __value {
+                        #(#arms,)*
+                        _ => { #default }
+                    },
+                }
+            });
+            quote! {
+                match __value.len() {
+                    #(#len_arms)*
+                    _ => { #default }
+                }
+            }
+        }
+        None => {
+            let bytes_mapping = &bytes_mapping;
+            quote! {
+                match __value {
+                    #(#bytes_mapping,)*
+                    _ => { #default }
+                }
+            }
+        }
+    };
 
     let expecting = expecting.unwrap_or(if is_variant {
         "variant identifier"
@@ -2381,42 +3129,65 @@ fn deserialize_identifier(
         }
     };
 
+    // Names matched through `rename_all_with` or `case_insensitive` guards
+    // compare as strings, so byte identifiers are converted up front.
+    let normalize_bytes = rename_all_with.is_some() || case_insensitive;
+
     let visit_borrowed = if fallthrough_borrowed.is_some() || collect_other_fields {
-        let str_mapping = str_mapping.clone();
-        let bytes_mapping = bytes_mapping.clone();
         let fallthrough_borrowed_arm = fallthrough_borrowed.as_ref().unwrap_or(fallthrough_arm);
+        let borrowed_bytes_body = if normalize_bytes {
+            quote! {
+                let __value = _serde::__private::from_utf8_lossy(__value);
+                _serde::de::Visitor::visit_str(self, &__value)
+            }
+        } else {
+            bytes_match_body(quote! {
+                #bytes_to_str
+                #value_as_borrowed_bytes_content
+                #fallthrough_borrowed_arm
+            })
+        };
+        let borrowed_str_body = str_match_body(quote! {
+            #value_as_borrowed_str_content
+            #fallthrough_borrowed_arm
+        });
         Some(quote! {
             fn visit_borrowed_str<__E>(self, __value: &'de str) -> _serde::__private::Result<Self::Value, __E>
             where
                 __E: _serde::de::Error,
             {
-                match __value {
-                    #(#str_mapping,)*
-                    _ => {
-                        #value_as_borrowed_str_content
-                        #fallthrough_borrowed_arm
-                    }
-                }
+                #borrowed_str_body
             }
 
             fn visit_borrowed_bytes<__E>(self, __value: &'de [u8]) -> _serde::__private::Result<Self::Value, __E>
             where
                 __E: _serde::de::Error,
             {
-                match __value {
-                    #(#bytes_mapping,)*
-                    _ => {
-                        #bytes_to_str
-                        #value_as_borrowed_bytes_content
-                        #fallthrough_borrowed_arm
-                    }
-                }
+                #borrowed_bytes_body
             }
         })
     } else {
         None
     };
 
+    let visit_bytes_body = if normalize_bytes {
+        quote! {
+            let __value = _serde::__private::from_utf8_lossy(__value);
+            _serde::de::Visitor::visit_str(self, &__value)
+        }
+    } else {
+        bytes_match_body(quote! {
+            #bytes_to_str
+            #value_as_bytes_content
+            #fallthrough_arm
+        })
+    };
+
+    let visit_str_body = str_match_body(quote! {
+        #value_as_str_content
+        #fallthrough_arm
+    });
+
     quote_block! {
         fn expecting(&self, __formatter: &mut _serde::__private::Formatter) -> _serde::__private::fmt::Result {
             _serde::__private::Formatter::write_str(__formatter, #expecting)
@@ -2428,27 +3199,14 @@ fn deserialize_identifier(
         where
             __E: _serde::de::Error,
         {
-            match __value {
-                #(#str_mapping,)*
-                _ => {
-                    #value_as_str_content
-                    #fallthrough_arm
-                }
-            }
+            #visit_str_body
         }
 
         fn visit_bytes<__E>(self, __value: &[u8]) -> _serde::__private::Result<Self::Value, __E>
         where
             __E: _serde::de::Error,
         {
-            match __value {
-                #(#bytes_mapping,)*
-                _ => {
-                    #bytes_to_str
-                    #value_as_bytes_content
-                    #fallthrough_arm
-                }
-            }
+            #visit_bytes_body
         }
 
         #visit_borrowed
@@ -2468,10 +3226,19 @@ fn deserialize_map(
         .map(|(i, field)| (field, field_i(i)))
         .collect();
 
+    // The field, if any, that receives keys which no named field matched.
+    let collect_unknown_field = fields_names
+        .iter()
+        .find(|&&(field, _)| field.attrs.collect_unknown() && !field.attrs.skip_deserializing());
+
     // Declare each field that will be deserialized.
     let let_values = fields_names
         .iter()
-        .filter(|&&(field, _)| !field.attrs.skip_deserializing() && !field.attrs.flatten())
+        .filter(|&&(field, _)| {
+            !field.attrs.skip_deserializing()
+                && !field.attrs.flatten()
+                && !field.attrs.collect_unknown()
+        })
         .map(|(field, name)| {
             let field_ty = field.ty;
             quote! {
@@ -2479,13 +3246,21 @@ fn deserialize_map(
             }
         });
 
+    // The collect_unknown map is filled while other keys are matched, so it
+    // starts out empty rather than as an Option.
+    let let_collect_unknown = collect_unknown_field.map(|(field, name)| {
+        let field_ty = field.ty;
+        quote! {
+            let mut #name: #field_ty = _serde::__private::Default::default();
+        }
+    });
+
     // Collect contents for flatten fields into a buffer
-    let let_collect = if cattrs.has_flatten() {
+    let let_collect = if cattrs.has_flatten() && collect_unknown_field.is_none() {
         Some(quote! {
-            let mut __collect = _serde::__private::Vec::<_serde::__private::Option<(
-                _serde::__private::de::Content,
-                _serde::__private::de::Content
-            )>>::new();
+            let mut __collect = _serde::__private::Vec::<
+                _serde::__private::Option<_serde::__private::de::FlatMapEntry>,
+            >::new();
         })
     } else {
         None
@@ -2494,22 +3269,57 @@ fn deserialize_map(
     // Match arms to extract a value for a field.
     let value_arms = fields_names
         .iter()
-        .filter(|&&(field, _)| !field.attrs.skip_deserializing() && !field.attrs.flatten())
+        .filter(|&&(field, _)| {
+            !field.attrs.skip_deserializing()
+                && !field.attrs.flatten()
+                && !field.attrs.collect_unknown()
+        })
         .map(|(field, name)| {
-            let deser_name = field.attrs.name().deserialize_name();
-
-            let visit = match field.attrs.deserialize_with() {
+            let deser_name = field.attrs.name().deserialize_name_expr();
+
+            let wrap = match field.attrs.deserialize_with() {
+                Some(path) => Some(wrap_deserialize_field_with(params, field.ty, path)),
+                None => field
+                    .attrs
+                    .key_with()
+                    .map(|path| wrap_deserialize_key_with(params, field.ty, path)),
+            };
+            let visit = match wrap {
                 None => {
-                    let field_ty = field.ty;
-                    let span = field.original.span();
-                    let func =
-                        quote_spanned!(span=> _serde::de::MapAccess::next_value::<#field_ty>);
-                    quote! {
-                        #func(&mut __map)?
+                    if let (Some(seed_with), Some(seed_ty)) =
+                        (field.attrs.seed_with(), cattrs.seed())
+                    {
+                        let span = field.original.span();
+                        let func = quote_spanned!(span=> _serde::de::MapAccess::next_value_seed);
+                        let (wrapper, wrapper_expr) =
+                            wrap_deserialize_seed_with(params, field.ty, seed_ty, seed_with);
+                        quote!({
+                            #wrapper
+                            #func(&mut __map, #wrapper_expr)?
+                        })
+                    } else if let Some(keyed_by) = field.attrs.keyed_by() {
+                        let span = field.original.span();
+                        let func = quote_spanned!(span=> _serde::de::MapAccess::next_value_seed);
+                        quote! {
+                            #func(&mut __map, _serde::__private::de::KeyedBySeed::new(#keyed_by))?
+                        }
+                    } else if field.attrs.map_from_pairs() {
+                        let span = field.original.span();
+                        let func = quote_spanned!(span=> _serde::de::MapAccess::next_value_seed);
+                        quote! {
+                            #func(&mut __map, _serde::__private::de::MapFromPairsSeed::new())?
+                        }
+                    } else {
+                        let field_ty = field.ty;
+                        let span = field.original.span();
+                        let func =
+                            quote_spanned!(span=> _serde::de::MapAccess::next_value::<#field_ty>);
+                        quote! {
+                            #func(&mut __map)?
+                        }
                     }
                 }
-                Some(path) => {
-                    let (wrapper, wrapper_ty) = wrap_deserialize_field_with(params, field.ty, path);
+                Some((wrapper, wrapper_ty)) => {
                     quote!({
                         #wrapper
                         match _serde::de::MapAccess::next_value::<#wrapper_ty>(&mut __map) {
@@ -2532,12 +3342,32 @@ fn deserialize_map(
         });
 
     // Visit ignored values to consume them
-    let ignored_arm = if cattrs.has_flatten() {
+    let ignored_arm = if let Some((_, collect_name)) = collect_unknown_field {
+        // Unknown keys land in the collect_unknown map; the value is
+        // deserialized directly instead of being buffered.
+        Some(quote! {
+            __Field::__other(__name) => {
+                if let _serde::__private::Some(__key) = __name.as_str() {
+                    _serde::__private::Extend::extend(
+                        &mut #collect_name,
+                        _serde::__private::Some((
+                            _serde::__private::ToString::to_string(__key),
+                            _serde::de::MapAccess::next_value(&mut __map)?,
+                        )),
+                    );
+                } else {
+                    return _serde::__private::Err(
+                        _serde::de::Error::custom(format_args!("unexpected map key")));
+                }
+            }
+        })
+    } else if cattrs.has_flatten() {
         Some(quote! {
             __Field::__other(__name) => {
-                __collect.push(_serde::__private::Some((
-                    __name,
-                    _serde::de::MapAccess::next_value(&mut __map)?)));
+                __collect.push(_serde::__private::Some(
+                    _serde::__private::de::FlatMapEntry::new(
+                        __name,
+                        _serde::de::MapAccess::next_value(&mut __map)?)));
             }
         })
     } else if cattrs.deny_unknown_fields() {
@@ -2570,15 +3400,21 @@ fn deserialize_map(
 
     let extract_values = fields_names
         .iter()
-        .filter(|&&(field, _)| !field.attrs.skip_deserializing() && !field.attrs.flatten())
+        .filter(|&&(field, _)| {
+            !field.attrs.skip_deserializing()
+                && !field.attrs.flatten()
+                && !field.attrs.collect_unknown()
+        })
         .map(|(field, name)| {
             let missing_expr = Match(expr_is_missing(field, cattrs));
+            let validate = field_validate_check(field, quote!(#name));
 
             quote! {
                 let #name = match #name {
                     _serde::__private::Some(#name) => #name,
                     _serde::__private::None => #missing_expr
                 };
+                #validate
             }
         });
 
@@ -2594,25 +3430,53 @@ fn deserialize_map(
                 }
                 Some(path) => quote!(#path),
             };
-            quote! {
-                let #name: #field_ty = #func(
+            let deserialize = quote! {
+                #func(
                     _serde::__private::de::FlatMapDeserializer(
                         &mut __collect,
-                        _serde::__private::PhantomData))?;
+                        _serde::__private::PhantomData))?
+            };
+            let validate = field_validate_check(field, quote!(#name));
+            let has_default = !field.attrs.default().is_none() || !cattrs.default().is_none();
+            if has_default {
+                // If every key that could have fed this flattened field is
+                // absent, fall back to the default instead of letting the
+                // inner type fail with missing-field errors.
+                let missing_expr = Expr(expr_is_missing(field, cattrs));
+                quote! {
+                    let #name: #field_ty = if _serde::__private::Iterator::all(
+                        &mut __collect.iter(),
+                        _serde::__private::Option::is_none)
+                    {
+                        #missing_expr
+                    } else {
+                        #deserialize
+                    };
+                    #validate
+                }
+            } else {
+                quote! {
+                    let #name: #field_ty = #deserialize;
+                    #validate
+                }
             }
         });
 
     let collected_deny_unknown_fields = if cattrs.has_flatten() && cattrs.deny_unknown_fields() {
         Some(quote! {
-            if let _serde::__private::Some(_serde::__private::Some((__key, _))) =
-                __collect.into_iter().filter(_serde::__private::Option::is_some).next()
-            {
-                if let _serde::__private::Some(__key) = __key.as_str() {
-                    return _serde::__private::Err(
-                        _serde::de::Error::custom(format_args!("unknown field `{}`", &__key)));
-                } else {
-                    return _serde::__private::Err(
-                        _serde::de::Error::custom(format_args!("unexpected map key")));
+            for __entry in &__collect {
+                if let _serde::__private::Some(__entry) = __entry {
+                    if let _serde::__private::Some(__key) =
+                        _serde::__private::de::FlatMapEntry::unused_key(__entry)
+                    {
+                        if let _serde::__private::Some(__key) = __key.as_str() {
+                            return _serde::__private::Err(
+                                _serde::de::Error::custom(format_args!("unknown field `{}`", &__key)));
+                        } else {
+                            return _serde::__private::Err(
+                                _serde::de::Error::custom(format_args!("unexpected map key")));
+                        }
+                    }
                 }
             }
         })
@@ -2656,6 +3520,8 @@ fn deserialize_map(
     quote_block! {
         #(#let_values)*
 
+        #let_collect_unknown
+
         #let_collect
 
         #match_keys
@@ -2703,17 +3569,45 @@ fn deserialize_map_in_place(
         .iter()
         .filter(|&&(field, _)| !field.attrs.skip_deserializing())
         .map(|(field, name)| {
-            let deser_name = field.attrs.name().deserialize_name();
+            let deser_name = field.attrs.name().deserialize_name_expr();
             let member = &field.member;
 
-            let visit = match field.attrs.deserialize_with() {
-                None => {
-                    quote! {
-                        _serde::de::MapAccess::next_value_seed(&mut __map, _serde::__private::de::InPlaceSeed(&mut self.place.#member))?
+            let wrap = match field.attrs.deserialize_with() {
+                Some(path) => Some(wrap_deserialize_field_with(params, field.ty, path)),
+                None => field
+                    .attrs
+                    .key_with()
+                    .map(|path| wrap_deserialize_key_with(params, field.ty, path)),
+            };
+            let visit = match (field.attrs.deserialize_with_in_place(), wrap) {
+                (Some(path), _) => {
+                    let (wrapper, wrapper_expr) = wrap_deserialize_with_in_place(
+                        params,
+                        field.ty,
+                        path,
+                        quote!(&mut self.place.#member),
+                    );
+                    quote!({
+                        #wrapper
+                        _serde::de::MapAccess::next_value_seed(&mut __map, #wrapper_expr)?;
+                    })
+                }
+                (None, None) => {
+                    if let Some(keyed_by) = field.attrs.keyed_by() {
+                        quote! {
+                            self.place.#member = _serde::de::MapAccess::next_value_seed(&mut __map, _serde::__private::de::KeyedBySeed::new(#keyed_by))?
+                        }
+                    } else if field.attrs.map_from_pairs() {
+                        quote! {
+                            self.place.#member = _serde::de::MapAccess::next_value_seed(&mut __map, _serde::__private::de::MapFromPairsSeed::new())?
+                        }
+                    } else {
+                        quote! {
+                            _serde::de::MapAccess::next_value_seed(&mut __map, _serde::__private::de::InPlaceSeed(&mut self.place.#member))?
+                        }
                     }
                 }
-                Some(path) => {
-                    let (wrapper, wrapper_ty) = wrap_deserialize_field_with(params, field.ty, path);
+                (None, Some((wrapper, wrapper_ty))) => {
                     quote!({
                         #wrapper
                         self.place.#member = match _serde::de::MapAccess::next_value::<#wrapper_ty>(&mut __map) {
@@ -2725,12 +3619,14 @@ fn deserialize_map_in_place(
                     })
                 }
             };
+            let validate = field_validate_check(field, quote!(self.place.#member));
             quote! {
                 __Field::#name => {
                     if #name {
                         return _serde::__private::Err(<__A::Error as _serde::de::Error>::duplicate_field(#deser_name));
                     }
                     #visit;
+                    #validate
                     #name = true;
                 }
             }
@@ -2786,9 +3682,11 @@ fn deserialize_map_in_place(
             } else {
                 let member = &field.member;
                 let missing_expr = Expr(missing_expr);
+                let validate = field_validate_check(field, quote!(self.place.#member));
                 quote! {
                     if !#name {
                         self.place.#member = #missing_expr;
+                        #validate
                     };
                 }
             }
@@ -2875,6 +3773,180 @@ fn wrap_deserialize_field_with(
     wrap_deserialize_with(params, &quote!(#field_ty), deserialize_with)
 }
 
+// Wraps a mutable borrow of one field in a DeserializeSeed impl deferring to
+// the field's #[serde(deserialize_with_in_place = "...")] function, for use
+// with next_value_seed / next_element_seed on the deserialize_in_place path.
+#[cfg(feature = "deserialize_in_place")]
+fn wrap_deserialize_with_in_place(
+    params: &Parameters,
+    field_ty: &syn::Type,
+    deserialize_with_in_place: &syn::ExprPath,
+    place: TokenStream,
+) -> (TokenStream, TokenStream) {
+    let this_type = &params.this_type;
+    let (de_impl_generics, de_ty_generics, ty_generics, where_clause) =
+        split_with_de_lifetime(params);
+    let delife = params.borrowed.de_lifetime();
+    let in_place_impl_generics = de_impl_generics.in_place();
+    let in_place_ty_generics = de_ty_generics.in_place();
+    let place_life = place_lifetime();
+
+    let wrapper = quote! {
+        #[doc(hidden)]
+        struct __DeserializeWithInPlace #in_place_impl_generics #where_clause {
+            place: &#place_life mut #field_ty,
+            phantom: _serde::__private::PhantomData<#this_type #ty_generics>,
+            lifetime: _serde::__private::PhantomData<&#delife ()>,
+        }
+
+        impl #in_place_impl_generics _serde::de::DeserializeSeed<#delife> for __DeserializeWithInPlace #in_place_ty_generics #where_clause {
+            type Value = ();
+
+            fn deserialize<__D>(self, __deserializer: __D) -> _serde::__private::Result<Self::Value, __D::Error>
+            where
+                __D: _serde::Deserializer<#delife>,
+            {
+                #deserialize_with_in_place(__deserializer, self.place)
+            }
+        }
+    };
+
+    let wrapper_expr = quote! {
+        __DeserializeWithInPlace {
+            place: #place,
+            phantom: _serde::__private::PhantomData,
+            lifetime: _serde::__private::PhantomData,
+        }
+    };
+
+    (wrapper, wrapper_expr)
+}
+
+// Wraps a borrow of the container's #[serde(seed)] state in a DeserializeSeed
+// impl deferring to the field's #[serde(seed_with = "...")] function, for use
+// with next_value_seed / next_element_seed. Expects a `__seed` binding holding
+// the state to be in scope.
+fn wrap_deserialize_seed_with(
+    params: &Parameters,
+    field_ty: &syn::Type,
+    seed_ty: &syn::Type,
+    seed_with: &syn::ExprPath,
+) -> (TokenStream, TokenStream) {
+    let delife = params.borrowed.de_lifetime();
+
+    let wrapper = quote! {
+        #[doc(hidden)]
+        struct __SeedWith<'__s> {
+            seed: &'__s #seed_ty,
+        }
+
+        impl<'__s, #delife> _serde::de::DeserializeSeed<#delife> for __SeedWith<'__s> {
+            type Value = #field_ty;
+
+            fn deserialize<__D>(self, __deserializer: __D) -> _serde::__private::Result<Self::Value, __D::Error>
+            where
+                __D: _serde::Deserializer<#delife>,
+            {
+                #seed_with(self.seed, __deserializer)
+            }
+        }
+    };
+
+    let wrapper_expr = quote!(__SeedWith { seed: &__seed });
+
+    (wrapper, wrapper_expr)
+}
+
+// Statement running the field's #[serde(validate = "...")] function against
+// the deserialized value, mapping failure into Error::custom. Empty for fields
+// without the attribute.
+fn field_validate_check(field: &Field, value: TokenStream) -> Option<TokenStream> {
+    field.attrs.validate().map(|validate| {
+        quote! {
+            if let _serde::__private::Err(__err) = #validate(&#value) {
+                return _serde::__private::Err(_serde::de::Error::custom(__err));
+            }
+        }
+    })
+}
+
+// Deserializes a map-typed field whose keys pass through the `deserialize`
+// function of a #[serde(key_with = "...")] module. Each key is buffered into a
+// Content tree so that the module function can be applied with the key's
+// concrete type, and the converted entries are collected through FromIterator.
+fn wrap_deserialize_key_with(
+    params: &Parameters,
+    field_ty: &syn::Type,
+    key_with: &syn::ExprPath,
+) -> (TokenStream, TokenStream) {
+    let this_type = &params.this_type;
+    let (de_impl_generics, de_ty_generics, ty_generics, where_clause) =
+        split_with_de_lifetime(params);
+    let delife = params.borrowed.de_lifetime();
+
+    let wrapper = quote! {
+        #[doc(hidden)]
+        struct __DeserializeKeyWith #de_impl_generics #where_clause {
+            value: #field_ty,
+            phantom: _serde::__private::PhantomData<#this_type #ty_generics>,
+            lifetime: _serde::__private::PhantomData<&#delife ()>,
+        }
+
+        impl #de_impl_generics _serde::Deserialize<#delife> for __DeserializeKeyWith #de_ty_generics #where_clause {
+            fn deserialize<__D>(__deserializer: __D) -> _serde::__private::Result<Self, __D::Error>
+            where
+                __D: _serde::Deserializer<#delife>,
+            {
+                #[doc(hidden)]
+                struct __Visitor #de_impl_generics #where_clause {
+                    marker: _serde::__private::PhantomData<#this_type #ty_generics>,
+                    lifetime: _serde::__private::PhantomData<&#delife ()>,
+                }
+
+                impl #de_impl_generics _serde::de::Visitor<#delife> for __Visitor #de_ty_generics #where_clause {
+                    type Value = #field_ty;
+
+                    fn expecting(&self, __formatter: &mut _serde::__private::Formatter) -> _serde::__private::fmt::Result {
+                        _serde::__private::Formatter::write_str(__formatter, "a map")
+                    }
+
+                    fn visit_map<__A>(self, mut __map: __A) -> _serde::__private::Result<Self::Value, __A::Error>
+                    where
+                        __A: _serde::de::MapAccess<#delife>,
+                    {
+                        let mut __entries = _serde::__private::Vec::new();
+                        while let _serde::__private::Some(__key) =
+                            _serde::de::MapAccess::next_key::<_serde::__private::de::Content>(&mut __map)?
+                        {
+                            let __value = _serde::de::MapAccess::next_value(&mut __map)?;
+                            __entries.push((
+                                #key_with::deserialize(
+                                    _serde::__private::de::ContentDeserializer::<__A::Error>::new(__key),
+                                )?,
+                                __value,
+                            ));
+                        }
+                        _serde::__private::Ok(_serde::__private::FromIterator::from_iter(__entries))
+                    }
+                }
+
+                _serde::__private::Ok(__DeserializeKeyWith {
+                    value: _serde::Deserializer::deserialize_map(__deserializer, __Visitor {
+                        marker: _serde::__private::PhantomData,
+                        lifetime: _serde::__private::PhantomData,
+                    })?,
+                    phantom: _serde::__private::PhantomData,
+                    lifetime: _serde::__private::PhantomData,
+                })
+            }
+        }
+    };
+
+    let wrapper_ty = quote!(__DeserializeKeyWith #de_ty_generics);
+
+    (wrapper, wrapper_ty)
+}
+
 fn wrap_deserialize_variant_with(
     params: &Parameters,
     variant: &Variant,
@@ -2958,19 +4030,35 @@ fn expr_is_missing(field: &Field, cattrs: &attr::Container) -> Fragment {
         attr::Default::None => { /* below */ }
     }
 
-    let name = field.attrs.name().deserialize_name();
-    match field.attrs.deserialize_with() {
-        None => {
-            let span = field.original.span();
-            let func = quote_spanned!(span=> _serde::__private::de::missing_field);
+    // The missing_field helper deserializes the field from a unit, which is
+    // not possible through a deserialize_with or seed_with function.
+    let has_custom_deserialize =
+        field.attrs.deserialize_with().is_some() || field.attrs.seed_with().is_some();
+
+    if let Some(message) = field.attrs.missing_field_error() {
+        return if has_custom_deserialize {
             quote_expr! {
-                #func(#name)?
+                return _serde::__private::Err(<__A::Error as _serde::de::Error>::custom(#message))
             }
-        }
-        Some(_) => {
+        } else {
+            let span = field.original.span();
+            let func = quote_spanned!(span=> _serde::__private::de::missing_field_custom);
             quote_expr! {
-                return _serde::__private::Err(<__A::Error as _serde::de::Error>::missing_field(#name))
+                #func(#message)?
             }
+        };
+    }
+
+    let name = field.attrs.name().deserialize_name_expr();
+    if has_custom_deserialize {
+        quote_expr! {
+            return _serde::__private::Err(<__A::Error as _serde::de::Error>::missing_field(#name))
+        }
+    } else {
+        let span = field.original.span();
+        let func = quote_spanned!(span=> _serde::__private::de::missing_field);
+        quote_expr! {
+            #func(#name)?
         }
     }
 }
@@ -83,7 +83,7 @@ impl<'a> Container<'a> {
                 for variant in variants {
                     variant.attrs.rename_by_rules(attrs.rename_all_rules());
                     for field in &mut variant.fields {
-                        if field.attrs.flatten() {
+                        if field.attrs.flatten() || field.attrs.collect_unknown() {
                             has_flatten = true;
                         }
                         field.attrs.rename_by_rules(
@@ -93,15 +93,21 @@ impl<'a> Container<'a> {
                                 .or(attrs.rename_all_fields_rules()),
                         );
                     }
+                    if let Style::Struct = variant.style {
+                        sort_fields(&mut variant.fields, &attrs);
+                    }
                 }
             }
-            Data::Struct(_, fields) => {
-                for field in fields {
-                    if field.attrs.flatten() {
+            Data::Struct(style, fields) => {
+                for field in fields.iter_mut() {
+                    if field.attrs.flatten() || field.attrs.collect_unknown() {
                         has_flatten = true;
                     }
                     field.attrs.rename_by_rules(attrs.rename_all_rules());
                 }
+                if let Style::Struct = style {
+                    sort_fields(fields, &attrs);
+                }
             }
         }
 
@@ -194,6 +200,25 @@ fn struct_from_ast<'a>(
     }
 }
 
+// Applies `#[serde(sort_fields = "alphabetical")]` and `#[serde(order = N)]`
+// to named fields. Fields with an explicit order come first, sorted ascending
+// by it; the remaining fields keep declaration order, or serialized-name order
+// under `sort_fields = "alphabetical"`. The sort runs after rename rules and
+// is seen by both Serialize and Deserialize, so formats that represent structs
+// as sequences stay consistent with the serialized order.
+fn sort_fields(fields: &mut [Field], attrs: &attr::Container) {
+    if attrs.sort_fields_alphabetical() {
+        fields.sort_by(|a, b| {
+            let a = a.attrs.name().serialize_name();
+            let b = b.attrs.name().serialize_name();
+            a.cmp(b)
+        });
+    }
+    if fields.iter().any(|field| field.attrs.order().is_some()) {
+        fields.sort_by_key(|field| field.attrs.order().unwrap_or(usize::MAX));
+    }
+}
+
 fn fields_from_ast<'a>(
     cx: &Ctxt,
     fields: &'a Punctuated<syn::Field, Token![,]>,
@@ -131,12 +131,22 @@ impl<'c, T> VecAttr<'c, T> {
 
 pub struct Name {
     serialize: String,
+    serialize_const: Option<syn::ExprPath>,
     serialize_renamed: bool,
     deserialize: String,
+    deserialize_const: Option<syn::ExprPath>,
     deserialize_renamed: bool,
     deserialize_aliases: BTreeSet<String>,
 }
 
+/// Value of a `rename` attribute: either a string literal, or a path to a
+/// `const &'static str` that the compiler substitutes at use sites.
+#[derive(Clone)]
+enum RenameValue {
+    Lit(syn::LitStr),
+    Const(syn::ExprPath),
+}
+
 fn unraw(ident: &Ident) -> String {
     ident.to_string().trim_start_matches("r#").to_owned()
 }
@@ -144,8 +154,8 @@ fn unraw(ident: &Ident) -> String {
 impl Name {
     fn from_attrs(
         source_name: String,
-        ser_name: Attr<String>,
-        de_name: Attr<String>,
+        ser_name: Attr<RenameValue>,
+        de_name: Attr<RenameValue>,
         de_aliases: Option<VecAttr<String>>,
     ) -> Name {
         let mut alias_set = BTreeSet::new();
@@ -155,20 +165,25 @@ impl Name {
             }
         }
 
-        let ser_name = ser_name.get();
+        let (ser_name, ser_const) = split_rename(ser_name.get());
         let ser_renamed = ser_name.is_some();
-        let de_name = de_name.get();
+        let (de_name, de_const) = split_rename(de_name.get());
         let de_renamed = de_name.is_some();
         Name {
             serialize: ser_name.unwrap_or_else(|| source_name.clone()),
+            serialize_const: ser_const,
             serialize_renamed: ser_renamed,
             deserialize: de_name.unwrap_or(source_name),
+            deserialize_const: de_const,
             deserialize_renamed: de_renamed,
             deserialize_aliases: alias_set,
         }
     }
 
     /// Return the container name for the container when serializing.
+    ///
+    /// For `rename = CONST` this is the rendered path, usable for error
+    /// messages and collision checks but not as the wire name.
     pub fn serialize_name(&self) -> &str {
         &self.serialize
     }
@@ -178,11 +193,47 @@ impl Name {
         &self.deserialize
     }
 
+    /// Expression for the serialized name: a string literal, or the const
+    /// named by `rename = CONST`.
+    pub fn serialize_name_expr(&self) -> TokenStream {
+        match &self.serialize_const {
+            Some(path) => path.to_token_stream(),
+            None => self.serialize.to_token_stream(),
+        }
+    }
+
+    /// Expression for the deserialized name: a string literal, or the const
+    /// named by `rename = CONST`.
+    pub fn deserialize_name_expr(&self) -> TokenStream {
+        match &self.deserialize_const {
+            Some(path) => path.to_token_stream(),
+            None => self.deserialize.to_token_stream(),
+        }
+    }
+
+    /// The const path given as `rename = CONST` for deserialization, if any.
+    /// Unlike literal names it cannot be baked into the alias set, so
+    /// identifier matching handles it separately.
+    pub fn deserialize_name_const(&self) -> Option<&syn::ExprPath> {
+        self.deserialize_const.as_ref()
+    }
+
     fn deserialize_aliases(&self) -> &BTreeSet<String> {
         &self.deserialize_aliases
     }
 }
 
+fn split_rename(value: Option<RenameValue>) -> (Option<String>, Option<syn::ExprPath>) {
+    match value {
+        None => (None, None),
+        Some(RenameValue::Lit(lit)) => (Some(lit.value()), None),
+        Some(RenameValue::Const(path)) => {
+            let rendered = path.to_token_stream().to_string().replace(' ', "");
+            (Some(rendered), Some(path))
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct RenameAllRules {
     serialize: RenameRule,
@@ -207,13 +258,23 @@ pub struct Container {
     deny_unknown_fields: bool,
     default: Default,
     rename_all_rules: RenameAllRules,
+    rename_all_with: Option<syn::ExprPath>,
+    case_insensitive: bool,
     rename_all_fields_rules: RenameAllRules,
     ser_bound: Option<Vec<syn::WherePredicate>>,
     de_bound: Option<Vec<syn::WherePredicate>>,
     tag: TagType,
+    tag_aliases: Vec<String>,
+    index_fallback: bool,
+    tag_deserialize_with: Option<syn::ExprPath>,
+    tag_repr: Option<syn::Type>,
     type_from: Option<syn::Type>,
     type_try_from: Option<syn::Type>,
     type_into: Option<syn::Type>,
+    type_try_into: Option<syn::Type>,
+    finalize: Option<syn::ExprPath>,
+    validate: Option<syn::ExprPath>,
+    seed: Option<syn::Type>,
     remote: Option<syn::Path>,
     identifier: Identifier,
     has_flatten: bool,
@@ -222,6 +283,13 @@ pub struct Container {
     /// Error message generated when type can't be deserialized
     expecting: Option<String>,
     non_exhaustive: bool,
+    sort_fields_alphabetical: bool,
+    skip_serializing_default: bool,
+    skip_none: bool,
+    compact_codegen: bool,
+    version: Option<u32>,
+    upgrade_from: Vec<(u32, syn::ExprPath)>,
+    meta: Vec<(String, String)>,
 }
 
 /// Styles of representing an enum.
@@ -294,19 +362,36 @@ impl Container {
         let mut rename_all_de_rule = Attr::none(cx, RENAME_ALL);
         let mut rename_all_fields_ser_rule = Attr::none(cx, RENAME_ALL_FIELDS);
         let mut rename_all_fields_de_rule = Attr::none(cx, RENAME_ALL_FIELDS);
+        let mut rename_all_with = Attr::none(cx, RENAME_ALL_WITH);
+        let mut case_insensitive = BoolAttr::none(cx, CASE_INSENSITIVE);
         let mut ser_bound = Attr::none(cx, BOUND);
         let mut de_bound = Attr::none(cx, BOUND);
         let mut untagged = BoolAttr::none(cx, UNTAGGED);
         let mut internal_tag = Attr::none(cx, TAG);
+        let mut tag_aliases = VecAttr::none(cx, TAG_ALIAS);
+        let mut index_fallback = BoolAttr::none(cx, INDEX_FALLBACK);
+        let mut tag_deserialize_with = Attr::none(cx, TAG_DESERIALIZE_WITH);
+        let mut tag_repr = Attr::none(cx, TAG_REPR);
         let mut content = Attr::none(cx, CONTENT);
         let mut type_from = Attr::none(cx, FROM);
         let mut type_try_from = Attr::none(cx, TRY_FROM);
         let mut type_into = Attr::none(cx, INTO);
+        let mut type_try_into = Attr::none(cx, TRY_INTO);
+        let mut finalize = Attr::none(cx, FINALIZE);
+        let mut validate = Attr::none(cx, VALIDATE);
+        let mut seed = Attr::none(cx, SEED);
         let mut remote = Attr::none(cx, REMOTE);
         let mut field_identifier = BoolAttr::none(cx, FIELD_IDENTIFIER);
         let mut variant_identifier = BoolAttr::none(cx, VARIANT_IDENTIFIER);
         let mut serde_path = Attr::none(cx, CRATE);
         let mut expecting = Attr::none(cx, EXPECTING);
+        let mut sort_fields = Attr::none(cx, SORT_FIELDS);
+        let mut skip_serializing_default = BoolAttr::none(cx, SKIP_SERIALIZING_DEFAULT);
+        let mut skip_none = BoolAttr::none(cx, SKIP_NONE);
+        let mut compact_codegen = BoolAttr::none(cx, COMPACT_CODEGEN);
+        let mut version = Attr::none(cx, VERSION);
+        let mut upgrade_from = Vec::new();
+        let mut metadata = VecAttr::none(cx, META);
         let mut non_exhaustive = false;
 
         for attr in &item.attrs {
@@ -326,9 +411,9 @@ impl Container {
                 if meta.path == RENAME {
                     // #[serde(rename = "foo")]
                     // #[serde(rename(serialize = "foo", deserialize = "bar"))]
-                    let (ser, de) = get_renames(cx, RENAME, &meta)?;
-                    ser_name.set_opt(&meta.path, ser.as_ref().map(syn::LitStr::value));
-                    de_name.set_opt(&meta.path, de.as_ref().map(syn::LitStr::value));
+                    let (ser, de) = get_rename_values(cx, &meta)?;
+                    ser_name.set_opt(&meta.path, ser);
+                    de_name.set_opt(&meta.path, de);
                 } else if meta.path == RENAME_ALL {
                     // #[serde(rename_all = "foo")]
                     // #[serde(rename_all(serialize = "foo", deserialize = "bar"))]
@@ -388,6 +473,14 @@ impl Container {
                             cx.syn_error(meta.error(msg));
                         }
                     }
+                } else if meta.path == RENAME_ALL_WITH {
+                    // #[serde(rename_all_with = "...")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, RENAME_ALL_WITH, &meta)? {
+                        rename_all_with.set(&meta.path, path);
+                    }
+                } else if meta.path == CASE_INSENSITIVE {
+                    // #[serde(case_insensitive)]
+                    case_insensitive.set_true(meta.path);
                 } else if meta.path == TRANSPARENT {
                     // #[serde(transparent)]
                     transparent.set_true(meta.path);
@@ -483,6 +576,56 @@ impl Container {
                             }
                         }
                     }
+                } else if meta.path == INDEX_FALLBACK {
+                    // #[serde(index_fallback)]
+                    match &item.data {
+                        syn::Data::Enum(_) => {
+                            index_fallback.set_true(meta.path);
+                        }
+                        syn::Data::Struct(_) | syn::Data::Union(_) => {
+                            let msg = "#[serde(index_fallback)] can only be used on enums";
+                            cx.syn_error(meta.error(msg));
+                        }
+                    }
+                } else if meta.path == TAG_ALIAS {
+                    // #[serde(tag_alias = "kind")]
+                    if let Some(s) = get_lit_str(cx, TAG_ALIAS, &meta)? {
+                        match &item.data {
+                            syn::Data::Enum(_) => {
+                                tag_aliases.insert(&meta.path, s.value());
+                            }
+                            syn::Data::Struct(_) | syn::Data::Union(_) => {
+                                let msg = "#[serde(tag_alias = \"...\")] can only be used on enums";
+                                cx.syn_error(meta.error(msg));
+                            }
+                        }
+                    }
+                } else if meta.path == TAG_DESERIALIZE_WITH {
+                    // #[serde(tag_deserialize_with = "...")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, TAG_DESERIALIZE_WITH, &meta)? {
+                        match &item.data {
+                            syn::Data::Enum(_) => {
+                                tag_deserialize_with.set(&meta.path, path);
+                            }
+                            syn::Data::Struct(_) | syn::Data::Union(_) => {
+                                let msg = "#[serde(tag_deserialize_with = \"...\")] can only be used on enums";
+                                cx.syn_error(meta.error(msg));
+                            }
+                        }
+                    }
+                } else if meta.path == TAG_REPR {
+                    // #[serde(tag_repr = "u16")]
+                    if let Some(ty) = parse_lit_into_ty(cx, TAG_REPR, &meta)? {
+                        match &item.data {
+                            syn::Data::Enum(_) => {
+                                tag_repr.set(&meta.path, ty);
+                            }
+                            syn::Data::Struct(_) | syn::Data::Union(_) => {
+                                let msg = "#[serde(tag_repr = \"...\")] can only be used on enums";
+                                cx.syn_error(meta.error(msg));
+                            }
+                        }
+                    }
                 } else if meta.path == CONTENT {
                     // #[serde(content = "c")]
                     if let Some(s) = get_lit_str(cx, CONTENT, &meta)? {
@@ -510,11 +653,31 @@ impl Container {
                     if let Some(try_from_ty) = parse_lit_into_ty(cx, TRY_FROM, &meta)? {
                         type_try_from.set_opt(&meta.path, Some(try_from_ty));
                     }
+                } else if meta.path == FINALIZE {
+                    // #[serde(finalize = "Type::finalize")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, FINALIZE, &meta)? {
+                        finalize.set(&meta.path, path);
+                    }
+                } else if meta.path == VALIDATE {
+                    // #[serde(validate = "Type::validate")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, VALIDATE, &meta)? {
+                        validate.set(&meta.path, path);
+                    }
+                } else if meta.path == SEED {
+                    // #[serde(seed = "MyState")]
+                    if let Some(seed_ty) = parse_lit_into_ty(cx, SEED, &meta)? {
+                        seed.set(&meta.path, seed_ty);
+                    }
                 } else if meta.path == INTO {
                     // #[serde(into = "Type")]
                     if let Some(into_ty) = parse_lit_into_ty(cx, INTO, &meta)? {
                         type_into.set_opt(&meta.path, Some(into_ty));
                     }
+                } else if meta.path == TRY_INTO {
+                    // #[serde(try_into = "Type")]
+                    if let Some(try_into_ty) = parse_lit_into_ty(cx, TRY_INTO, &meta)? {
+                        type_try_into.set_opt(&meta.path, Some(try_into_ty));
+                    }
                 } else if meta.path == REMOTE {
                     // #[serde(remote = "...")]
                     if let Some(path) = parse_lit_into_path(cx, REMOTE, &meta)? {
@@ -540,6 +703,70 @@ impl Container {
                     if let Some(s) = get_lit_str(cx, EXPECTING, &meta)? {
                         expecting.set(&meta.path, s.value());
                     }
+                } else if meta.path == SORT_FIELDS {
+                    // #[serde(sort_fields = "alphabetical")]
+                    if let Some(s) = get_lit_str(cx, SORT_FIELDS, &meta)? {
+                        if s.value() == "alphabetical" {
+                            sort_fields.set(&meta.path, true);
+                        } else {
+                            cx.error_spanned_by(
+                                &s,
+                                format!("unknown sort order `{}`, expected \"alphabetical\"", s.value()),
+                            );
+                        }
+                    }
+                } else if meta.path == SKIP_SERIALIZING_DEFAULT {
+                    // #[serde(skip_serializing_default)]
+                    match &item.data {
+                        syn::Data::Struct(_) => {
+                            skip_serializing_default.set_true(meta.path);
+                        }
+                        syn::Data::Enum(_) | syn::Data::Union(_) => {
+                            let msg =
+                                "#[serde(skip_serializing_default)] can only be used on structs";
+                            cx.syn_error(meta.error(msg));
+                        }
+                    }
+                } else if meta.path == SKIP_NONE {
+                    // #[serde(skip_none)]
+                    skip_none.set_true(meta.path);
+                } else if meta.path == COMPACT_CODEGEN {
+                    // #[serde(compact_codegen)]
+                    compact_codegen.set_true(meta.path);
+                } else if meta.path == VERSION {
+                    // #[serde(version = 2)]
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    let n: u32 = lit.base10_parse()?;
+                    if n == 0 {
+                        cx.error_spanned_by(lit, "#[serde(version = ...)] must be at least 1");
+                    } else {
+                        version.set(&meta.path, n);
+                    }
+                } else if meta.path == UPGRADE_FROM {
+                    // #[serde(upgrade_from(1 = "migrate_v1", ...))]
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    while !content.is_empty() {
+                        let lit: syn::LitInt = content.parse()?;
+                        let from: u32 = lit.base10_parse()?;
+                        let _: Token![=] = content.parse()?;
+                        let func: syn::LitStr = content.parse()?;
+                        let func: syn::ExprPath = func.parse()?;
+                        if upgrade_from.iter().any(|(existing, _)| *existing == from) {
+                            cx.error_spanned_by(
+                                lit,
+                                format!("duplicate migration for version {}", from),
+                            );
+                        } else {
+                            upgrade_from.push((from, func));
+                        }
+                        if !content.is_empty() {
+                            let _: Token![,] = content.parse()?;
+                        }
+                    }
+                } else if meta.path == META {
+                    // #[serde(meta(key = "value"))]
+                    parse_metadata(cx, &meta, &mut metadata)?;
                 } else {
                     let path = meta.path.to_token_stream().to_string().replace(' ', "");
                     return Err(
@@ -566,6 +793,26 @@ impl Container {
             }
         }
 
+        let version = version.get();
+        if let Some(version) = version {
+            for (from, func) in &upgrade_from {
+                if *from >= version {
+                    cx.error_spanned_by(
+                        func,
+                        format!(
+                            "upgrade_from version {} must be less than the container version {}",
+                            from, version
+                        ),
+                    );
+                }
+            }
+        } else if !upgrade_from.is_empty() {
+            cx.error_spanned_by(
+                item,
+                "#[serde(upgrade_from(...))] requires #[serde(version = ...)]",
+            );
+        }
+
         Container {
             name: Name::from_attrs(unraw(&item.ident), ser_name, de_name, None),
             transparent: transparent.get(),
@@ -575,16 +822,26 @@ impl Container {
                 serialize: rename_all_ser_rule.get().unwrap_or(RenameRule::None),
                 deserialize: rename_all_de_rule.get().unwrap_or(RenameRule::None),
             },
+            rename_all_with: rename_all_with.get(),
+            case_insensitive: case_insensitive.get(),
             rename_all_fields_rules: RenameAllRules {
                 serialize: rename_all_fields_ser_rule.get().unwrap_or(RenameRule::None),
                 deserialize: rename_all_fields_de_rule.get().unwrap_or(RenameRule::None),
             },
             ser_bound: ser_bound.get(),
             de_bound: de_bound.get(),
-            tag: decide_tag(cx, item, untagged, internal_tag, content),
+            tag: decide_tag(cx, untagged, internal_tag, content),
+            tag_aliases: tag_aliases.get(),
+            index_fallback: index_fallback.get(),
+            tag_deserialize_with: tag_deserialize_with.get(),
+            tag_repr: tag_repr.get(),
             type_from: type_from.get(),
             type_try_from: type_try_from.get(),
+            finalize: finalize.get(),
+            validate: validate.get(),
+            seed: seed.get(),
             type_into: type_into.get(),
+            type_try_into: type_try_into.get(),
             remote: remote.get(),
             identifier: decide_identifier(cx, item, field_identifier, variant_identifier),
             has_flatten: false,
@@ -592,6 +849,13 @@ impl Container {
             is_packed,
             expecting: expecting.get(),
             non_exhaustive,
+            sort_fields_alphabetical: sort_fields.get().unwrap_or(false),
+            skip_serializing_default: skip_serializing_default.get(),
+            skip_none: skip_none.get(),
+            compact_codegen: compact_codegen.get(),
+            version,
+            upgrade_from,
+            meta: metadata.get(),
         }
     }
 
@@ -607,6 +871,14 @@ impl Container {
         self.rename_all_fields_rules
     }
 
+    pub fn rename_all_with(&self) -> Option<&syn::ExprPath> {
+        self.rename_all_with.as_ref()
+    }
+
+    pub fn case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
     pub fn transparent(&self) -> bool {
         self.transparent
     }
@@ -631,6 +903,22 @@ impl Container {
         &self.tag
     }
 
+    pub fn tag_aliases(&self) -> &[String] {
+        &self.tag_aliases
+    }
+
+    pub fn index_fallback(&self) -> bool {
+        self.index_fallback
+    }
+
+    pub fn tag_deserialize_with(&self) -> Option<&syn::ExprPath> {
+        self.tag_deserialize_with.as_ref()
+    }
+
+    pub fn tag_repr(&self) -> Option<&syn::Type> {
+        self.tag_repr.as_ref()
+    }
+
     pub fn type_from(&self) -> Option<&syn::Type> {
         self.type_from.as_ref()
     }
@@ -639,10 +927,26 @@ impl Container {
         self.type_try_from.as_ref()
     }
 
+    pub fn finalize(&self) -> Option<&syn::ExprPath> {
+        self.finalize.as_ref()
+    }
+
+    pub fn validate(&self) -> Option<&syn::ExprPath> {
+        self.validate.as_ref()
+    }
+
+    pub fn seed(&self) -> Option<&syn::Type> {
+        self.seed.as_ref()
+    }
+
     pub fn type_into(&self) -> Option<&syn::Type> {
         self.type_into.as_ref()
     }
 
+    pub fn type_try_into(&self) -> Option<&syn::Type> {
+        self.type_try_into.as_ref()
+    }
+
     pub fn remote(&self) -> Option<&syn::Path> {
         self.remote.as_ref()
     }
@@ -674,6 +978,10 @@ impl Container {
 
     /// Error message generated when type can't be deserialized.
     /// If `None`, default message will be used
+    pub fn meta(&self) -> &[(String, String)] {
+        &self.meta
+    }
+
     pub fn expecting(&self) -> Option<&str> {
         self.expecting.as_ref().map(String::as_ref)
     }
@@ -681,11 +989,34 @@ impl Container {
     pub fn non_exhaustive(&self) -> bool {
         self.non_exhaustive
     }
+
+    pub fn sort_fields_alphabetical(&self) -> bool {
+        self.sort_fields_alphabetical
+    }
+
+    pub fn skip_serializing_default(&self) -> bool {
+        self.skip_serializing_default
+    }
+
+    pub fn skip_none(&self) -> bool {
+        self.skip_none
+    }
+
+    pub fn compact_codegen(&self) -> bool {
+        self.compact_codegen
+    }
+
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    pub fn upgrade_from(&self) -> &[(u32, syn::ExprPath)] {
+        &self.upgrade_from
+    }
 }
 
 fn decide_tag(
     cx: &Ctxt,
-    item: &syn::DeriveInput,
     untagged: BoolAttr,
     internal_tag: Attr<String>,
     content: Attr<String>,
@@ -697,25 +1028,7 @@ fn decide_tag(
     ) {
         (None, None, None) => TagType::External,
         (Some(_), None, None) => TagType::None,
-        (None, Some((_, tag)), None) => {
-            // Check that there are no tuple variants.
-            if let syn::Data::Enum(data) = &item.data {
-                for variant in &data.variants {
-                    match &variant.fields {
-                        syn::Fields::Named(_) | syn::Fields::Unit => {}
-                        syn::Fields::Unnamed(fields) => {
-                            if fields.unnamed.len() != 1 {
-                                let msg =
-                                    "#[serde(tag = \"...\")] cannot be used with tuple variants";
-                                cx.error_spanned_by(variant, msg);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-            TagType::Internal { tag }
-        }
+        (None, Some((_, tag)), None) => TagType::Internal { tag },
         (Some((untagged_tokens, ())), Some((tag_tokens, _)), None) => {
             let msg = "enum cannot be both untagged and internally tagged";
             cx.error_spanned_by(untagged_tokens, msg);
@@ -801,6 +1114,7 @@ pub struct Variant {
     deserialize_with: Option<syn::ExprPath>,
     borrow: Option<BorrowAttribute>,
     untagged: bool,
+    meta: Vec<(String, String)>,
 }
 
 struct BorrowAttribute {
@@ -824,6 +1138,7 @@ impl Variant {
         let mut deserialize_with = Attr::none(cx, DESERIALIZE_WITH);
         let mut borrow = Attr::none(cx, BORROW);
         let mut untagged = BoolAttr::none(cx, UNTAGGED);
+        let mut metadata = VecAttr::none(cx, META);
 
         for attr in &variant.attrs {
             if attr.path() != SERDE {
@@ -841,10 +1156,20 @@ impl Variant {
                     // #[serde(rename = "foo")]
                     // #[serde(rename(serialize = "foo", deserialize = "bar"))]
                     let (ser, de) = get_multiple_renames(cx, &meta)?;
-                    ser_name.set_opt(&meta.path, ser.as_ref().map(syn::LitStr::value));
+                    ser_name.set_opt(&meta.path, ser);
                     for de_value in de {
-                        de_name.set_if_none(de_value.value());
-                        de_aliases.insert(&meta.path, de_value.value());
+                        match de_value {
+                            RenameValue::Lit(lit) => {
+                                de_name.set_if_none(RenameValue::Lit(lit.clone()));
+                                de_aliases.insert(&meta.path, lit.value());
+                            }
+                            // Aliases must be known at macro expansion time,
+                            // so only the primary deserialize name may come
+                            // from a const.
+                            RenameValue::Const(path) => {
+                                de_name.set_if_none(RenameValue::Const(path));
+                            }
+                        }
                     }
                 } else if meta.path == ALIAS {
                     // #[serde(alias = "foo")]
@@ -943,6 +1268,9 @@ impl Variant {
                     }
                 } else if meta.path == UNTAGGED {
                     untagged.set_true(&meta.path);
+                } else if meta.path == META {
+                    // #[serde(meta(key = "value"))]
+                    parse_metadata(cx, &meta, &mut metadata)?;
                 } else {
                     let path = meta.path.to_token_stream().to_string().replace(' ', "");
                     return Err(
@@ -970,6 +1298,7 @@ impl Variant {
             deserialize_with: deserialize_with.get(),
             borrow: borrow.get(),
             untagged: untagged.get(),
+            meta: metadata.get(),
         }
     }
 
@@ -988,9 +1317,11 @@ impl Variant {
         if !self.name.deserialize_renamed {
             self.name.deserialize = rules.deserialize.apply_to_variant(&self.name.deserialize);
         }
-        self.name
-            .deserialize_aliases
-            .insert(self.name.deserialize.clone());
+        if self.name.deserialize_const.is_none() {
+            self.name
+                .deserialize_aliases
+                .insert(self.name.deserialize.clone());
+        }
     }
 
     pub fn rename_all_rules(&self) -> RenameAllRules {
@@ -1025,6 +1356,10 @@ impl Variant {
         self.deserialize_with.as_ref()
     }
 
+    pub fn meta(&self) -> &[(String, String)] {
+        &self.meta
+    }
+
     pub fn untagged(&self) -> bool {
         self.untagged
     }
@@ -1037,14 +1372,25 @@ pub struct Field {
     skip_deserializing: bool,
     skip_serializing_if: Option<syn::ExprPath>,
     default: Default,
+    missing_field_error: Option<String>,
+    validate: Option<syn::ExprPath>,
     serialize_with: Option<syn::ExprPath>,
+    serialize_seed_with: Option<syn::ExprPath>,
     deserialize_with: Option<syn::ExprPath>,
+    deserialize_with_in_place: Option<syn::ExprPath>,
+    seed_with: Option<syn::ExprPath>,
+    key_with: Option<syn::ExprPath>,
+    keyed_by: Option<String>,
+    map_from_pairs: bool,
     ser_bound: Option<Vec<syn::WherePredicate>>,
     de_bound: Option<Vec<syn::WherePredicate>>,
     borrowed_lifetimes: BTreeSet<syn::Lifetime>,
     getter: Option<syn::ExprPath>,
     flatten: bool,
+    collect_unknown: bool,
+    order: Option<usize>,
     transparent: bool,
+    meta: Vec<(String, String)>,
 }
 
 /// Represents the default to use for a field when deserializing.
@@ -1082,13 +1428,24 @@ impl Field {
         let mut skip_deserializing = BoolAttr::none(cx, SKIP_DESERIALIZING);
         let mut skip_serializing_if = Attr::none(cx, SKIP_SERIALIZING_IF);
         let mut default = Attr::none(cx, DEFAULT);
+        let mut missing_field_error = Attr::none(cx, MISSING_FIELD_ERROR);
+        let mut validate = Attr::none(cx, VALIDATE);
         let mut serialize_with = Attr::none(cx, SERIALIZE_WITH);
+        let mut serialize_seed_with = Attr::none(cx, SERIALIZE_SEED_WITH);
         let mut deserialize_with = Attr::none(cx, DESERIALIZE_WITH);
+        let mut deserialize_with_in_place = Attr::none(cx, DESERIALIZE_WITH_IN_PLACE);
+        let mut seed_with = Attr::none(cx, SEED_WITH);
+        let mut key_with = Attr::none(cx, KEY_WITH);
+        let mut keyed_by = Attr::none(cx, KEYED_BY);
+        let mut map_from_pairs = BoolAttr::none(cx, MAP_FROM_PAIRS);
         let mut ser_bound = Attr::none(cx, BOUND);
         let mut de_bound = Attr::none(cx, BOUND);
         let mut borrowed_lifetimes = Attr::none(cx, BORROW);
         let mut getter = Attr::none(cx, GETTER);
         let mut flatten = BoolAttr::none(cx, FLATTEN);
+        let mut collect_unknown = BoolAttr::none(cx, COLLECT_UNKNOWN);
+        let mut order = Attr::none(cx, ORDER);
+        let mut metadata = VecAttr::none(cx, META);
 
         let ident = match &field.ident {
             Some(ident) => unraw(ident),
@@ -1128,10 +1485,20 @@ impl Field {
                     // #[serde(rename = "foo")]
                     // #[serde(rename(serialize = "foo", deserialize = "bar"))]
                     let (ser, de) = get_multiple_renames(cx, &meta)?;
-                    ser_name.set_opt(&meta.path, ser.as_ref().map(syn::LitStr::value));
+                    ser_name.set_opt(&meta.path, ser);
                     for de_value in de {
-                        de_name.set_if_none(de_value.value());
-                        de_aliases.insert(&meta.path, de_value.value());
+                        match de_value {
+                            RenameValue::Lit(lit) => {
+                                de_name.set_if_none(RenameValue::Lit(lit.clone()));
+                                de_aliases.insert(&meta.path, lit.value());
+                            }
+                            // Aliases must be known at macro expansion time,
+                            // so only the primary deserialize name may come
+                            // from a const.
+                            RenameValue::Const(path) => {
+                                de_name.set_if_none(RenameValue::Const(path));
+                            }
+                        }
                     }
                 } else if meta.path == ALIAS {
                     // #[serde(alias = "foo")]
@@ -1173,6 +1540,21 @@ impl Field {
                     if let Some(path) = parse_lit_into_expr_path(cx, DESERIALIZE_WITH, &meta)? {
                         deserialize_with.set(&meta.path, path);
                     }
+                } else if meta.path == DESERIALIZE_WITH_IN_PLACE {
+                    // #[serde(deserialize_with_in_place = "...")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, DESERIALIZE_WITH_IN_PLACE, &meta)? {
+                        deserialize_with_in_place.set(&meta.path, path);
+                    }
+                } else if meta.path == SEED_WITH {
+                    // #[serde(seed_with = "...")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, SEED_WITH, &meta)? {
+                        seed_with.set(&meta.path, path);
+                    }
+                } else if meta.path == SERIALIZE_SEED_WITH {
+                    // #[serde(serialize_seed_with = "...")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, SERIALIZE_SEED_WITH, &meta)? {
+                        serialize_seed_with.set(&meta.path, path);
+                    }
                 } else if meta.path == WITH {
                     // #[serde(with = "...")]
                     if let Some(path) = parse_lit_into_expr_path(cx, WITH, &meta)? {
@@ -1189,6 +1571,29 @@ impl Field {
                             .push(Ident::new("deserialize", Span::call_site()).into());
                         deserialize_with.set(&meta.path, de_path);
                     }
+                } else if meta.path == KEY_WITH {
+                    // #[serde(key_with = "...")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, KEY_WITH, &meta)? {
+                        key_with.set(&meta.path, path);
+                    }
+                } else if meta.path == KEYED_BY {
+                    // #[serde(keyed_by = "id")]
+                    if let Some(s) = get_lit_str(cx, KEYED_BY, &meta)? {
+                        keyed_by.set(&meta.path, s.value());
+                    }
+                } else if meta.path == MAP_FROM_PAIRS {
+                    // #[serde(map_from_pairs)]
+                    map_from_pairs.set_true(meta.path);
+                } else if meta.path == MISSING_FIELD_ERROR {
+                    // #[serde(missing_field_error = "set `api_key` in config.toml")]
+                    if let Some(s) = get_lit_str(cx, MISSING_FIELD_ERROR, &meta)? {
+                        missing_field_error.set(&meta.path, s.value());
+                    }
+                } else if meta.path == VALIDATE {
+                    // #[serde(validate = "validate_port")]
+                    if let Some(path) = parse_lit_into_expr_path(cx, VALIDATE, &meta)? {
+                        validate.set(&meta.path, path);
+                    }
                 } else if meta.path == BOUND {
                     // #[serde(bound = "T: SomeBound")]
                     // #[serde(bound(serialize = "...", deserialize = "..."))]
@@ -1225,6 +1630,16 @@ impl Field {
                 } else if meta.path == FLATTEN {
                     // #[serde(flatten)]
                     flatten.set_true(&meta.path);
+                } else if meta.path == COLLECT_UNKNOWN {
+                    // #[serde(collect_unknown)]
+                    collect_unknown.set_true(&meta.path);
+                } else if meta.path == ORDER {
+                    // #[serde(order = 1)]
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    order.set(&meta.path, lit.base10_parse()?);
+                } else if meta.path == META {
+                    // #[serde(meta(key = "value"))]
+                    parse_metadata(cx, &meta, &mut metadata)?;
                 } else {
                     let path = meta.path.to_token_stream().to_string().replace(' ', "");
                     return Err(
@@ -1237,6 +1652,13 @@ impl Field {
             }
         }
 
+        if deserialize_with_in_place.value.is_some() && deserialize_with.value.is_none() {
+            cx.error_spanned_by(
+                field,
+                "#[serde(deserialize_with_in_place = \"...\")] can only be used together with #[serde(deserialize_with = \"...\")]",
+            );
+        }
+
         // Is skip_deserializing, initialize the field to Default::default() unless a
         // different default is specified by `#[serde(default = "...")]` on
         // ourselves or our container (e.g. the struct we are in).
@@ -1257,24 +1679,32 @@ impl Field {
             //
             //     impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, str>
             //     impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]>
-            if is_cow(&field.ty, is_str) {
-                let mut path = syn::Path {
-                    leading_colon: None,
-                    segments: Punctuated::new(),
-                };
-                let span = Span::call_site();
-                path.segments.push(Ident::new("_serde", span).into());
-                path.segments.push(Ident::new("__private", span).into());
-                path.segments.push(Ident::new("de", span).into());
-                path.segments
-                    .push(Ident::new("borrow_cow_str", span).into());
-                let expr = syn::ExprPath {
-                    attrs: Vec::new(),
-                    qself: None,
-                    path,
-                };
-                deserialize_with.set_if_none(expr);
+            //
+            // The same applies to Cow<Path> and Cow<OsStr>, which can borrow
+            // from a string in the input, and to Cow<str> / Cow<[u8]> nested
+            // directly in an Option, Vec, or map key position.
+            let helper = if is_cow(&field.ty, is_str) {
+                Some("borrow_cow_str")
             } else if is_cow(&field.ty, is_slice_u8) {
+                Some("borrow_cow_bytes")
+            } else if is_cow(&field.ty, is_path) {
+                Some("borrow_cow_path")
+            } else if is_cow(&field.ty, is_os_str) {
+                Some("borrow_cow_os_str")
+            } else if is_option(&field.ty, is_cow_str) {
+                Some("borrow_cow_str_option")
+            } else if is_option(&field.ty, is_cow_bytes) {
+                Some("borrow_cow_bytes_option")
+            } else if is_vec(&field.ty, is_cow_str) {
+                Some("borrow_cow_str_vec")
+            } else if is_vec(&field.ty, is_cow_bytes) {
+                Some("borrow_cow_bytes_vec")
+            } else if is_map_with_key(&field.ty, is_cow_str) {
+                Some("borrow_cow_str_map")
+            } else {
+                None
+            };
+            if let Some(helper) = helper {
                 let mut path = syn::Path {
                     leading_colon: None,
                     segments: Punctuated::new(),
@@ -1283,8 +1713,7 @@ impl Field {
                 path.segments.push(Ident::new("_serde", span).into());
                 path.segments.push(Ident::new("__private", span).into());
                 path.segments.push(Ident::new("de", span).into());
-                path.segments
-                    .push(Ident::new("borrow_cow_bytes", span).into());
+                path.segments.push(Ident::new(helper, span).into());
                 let expr = syn::ExprPath {
                     attrs: Vec::new(),
                     qself: None,
@@ -1304,14 +1733,25 @@ impl Field {
             skip_deserializing: skip_deserializing.get(),
             skip_serializing_if: skip_serializing_if.get(),
             default: default.get().unwrap_or(Default::None),
+            missing_field_error: missing_field_error.get(),
+            validate: validate.get(),
             serialize_with: serialize_with.get(),
+            serialize_seed_with: serialize_seed_with.get(),
             deserialize_with: deserialize_with.get(),
+            deserialize_with_in_place: deserialize_with_in_place.get(),
+            seed_with: seed_with.get(),
+            key_with: key_with.get(),
+            keyed_by: keyed_by.get(),
+            map_from_pairs: map_from_pairs.get(),
             ser_bound: ser_bound.get(),
             de_bound: de_bound.get(),
             borrowed_lifetimes,
             getter: getter.get(),
             flatten: flatten.get(),
+            collect_unknown: collect_unknown.get(),
+            order: order.get(),
             transparent: false,
+            meta: metadata.get(),
         }
     }
 
@@ -1330,9 +1770,11 @@ impl Field {
         if !self.name.deserialize_renamed {
             self.name.deserialize = rules.deserialize.apply_to_field(&self.name.deserialize);
         }
-        self.name
-            .deserialize_aliases
-            .insert(self.name.deserialize.clone());
+        if self.name.deserialize_const.is_none() {
+            self.name
+                .deserialize_aliases
+                .insert(self.name.deserialize.clone());
+        }
     }
 
     pub fn skip_serializing(&self) -> bool {
@@ -1351,6 +1793,14 @@ impl Field {
         &self.default
     }
 
+    pub fn missing_field_error(&self) -> Option<&str> {
+        self.missing_field_error.as_deref()
+    }
+
+    pub fn validate(&self) -> Option<&syn::ExprPath> {
+        self.validate.as_ref()
+    }
+
     pub fn serialize_with(&self) -> Option<&syn::ExprPath> {
         self.serialize_with.as_ref()
     }
@@ -1359,6 +1809,30 @@ impl Field {
         self.deserialize_with.as_ref()
     }
 
+    pub fn deserialize_with_in_place(&self) -> Option<&syn::ExprPath> {
+        self.deserialize_with_in_place.as_ref()
+    }
+
+    pub fn seed_with(&self) -> Option<&syn::ExprPath> {
+        self.seed_with.as_ref()
+    }
+
+    pub fn serialize_seed_with(&self) -> Option<&syn::ExprPath> {
+        self.serialize_seed_with.as_ref()
+    }
+
+    pub fn key_with(&self) -> Option<&syn::ExprPath> {
+        self.key_with.as_ref()
+    }
+
+    pub fn keyed_by(&self) -> Option<&str> {
+        self.keyed_by.as_deref()
+    }
+
+    pub fn map_from_pairs(&self) -> bool {
+        self.map_from_pairs
+    }
+
     pub fn ser_bound(&self) -> Option<&[syn::WherePredicate]> {
         self.ser_bound.as_ref().map(|vec| &vec[..])
     }
@@ -1379,6 +1853,18 @@ impl Field {
         self.flatten
     }
 
+    pub fn collect_unknown(&self) -> bool {
+        self.collect_unknown
+    }
+
+    pub fn order(&self) -> Option<usize> {
+        self.order
+    }
+
+    pub fn meta(&self) -> &[(String, String)] {
+        &self.meta
+    }
+
     pub fn transparent(&self) -> bool {
         self.transparent
     }
@@ -1444,11 +1930,19 @@ fn get_renames(
     Ok((ser.at_most_one(), de.at_most_one()))
 }
 
+fn get_rename_values(
+    cx: &Ctxt,
+    meta: &ParseNestedMeta,
+) -> syn::Result<SerAndDe<RenameValue>> {
+    let (ser, de) = get_ser_and_de(cx, RENAME, meta, get_rename_value)?;
+    Ok((ser.at_most_one(), de.at_most_one()))
+}
+
 fn get_multiple_renames(
     cx: &Ctxt,
     meta: &ParseNestedMeta,
-) -> syn::Result<(Option<syn::LitStr>, Vec<syn::LitStr>)> {
-    let (ser, de) = get_ser_and_de(cx, RENAME, meta, get_lit_str2)?;
+) -> syn::Result<(Option<RenameValue>, Vec<RenameValue>)> {
+    let (ser, de) = get_ser_and_de(cx, RENAME, meta, get_rename_value)?;
     Ok((ser.at_most_one(), de.get()))
 }
 
@@ -1460,6 +1954,20 @@ fn get_where_predicates(
     Ok((ser.at_most_one(), de.at_most_one()))
 }
 
+fn parse_metadata(
+    cx: &Ctxt,
+    meta: &ParseNestedMeta,
+    metadata: &mut VecAttr<(String, String)>,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|meta| {
+        let key = meta.path.require_ident()?.to_string();
+        if let Some(value) = get_lit_str2(cx, META, META, &meta)? {
+            metadata.insert(&meta.path, (key, value.value()));
+        }
+        Ok(())
+    })
+}
+
 fn get_lit_str(
     cx: &Ctxt,
     attr_name: Symbol,
@@ -1504,6 +2012,45 @@ fn get_lit_str2(
     }
 }
 
+fn get_rename_value(
+    cx: &Ctxt,
+    attr_name: Symbol,
+    meta_item_name: Symbol,
+    meta: &ParseNestedMeta,
+) -> syn::Result<Option<RenameValue>> {
+    let expr: syn::Expr = meta.value()?.parse()?;
+    let mut value = &expr;
+    while let syn::Expr::Group(e) = value {
+        value = &e.expr;
+    }
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => {
+            let suffix = lit.suffix();
+            if !suffix.is_empty() {
+                cx.error_spanned_by(
+                    lit,
+                    format!("unexpected suffix `{}` on string literal", suffix),
+                );
+            }
+            Ok(Some(RenameValue::Lit(lit.clone())))
+        }
+        syn::Expr::Path(path) => Ok(Some(RenameValue::Const(path.clone()))),
+        _ => {
+            cx.error_spanned_by(
+                expr,
+                format!(
+                    "expected serde {} attribute to be a string or a path to a const: `{} = \"...\"` or `{} = CONST`",
+                    attr_name, meta_item_name, meta_item_name
+                ),
+            );
+            Ok(None)
+        }
+    }
+}
+
 fn parse_lit_into_path(
     cx: &Ctxt,
     attr_name: Symbol,
@@ -1690,6 +2237,80 @@ fn is_cow(ty: &syn::Type, elem: fn(&syn::Type) -> bool) -> bool {
         }
 }
 
+fn is_cow_str(ty: &syn::Type) -> bool {
+    is_cow(ty, is_str)
+}
+
+fn is_cow_bytes(ty: &syn::Type) -> bool {
+    is_cow(ty, is_slice_u8)
+}
+
+fn is_vec(ty: &syn::Type, elem: fn(&syn::Type) -> bool) -> bool {
+    let path = match ungroup(ty) {
+        syn::Type::Path(ty) => &ty.path,
+        _ => {
+            return false;
+        }
+    };
+    let seg = match path.segments.last() {
+        Some(seg) => seg,
+        None => {
+            return false;
+        }
+    };
+    let args = match &seg.arguments {
+        syn::PathArguments::AngleBracketed(bracketed) => &bracketed.args,
+        _ => {
+            return false;
+        }
+    };
+    seg.ident == "Vec"
+        && args.len() == 1
+        && match &args[0] {
+            syn::GenericArgument::Type(arg) => elem(arg),
+            _ => false,
+        }
+}
+
+// Whether the type looks like a HashMap or BTreeMap whose key type matches
+// `key`. HashMap may carry a third hasher parameter.
+fn is_map_with_key(ty: &syn::Type, key: fn(&syn::Type) -> bool) -> bool {
+    let path = match ungroup(ty) {
+        syn::Type::Path(ty) => &ty.path,
+        _ => {
+            return false;
+        }
+    };
+    let seg = match path.segments.last() {
+        Some(seg) => seg,
+        None => {
+            return false;
+        }
+    };
+    let args = match &seg.arguments {
+        syn::PathArguments::AngleBracketed(bracketed) => &bracketed.args,
+        _ => {
+            return false;
+        }
+    };
+    let arity_ok = match seg.ident.to_string().as_str() {
+        "HashMap" => args.len() == 2 || args.len() == 3,
+        "BTreeMap" => args.len() == 2,
+        _ => return false,
+    };
+    arity_ok
+        && match &args[0] {
+            syn::GenericArgument::Type(arg) => key(arg),
+            _ => false,
+        }
+}
+
+// Whether the type is spelled as `Option<T>`, for the container-level
+// `skip_none` attribute. Type aliases of `Option` are not seen through.
+pub(crate) fn is_option_type(ty: &syn::Type) -> bool {
+    is_option(ty, |_| true)
+}
+
 fn is_option(ty: &syn::Type, elem: fn(&syn::Type) -> bool) -> bool {
     let path = match ungroup(ty) {
         syn::Type::Path(ty) => &ty.path,
@@ -1755,6 +2376,29 @@ fn is_slice_u8(ty: &syn::Type) -> bool {
     }
 }
 
+fn is_path(ty: &syn::Type) -> bool {
+    is_std_type(ty, "Path")
+}
+
+fn is_os_str(ty: &syn::Type) -> bool {
+    is_std_type(ty, "OsStr")
+}
+
+// `Path` and `OsStr` may be spelled with or without their `std::path` and
+// `std::ffi` prefixes, so only the last path segment is compared.
+fn is_std_type(ty: &syn::Type, name: &str) -> bool {
+    match ungroup(ty) {
+        syn::Type::Path(ty) => {
+            ty.qself.is_none()
+                && match ty.path.segments.last() {
+                    Some(seg) => seg.ident == name && seg.arguments.is_empty(),
+                    None => false,
+                }
+        }
+        _ => false,
+    }
+}
+
 fn is_primitive_type(ty: &syn::Type, primitive: &str) -> bool {
     match ungroup(ty) {
         syn::Type::Path(ty) => ty.qself.is_none() && is_primitive_path(&ty.path, primitive),
@@ -10,10 +10,19 @@ pub fn check(cx: &Ctxt, cont: &mut Container, derive: Derive) {
     check_remote_generic(cx, cont);
     check_getter(cx, cont);
     check_flatten(cx, cont);
+    check_field_order(cx, cont);
+    check_keyed_by(cx, cont);
+    check_map_from_pairs(cx, cont);
+    check_collect_unknown(cx, cont);
+    check_seed(cx, cont);
     check_identifier(cx, cont);
     check_variant_skip_attrs(cx, cont);
     check_internal_tag_field_name_conflict(cx, cont);
     check_adjacent_tag_conflict(cx, cont);
+    check_tag_aliases(cx, cont);
+    check_index_fallback(cx, cont);
+    check_tag_deserialize_with(cx, cont);
+    check_tag_repr(cx, cont);
     check_transparent(cx, cont, derive);
     check_from_and_try_from(cx, cont);
 }
@@ -135,6 +144,251 @@ fn check_flatten_field(cx: &Ctxt, style: Style, field: &Field) {
     }
 }
 
+// Tuple and newtype fields are identified by their position, so reordering
+// them would change which value lands in which field. `order` is only
+// meaningful on named fields.
+fn check_field_order(cx: &Ctxt, cont: &Container) {
+    for field in cont.data.all_fields() {
+        if field.attrs.order().is_some() {
+            if let Member::Unnamed(_) = field.member {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(order)] cannot be used on tuple fields",
+                );
+            }
+        }
+    }
+}
+
+// `keyed_by` rewrites the representation of a whole named field, so it cannot
+// be combined with the attributes that already take over (de)serialization of
+// that field.
+fn check_keyed_by(cx: &Ctxt, cont: &Container) {
+    for field in cont.data.all_fields() {
+        if field.attrs.keyed_by().is_none() {
+            continue;
+        }
+        if let Member::Unnamed(_) = field.member {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(keyed_by)] cannot be used on tuple fields",
+            );
+        }
+        if field.attrs.flatten() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(keyed_by)] cannot be combined with flatten",
+            );
+        }
+        if field.attrs.serialize_with().is_some() || field.attrs.deserialize_with().is_some() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(keyed_by)] cannot be combined with serialize_with or deserialize_with",
+            );
+        }
+    }
+    if cont.attrs.transparent() {
+        if let Some(field) = cont.data.all_fields().find(|f| f.attrs.keyed_by().is_some()) {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(keyed_by)] cannot be used inside a transparent container",
+            );
+        }
+    }
+}
+
+fn check_map_from_pairs(cx: &Ctxt, cont: &Container) {
+    for field in cont.data.all_fields() {
+        if !field.attrs.map_from_pairs() {
+            continue;
+        }
+        if field.attrs.flatten() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(map_from_pairs)] cannot be combined with flatten",
+            );
+        }
+        if field.attrs.deserialize_with().is_some() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(map_from_pairs)] cannot be combined with deserialize_with",
+            );
+        }
+        if field.attrs.keyed_by().is_some() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(map_from_pairs)] cannot be combined with keyed_by",
+            );
+        }
+    }
+}
+
+// A `collect_unknown` field receives every key that no named field matched,
+// so there can be at most one of them and it cannot coexist with flatten,
+// which expects to see those same leftover keys.
+fn check_collect_unknown(cx: &Ctxt, cont: &Container) {
+    match &cont.data {
+        Data::Enum(variants) => {
+            for variant in variants {
+                check_collect_unknown_fields(cx, cont, variant.style, &variant.fields);
+            }
+        }
+        Data::Struct(style, fields) => {
+            check_collect_unknown_fields(cx, cont, *style, fields);
+        }
+    }
+}
+
+fn check_collect_unknown_fields(cx: &Ctxt, cont: &Container, style: Style, fields: &[Field]) {
+    let mut seen = false;
+    for field in fields {
+        if !field.attrs.collect_unknown() {
+            continue;
+        }
+        if let Style::Struct = style {
+        } else {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(collect_unknown)] can only be used on named fields",
+            );
+        }
+        if seen {
+            cx.error_spanned_by(
+                field.original,
+                "duplicate #[serde(collect_unknown)] field",
+            );
+        }
+        seen = true;
+        if field.attrs.flatten() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(collect_unknown)] cannot be combined with flatten",
+            );
+        }
+        if cont.attrs.deny_unknown_fields() {
+            cx.error_spanned_by(
+                field.original,
+                "#[serde(collect_unknown)] cannot be combined with deny_unknown_fields",
+            );
+        }
+    }
+    if seen {
+        if let Some(flattened) = fields.iter().find(|field| field.attrs.flatten()) {
+            cx.error_spanned_by(
+                flattened.original,
+                "#[serde(flatten)] cannot be combined with a collect_unknown field",
+            );
+        }
+    }
+}
+
+// Seeded deserialization replaces the Deserialize impl with a DeserializeSeed
+// impl for the state type, so it is restricted to plain structs with named
+// fields and cannot coexist with attributes that change the shape of the impl.
+fn check_seed(cx: &Ctxt, cont: &Container) {
+    for field in cont.data.all_fields() {
+        if field.attrs.seed_with().is_some() {
+            if cont.attrs.seed().is_none() {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(seed_with)] requires a #[serde(seed = \"...\")] attribute on the container",
+                );
+            }
+            if field.attrs.deserialize_with().is_some() {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(seed_with)] cannot be combined with deserialize_with",
+                );
+            }
+            if field.attrs.flatten() {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(seed_with)] cannot be combined with flatten",
+                );
+            }
+        }
+        if field.attrs.serialize_seed_with().is_some() {
+            if cont.attrs.seed().is_none() {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(serialize_seed_with)] requires a #[serde(seed = \"...\")] attribute on the container",
+                );
+            }
+            if field.attrs.serialize_with().is_some() {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(serialize_seed_with)] cannot be combined with serialize_with",
+                );
+            }
+            if field.attrs.flatten() {
+                cx.error_spanned_by(
+                    field.original,
+                    "#[serde(serialize_seed_with)] cannot be combined with flatten",
+                );
+            }
+        }
+    }
+
+    let seed = match cont.attrs.seed() {
+        Some(seed) => seed,
+        None => return,
+    };
+
+    if let Data::Struct(Style::Struct, _) = &cont.data {
+    } else {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] can only be used on structs with named fields",
+        );
+    }
+
+    if !cont.generics.params.is_empty() {
+        // The generated impl is `impl DeserializeSeed for State` with
+        // `type Value = Self_`, so generic parameters of the container would
+        // be unconstrained.
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] cannot be used on generic types",
+        );
+    }
+
+    if let Type::Reference(_) = ungroup(seed) {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] requires an owned state type; wrap shared state in Rc or Arc",
+        );
+    }
+
+    if cont.attrs.has_flatten() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] cannot be combined with flatten",
+        );
+    }
+    if cont.attrs.remote().is_some() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] cannot be combined with remote",
+        );
+    }
+    if cont.attrs.transparent() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] cannot be combined with transparent",
+        );
+    }
+    if cont.attrs.type_from().is_some()
+        || cont.attrs.type_try_from().is_some()
+        || cont.attrs.type_into().is_some()
+        || cont.attrs.type_try_into().is_some()
+    {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(seed)] cannot be combined with from, try_from, into, or try_into",
+        );
+    }
+}
+
 // The `other` attribute must be used at most once and it must be the last
 // variant of an enum.
 //
@@ -180,11 +434,34 @@ fn check_identifier(cx: &Ctxt, cont: &Container) {
                 }
             }
 
+            // A newtype variant may capture the unrecognized tag, but only the
+            // internally and adjacently tagged representations deserialize the
+            // tag separately from the content and can supply it.
+            (
+                Style::Newtype,
+                Identifier::No,
+                true,
+                &TagType::Internal { .. } | &TagType::Adjacent { .. },
+            ) => {
+                if i < variants.len() - 1 {
+                    cx.error_spanned_by(
+                        variant.original,
+                        "#[serde(other)] must be on the last variant",
+                    );
+                }
+                if cont.attrs.index_fallback() {
+                    cx.error_spanned_by(
+                        variant.original,
+                        "#[serde(other)] on a newtype variant cannot be combined with index_fallback",
+                    );
+                }
+            }
+
             // Variant with `other` attribute must be a unit variant.
             (_, Identifier::Field, true, _) | (_, Identifier::No, true, _) => {
                 cx.error_spanned_by(
                     variant.original,
-                    "#[serde(other)] must be on a unit variant",
+                    "#[serde(other)] must be on a unit variant, or a newtype variant of an internally or adjacently tagged enum",
                 );
             }
 
@@ -363,6 +640,92 @@ fn check_adjacent_tag_conflict(cx: &Ctxt, cont: &Container) {
     }
 }
 
+// `tag_alias` adds alternative tag keys to the tag lookup performed by the
+// internally and adjacently tagged representations; the other representations
+// do not have a tag key.
+fn check_tag_aliases(cx: &Ctxt, cont: &Container) {
+    if !cont.attrs.tag_aliases().is_empty()
+        && !matches!(
+            cont.attrs.tag(),
+            TagType::Internal { .. } | TagType::Adjacent { .. }
+        )
+    {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(tag_alias = \"...\")] can only be used on internally or adjacently tagged enums",
+        );
+    }
+}
+
+// `index_fallback` switches to the externally tagged representation on
+// non-self-describing formats. The externally tagged representation works
+// everywhere already, and the adjacently tagged representation does not need
+// introspection, so the fallback only makes sense for the untagged and
+// internally tagged representations.
+fn check_index_fallback(cx: &Ctxt, cont: &Container) {
+    if cont.attrs.index_fallback()
+        && !matches!(
+            cont.attrs.tag(),
+            TagType::None | TagType::Internal { .. }
+        )
+    {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(index_fallback)] can only be used on untagged or internally tagged enums",
+        );
+    }
+}
+
+// `tag_deserialize_with` hooks into the tag lookup of the internally tagged
+// representation; the other representations do not perform one.
+fn check_tag_deserialize_with(cx: &Ctxt, cont: &Container) {
+    if cont.attrs.tag_deserialize_with().is_some()
+        && !matches!(cont.attrs.tag(), TagType::Internal { .. })
+    {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(tag_deserialize_with = \"...\")] can only be used on internally tagged enums",
+        );
+    }
+}
+
+// `tag_repr` replaces the string tag of the internally and adjacently tagged
+// representations with the variant index, so it needs both a tag key and an
+// integer type to cast the index to.
+fn check_tag_repr(cx: &Ctxt, cont: &Container) {
+    let repr = match cont.attrs.tag_repr() {
+        Some(repr) => repr,
+        None => return,
+    };
+
+    if !matches!(
+        cont.attrs.tag(),
+        TagType::Internal { .. } | TagType::Adjacent { .. }
+    ) {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(tag_repr = \"...\")] can only be used on internally or adjacently tagged enums",
+        );
+        return;
+    }
+
+    let is_integer = match repr {
+        syn::Type::Path(ty) => ty.path.get_ident().map_or(false, |ident| {
+            matches!(
+                ident.to_string().as_str(),
+                "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64"
+            )
+        }),
+        _ => false,
+    };
+    if !is_integer {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(tag_repr = \"...\")] must be a primitive integer type",
+        );
+    }
+}
+
 // Enums and unit structs cannot be transparent.
 fn check_transparent(cx: &Ctxt, cont: &mut Container, derive: Derive) {
     if !cont.attrs.transparent() {
@@ -390,6 +753,13 @@ fn check_transparent(cx: &Ctxt, cont: &mut Container, derive: Derive) {
         );
     }
 
+    if cont.attrs.type_try_into().is_some() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(transparent)] is not allowed with #[serde(try_into = \"...\")]",
+        );
+    }
+
     let fields = match &mut cont.data {
         Data::Enum(_) => {
             cx.error_spanned_by(
@@ -471,4 +841,11 @@ fn check_from_and_try_from(cx: &Ctxt, cont: &mut Container) {
             "#[serde(from = \"...\")] and #[serde(try_from = \"...\")] conflict with each other",
         );
     }
+
+    if cont.attrs.type_into().is_some() && cont.attrs.type_try_into().is_some() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(into = \"...\")] and #[serde(try_into = \"...\")] conflict with each other",
+        );
+    }
 }
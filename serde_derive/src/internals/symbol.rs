@@ -7,37 +7,63 @@ pub struct Symbol(&'static str);
 pub const ALIAS: Symbol = Symbol("alias");
 pub const BORROW: Symbol = Symbol("borrow");
 pub const BOUND: Symbol = Symbol("bound");
+pub const CASE_INSENSITIVE: Symbol = Symbol("case_insensitive");
+pub const COLLECT_UNKNOWN: Symbol = Symbol("collect_unknown");
+pub const COMPACT_CODEGEN: Symbol = Symbol("compact_codegen");
 pub const CONTENT: Symbol = Symbol("content");
 pub const CRATE: Symbol = Symbol("crate");
 pub const DEFAULT: Symbol = Symbol("default");
 pub const DENY_UNKNOWN_FIELDS: Symbol = Symbol("deny_unknown_fields");
 pub const DESERIALIZE: Symbol = Symbol("deserialize");
 pub const DESERIALIZE_WITH: Symbol = Symbol("deserialize_with");
+pub const DESERIALIZE_WITH_IN_PLACE: Symbol = Symbol("deserialize_with_in_place");
 pub const EXPECTING: Symbol = Symbol("expecting");
 pub const FIELD_IDENTIFIER: Symbol = Symbol("field_identifier");
+pub const FINALIZE: Symbol = Symbol("finalize");
 pub const FLATTEN: Symbol = Symbol("flatten");
 pub const FROM: Symbol = Symbol("from");
 pub const GETTER: Symbol = Symbol("getter");
+pub const INDEX_FALLBACK: Symbol = Symbol("index_fallback");
+pub const KEYED_BY: Symbol = Symbol("keyed_by");
+pub const KEY_WITH: Symbol = Symbol("key_with");
 pub const INTO: Symbol = Symbol("into");
+pub const MAP_FROM_PAIRS: Symbol = Symbol("map_from_pairs");
+pub const META: Symbol = Symbol("meta");
+pub const MISSING_FIELD_ERROR: Symbol = Symbol("missing_field_error");
 pub const NON_EXHAUSTIVE: Symbol = Symbol("non_exhaustive");
+pub const ORDER: Symbol = Symbol("order");
 pub const OTHER: Symbol = Symbol("other");
 pub const REMOTE: Symbol = Symbol("remote");
 pub const RENAME: Symbol = Symbol("rename");
 pub const RENAME_ALL: Symbol = Symbol("rename_all");
 pub const RENAME_ALL_FIELDS: Symbol = Symbol("rename_all_fields");
+pub const RENAME_ALL_WITH: Symbol = Symbol("rename_all_with");
 pub const REPR: Symbol = Symbol("repr");
+pub const SEED: Symbol = Symbol("seed");
+pub const SEED_WITH: Symbol = Symbol("seed_with");
 pub const SERDE: Symbol = Symbol("serde");
 pub const SERIALIZE: Symbol = Symbol("serialize");
+pub const SERIALIZE_SEED_WITH: Symbol = Symbol("serialize_seed_with");
 pub const SERIALIZE_WITH: Symbol = Symbol("serialize_with");
 pub const SKIP: Symbol = Symbol("skip");
 pub const SKIP_DESERIALIZING: Symbol = Symbol("skip_deserializing");
+pub const SKIP_NONE: Symbol = Symbol("skip_none");
 pub const SKIP_SERIALIZING: Symbol = Symbol("skip_serializing");
+pub const SKIP_SERIALIZING_DEFAULT: Symbol = Symbol("skip_serializing_default");
 pub const SKIP_SERIALIZING_IF: Symbol = Symbol("skip_serializing_if");
+pub const SORT_FIELDS: Symbol = Symbol("sort_fields");
 pub const TAG: Symbol = Symbol("tag");
+pub const TAG_ALIAS: Symbol = Symbol("tag_alias");
+pub const TAG_DESERIALIZE_WITH: Symbol = Symbol("tag_deserialize_with");
+pub const TAG_REPR: Symbol = Symbol("tag_repr");
 pub const TRANSPARENT: Symbol = Symbol("transparent");
 pub const TRY_FROM: Symbol = Symbol("try_from");
+pub const TRY_INTO: Symbol = Symbol("try_into");
 pub const UNTAGGED: Symbol = Symbol("untagged");
+pub const UPGRADE_FROM: Symbol = Symbol("upgrade_from");
+pub const VALIDATE: Symbol = Symbol("validate");
 pub const VARIANT_IDENTIFIER: Symbol = Symbol("variant_identifier");
+pub const VERSION: Symbol = Symbol("version");
 pub const WITH: Symbol = Symbol("with");
 
 impl PartialEq<Symbol> for Ident {
@@ -1,4 +1,4 @@
-use crate::fragment::{Fragment, Match, Stmts};
+use crate::fragment::{Expr, Fragment, Match, Stmts};
 use crate::internals::ast::{Container, Data, Field, Style, Variant};
 use crate::internals::{attr, replace_receiver, Ctxt, Derive};
 use crate::{bound, dummy, pretend, this};
@@ -19,12 +19,34 @@ pub fn expand_derive_serialize(input: &mut syn::DeriveInput) -> syn::Result<Toke
     ctxt.check()?;
 
     let ident = &cont.ident;
-    let params = Parameters::new(&cont);
+    let mut params = Parameters::new(&cont);
+    if cont.attrs.version().is_some() {
+        // The unversioned body is emitted inside a wrapper struct's impl
+        // where the value is reachable as `__self` rather than `self`.
+        params.self_var = Ident::new("__self", Span::call_site());
+    }
     let (impl_generics, ty_generics, where_clause) = params.generics.split_for_impl();
     let body = Stmts(serialize_body(&cont, &params));
     let serde = cont.attrs.serde_path();
 
-    let impl_block = if let Some(remote) = cont.attrs.remote() {
+    let impl_block = if let Some(seed_ty) = cont.attrs.seed() {
+        // #[serde(seed = "State")] generates a SerializeSeed impl instead of
+        // a Serialize impl, making the state visible to serialize_seed_with
+        // fields as `__seed`.
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #serde::ser::SerializeSeed for #ident #ty_generics #where_clause {
+                type Seed = #seed_ty;
+
+                fn serialize_seed<__S>(&self, __serializer: __S, __seed: &Self::Seed) -> #serde::__private::Result<__S::Ok, __S::Error>
+                where
+                    __S: #serde::Serializer,
+                {
+                    #body
+                }
+            }
+        }
+    } else if let Some(remote) = cont.attrs.remote() {
         let vis = &input.vis;
         let used = pretend::pretend_used(&cont, params.is_packed);
         quote! {
@@ -38,6 +60,37 @@ pub fn expand_derive_serialize(input: &mut syn::DeriveInput) -> syn::Result<Toke
                 }
             }
         }
+    } else if let Some(version) = cont.attrs.version() {
+        // #[serde(version = N)] wraps the normal encoding in a two-field
+        // `Versioned` struct carrying the version number, which deserialize
+        // dispatches on to run migrations. Preconditions reject generic
+        // containers, so the wrapper struct needs no generics.
+        quote! {
+            #[automatically_derived]
+            impl #serde::Serialize for #ident {
+                fn serialize<__S>(&self, __serializer: __S) -> #serde::__private::Result<__S::Ok, __S::Error>
+                where
+                    __S: #serde::Serializer,
+                {
+                    struct __Unversioned<'__a>(&'__a #ident);
+
+                    impl<'__a> #serde::Serialize for __Unversioned<'__a> {
+                        fn serialize<__S>(&self, __serializer: __S) -> #serde::__private::Result<__S::Ok, __S::Error>
+                        where
+                            __S: #serde::Serializer,
+                        {
+                            let __self = self.0;
+                            #body
+                        }
+                    }
+
+                    let mut __serde_state = #serde::Serializer::serialize_struct(__serializer, "Versioned", 2)?;
+                    #serde::ser::SerializeStruct::serialize_field(&mut __serde_state, "version", &#version)?;
+                    #serde::ser::SerializeStruct::serialize_field(&mut __serde_state, "value", &__Unversioned(self))?;
+                    #serde::ser::SerializeStruct::end(__serde_state)
+                }
+            }
+        }
     } else {
         quote! {
             #[automatically_derived]
@@ -52,13 +105,107 @@ pub fn expand_derive_serialize(input: &mut syn::DeriveInput) -> syn::Result<Toke
         }
     };
 
+    let metadata_impl = metadata_impl(&cont, &serde);
+
     Ok(dummy::wrap_in_const(
         cont.attrs.custom_serde_path(),
-        impl_block,
+        quote! {
+            #impl_block
+            #metadata_impl
+        },
     ))
 }
 
+/// Generates an implementation of `serde::meta::Metadata` when at least one
+/// `#[serde(meta(...))]` attribute is present. Emitted from the Serialize
+/// derive only, so that deriving both traits does not produce two impls.
+fn metadata_impl(cont: &Container, serde: &syn::Path) -> Option<TokenStream> {
+    if cont.attrs.remote().is_some() {
+        return None;
+    }
+
+    let container_meta = cont.attrs.meta();
+    let mut field_meta: Vec<(String, &[(String, String)])> = Vec::new();
+    let mut variant_meta: Vec<(String, &[(String, String)])> = Vec::new();
+    match &cont.data {
+        Data::Struct(_, fields) => {
+            for field in fields {
+                if !field.attrs.meta().is_empty() {
+                    field_meta.push((
+                        field.attrs.name().serialize_name().to_owned(),
+                        field.attrs.meta(),
+                    ));
+                }
+            }
+        }
+        Data::Enum(variants) => {
+            for variant in variants {
+                if !variant.attrs.meta().is_empty() {
+                    variant_meta.push((
+                        variant.attrs.name().serialize_name().to_owned(),
+                        variant.attrs.meta(),
+                    ));
+                }
+                for field in &variant.fields {
+                    if !field.attrs.meta().is_empty() {
+                        field_meta.push((
+                            field.attrs.name().serialize_name().to_owned(),
+                            field.attrs.meta(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if container_meta.is_empty() && field_meta.is_empty() && variant_meta.is_empty() {
+        return None;
+    }
+
+    let ident = &cont.ident;
+    let (impl_generics, ty_generics, where_clause) = cont.generics.split_for_impl();
+
+    let pairs = |meta: &[(String, String)]| {
+        let pairs = meta.iter().map(|(key, value)| quote!((#key, #value)));
+        quote!(&[#(#pairs),*])
+    };
+
+    let container_body = pairs(container_meta);
+    let field_arms = field_meta.iter().map(|(name, meta)| {
+        let entries = pairs(meta);
+        quote!(#name => #entries,)
+    });
+    let variant_arms = variant_meta.iter().map(|(name, meta)| {
+        let entries = pairs(meta);
+        quote!(#name => #entries,)
+    });
+
+    Some(quote! {
+        #[automatically_derived]
+        impl #impl_generics #serde::meta::Metadata for #ident #ty_generics #where_clause {
+            fn container_metadata() -> &'static [(&'static str, &'static str)] {
+                #container_body
+            }
+
+            fn field_metadata(__field: &str) -> &'static [(&'static str, &'static str)] {
+                match __field {
+                    #(#field_arms)*
+                    _ => &[],
+                }
+            }
+
+            fn variant_metadata(__variant: &str) -> &'static [(&'static str, &'static str)] {
+                match __variant {
+                    #(#variant_arms)*
+                    _ => &[],
+                }
+            }
+        }
+    })
+}
+
 fn precondition(cx: &Ctxt, cont: &Container) {
+    precondition_version(cx, cont);
     match cont.attrs.identifier() {
         attr::Identifier::No => {}
         attr::Identifier::Field => {
@@ -70,6 +217,28 @@ fn precondition(cx: &Ctxt, cont: &Container) {
     }
 }
 
+// Restrictions shared by the Serialize and Deserialize expansions for
+// containers using `#[serde(version = N)]`. The versioned wrapper types are
+// generated without generics, and the wrapper encoding cannot be combined
+// with attributes that replace the whole impl.
+pub(crate) fn precondition_version(cx: &Ctxt, cont: &Container) {
+    if cont.attrs.version().is_none() {
+        return;
+    }
+    if !cont.generics.params.is_empty() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(version = ...)] cannot be used on generic types",
+        );
+    }
+    if cont.attrs.remote().is_some() || cont.attrs.transparent() || cont.attrs.seed().is_some() {
+        cx.error_spanned_by(
+            cont.original,
+            "#[serde(version = ...)] cannot be combined with remote, transparent, or seed",
+        );
+    }
+}
+
 struct Parameters {
     /// Variable holding the value being serialized. Either `self` for local
     /// types or `__self` for remote types.
@@ -168,6 +337,8 @@ fn serialize_body(cont: &Container, params: &Parameters) -> Fragment {
         serialize_transparent(cont, params)
     } else if let Some(type_into) = cont.attrs.type_into() {
         serialize_into(params, type_into)
+    } else if let Some(type_try_into) = cont.attrs.type_try_into() {
+        serialize_try_into(params, type_try_into)
     } else {
         match &cont.data {
             Data::Enum(variants) => serialize_enum(params, variants, &cont.attrs),
@@ -201,8 +372,16 @@ fn serialize_transparent(cont: &Container, params: &Parameters) -> Fragment {
         }
     };
 
+    // Copy the field out of a packed struct before taking a reference to it,
+    // the same as get_member does, to avoid an unaligned reference.
+    let field = if params.is_packed {
+        quote!(&{#self_var.#member})
+    } else {
+        quote!(&#self_var.#member)
+    };
+
     quote_block! {
-        #path(&#self_var.#member, __serializer)
+        #path(#field, __serializer)
     }
 }
 
@@ -215,8 +394,18 @@ fn serialize_into(params: &Parameters, type_into: &syn::Type) -> Fragment {
     }
 }
 
+fn serialize_try_into(params: &Parameters, type_try_into: &syn::Type) -> Fragment {
+    let self_var = &params.self_var;
+    quote_block! {
+        match _serde::__private::TryInto::<#type_try_into>::try_into(_serde::__private::Clone::clone(#self_var)) {
+            _serde::__private::Ok(__value) => _serde::Serialize::serialize(&__value, __serializer),
+            _serde::__private::Err(__err) => _serde::__private::Err(_serde::ser::Error::custom(__err)),
+        }
+    }
+}
+
 fn serialize_unit_struct(cattrs: &attr::Container) -> Fragment {
-    let type_name = cattrs.name().serialize_name();
+    let type_name = cattrs.name().serialize_name_expr();
 
     quote_expr! {
         _serde::Serializer::serialize_unit_struct(__serializer, #type_name)
@@ -228,7 +417,7 @@ fn serialize_newtype_struct(
     field: &Field,
     cattrs: &attr::Container,
 ) -> Fragment {
-    let type_name = cattrs.name().serialize_name();
+    let type_name = cattrs.name().serialize_name_expr();
 
     let mut field_expr = get_member(
         params,
@@ -257,7 +446,7 @@ fn serialize_tuple_struct(
     let serialize_stmts =
         serialize_tuple_struct_visitor(fields, params, false, &TupleTrait::SerializeTupleStruct);
 
-    let type_name = cattrs.name().serialize_name();
+    let type_name = cattrs.name().serialize_name_expr();
 
     let mut serialized_fields = fields
         .iter()
@@ -301,7 +490,7 @@ fn serialize_struct(params: &Parameters, fields: &[Field], cattrs: &attr::Contai
 fn serialize_struct_tag_field(cattrs: &attr::Container, struct_trait: &StructTrait) -> TokenStream {
     match cattrs.tag() {
         attr::TagType::Internal { tag } => {
-            let type_name = cattrs.name().serialize_name();
+            let type_name = cattrs.name().serialize_name_expr();
             let func = struct_trait.serialize_field(Span::call_site());
             quote! {
                 #func(&mut __serde_state, #tag, #type_name)?;
@@ -317,9 +506,9 @@ fn serialize_struct_as_struct(
     cattrs: &attr::Container,
 ) -> Fragment {
     let serialize_fields =
-        serialize_struct_visitor(fields, params, false, &StructTrait::SerializeStruct);
+        serialize_struct_visitor(fields, params, false, cattrs, &StructTrait::SerializeStruct);
 
-    let type_name = cattrs.name().serialize_name();
+    let type_name = cattrs.name().serialize_name_expr();
 
     let tag_field = serialize_struct_tag_field(cattrs, &StructTrait::SerializeStruct);
     let tag_field_exists = !tag_field.is_empty();
@@ -332,11 +521,11 @@ fn serialize_struct_as_struct(
     let let_mut = mut_if(serialized_fields.peek().is_some() || tag_field_exists);
 
     let len = serialized_fields
-        .map(|field| match field.attrs.skip_serializing_if() {
-            None => quote!(1),
-            Some(path) => {
-                let field_expr = get_member(params, field, &field.member);
-                quote!(if #path(#field_expr) { 0 } else { 1 })
+        .map(|field| {
+            let field_expr = get_member(params, field, &field.member);
+            match field_skip_expr(field, cattrs, &field_expr) {
+                None => quote!(1),
+                Some(skip) => quote!(if #skip { 0 } else { 1 }),
             }
         })
         .fold(
@@ -358,7 +547,7 @@ fn serialize_struct_as_map(
     cattrs: &attr::Container,
 ) -> Fragment {
     let serialize_fields =
-        serialize_struct_visitor(fields, params, false, &StructTrait::SerializeMap);
+        serialize_struct_visitor(fields, params, false, cattrs, &StructTrait::SerializeMap);
 
     let tag_field = serialize_struct_tag_field(cattrs, &StructTrait::SerializeMap);
     let tag_field_exists = !tag_field.is_empty();
@@ -374,11 +563,11 @@ fn serialize_struct_as_map(
         quote!(_serde::__private::None)
     } else {
         let len = serialized_fields
-            .map(|field| match field.attrs.skip_serializing_if() {
-                None => quote!(1),
-                Some(path) => {
-                    let field_expr = get_member(params, field, &field.member);
-                    quote!(if #path(#field_expr) { 0 } else { 1 })
+            .map(|field| {
+                let field_expr = get_member(params, field, &field.member);
+                match field_skip_expr(field, cattrs, &field_expr) {
+                    None => quote!(1),
+                    Some(skip) => quote!(if #skip { 0 } else { 1 }),
                 }
             })
             .fold(
@@ -476,12 +665,12 @@ fn serialize_variant(
             }
         };
 
-        let body = Match(match (cattrs.tag(), variant.attrs.untagged()) {
+        let fragment = match (cattrs.tag(), variant.attrs.untagged()) {
             (attr::TagType::External, false) => {
                 serialize_externally_tagged_variant(params, variant, variant_index, cattrs)
             }
             (attr::TagType::Internal { tag }, false) => {
-                serialize_internally_tagged_variant(params, variant, cattrs, tag)
+                serialize_internally_tagged_variant(params, variant, cattrs, variant_index, tag)
             }
             (attr::TagType::Adjacent { tag, content }, false) => {
                 serialize_adjacently_tagged_variant(
@@ -496,7 +685,31 @@ fn serialize_variant(
             (attr::TagType::None, _) | (_, true) => {
                 serialize_untagged_variant(params, variant, cattrs)
             }
-        });
+        };
+
+        // The untagged and internally tagged representations cannot be
+        // deserialized from a format that does not describe its own
+        // structure. With `index_fallback` such formats get the externally
+        // tagged representation instead, which identifies the variant by
+        // index.
+        let body = if cattrs.index_fallback() {
+            let self_describing = Expr(fragment);
+            let external = Expr(serialize_externally_tagged_variant(
+                params,
+                variant,
+                variant_index,
+                cattrs,
+            ));
+            Match(Fragment::Block(quote! {
+                if _serde::Serializer::is_self_describing(&__serializer) {
+                    #self_describing
+                } else {
+                    #external
+                }
+            }))
+        } else {
+            Match(fragment)
+        };
 
         quote! {
             #case => #body
@@ -510,8 +723,8 @@ fn serialize_externally_tagged_variant(
     variant_index: u32,
     cattrs: &attr::Container,
 ) -> Fragment {
-    let type_name = cattrs.name().serialize_name();
-    let variant_name = variant.attrs.name().serialize_name();
+    let type_name = cattrs.name().serialize_name_expr();
+    let variant_name = variant.attrs.name().serialize_name_expr();
 
     if let Some(path) = variant.attrs.serialize_with() {
         let ser = wrap_serialize_variant_with(params, path, variant);
@@ -572,7 +785,8 @@ fn serialize_externally_tagged_variant(
             },
             params,
             &variant.fields,
-            type_name,
+            cattrs,
+            &type_name,
         ),
     }
 }
@@ -581,10 +795,18 @@ fn serialize_internally_tagged_variant(
     params: &Parameters,
     variant: &Variant,
     cattrs: &attr::Container,
+    variant_index: u32,
     tag: &str,
 ) -> Fragment {
-    let type_name = cattrs.name().serialize_name();
-    let variant_name = variant.attrs.name().serialize_name();
+    let type_name = cattrs.name().serialize_name_expr();
+    let variant_name = variant.attrs.name().serialize_name_expr();
+
+    // With `tag_repr` the tag value is the variant index cast to the requested
+    // integer type instead of the variant name.
+    let tag_value = match cattrs.tag_repr() {
+        Some(repr) => quote!(#variant_index as #repr),
+        None => variant_name,
+    };
 
     let enum_ident_str = params.type_name();
     let variant_ident_str = variant.ident.to_string();
@@ -597,19 +819,31 @@ fn serialize_internally_tagged_variant(
                 #enum_ident_str,
                 #variant_ident_str,
                 #tag,
-                #variant_name,
+                #tag_value,
                 #ser,
             )
         };
     }
 
+    // A newtype `other` variant holds a tag captured during deserialization;
+    // re-emit that tag instead of the variant name.
+    if variant.attrs.other() && matches!(effective_style(variant), Style::Newtype) {
+        return quote_block! {
+            let mut __struct = _serde::Serializer::serialize_struct(
+                __serializer, #type_name, 1)?;
+            _serde::ser::SerializeStruct::serialize_field(
+                &mut __struct, #tag, __field0)?;
+            _serde::ser::SerializeStruct::end(__struct)
+        };
+    }
+
     match effective_style(variant) {
         Style::Unit => {
             quote_block! {
                 let mut __struct = _serde::Serializer::serialize_struct(
                     __serializer, #type_name, 1)?;
                 _serde::ser::SerializeStruct::serialize_field(
-                    &mut __struct, #tag, #variant_name)?;
+                    &mut __struct, #tag, &(#tag_value))?;
                 _serde::ser::SerializeStruct::end(__struct)
             }
         }
@@ -628,18 +862,51 @@ fn serialize_internally_tagged_variant(
                     #enum_ident_str,
                     #variant_ident_str,
                     #tag,
-                    #variant_name,
+                    #tag_value,
                     #field_expr,
                 )
             }
         }
         Style::Struct => serialize_struct_variant(
-            StructVariant::InternallyTagged { tag, variant_name },
+            StructVariant::InternallyTagged {
+                tag,
+                tag_value,
+            },
             params,
             &variant.fields,
-            type_name,
+            cattrs,
+            &type_name,
         ),
-        Style::Tuple => unreachable!("checked in serde_derive_internals"),
+        Style::Tuple => {
+            // Tuple variants have no field names to merge the tag into, so
+            // fall back to an adjacent layout with the tuple under the
+            // conventional "content" key.
+            let field_exprs = variant
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| !field.attrs.skip_serializing())
+                .map(|(i, field)| {
+                    let mut field_expr = {
+                        let id = Ident::new(&format!("__field{}", i), Span::call_site());
+                        quote!(#id)
+                    };
+                    if let Some(path) = field.attrs.serialize_with() {
+                        field_expr =
+                            wrap_serialize_field_with(params, field.ty, path, &field_expr);
+                    }
+                    field_expr
+                });
+            quote_block! {
+                let mut __struct = _serde::Serializer::serialize_struct(
+                    __serializer, #type_name, 2)?;
+                _serde::ser::SerializeStruct::serialize_field(
+                    &mut __struct, #tag, &(#tag_value))?;
+                _serde::ser::SerializeStruct::serialize_field(
+                    &mut __struct, "content", &(#(#field_exprs,)*))?;
+                _serde::ser::SerializeStruct::end(__struct)
+            }
+        }
     }
 }
 
@@ -652,14 +919,29 @@ fn serialize_adjacently_tagged_variant(
     content: &str,
 ) -> Fragment {
     let this_type = &params.this_type;
-    let type_name = cattrs.name().serialize_name();
-    let variant_name = variant.attrs.name().serialize_name();
-    let serialize_variant = quote! {
-        &_serde::__private::ser::AdjacentlyTaggedEnumVariant {
-            enum_name: #type_name,
-            variant_index: #variant_index,
-            variant_name: #variant_name,
-        }
+    let type_name = cattrs.name().serialize_name_expr();
+    let variant_name = variant.attrs.name().serialize_name_expr();
+    // A newtype `other` variant holds a tag captured during deserialization;
+    // re-emit that tag instead of the variant name.
+    if variant.attrs.other() && matches!(effective_style(variant), Style::Newtype) {
+        return quote_block! {
+            let mut __struct = _serde::Serializer::serialize_struct(
+                __serializer, #type_name, 1)?;
+            _serde::ser::SerializeStruct::serialize_field(
+                &mut __struct, #tag, __field0)?;
+            _serde::ser::SerializeStruct::end(__struct)
+        };
+    }
+
+    let serialize_variant = match cattrs.tag_repr() {
+        Some(repr) => quote!(&(#variant_index as #repr)),
+        None => quote! {
+            &_serde::__private::ser::AdjacentlyTaggedEnumVariant {
+                enum_name: #type_name,
+                variant_index: #variant_index,
+                variant_name: #variant_name,
+            }
+        },
     };
 
     let inner = Stmts(if let Some(path) = variant.attrs.serialize_with() {
@@ -704,7 +986,8 @@ fn serialize_adjacently_tagged_variant(
                 StructVariant::Untagged,
                 params,
                 &variant.fields,
-                variant_name,
+                cattrs,
+                &variant_name,
             ),
         }
     });
@@ -799,17 +1082,23 @@ fn serialize_untagged_variant(
         }
         Style::Tuple => serialize_tuple_variant(TupleVariant::Untagged, params, &variant.fields),
         Style::Struct => {
-            let type_name = cattrs.name().serialize_name();
-            serialize_struct_variant(StructVariant::Untagged, params, &variant.fields, type_name)
+            let type_name = cattrs.name().serialize_name_expr();
+            serialize_struct_variant(
+                StructVariant::Untagged,
+                params,
+                &variant.fields,
+                cattrs,
+                &type_name,
+            )
         }
     }
 }
 
-enum TupleVariant<'a> {
+enum TupleVariant {
     ExternallyTagged {
-        type_name: &'a str,
+        type_name: TokenStream,
         variant_index: u32,
-        variant_name: &'a str,
+        variant_name: TokenStream,
     },
     Untagged,
 }
@@ -876,11 +1165,11 @@ fn serialize_tuple_variant(
 enum StructVariant<'a> {
     ExternallyTagged {
         variant_index: u32,
-        variant_name: &'a str,
+        variant_name: TokenStream,
     },
     InternallyTagged {
         tag: &'a str,
-        variant_name: &'a str,
+        tag_value: TokenStream,
     },
     Untagged,
 }
@@ -889,10 +1178,14 @@ fn serialize_struct_variant(
     context: StructVariant,
     params: &Parameters,
     fields: &[Field],
-    name: &str,
+    cattrs: &attr::Container,
+    name: &TokenStream,
 ) -> Fragment {
-    if fields.iter().any(|field| field.attrs.flatten()) {
-        return serialize_struct_variant_with_flatten(context, params, fields, name);
+    if fields
+        .iter()
+        .any(|field| field.attrs.flatten() || field.attrs.collect_unknown())
+    {
+        return serialize_struct_variant_with_flatten(context, params, fields, cattrs, name);
     }
 
     let struct_trait = match context {
@@ -902,7 +1195,7 @@ fn serialize_struct_variant(
         }
     };
 
-    let serialize_fields = serialize_struct_visitor(fields, params, true, &struct_trait);
+    let serialize_fields = serialize_struct_visitor(fields, params, true, cattrs, &struct_trait);
 
     let mut serialized_fields = fields
         .iter()
@@ -939,7 +1232,7 @@ fn serialize_struct_variant(
                 _serde::ser::SerializeStructVariant::end(__serde_state)
             }
         }
-        StructVariant::InternallyTagged { tag, variant_name } => {
+        StructVariant::InternallyTagged { tag, tag_value } => {
             quote_block! {
                 let mut __serde_state = _serde::Serializer::serialize_struct(
                     __serializer,
@@ -949,7 +1242,7 @@ fn serialize_struct_variant(
                 _serde::ser::SerializeStruct::serialize_field(
                     &mut __serde_state,
                     #tag,
-                    #variant_name,
+                    &(#tag_value),
                 )?;
                 #(#serialize_fields)*
                 _serde::ser::SerializeStruct::end(__serde_state)
@@ -973,10 +1266,11 @@ fn serialize_struct_variant_with_flatten(
     context: StructVariant,
     params: &Parameters,
     fields: &[Field],
-    name: &str,
+    cattrs: &attr::Container,
+    name: &TokenStream,
 ) -> Fragment {
     let struct_trait = StructTrait::SerializeMap;
-    let serialize_fields = serialize_struct_visitor(fields, params, true, &struct_trait);
+    let serialize_fields = serialize_struct_visitor(fields, params, true, cattrs, &struct_trait);
 
     let mut serialized_fields = fields
         .iter()
@@ -1030,7 +1324,7 @@ fn serialize_struct_variant_with_flatten(
                     })
             }
         }
-        StructVariant::InternallyTagged { tag, variant_name } => {
+        StructVariant::InternallyTagged { tag, tag_value } => {
             quote_block! {
                 let #let_mut __serde_state = _serde::Serializer::serialize_map(
                     __serializer,
@@ -1038,7 +1332,7 @@ fn serialize_struct_variant_with_flatten(
                 _serde::ser::SerializeMap::serialize_entry(
                     &mut __serde_state,
                     #tag,
-                    #variant_name,
+                    &(#tag_value),
                 )?;
                 #(#serialize_fields)*
                 _serde::ser::SerializeMap::end(__serde_state)
@@ -1104,10 +1398,43 @@ fn serialize_tuple_struct_visitor(
         .collect()
 }
 
+// The expression deciding whether a field is skipped during serialization:
+// either the field's own `skip_serializing_if` predicate, or, under
+// container-level `skip_none` / `skip_serializing_default`, a `None` check on
+// `Option` fields or a comparison against the field type's `Default` value.
+// Flattened fields do not map to a single key and are left out of the
+// container-level rules.
+fn field_skip_expr(
+    field: &Field,
+    cattrs: &attr::Container,
+    field_expr: &TokenStream,
+) -> Option<TokenStream> {
+    if let Some(path) = field.attrs.skip_serializing_if() {
+        Some(quote!(#path(#field_expr)))
+    } else if cattrs.skip_none()
+        && attr::is_option_type(field.ty)
+        && !field.attrs.flatten()
+        && !field.attrs.collect_unknown()
+    {
+        Some(quote!(_serde::__private::Option::is_none(#field_expr)))
+    } else if cattrs.skip_serializing_default()
+        && !field.attrs.flatten()
+        && !field.attrs.collect_unknown()
+    {
+        let field_ty = field.ty;
+        Some(quote! {
+            (#field_expr == &<#field_ty as _serde::__private::Default>::default())
+        })
+    } else {
+        None
+    }
+}
+
 fn serialize_struct_visitor(
     fields: &[Field],
     params: &Parameters,
     is_enum: bool,
+    cattrs: &attr::Container,
     struct_trait: &StructTrait,
 ) -> Vec<TokenStream> {
     fields
@@ -1122,19 +1449,28 @@ fn serialize_struct_visitor(
                 get_member(params, field, member)
             };
 
-            let key_expr = field.attrs.name().serialize_name();
+            let key_expr = field.attrs.name().serialize_name_expr();
 
-            let skip = field
-                .attrs
-                .skip_serializing_if()
-                .map(|path| quote!(#path(#field_expr)));
+            let skip = field_skip_expr(field, cattrs, &field_expr);
 
-            if let Some(path) = field.attrs.serialize_with() {
+            if let (Some(path), Some(seed_ty)) = (field.attrs.serialize_seed_with(), cattrs.seed())
+            {
+                field_expr = wrap_serialize_seed_with(params, field.ty, seed_ty, path, &field_expr);
+            } else if let Some(path) = field.attrs.serialize_with() {
                 field_expr = wrap_serialize_field_with(params, field.ty, path, &field_expr);
+            } else if let Some(path) = field.attrs.key_with() {
+                field_expr = wrap_serialize_map_key_with(params, field.ty, path, &field_expr);
+            } else if let Some(keyed_by) = field.attrs.keyed_by() {
+                field_expr = quote! {
+                    &_serde::__private::ser::KeyedBy {
+                        key: #keyed_by,
+                        collection: #field_expr,
+                    }
+                };
             }
 
             let span = field.original.span();
-            let ser = if field.attrs.flatten() {
+            let ser = if field.attrs.flatten() || field.attrs.collect_unknown() {
                 let func = quote_spanned!(span=> _serde::Serialize::serialize);
                 quote! {
                     #func(&#field_expr, _serde::__private::ser::FlatMapSerializer(&mut __serde_state))?;
@@ -1179,6 +1515,45 @@ fn wrap_serialize_field_with(
     wrap_serialize_with(params, serialize_with, &[field_ty], &[quote!(#field_expr)])
 }
 
+// Wraps a field behind a Serialize impl deferring to the field's
+// #[serde(serialize_seed_with = "...")] function with a borrow of the
+// container's #[serde(seed)] state. Expects a `__seed` binding holding a
+// reference to the state to be in scope.
+fn wrap_serialize_seed_with(
+    params: &Parameters,
+    field_ty: &syn::Type,
+    seed_ty: &syn::Type,
+    serialize_seed_with: &syn::ExprPath,
+    field_expr: &TokenStream,
+) -> TokenStream {
+    let this_type = &params.this_type;
+    let (_, ty_generics, _) = params.generics.split_for_impl();
+
+    quote!({
+        #[doc(hidden)]
+        struct __SerializeSeedWith<'__a> {
+            value: &'__a #field_ty,
+            seed: &'__a #seed_ty,
+            phantom: _serde::__private::PhantomData<#this_type #ty_generics>,
+        }
+
+        impl<'__a> _serde::Serialize for __SerializeSeedWith<'__a> {
+            fn serialize<__S>(&self, __s: __S) -> _serde::__private::Result<__S::Ok, __S::Error>
+            where
+                __S: _serde::Serializer,
+            {
+                #serialize_seed_with(self.seed, self.value, __s)
+            }
+        }
+
+        &__SerializeSeedWith {
+            value: #field_expr,
+            seed: __seed,
+            phantom: _serde::__private::PhantomData::<#this_type #ty_generics>,
+        }
+    })
+}
+
 fn wrap_serialize_variant_with(
     params: &Parameters,
     serialize_with: &syn::ExprPath,
@@ -1252,6 +1627,55 @@ fn wrap_serialize_with(
     })
 }
 
+// Serializes a map-typed field whose keys pass through the `serialize`
+// function of a #[serde(key_with = "...")] module. Each key is serialized
+// eagerly into a Content tree so that the module function can be applied with
+// the key's concrete type, then the converted entries are fed to collect_map.
+fn wrap_serialize_map_key_with(
+    params: &Parameters,
+    field_ty: &syn::Type,
+    key_with: &syn::ExprPath,
+    field_expr: &TokenStream,
+) -> TokenStream {
+    let this_type = &params.this_type;
+    let (_, ty_generics, where_clause) = params.generics.split_for_impl();
+
+    let wrapper_generics = bound::with_lifetime_bound(&params.generics, "'__a");
+    let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
+
+    quote!({
+        #[doc(hidden)]
+        struct __SerializeKeyWith #wrapper_impl_generics #where_clause {
+            values: (&'__a #field_ty,),
+            phantom: _serde::__private::PhantomData<#this_type #ty_generics>,
+        }
+
+        impl #wrapper_impl_generics _serde::Serialize for __SerializeKeyWith #wrapper_ty_generics #where_clause {
+            fn serialize<__S>(&self, __s: __S) -> _serde::__private::Result<__S::Ok, __S::Error>
+            where
+                __S: _serde::Serializer,
+            {
+                let mut __entries = _serde::__private::Vec::new();
+                for (__key, __value) in self.values.0 {
+                    __entries.push((
+                        #key_with::serialize(
+                            __key,
+                            _serde::__private::ser::ContentSerializer::<__S::Error>::new(),
+                        )?,
+                        __value,
+                    ));
+                }
+                _serde::Serializer::collect_map(__s, __entries)
+            }
+        }
+
+        &__SerializeKeyWith {
+            values: (#field_expr,),
+            phantom: _serde::__private::PhantomData::<#this_type #ty_generics>,
+        }
+    })
+}
+
 // Serialization of an empty struct results in code like:
 //
 //     let mut __serde_state = serializer.serialize_struct("S", 0)?;
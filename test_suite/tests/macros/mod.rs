@@ -1,6 +1,9 @@
-#![allow(unused_macro_rules)]
+#![allow(dead_code, unused_macro_rules)]
 
+use serde::de::{self, DeserializeOwned, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize};
 use serde_test::Token;
+use std::fmt::{self, Debug, Display};
 use std::iter;
 
 macro_rules! btreeset {
@@ -77,3 +80,729 @@ macro_rules! seq {
         vec
     }};
 }
+
+/// Asserts that `value` serializes to `head`, followed by the `groups` in any
+/// order, followed by `tail`. This makes it possible to write serialization
+/// assertions for `HashMap` and `HashSet`, whose iteration order is
+/// nondeterministic.
+#[track_caller]
+pub fn assert_ser_tokens_unordered<T>(value: &T, head: &[Token], groups: &[&[Token]], tail: &[Token])
+where
+    T: Serialize,
+{
+    let mut recorder = TokenRecorder { tokens: Vec::new() };
+    value
+        .serialize(&mut recorder)
+        .unwrap_or_else(|err| panic!("value failed to serialize: {}", err));
+    let actual = recorder.tokens;
+
+    let middle_matches = |middle: &[Token]| {
+        fn consume(middle: &[Token], groups: &[&[Token]], used: &mut [bool]) -> bool {
+            if middle.is_empty() {
+                return used.iter().all(|&used| used);
+            }
+            for (i, group) in groups.iter().enumerate() {
+                if !used[i] && middle.starts_with(group) {
+                    used[i] = true;
+                    if consume(&middle[group.len()..], groups, used) {
+                        return true;
+                    }
+                    used[i] = false;
+                }
+            }
+            false
+        }
+        consume(middle, groups, &mut vec![false; groups.len()])
+    };
+
+    let expected_len = head.len() + groups.iter().map(|group| group.len()).sum::<usize>() + tail.len();
+    let matched = actual.len() == expected_len
+        && actual.starts_with(head)
+        && actual.ends_with(tail)
+        && middle_matches(&actual[head.len()..actual.len() - tail.len()]);
+    assert!(
+        matched,
+        "tokens do not match any permutation of the expected groups:\n actual: {:?}",
+        actual,
+    );
+}
+
+/// Serialize→tokens→Deserialize roundtrip derived from the value itself.
+/// The token stream is recorded from the Serialize impl rather than written
+/// out by hand, so the assertion holds for whatever shape the value takes —
+/// suitable for driving from property-based generators.
+#[track_caller]
+pub fn roundtrip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let mut recorder = TokenRecorder { tokens: Vec::new() };
+    value
+        .serialize(&mut recorder)
+        .unwrap_or_else(|err| panic!("value failed to serialize: {}", err));
+    let tokens: &'static [Token] = Box::leak(recorder.tokens.into_boxed_slice());
+    serde_test::assert_de_tokens(value, tokens);
+}
+
+/// Runs [`roundtrip`] over every value an iterator yields, e.g. the output
+/// of a quickcheck/proptest-style generator.
+#[track_caller]
+pub fn roundtrip_values<T, I>(values: I)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+    I: IntoIterator<Item = T>,
+{
+    for value in values {
+        roundtrip(&value);
+    }
+}
+
+/// Like `serde_test::assert_ser_tokens`, but on mismatch panics with an
+/// aligned side-by-side diff of the expected and actual token streams,
+/// indexed and indented by nesting depth.
+#[track_caller]
+pub fn assert_ser_tokens_diff<T>(value: &T, expected: &[Token])
+where
+    T: Serialize,
+{
+    let mut recorder = TokenRecorder { tokens: Vec::new() };
+    value
+        .serialize(&mut recorder)
+        .unwrap_or_else(|err| panic!("value failed to serialize: {}", err));
+    let actual = recorder.tokens;
+    if actual != expected {
+        panic!("tokens do not match:\n{}", token_diff(expected, &actual));
+    }
+}
+
+fn opens_nesting(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Seq { .. }
+            | Token::Tuple { .. }
+            | Token::TupleStruct { .. }
+            | Token::TupleVariant { .. }
+            | Token::Map { .. }
+            | Token::Struct { .. }
+            | Token::StructVariant { .. }
+    )
+}
+
+fn closes_nesting(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::SeqEnd
+            | Token::TupleEnd
+            | Token::TupleStructEnd
+            | Token::TupleVariantEnd
+            | Token::MapEnd
+            | Token::StructEnd
+            | Token::StructVariantEnd
+    )
+}
+
+/// Renders expected vs. actual token streams side by side, one token per
+/// line, marking the lines that differ.
+pub fn token_diff(expected: &[Token], actual: &[Token]) -> String {
+    let render = |tokens: &[Token]| {
+        let mut depth = 0usize;
+        tokens
+            .iter()
+            .map(|token| {
+                if closes_nesting(token) {
+                    depth = depth.saturating_sub(1);
+                }
+                let line = format!("{}{:?}", "    ".repeat(depth), token);
+                if opens_nesting(token) {
+                    depth += 1;
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+    };
+    let expected_lines = render(expected);
+    let actual_lines = render(actual);
+    let width = iter::once("expected".len())
+        .chain(expected_lines.iter().map(String::len))
+        .max()
+        .unwrap();
+
+    let mut diff = format!("     {:<width$}   actual\n", "expected", width = width);
+    for i in 0..Ord::max(expected_lines.len(), actual_lines.len()) {
+        let expected_line = expected_lines.get(i).map_or("", String::as_str);
+        let actual_line = actual_lines.get(i).map_or("", String::as_str);
+        let marker = if expected.get(i) == actual.get(i) {
+            ' '
+        } else {
+            '!'
+        };
+        diff.push_str(&format!(
+            "{} {:>3} {:<width$}   {}\n",
+            marker,
+            i,
+            expected_line,
+            actual_line,
+            width = width,
+        ));
+    }
+    diff
+}
+
+/// Serializer that records the `Token` stream a value produces, leaking
+/// strings and byte strings to satisfy the `&'static` payloads of `Token`.
+struct TokenRecorder {
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug)]
+struct TokenError(String);
+
+impl Display for TokenError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+impl ser::Error for TokenError {
+    fn custom<T: Display>(msg: T) -> Self {
+        TokenError(msg.to_string())
+    }
+}
+
+fn leak_str(string: &str) -> &'static str {
+    Box::leak(string.to_owned().into_boxed_str())
+}
+
+fn leak_bytes(bytes: &[u8]) -> &'static [u8] {
+    Box::leak(bytes.to_owned().into_boxed_slice())
+}
+
+struct Compound<'a> {
+    recorder: &'a mut TokenRecorder,
+    end: Token,
+}
+
+impl<'a> ser::Serializer for &'a mut TokenRecorder {
+    type Ok = ();
+    type Error = TokenError;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), TokenError> {
+        self.tokens.push(Token::Bool(v));
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), TokenError> {
+        self.tokens.push(Token::I8(v));
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), TokenError> {
+        self.tokens.push(Token::I16(v));
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), TokenError> {
+        self.tokens.push(Token::I32(v));
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), TokenError> {
+        self.tokens.push(Token::I64(v));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), TokenError> {
+        self.tokens.push(Token::U8(v));
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), TokenError> {
+        self.tokens.push(Token::U16(v));
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), TokenError> {
+        self.tokens.push(Token::U32(v));
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), TokenError> {
+        self.tokens.push(Token::U64(v));
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), TokenError> {
+        self.tokens.push(Token::F32(v));
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), TokenError> {
+        self.tokens.push(Token::F64(v));
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), TokenError> {
+        self.tokens.push(Token::Char(v));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), TokenError> {
+        self.tokens.push(Token::Str(leak_str(v)));
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), TokenError> {
+        self.tokens.push(Token::Bytes(leak_bytes(v)));
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), TokenError> {
+        self.tokens.push(Token::None);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.tokens.push(Token::Some);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), TokenError> {
+        self.tokens.push(Token::Unit);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), TokenError> {
+        self.tokens.push(Token::UnitStruct { name });
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), TokenError> {
+        self.tokens.push(Token::UnitVariant { name, variant });
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.tokens.push(Token::NewtypeStruct { name });
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.tokens.push(Token::NewtypeVariant { name, variant });
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::Seq { len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::SeqEnd,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::Tuple { len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::TupleEnd,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::TupleStruct { name, len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::TupleStructEnd,
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::TupleVariant { name, variant, len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::TupleVariantEnd,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::Map { len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::MapEnd,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::Struct { name, len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::StructEnd,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, TokenError> {
+        self.tokens.push(Token::StructVariant { name, variant, len });
+        Ok(Compound {
+            recorder: self,
+            end: Token::StructVariantEnd,
+        })
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(&mut *self.recorder)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.recorder.tokens.push(Token::Str(key));
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = TokenError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), TokenError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.recorder.tokens.push(Token::Str(key));
+        value.serialize(&mut *self.recorder)
+    }
+
+    fn end(self) -> Result<(), TokenError> {
+        self.recorder.tokens.push(self.end);
+        Ok(())
+    }
+}
+
+/// Deserializer adapter that behaves like a non-self-describing format:
+/// every typed `deserialize_*` call is forwarded to the inner deserializer,
+/// while `deserialize_any` and `deserialize_ignored_any` fail. Deserialize
+/// impls that work through this adapter do not depend on the format being
+/// self-describing.
+pub struct NonSelfDescribing<D>(pub D);
+
+macro_rules! forward_typed {
+    ($($name:ident,)*) => {
+        $(
+            fn $name<V>(self, visitor: V) -> Result<V::Value, D::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.0.$name(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D> Deserializer<'de> for NonSelfDescribing<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "deserialize_any is not supported by non-self-describing formats",
+        ))
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    forward_typed! {
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_seq,
+        deserialize_map,
+        deserialize_identifier,
+    }
+
+    fn deserialize_unit_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+
+    fn is_self_describing(&self) -> bool {
+        false
+    }
+}
+
+/// Seq/map access adapter that reports no size hint, mimicking formats that
+/// cannot tell the length of a collection up front.
+pub struct NoSizeHint<A>(pub A);
+
+impl<'de, A> SeqAccess<'de> for NoSizeHint<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<'de, A> MapAccess<'de> for NoSizeHint<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        self.0.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.0.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
@@ -161,6 +161,23 @@ fn test_default_struct() {
             Token::StructEnd,
         ],
     );
+
+    // Trailing elements missing from the compact sequence form fall back to
+    // the field defaults as well.
+    assert_de_tokens(
+        &DefaultStruct {
+            a1: 1,
+            a2: 0,
+            a3: 123,
+            a4: 0,
+            a5: 123,
+        },
+        &[
+            Token::Seq { len: Some(1) },
+            Token::I32(1),
+            Token::SeqEnd,
+        ],
+    );
 }
 
 #[test]
@@ -782,6 +799,8 @@ fn test_unknown_field_rename_enum() {
             variant: "SailorMoon",
             len: 3,
         }],
+        // No "did you mean" suggestion: with a single expected variant the
+        // message already names it.
         "unknown variant `SailorMoon`, expected `sailor_moon`",
     );
 
@@ -1323,6 +1342,32 @@ fn test_deserialize_with_variant() {
     );
 }
 
+mod variant_as_string {
+    pub(crate) use super::{
+        deserialize_string_as_variant as deserialize, serialize_variant_as_string as serialize,
+    };
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum WithModuleVariant {
+    #[serde(with = "variant_as_string")]
+    Tuple(String, u8),
+}
+
+#[test]
+fn test_with_variant() {
+    assert_tokens(
+        &WithModuleVariant::Tuple("hello".into(), 0),
+        &[
+            Token::NewtypeVariant {
+                name: "WithModuleVariant",
+                variant: "Tuple",
+            },
+            Token::Str("hello;0"),
+        ],
+    );
+}
+
 #[derive(Debug, PartialEq, Deserialize)]
 struct DeserializeWithStruct<B>
 where
@@ -1413,6 +1458,67 @@ fn test_deserialize_with_enum() {
     );
 }
 
+#[test]
+fn test_deserialize_with_in_place() {
+    fn comma_joined<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let joined = String::deserialize(deserializer)?;
+        Ok(joined.split(',').map(str::to_owned).collect())
+    }
+
+    fn comma_joined_in_place<'de, D>(
+        deserializer: D,
+        place: &mut Vec<String>,
+    ) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let joined = String::deserialize(deserializer)?;
+        place.clear();
+        place.extend(joined.split(',').map(str::to_owned));
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Tags {
+        #[serde(
+            deserialize_with = "comma_joined",
+            deserialize_with_in_place = "comma_joined_in_place"
+        )]
+        tags: Vec<String>,
+    }
+
+    // assert_de_tokens drives both deserialize and deserialize_in_place, so
+    // this covers the plain function as well as its in-place variant.
+    assert_de_tokens(
+        &Tags {
+            tags: vec!["a".to_owned(), "b".to_owned()],
+        },
+        &[
+            Token::Struct {
+                name: "Tags",
+                len: 1,
+            },
+            Token::Str("tags"),
+            Token::Str("a,b"),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Tags {
+            tags: vec!["a".to_owned(), "b".to_owned()],
+        },
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Str("a,b"),
+            Token::SeqEnd,
+        ],
+    );
+}
+
 #[test]
 fn test_missing_renamed_field_struct() {
     assert_de_tokens_error::<RenameStruct>(
@@ -1576,6 +1682,26 @@ impl TryFrom<u32> for TryFromU32 {
     }
 }
 
+#[derive(Clone, Serialize, PartialEq, Debug)]
+#[serde(try_into = "u32")]
+enum TryIntoU32 {
+    One,
+    Two,
+    Other,
+}
+
+impl TryFrom<TryIntoU32> for u32 {
+    type Error = String;
+
+    fn try_from(value: TryIntoU32) -> Result<Self, Self::Error> {
+        match value {
+            TryIntoU32::One => Ok(1),
+            TryIntoU32::Two => Ok(2),
+            TryIntoU32::Other => Err("out of range".to_owned()),
+        }
+    }
+}
+
 #[test]
 fn test_from_into_traits() {
     assert_ser_tokens(&EnumToU32::One, &[Token::Some, Token::U32(1)]);
@@ -1586,6 +1712,8 @@ fn test_from_into_traits() {
     assert_de_tokens(&StructFromEnum(Some(2)), &[Token::Some, Token::U32(2)]);
     assert_de_tokens(&TryFromU32::Two, &[Token::U32(2)]);
     assert_de_tokens_error::<TryFromU32>(&[Token::U32(5)], "out of range");
+    assert_ser_tokens(&TryIntoU32::Two, &[Token::U32(2)]);
+    assert_ser_tokens_error(&TryIntoU32::Other, &[], "out of range");
 }
 
 #[test]
@@ -1645,6 +1773,180 @@ fn test_unknown_field_in_flatten() {
     );
 }
 
+#[test]
+fn test_flatten_map_deny_unknown_fields() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Outer {
+        dummy: String,
+        #[serde(flatten)]
+        extra: HashMap<String, u32>,
+    }
+
+    let mut extra = HashMap::new();
+    extra.insert("a".to_owned(), 1);
+    extra.insert("b".to_owned(), 2);
+
+    // Keys consumed by the flattened map are not unknown fields.
+    assert_de_tokens(
+        &Outer {
+            dummy: "23".into(),
+            extra,
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("dummy"),
+            Token::Str("23"),
+            Token::Str("a"),
+            Token::U32(1),
+            Token::Str("b"),
+            Token::U32(2),
+            Token::MapEnd,
+        ],
+    );
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct OuterStruct {
+        dummy: String,
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Inner {
+        a: u32,
+    }
+
+    // Keys claimed by neither the outer struct nor any flattened field are
+    // still rejected.
+    assert_de_tokens_error::<OuterStruct>(
+        &[
+            Token::Map { len: None },
+            Token::Str("dummy"),
+            Token::Str("23"),
+            Token::Str("a"),
+            Token::U32(1),
+            Token::Str("b"),
+            Token::U32(2),
+            Token::MapEnd,
+        ],
+        "unknown field `b`",
+    );
+}
+
+#[test]
+fn test_flatten_default() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Outer {
+        y: u32,
+        #[serde(flatten, default)]
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, Default, Serialize, Deserialize)]
+    struct Inner {
+        a: u32,
+        b: u32,
+    }
+
+    // Inner keys entirely absent: fall back to Inner::default().
+    assert_de_tokens(
+        &Outer {
+            y: 0,
+            inner: Inner::default(),
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("y"),
+            Token::U32(0),
+            Token::MapEnd,
+        ],
+    );
+
+    // Inner keys present: deserialized as usual.
+    assert_de_tokens(
+        &Outer {
+            y: 0,
+            inner: Inner { a: 1, b: 2 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("y"),
+            Token::U32(0),
+            Token::Str("a"),
+            Token::U32(1),
+            Token::Str("b"),
+            Token::U32(2),
+            Token::MapEnd,
+        ],
+    );
+
+    // Inner keys partially present: missing fields are still an error.
+    assert_de_tokens_error::<Outer>(
+        &[
+            Token::Map { len: None },
+            Token::Str("y"),
+            Token::U32(0),
+            Token::Str("a"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+        "missing field `b`",
+    );
+}
+
+#[test]
+fn test_key_with() {
+    mod as_string {
+        use serde::de::{Deserialize, Deserializer, Error};
+        use serde::ser::Serializer;
+
+        pub fn serialize<S>(key: &u32, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(key)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u32, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(D::Error::custom)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Data {
+        #[serde(key_with = "as_string")]
+        counts: BTreeMap<u32, u32>,
+    }
+
+    let mut counts = BTreeMap::new();
+    counts.insert(1, 10);
+    counts.insert(2, 20);
+
+    assert_tokens(
+        &Data { counts },
+        &[
+            Token::Struct {
+                name: "Data",
+                len: 1,
+            },
+            Token::Str("counts"),
+            Token::Map { len: Some(2) },
+            Token::Str("1"),
+            Token::U32(10),
+            Token::Str("2"),
+            Token::U32(20),
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
 #[test]
 fn test_complex_flatten() {
     #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -2091,60 +2393,129 @@ fn test_internally_tagged_enum_new_type_with_unit() {
 }
 
 #[test]
-fn test_adjacently_tagged_enum_bytes() {
+fn test_internally_tagged_enum_tuple_variant() {
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
-    #[serde(tag = "t", content = "c")]
+    #[serde(tag = "t")]
     enum Data {
         A { a: i32 },
+        B(i32, String),
     }
 
-    let data = Data::A { a: 0 };
-
+    // Tuple variants fall back to an adjacent layout with the tuple under
+    // the "content" key.
     assert_tokens(
-        &data,
+        &Data::B(1, "hello".to_owned()),
         &[
             Token::Struct {
                 name: "Data",
                 len: 2,
             },
             Token::Str("t"),
-            Token::UnitVariant {
-                name: "Data",
-                variant: "A",
-            },
-            Token::Str("c"),
-            Token::Struct { name: "A", len: 1 },
-            Token::Str("a"),
-            Token::I32(0),
-            Token::StructEnd,
+            Token::Str("B"),
+            Token::Str("content"),
+            Token::Tuple { len: 2 },
+            Token::I32(1),
+            Token::Str("hello"),
+            Token::TupleEnd,
             Token::StructEnd,
         ],
     );
 
-    assert_de_tokens(
-        &data,
+    // Struct variants in the same enum keep the plain internally tagged
+    // layout.
+    assert_tokens(
+        &Data::A { a: 1 },
         &[
             Token::Struct {
                 name: "Data",
                 len: 2,
             },
-            Token::Bytes(b"t"),
-            Token::UnitVariant {
-                name: "Data",
-                variant: "A",
-            },
-            Token::Bytes(b"c"),
-            Token::Struct { name: "A", len: 1 },
+            Token::Str("t"),
+            Token::Str("A"),
             Token::Str("a"),
-            Token::I32(0),
-            Token::StructEnd,
+            Token::I32(1),
             Token::StructEnd,
         ],
     );
+
+    // In seq form the tuple elements follow the tag inline.
+    assert_de_tokens(
+        &Data::B(1, "hello".to_owned()),
+        &[
+            Token::Seq { len: Some(3) },
+            Token::Str("B"),
+            Token::I32(1),
+            Token::Str("hello"),
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Data>(
+        &[
+            Token::Map { len: Some(1) },
+            Token::Str("t"),
+            Token::Str("B"),
+            Token::MapEnd,
+        ],
+        "missing field `content`",
+    );
 }
 
 #[test]
-fn test_adjacently_tagged_enum_containing_flatten() {
+fn test_adjacently_tagged_enum_bytes() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "t", content = "c")]
+    enum Data {
+        A { a: i32 },
+    }
+
+    let data = Data::A { a: 0 };
+
+    assert_tokens(
+        &data,
+        &[
+            Token::Struct {
+                name: "Data",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::UnitVariant {
+                name: "Data",
+                variant: "A",
+            },
+            Token::Str("c"),
+            Token::Struct { name: "A", len: 1 },
+            Token::Str("a"),
+            Token::I32(0),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &data,
+        &[
+            Token::Struct {
+                name: "Data",
+                len: 2,
+            },
+            Token::Bytes(b"t"),
+            Token::UnitVariant {
+                name: "Data",
+                variant: "A",
+            },
+            Token::Bytes(b"c"),
+            Token::Struct { name: "A", len: 1 },
+            Token::Str("a"),
+            Token::I32(0),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_adjacently_tagged_enum_containing_flatten() {
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
     #[serde(tag = "t", content = "c")]
     enum Data {
@@ -2224,6 +2595,33 @@ fn test_untagged_enum_containing_flatten() {
     );
 }
 
+#[test]
+fn test_untagged_recursion_limit() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Nested {
+        List(Vec<Nested>),
+        Int(i32),
+    }
+
+    // Nesting below the limit buffers fine.
+    assert_de_tokens(
+        &Nested::List(vec![Nested::List(vec![Nested::Int(1)])]),
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(1) },
+            Token::I32(1),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+
+    // Sequences nested beyond the buffering depth limit of 128 are rejected
+    // instead of overflowing the stack.
+    let tokens = vec![Token::Seq { len: None }; 129];
+    assert_de_tokens_error::<Nested>(&tokens, "recursion limit exceeded");
+}
+
 #[test]
 fn test_partially_untagged_enum() {
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
@@ -2255,6 +2653,61 @@ fn test_partially_untagged_enum() {
     );
 }
 
+#[test]
+fn test_partially_untagged_struct_variant_catch_all() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "type")]
+    enum Msg {
+        Known { value: u32 },
+        #[serde(untagged)]
+        Other { value: u32 },
+    }
+
+    assert_tokens(
+        &Msg::Known { value: 5 },
+        &[
+            Token::Struct {
+                name: "Msg",
+                len: 2,
+            },
+            Token::Str("type"),
+            Token::Str("Known"),
+            Token::Str("value"),
+            Token::U32(5),
+            Token::StructEnd,
+        ],
+    );
+
+    // The catch-all serializes without the tag.
+    assert_tokens(
+        &Msg::Other { value: 7 },
+        &[
+            Token::Struct {
+                name: "Msg",
+                len: 1,
+            },
+            Token::Str("value"),
+            Token::U32(7),
+            Token::StructEnd,
+        ],
+    );
+
+    // Input whose tag does not match any variant falls through to the
+    // untagged catch-all; the unmatched tag is ignored like any unknown
+    // field.
+    assert_de_tokens(
+        &Msg::Other { value: 7 },
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("type"),
+            Token::Str("Mystery"),
+            Token::Str("value"),
+            Token::U32(7),
+            Token::MapEnd,
+        ],
+    );
+}
+
 #[test]
 fn test_partially_untagged_enum_generic() {
     trait Trait<T> {
@@ -2407,7 +2860,17 @@ fn test_partially_untagged_internally_tagged_enum() {
 
     assert_de_tokens(&data, &[Token::U32(42)]);
 
-    // TODO test error output
+    // When no untagged variant matches either, the error from the tagged
+    // representation is reported because it names the expected variants.
+    assert_de_tokens_error::<Data>(
+        &[
+            Token::Map { len: None },
+            Token::Str("t"),
+            Token::Str("C"),
+            Token::MapEnd,
+        ],
+        "unknown variant `C`, expected `A` or `B`",
+    );
 }
 
 #[test]
@@ -2439,7 +2902,15 @@ fn test_partially_untagged_adjacently_tagged_enum() {
 
     assert_de_tokens(&data, &[Token::U32(42)]);
 
-    // TODO test error output
+    assert_de_tokens_error::<Data>(
+        &[
+            Token::Map { len: None },
+            Token::Str("t"),
+            Token::Str("C"),
+            Token::MapEnd,
+        ],
+        "unknown variant `C`, expected `A` or `B`",
+    );
 }
 
 #[test]
@@ -3232,3 +3703,1656 @@ mod flatten {
         }
     }
 }
+
+#[test]
+fn test_meta_attribute() {
+    use serde::meta::Metadata;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(meta(xmlns = "http://example.com/ns", version = "2"))]
+    struct Document {
+        #[serde(rename = "Title", meta(column = "TEXT"))]
+        title: String,
+        body: String,
+    }
+
+    assert_eq!(
+        Document::container_metadata(),
+        [("xmlns", "http://example.com/ns"), ("version", "2")]
+    );
+    assert_eq!(Document::field_metadata("Title"), [("column", "TEXT")]);
+    assert!(Document::field_metadata("body").is_empty());
+    assert!(Document::field_metadata("nonexistent").is_empty());
+
+    #[derive(Serialize)]
+    enum Shape {
+        #[serde(meta(tag = "1"))]
+        Circle {
+            #[serde(meta(unit = "mm"))]
+            radius: u32,
+        },
+        Square,
+    }
+
+    assert!(Shape::container_metadata().is_empty());
+    assert_eq!(Shape::variant_metadata("Circle"), [("tag", "1")]);
+    assert!(Shape::variant_metadata("Square").is_empty());
+    assert_eq!(Shape::field_metadata("radius"), [("unit", "mm")]);
+}
+
+#[test]
+fn test_field_order() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct S {
+        b: i32,
+        #[serde(order = 1)]
+        c: i32,
+        #[serde(order = 0)]
+        a: i32,
+    }
+
+    // Explicitly ordered fields come first, sorted by their order; the rest
+    // keep declaration order.
+    assert_tokens(
+        &S { b: 2, c: 3, a: 1 },
+        &[
+            Token::Struct { name: "S", len: 3 },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::Str("c"),
+            Token::I32(3),
+            Token::Str("b"),
+            Token::I32(2),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_sort_fields_alphabetical() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(sort_fields = "alphabetical")]
+    struct S {
+        gamma: i32,
+        #[serde(rename = "zeta")]
+        alpha: i32,
+        beta: i32,
+    }
+
+    // The sort uses serialized names, so the rename is already applied.
+    assert_tokens(
+        &S {
+            gamma: 3,
+            alpha: 1,
+            beta: 2,
+        },
+        &[
+            Token::Struct { name: "S", len: 3 },
+            Token::Str("beta"),
+            Token::I32(2),
+            Token::Str("gamma"),
+            Token::I32(3),
+            Token::Str("zeta"),
+            Token::I32(1),
+            Token::StructEnd,
+        ],
+    );
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(sort_fields = "alphabetical")]
+    enum E {
+        V { b: i32, a: i32 },
+    }
+
+    assert_tokens(
+        &E::V { b: 2, a: 1 },
+        &[
+            Token::StructVariant {
+                name: "E",
+                variant: "V",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::Str("b"),
+            Token::I32(2),
+            Token::StructVariantEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_keyed_by() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: String,
+        weight: u32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Registry {
+        #[serde(keyed_by = "id")]
+        items: Vec<Item>,
+    }
+
+    // The map keys come from the `id` field of each item, which is removed
+    // from the serialized values and injected back when deserializing.
+    assert_tokens(
+        &Registry {
+            items: vec![
+                Item {
+                    id: "a".to_owned(),
+                    weight: 1,
+                },
+                Item {
+                    id: "b".to_owned(),
+                    weight: 2,
+                },
+            ],
+        },
+        &[
+            Token::Struct {
+                name: "Registry",
+                len: 1,
+            },
+            Token::Str("items"),
+            Token::Map { len: Some(2) },
+            Token::Str("a"),
+            Token::Struct {
+                name: "Item",
+                len: 1,
+            },
+            Token::Str("weight"),
+            Token::U32(1),
+            Token::StructEnd,
+            Token::Str("b"),
+            Token::Struct {
+                name: "Item",
+                len: 1,
+            },
+            Token::Str("weight"),
+            Token::U32(2),
+            Token::StructEnd,
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct KeyedRegistry {
+        #[serde(keyed_by = "id")]
+        items: BTreeMap<String, Item>,
+    }
+
+    let mut items = BTreeMap::new();
+    items.insert(
+        "a".to_owned(),
+        Item {
+            id: "a".to_owned(),
+            weight: 1,
+        },
+    );
+
+    assert_tokens(
+        &KeyedRegistry { items },
+        &[
+            Token::Struct {
+                name: "KeyedRegistry",
+                len: 1,
+            },
+            Token::Str("items"),
+            Token::Map { len: Some(1) },
+            Token::Str("a"),
+            Token::Struct {
+                name: "Item",
+                len: 1,
+            },
+            Token::Str("weight"),
+            Token::U32(1),
+            Token::StructEnd,
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_tag_deserialize_with() {
+    fn normalize_tag<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(match raw.to_lowercase().as_str() {
+            // Legacy name from before the variant was renamed.
+            "legacy_circle" => "circle".to_owned(),
+            other => other.to_owned(),
+        })
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(
+        tag = "type",
+        rename_all = "lowercase",
+        tag_deserialize_with = "normalize_tag"
+    )]
+    enum Shape {
+        Circle { radius: u32 },
+        Square { side: u32 },
+    }
+
+    // Tags are normalized before variant matching, so casing and legacy
+    // spellings are accepted.
+    assert_de_tokens(
+        &Shape::Circle { radius: 1 },
+        &[
+            Token::Map { len: None },
+            Token::Str("type"),
+            Token::Str("Circle"),
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Shape::Circle { radius: 1 },
+        &[
+            Token::Map { len: None },
+            Token::Str("type"),
+            Token::Str("LEGACY_CIRCLE"),
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+
+    // Serialization still emits the canonical tag.
+    assert_tokens(
+        &Shape::Square { side: 2 },
+        &[
+            Token::Struct {
+                name: "Shape",
+                len: 2,
+            },
+            Token::Str("type"),
+            Token::Str("square"),
+            Token::Str("side"),
+            Token::U32(2),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_tag_alias() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type", tag_alias = "kind", rename_all = "lowercase")]
+    enum Internal {
+        Circle { radius: u32 },
+    }
+
+    // The canonical tag key and the alias are both accepted.
+    assert_de_tokens(
+        &Internal::Circle { radius: 1 },
+        &[
+            Token::Map { len: None },
+            Token::Str("type"),
+            Token::Str("circle"),
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Internal::Circle { radius: 1 },
+        &[
+            Token::Map { len: None },
+            Token::Str("kind"),
+            Token::Str("circle"),
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+
+    // Serialization always emits the canonical key.
+    assert_tokens(
+        &Internal::Circle { radius: 1 },
+        &[
+            Token::Struct {
+                name: "Internal",
+                len: 2,
+            },
+            Token::Str("type"),
+            Token::Str("circle"),
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::StructEnd,
+        ],
+    );
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(
+        tag = "type",
+        tag_alias = "kind",
+        content = "data",
+        rename_all = "lowercase"
+    )]
+    enum Adjacent {
+        Circle { radius: u32 },
+    }
+
+    assert_de_tokens(
+        &Adjacent::Circle { radius: 1 },
+        &[
+            Token::Map { len: None },
+            Token::Str("kind"),
+            Token::UnitVariant {
+                name: "Adjacent",
+                variant: "circle",
+            },
+            Token::Str("data"),
+            Token::Map { len: None },
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::MapEnd,
+            Token::MapEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Adjacent::Circle { radius: 1 },
+        &[
+            Token::Struct {
+                name: "Adjacent",
+                len: 2,
+            },
+            Token::Str("type"),
+            Token::UnitVariant {
+                name: "Adjacent",
+                variant: "circle",
+            },
+            Token::Str("data"),
+            Token::Struct {
+                name: "circle",
+                len: 1,
+            },
+            Token::Str("radius"),
+            Token::U32(1),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_tag_repr_internally_tagged() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        x: u8,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "t", tag_repr = "u16")]
+    enum Internal {
+        Unit,
+        Newtype(Payload),
+        Struct { a: u8 },
+    }
+
+    // The tag is the variant index serialized as the repr type.
+    assert_tokens(
+        &Internal::Unit,
+        &[
+            Token::Struct {
+                name: "Internal",
+                len: 1,
+            },
+            Token::Str("t"),
+            Token::U16(0),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Internal::Newtype(Payload { x: 3 }),
+        &[
+            Token::Struct {
+                name: "Payload",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::U16(1),
+            Token::Str("x"),
+            Token::U8(3),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Internal::Struct { a: 5 },
+        &[
+            Token::Struct {
+                name: "Internal",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::U16(2),
+            Token::Str("a"),
+            Token::U8(5),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Internal>(
+        &[
+            Token::Map { len: None },
+            Token::Str("t"),
+            Token::U16(3),
+            Token::MapEnd,
+        ],
+        "invalid value: integer `3`, expected variant index 0 <= i < 3",
+    );
+}
+
+#[test]
+fn test_tag_repr_adjacently_tagged() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "t", content = "c", tag_repr = "u8")]
+    enum Adjacent {
+        Unit,
+        Newtype(u8),
+    }
+
+    assert_tokens(
+        &Adjacent::Unit,
+        &[
+            Token::Struct {
+                name: "Adjacent",
+                len: 1,
+            },
+            Token::Str("t"),
+            Token::U8(0),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Adjacent::Newtype(7),
+        &[
+            Token::Struct {
+                name: "Adjacent",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::U8(1),
+            Token::Str("c"),
+            Token::U8(7),
+            Token::StructEnd,
+        ],
+    );
+
+    // Integer tags are matched even when the content comes first and has to
+    // be buffered.
+    assert_de_tokens(
+        &Adjacent::Newtype(7),
+        &[
+            Token::Map { len: None },
+            Token::Str("c"),
+            Token::U8(7),
+            Token::Str("t"),
+            Token::U8(1),
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Adjacent>(
+        &[
+            Token::Map { len: None },
+            Token::Str("t"),
+            Token::U8(2),
+            Token::MapEnd,
+        ],
+        "invalid value: integer `2`, expected variant index 0 <= i < 2",
+    );
+}
+
+#[test]
+fn test_other_newtype_internally_tagged() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "t")]
+    enum Message {
+        Known { a: u8 },
+        #[serde(other)]
+        Unknown(String),
+    }
+
+    assert_tokens(
+        &Message::Known { a: 1 },
+        &[
+            Token::Struct {
+                name: "Message",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::Str("Known"),
+            Token::Str("a"),
+            Token::U8(1),
+            Token::StructEnd,
+        ],
+    );
+
+    // An unrecognized tag is captured, and serialization re-emits it.
+    assert_tokens(
+        &Message::Unknown("mystery".to_owned()),
+        &[
+            Token::Struct {
+                name: "Message",
+                len: 1,
+            },
+            Token::Str("t"),
+            Token::Str("mystery"),
+            Token::StructEnd,
+        ],
+    );
+
+    // Content alongside an unrecognized tag is dropped.
+    assert_de_tokens(
+        &Message::Unknown("mystery".to_owned()),
+        &[
+            Token::Map { len: None },
+            Token::Str("t"),
+            Token::Str("mystery"),
+            Token::Str("payload"),
+            Token::U8(9),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_other_newtype_adjacently_tagged() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "t", content = "c")]
+    enum Message {
+        Known(u8),
+        #[serde(other)]
+        Unknown(String),
+    }
+
+    assert_tokens(
+        &Message::Known(1),
+        &[
+            Token::Struct {
+                name: "Message",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::UnitVariant {
+                name: "Message",
+                variant: "Known",
+            },
+            Token::Str("c"),
+            Token::U8(1),
+            Token::StructEnd,
+        ],
+    );
+
+    // An unrecognized tag with no content round-trips through the catch-all.
+    assert_tokens(
+        &Message::Unknown("mystery".to_owned()),
+        &[
+            Token::Struct {
+                name: "Message",
+                len: 1,
+            },
+            Token::Str("t"),
+            Token::Str("mystery"),
+            Token::StructEnd,
+        ],
+    );
+
+    // Content is discarded, whether it comes before or after the tag.
+    assert_de_tokens(
+        &Message::Unknown("mystery".to_owned()),
+        &[
+            Token::Map { len: None },
+            Token::Str("t"),
+            Token::Str("mystery"),
+            Token::Str("c"),
+            Token::U8(9),
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Message::Unknown("mystery".to_owned()),
+        &[
+            Token::Map { len: None },
+            Token::Str("c"),
+            Token::U8(9),
+            Token::Str("t"),
+            Token::Str("mystery"),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_rename_all_with() {
+    fn dashed_upper(name: &str) -> String {
+        name.to_uppercase().replace('_', "-")
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all_with = "dashed_upper")]
+    struct Packet {
+        first_field: u8,
+        second_field: u8,
+    }
+
+    assert_de_tokens(
+        &Packet {
+            first_field: 1,
+            second_field: 2,
+        },
+        &[
+            Token::Struct {
+                name: "Packet",
+                len: 2,
+            },
+            Token::Str("FIRST-FIELD"),
+            Token::U8(1),
+            Token::Str("SECOND-FIELD"),
+            Token::U8(2),
+            Token::StructEnd,
+        ],
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all_with = "dashed_upper")]
+    enum Choice {
+        FirstChoice,
+        SecondChoice,
+    }
+
+    assert_de_tokens(
+        &Choice::SecondChoice,
+        &[
+            Token::Enum { name: "Choice" },
+            Token::Str("SECONDCHOICE"),
+            Token::Unit,
+        ],
+    );
+}
+
+#[test]
+fn test_case_insensitive() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(case_insensitive)]
+    struct Config {
+        timeout: u32,
+        max_retries: u32,
+    }
+
+    assert_de_tokens(
+        &Config {
+            timeout: 10,
+            max_retries: 3,
+        },
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 2,
+            },
+            Token::Str("TimeOut"),
+            Token::U32(10),
+            Token::Str("MAX_RETRIES"),
+            Token::U32(3),
+            Token::StructEnd,
+        ],
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(case_insensitive)]
+    enum Mode {
+        Fast,
+        Thorough,
+    }
+
+    assert_de_tokens(
+        &Mode::Thorough,
+        &[
+            Token::Enum { name: "Mode" },
+            Token::Str("THOROUGH"),
+            Token::Unit,
+        ],
+    );
+}
+
+#[test]
+fn test_case_insensitive_with_rename_and_deny_unknown() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(case_insensitive, deny_unknown_fields)]
+    struct Config {
+        #[serde(rename = "connectTimeout", alias = "timeout")]
+        connect_timeout: u32,
+    }
+
+    // Renamed names and aliases also match ignoring ASCII case.
+    assert_de_tokens(
+        &Config {
+            connect_timeout: 10,
+        },
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 1,
+            },
+            Token::Str("CONNECTTIMEOUT"),
+            Token::U32(10),
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &Config {
+            connect_timeout: 10,
+        },
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 1,
+            },
+            Token::Str("TimeOut"),
+            Token::U32(10),
+            Token::StructEnd,
+        ],
+    );
+
+    // Fields that do not match under any casing are still rejected.
+    assert_de_tokens_error::<Config>(
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 1,
+            },
+            Token::Str("retries"),
+        ],
+        "unknown field `retries`, expected `connectTimeout` or `timeout`",
+    );
+}
+
+#[test]
+fn test_map_from_pairs() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Data {
+        #[serde(map_from_pairs)]
+        headers: BTreeMap<String, u32>,
+    }
+
+    let data = Data {
+        headers: {
+            let mut map = BTreeMap::new();
+            map.insert("a".to_owned(), 1);
+            map.insert("b".to_owned(), 2);
+            map
+        },
+    };
+
+    // The usual map representation still roundtrips.
+    assert_tokens(
+        &data,
+        &[
+            Token::Struct {
+                name: "Data",
+                len: 1,
+            },
+            Token::Str("headers"),
+            Token::Map { len: Some(2) },
+            Token::Str("a"),
+            Token::U32(1),
+            Token::Str("b"),
+            Token::U32(2),
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // A sequence of [key, value] pairs is accepted as well.
+    assert_de_tokens(
+        &data,
+        &[
+            Token::Struct {
+                name: "Data",
+                len: 1,
+            },
+            Token::Str("headers"),
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::Str("a"),
+            Token::U32(1),
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::Str("b"),
+            Token::U32(2),
+            Token::SeqEnd,
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // Entries that are not [key, value] pairs are rejected.
+    assert_de_tokens_error::<Data>(
+        &[
+            Token::Struct {
+                name: "Data",
+                len: 1,
+            },
+            Token::Str("headers"),
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(1) },
+            Token::Str("a"),
+            Token::SeqEnd,
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+        "invalid length 1, expected a [key, value] pair",
+    );
+}
+
+#[test]
+fn test_skip_serializing_default() {
+    #[derive(Debug, PartialEq, Default, Serialize, Deserialize)]
+    #[serde(skip_serializing_default, default)]
+    struct Config {
+        host: String,
+        port: u16,
+        verbose: bool,
+    }
+
+    // Fields equal to their type's default are omitted.
+    assert_tokens(
+        &Config {
+            host: String::new(),
+            port: 8080,
+            verbose: false,
+        },
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 1,
+            },
+            Token::Str("port"),
+            Token::U16(8080),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Config::default(),
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 0,
+            },
+            Token::StructEnd,
+        ],
+    );
+
+    // A field-level skip_serializing_if takes precedence over the
+    // container-level rule.
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(skip_serializing_default)]
+    struct Mixed {
+        #[serde(skip_serializing_if = "is_zero")]
+        a: u8,
+        b: u8,
+    }
+
+    fn is_zero(v: &u8) -> bool {
+        *v == 0
+    }
+
+    assert_ser_tokens(
+        &Mixed { a: 1, b: 0 },
+        &[
+            Token::Struct {
+                name: "Mixed",
+                len: 1,
+            },
+            Token::Str("a"),
+            Token::U8(1),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_skip_none() {
+    #[derive(Debug, PartialEq, Default, Serialize, Deserialize)]
+    #[serde(skip_none, default)]
+    struct Config {
+        host: Option<String>,
+        port: Option<u16>,
+        verbose: bool,
+    }
+
+    // `None` fields are omitted and the struct len shrinks accordingly.
+    assert_tokens(
+        &Config {
+            host: None,
+            port: Some(8080),
+            verbose: false,
+        },
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 2,
+            },
+            Token::Str("port"),
+            Token::Some,
+            Token::U16(8080),
+            Token::Str("verbose"),
+            Token::Bool(false),
+            Token::StructEnd,
+        ],
+    );
+
+    // A field-level skip_serializing_if takes precedence over the
+    // container-level rule.
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(skip_none)]
+    struct Mixed {
+        #[serde(skip_serializing_if = "Option::is_some")]
+        a: Option<u8>,
+        b: Option<u8>,
+    }
+
+    assert_ser_tokens(
+        &Mixed {
+            a: Some(1),
+            b: None,
+        },
+        &[
+            Token::Struct {
+                name: "Mixed",
+                len: 0,
+            },
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_skip_none_flatten() {
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(skip_none)]
+    struct Outer {
+        a: Option<u8>,
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    #[serde(skip_none)]
+    struct Inner {
+        b: Option<u8>,
+        c: Option<u8>,
+    }
+
+    // The rule applies within the flattened struct's own fields too.
+    assert_ser_tokens(
+        &Outer {
+            a: None,
+            inner: Inner {
+                b: Some(2),
+                c: None,
+            },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("b"),
+            Token::Some,
+            Token::U8(2),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_rename_const() {
+    const TYPE_NAME: &str = "renamed_struct";
+    const FIELD_NAME: &str = "renamed_field";
+    const VARIANT_NAME: &str = "renamed_variant";
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename = TYPE_NAME)]
+    struct Renamed {
+        #[serde(rename = FIELD_NAME)]
+        value: u32,
+    }
+
+    assert_tokens(
+        &Renamed { value: 1 },
+        &[
+            Token::Struct {
+                name: "renamed_struct",
+                len: 1,
+            },
+            Token::Str("renamed_field"),
+            Token::U32(1),
+            Token::StructEnd,
+        ],
+    );
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum ConstVariant {
+        #[serde(rename = VARIANT_NAME)]
+        Renamed {
+            #[serde(rename(serialize = FIELD_NAME, deserialize = FIELD_NAME))]
+            value: u32,
+        },
+    }
+
+    assert_tokens(
+        &ConstVariant::Renamed { value: 2 },
+        &[
+            Token::StructVariant {
+                name: "ConstVariant",
+                variant: "renamed_variant",
+                len: 1,
+            },
+            Token::Str("renamed_field"),
+            Token::U32(2),
+            Token::StructVariantEnd,
+        ],
+    );
+
+    // A const rename still participates in the unknown field message.
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct Strict {
+        #[serde(rename = FIELD_NAME)]
+        value: u32,
+    }
+
+    assert_de_tokens_error::<Strict>(
+        &[
+            Token::Struct {
+                name: "Strict",
+                len: 1,
+            },
+            Token::Str("wrong"),
+        ],
+        "unknown field `wrong`, expected `renamed_field`",
+    );
+}
+
+#[test]
+fn test_missing_field_error_message() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Config {
+        #[serde(missing_field_error = "set `api_key` in config.toml or the API_KEY env var")]
+        api_key: String,
+        #[serde(missing_field_error = "this message never fires for an optional field")]
+        retries: Option<u32>,
+    }
+
+    assert_de_tokens_error::<Config>(
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 2,
+            },
+            Token::StructEnd,
+        ],
+        "set `api_key` in config.toml or the API_KEY env var",
+    );
+
+    // An absent Option field is still None rather than an error.
+    assert_de_tokens(
+        &Config {
+            api_key: "secret".to_owned(),
+            retries: None,
+        },
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 2,
+            },
+            Token::Str("api_key"),
+            Token::Str("secret"),
+            Token::StructEnd,
+        ],
+    );
+
+    // The message does not replace the one for present-but-invalid values.
+    assert_de_tokens_error::<Config>(
+        &[
+            Token::Struct {
+                name: "Config",
+                len: 2,
+            },
+            Token::Str("api_key"),
+            Token::I32(7),
+            Token::StructEnd,
+        ],
+        "invalid type: integer `7`, expected a string",
+    );
+}
+
+#[test]
+fn test_finalize() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(finalize = "Totals::finalize")]
+    struct Totals {
+        a: u32,
+        b: u32,
+        #[serde(skip)]
+        sum: u32,
+    }
+
+    impl Totals {
+        fn finalize(&mut self) {
+            self.sum = self.a + self.b;
+        }
+    }
+
+    // The skipped field is recomputed from the deserialized data, in both
+    // deserialize and deserialize_in_place.
+    assert_de_tokens(
+        &Totals { a: 1, b: 2, sum: 3 },
+        &[
+            Token::Struct {
+                name: "Totals",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::U32(1),
+            Token::Str("b"),
+            Token::U32(2),
+            Token::StructEnd,
+        ],
+    );
+
+    fn uppercase_label(label: &mut Label) {
+        match label {
+            Label::Name(name) => name.make_ascii_uppercase(),
+        }
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(finalize = "uppercase_label")]
+    enum Label {
+        Name(String),
+    }
+
+    assert_de_tokens(
+        &Label::Name("ABC".to_owned()),
+        &[
+            Token::NewtypeVariant {
+                name: "Label",
+                variant: "Name",
+            },
+            Token::Str("abc"),
+        ],
+    );
+}
+
+#[test]
+fn test_collect_unknown() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Config {
+        name: String,
+        #[serde(collect_unknown)]
+        extra: BTreeMap<String, String>,
+    }
+
+    // Unknown keys land in the collect_unknown map and are flattened back
+    // into the container on serialization.
+    assert_tokens(
+        &Config {
+            name: "app".to_owned(),
+            extra: {
+                let mut extra = BTreeMap::new();
+                extra.insert("vendor_a".to_owned(), "1".to_owned());
+                extra.insert("vendor_b".to_owned(), "2".to_owned());
+                extra
+            },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("name"),
+            Token::Str("app"),
+            Token::Str("vendor_a"),
+            Token::Str("1"),
+            Token::Str("vendor_b"),
+            Token::Str("2"),
+            Token::MapEnd,
+        ],
+    );
+
+    // No unknown keys leaves the map empty.
+    assert_de_tokens(
+        &Config {
+            name: "app".to_owned(),
+            extra: BTreeMap::new(),
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("name"),
+            Token::Str("app"),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_validate_container() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(validate = "Range::validate")]
+    struct Range {
+        min: u32,
+        max: u32,
+    }
+
+    impl Range {
+        fn validate(&self) -> Result<(), String> {
+            if self.min > self.max {
+                return Err(format!("min {} exceeds max {}", self.min, self.max));
+            }
+            Ok(())
+        }
+    }
+
+    // Runs after the whole value is built, in both deserialize and
+    // deserialize_in_place.
+    assert_de_tokens(
+        &Range { min: 1, max: 10 },
+        &[
+            Token::Struct {
+                name: "Range",
+                len: 2,
+            },
+            Token::Str("min"),
+            Token::U32(1),
+            Token::Str("max"),
+            Token::U32(10),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Range>(
+        &[
+            Token::Struct {
+                name: "Range",
+                len: 2,
+            },
+            Token::Str("min"),
+            Token::U32(7),
+            Token::Str("max"),
+            Token::U32(2),
+            Token::StructEnd,
+        ],
+        "min 7 exceeds max 2",
+    );
+}
+
+#[test]
+fn test_validate_field() {
+    fn nonzero(port: &u16) -> Result<(), &'static str> {
+        if *port == 0 {
+            return Err("port must be nonzero");
+        }
+        Ok(())
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Server {
+        host: String,
+        #[serde(validate = "nonzero")]
+        port: u16,
+    }
+
+    assert_de_tokens(
+        &Server {
+            host: "localhost".to_owned(),
+            port: 80,
+        },
+        &[
+            Token::Struct {
+                name: "Server",
+                len: 2,
+            },
+            Token::Str("host"),
+            Token::Str("localhost"),
+            Token::Str("port"),
+            Token::U16(80),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Server>(
+        &[
+            Token::Struct {
+                name: "Server",
+                len: 2,
+            },
+            Token::Str("host"),
+            Token::Str("localhost"),
+            Token::Str("port"),
+            Token::U16(0),
+            Token::StructEnd,
+        ],
+        "port must be nonzero",
+    );
+
+    // Struct deserialized from a sequence runs the same checks.
+    assert_de_tokens_error::<Server>(
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Str("localhost"),
+            Token::U16(0),
+            Token::SeqEnd,
+        ],
+        "port must be nonzero",
+    );
+
+    // A default coming from a missing field is validated too.
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Defaulted {
+        #[serde(default, validate = "nonzero")]
+        port: u16,
+    }
+
+    assert_de_tokens_error::<Defaulted>(
+        &[
+            Token::Struct {
+                name: "Defaulted",
+                len: 1,
+            },
+            Token::StructEnd,
+        ],
+        "port must be nonzero",
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(version = 2, upgrade_from(1 = "migrate_settings_v1"))]
+struct Settings {
+    name: String,
+    retries: u32,
+}
+
+fn migrate_settings_v1<'de, D>(deserializer: D) -> Result<Settings, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct SettingsV1 {
+        name: String,
+    }
+
+    let old = SettingsV1::deserialize(deserializer)?;
+    Ok(Settings {
+        name: old.name,
+        retries: 3,
+    })
+}
+
+#[test]
+fn test_version() {
+    // The current version round-trips through the `Versioned` wrapper.
+    assert_tokens(
+        &Settings {
+            name: "a".to_owned(),
+            retries: 5,
+        },
+        &[
+            Token::Struct {
+                name: "Versioned",
+                len: 2,
+            },
+            Token::Str("version"),
+            Token::U32(2),
+            Token::Str("value"),
+            Token::Struct {
+                name: "Settings",
+                len: 2,
+            },
+            Token::Str("name"),
+            Token::Str("a"),
+            Token::Str("retries"),
+            Token::U32(5),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // Old payloads are deserialized into the previous shape and upgraded by
+    // the migration hook.
+    assert_de_tokens(
+        &Settings {
+            name: "a".to_owned(),
+            retries: 3,
+        },
+        &[
+            Token::Struct {
+                name: "Versioned",
+                len: 2,
+            },
+            Token::Str("version"),
+            Token::U32(1),
+            Token::Str("value"),
+            Token::Struct {
+                name: "SettingsV1",
+                len: 1,
+            },
+            Token::Str("name"),
+            Token::Str("a"),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // Compact formats represent the wrapper as a sequence.
+    assert_de_tokens(
+        &Settings {
+            name: "a".to_owned(),
+            retries: 5,
+        },
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U32(2),
+            Token::Seq { len: Some(2) },
+            Token::Str("a"),
+            Token::U32(5),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_version_errors() {
+    assert_de_tokens_error::<Settings>(
+        &[
+            Token::Struct {
+                name: "Versioned",
+                len: 2,
+            },
+            Token::Str("version"),
+            Token::U32(7),
+            Token::Str("value"),
+        ],
+        "unsupported version 7 of Settings",
+    );
+
+    // The version must precede the value so that the value can be read with
+    // the right shape.
+    assert_de_tokens_error::<Settings>(
+        &[
+            Token::Struct {
+                name: "Versioned",
+                len: 2,
+            },
+            Token::Str("value"),
+        ],
+        "missing field `version`",
+    );
+}
+
+#[test]
+fn test_compact_codegen() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(compact_codegen)]
+    struct CompactStruct {
+        a: i32,
+        #[serde(alias = "bee")]
+        b: i32,
+    }
+
+    // Field names and aliases resolve through the shared name table.
+    assert_de_tokens(
+        &CompactStruct { a: 1, b: 2 },
+        &[
+            Token::Struct {
+                name: "CompactStruct",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::Str("bee"),
+            Token::I32(2),
+            Token::StructEnd,
+        ],
+    );
+
+    // Field indices resolve by position in declaration order.
+    assert_de_tokens(
+        &CompactStruct { a: 1, b: 2 },
+        &[
+            Token::Map { len: Some(2) },
+            Token::U64(0),
+            Token::I32(1),
+            Token::U64(1),
+            Token::I32(2),
+            Token::MapEnd,
+        ],
+    );
+
+    // Unknown fields are ignored by default.
+    assert_de_tokens(
+        &CompactStruct { a: 1, b: 2 },
+        &[
+            Token::Struct {
+                name: "CompactStruct",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::Str("unknown"),
+            Token::I32(9),
+            Token::Str("b"),
+            Token::I32(2),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_compact_codegen_deny_unknown_fields() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(compact_codegen, deny_unknown_fields)]
+    struct CompactDeny {
+        a: i32,
+    }
+
+    assert_de_tokens(
+        &CompactDeny { a: 1 },
+        &[
+            Token::Struct {
+                name: "CompactDeny",
+                len: 1,
+            },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<CompactDeny>(
+        &[
+            Token::Struct {
+                name: "CompactDeny",
+                len: 1,
+            },
+            Token::Str("b"),
+        ],
+        "unknown field `b`, expected `a`",
+    );
+}
+
+#[test]
+fn test_alias_on_unit_and_newtype_variants() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Renamed {
+        #[serde(alias = "Legacy")]
+        Current,
+        #[serde(rename = "payload", alias = "body")]
+        Payload(u32),
+    }
+
+    // The new name and the alias both deserialize.
+    assert_de_tokens(
+        &Renamed::Current,
+        &[Token::UnitVariant {
+            name: "Renamed",
+            variant: "Current",
+        }],
+    );
+    assert_de_tokens(
+        &Renamed::Current,
+        &[Token::UnitVariant {
+            name: "Renamed",
+            variant: "Legacy",
+        }],
+    );
+
+    assert_de_tokens(
+        &Renamed::Payload(3),
+        &[
+            Token::NewtypeVariant {
+                name: "Renamed",
+                variant: "body",
+            },
+            Token::U32(3),
+        ],
+    );
+}
+
+#[test]
+fn test_alias_on_internally_tagged_variant() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "type")]
+    enum Message {
+        #[serde(alias = "Greeting")]
+        Hello { who: String },
+    }
+
+    assert_de_tokens(
+        &Message::Hello {
+            who: "world".to_owned(),
+        },
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("type"),
+            Token::Str("Greeting"),
+            Token::Str("who"),
+            Token::Str("world"),
+            Token::MapEnd,
+        ],
+    );
+}
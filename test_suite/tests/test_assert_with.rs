@@ -0,0 +1,147 @@
+//! Closure-based variants of the serde_test deserialization assertions.
+//!
+//! `assert_de_tokens` requires `PartialEq` and `Debug` on the deserialized
+//! type, which rules out types without equality and makes approximate
+//! comparisons (e.g. floats) impossible. serde_test is maintained out of
+//! tree, so the closure-based helpers live here alongside the tests that
+//! exercise them.
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde::de::{Deserialize, Deserializer};
+use serde_derive::Deserialize;
+use serde_test::{assert_de_tokens, Token};
+use std::cell::Cell;
+use std::fmt;
+
+/// Asserts that `tokens` deserialize into a value that `compare` accepts
+/// against `expected`, rendering both sides with `format` on failure.
+#[track_caller]
+pub fn assert_de_tokens_with<'de, T, C, F>(expected: &T, tokens: &'de [Token], compare: C, format: F)
+where
+    T: Deserialize<'de>,
+    C: FnOnce(&T, &T) -> bool,
+    F: Fn(&T) -> String,
+{
+    let actual = de_tokens::<T>(tokens);
+    assert!(
+        compare(&actual, expected),
+        "tokens deserialized to {}, expected {}",
+        format(&actual),
+        format(expected),
+    );
+}
+
+/// Deserializes a value from `tokens`, panicking with the serde_test error
+/// message if the tokens do not match the type.
+///
+/// serde_test does not expose its token deserializer directly, only the
+/// comparing assertions, so the value is smuggled out of `assert_de_tokens`
+/// through an always-equal wrapper whose `PartialEq` hands the deserialized
+/// value over to the expected side.
+#[track_caller]
+pub fn de_tokens<'de, T>(tokens: &'de [Token]) -> T
+where
+    T: Deserialize<'de>,
+{
+    let holder = Extract(Cell::new(None));
+    assert_de_tokens(&holder, tokens);
+    match holder.0.into_inner() {
+        Some(value) => value,
+        None => panic!("deserialized value was not captured"),
+    }
+}
+
+struct Extract<T>(Cell<Option<T>>);
+
+impl<T> PartialEq for Extract<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // The deserialized side holds the value; move it into the expected
+        // side where the caller still has a handle on it.
+        if let Some(value) = self.0.take() {
+            other.0.set(Some(value));
+        }
+        true
+    }
+}
+
+impl<T> fmt::Debug for Extract<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Extract")
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Extract<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(|value| Extract(Cell::new(Some(value))))
+    }
+}
+
+#[test]
+fn test_approximate_float_comparison() {
+    #[derive(Deserialize)]
+    struct Measurement {
+        value: f64,
+    }
+
+    assert_de_tokens_with(
+        &Measurement { value: 0.3 },
+        &[
+            Token::Struct {
+                name: "Measurement",
+                len: 1,
+            },
+            Token::Str("value"),
+            Token::F64(0.1 + 0.2),
+            Token::StructEnd,
+        ],
+        |actual, expected| (actual.value - expected.value).abs() < 1e-9,
+        |m| format!("Measurement {{ value: {} }}", m.value),
+    );
+}
+
+#[test]
+fn test_type_without_partial_eq_or_debug() {
+    // A validating wrapper with no PartialEq or Debug of its own.
+    struct Positive(u32);
+
+    impl<'de> Deserialize<'de> for Positive {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let value = u32::deserialize(deserializer)?;
+            if value == 0 {
+                return Err(serde::de::Error::custom("expected a positive integer"));
+            }
+            Ok(Positive(value))
+        }
+    }
+
+    assert_de_tokens_with(
+        &Positive(7),
+        &[Token::U32(7)],
+        |actual, expected| actual.0 == expected.0,
+        |p| p.0.to_string(),
+    );
+
+    let value = de_tokens::<Positive>(&[Token::U32(1)]);
+    assert_eq!(value.0, 1);
+}
+
+#[test]
+#[should_panic(expected = "tokens deserialized to 2, expected 1")]
+fn test_comparison_failure_uses_format_closure() {
+    assert_de_tokens_with(
+        &1u32,
+        &[Token::U32(2)],
+        |actual, expected| actual == expected,
+        |v| v.to_string(),
+    );
+}
@@ -4,7 +4,7 @@
     clippy::used_underscore_binding
 )]
 
-use serde::de::value::{BorrowedStrDeserializer, MapDeserializer};
+use serde::de::value::{BorrowedStrDeserializer, MapDeserializer, SeqDeserializer};
 use serde::de::{Deserialize, Deserializer, IntoDeserializer};
 use serde_derive::Deserialize;
 use serde_test::{assert_de_tokens, assert_de_tokens_error, Token};
@@ -159,6 +159,209 @@ fn test_cow() {
     }
 }
 
+#[test]
+fn test_cow_path() {
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    #[derive(Deserialize)]
+    struct Paths<'a, 'b, 'c> {
+        copied: Cow<'a, Path>,
+
+        #[serde(borrow)]
+        borrowed: Cow<'b, Path>,
+
+        #[serde(borrow)]
+        os: Cow<'c, OsStr>,
+    }
+
+    struct BorrowedStr(&'static str);
+
+    impl<'de> IntoDeserializer<'de> for BorrowedStr {
+        type Deserializer = BorrowedStrDeserializer<'de, serde::de::value::Error>;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            BorrowedStrDeserializer::new(self.0)
+        }
+    }
+
+    let de = MapDeserializer::new(IntoIterator::into_iter([
+        ("copied", BorrowedStr("/copied")),
+        ("borrowed", BorrowedStr("/borrowed")),
+        ("os", BorrowedStr("os")),
+    ]));
+
+    let paths = Paths::deserialize(de).unwrap();
+
+    match paths.copied {
+        Cow::Owned(ref path) if path == Path::new("/copied") => {}
+        _ => panic!("expected a copied path"),
+    }
+
+    match paths.borrowed {
+        Cow::Borrowed(path) if path == Path::new("/borrowed") => {}
+        _ => panic!("expected a borrowed path"),
+    }
+
+    match paths.os {
+        Cow::Borrowed(os) if os == OsStr::new("os") => {}
+        _ => panic!("expected a borrowed OS string"),
+    }
+}
+
+#[test]
+fn test_cow_in_containers() {
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Nested<'a> {
+        #[serde(borrow)]
+        maybe: Option<Cow<'a, str>>,
+
+        #[serde(borrow)]
+        many: Vec<Cow<'a, str>>,
+
+        #[serde(borrow)]
+        raw: Option<Cow<'a, [u8]>>,
+
+        #[serde(borrow)]
+        keyed: BTreeMap<Cow<'a, str>, u32>,
+    }
+
+    let mut keyed = BTreeMap::new();
+    keyed.insert(Cow::Borrowed("key"), 1);
+    assert_de_tokens(
+        &Nested {
+            maybe: Some(Cow::Borrowed("maybe")),
+            many: vec![Cow::Borrowed("a"), Cow::Borrowed("b")],
+            raw: Some(Cow::Borrowed(b"raw")),
+            keyed,
+        },
+        &[
+            Token::Struct {
+                name: "Nested",
+                len: 4,
+            },
+            Token::BorrowedStr("maybe"),
+            Token::Some,
+            Token::BorrowedStr("maybe"),
+            Token::BorrowedStr("many"),
+            Token::Seq { len: Some(2) },
+            Token::BorrowedStr("a"),
+            Token::BorrowedStr("b"),
+            Token::SeqEnd,
+            Token::BorrowedStr("raw"),
+            Token::Some,
+            Token::BorrowedBytes(b"raw"),
+            Token::BorrowedStr("keyed"),
+            Token::Map { len: Some(1) },
+            Token::BorrowedStr("key"),
+            Token::U32(1),
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // The elements really are borrowed, not owned copies that happen to
+    // compare equal.
+    struct BorrowedStr(&'static str);
+
+    impl<'de> IntoDeserializer<'de> for BorrowedStr {
+        type Deserializer = BorrowedStrDeserializer<'de, serde::de::value::Error>;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            BorrowedStrDeserializer::new(self.0)
+        }
+    }
+
+    struct BorrowedSeq(Vec<BorrowedStr>);
+
+    impl<'de> IntoDeserializer<'de> for BorrowedSeq {
+        type Deserializer = SeqDeserializer<
+            <Vec<BorrowedStr> as IntoIterator>::IntoIter,
+            serde::de::value::Error,
+        >;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            SeqDeserializer::new(self.0.into_iter())
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct JustVec<'a> {
+        #[serde(borrow)]
+        many: Vec<Cow<'a, str>>,
+    }
+
+    let de = MapDeserializer::new(IntoIterator::into_iter([(
+        "many",
+        BorrowedSeq(vec![BorrowedStr("borrowed")]),
+    )]));
+    let just = JustVec::deserialize(de).unwrap();
+    match just.many.as_slice() {
+        [Cow::Borrowed("borrowed")] => {}
+        _ => panic!("expected a borrowed string"),
+    }
+}
+
+#[test]
+fn test_flatten() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Inner<'a> {
+        #[serde(borrow)]
+        name: &'a str,
+
+        #[serde(borrow)]
+        title: Cow<'a, str>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Outer<'a> {
+        #[serde(borrow, flatten)]
+        inner: Inner<'a>,
+    }
+
+    // Borrowed strings survive the flatten buffer.
+    assert_de_tokens(
+        &Outer {
+            inner: Inner {
+                name: "name",
+                title: Cow::Borrowed("title"),
+            },
+        },
+        &[
+            Token::Map { len: None },
+            Token::BorrowedStr("name"),
+            Token::BorrowedStr("name"),
+            Token::BorrowedStr("title"),
+            Token::BorrowedStr("title"),
+            Token::MapEnd,
+        ],
+    );
+
+    struct BorrowedStr(&'static str);
+
+    impl<'de> IntoDeserializer<'de> for BorrowedStr {
+        type Deserializer = BorrowedStrDeserializer<'de, serde::de::value::Error>;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            BorrowedStrDeserializer::new(self.0)
+        }
+    }
+
+    let de = MapDeserializer::new(IntoIterator::into_iter([
+        ("name", BorrowedStr("name")),
+        ("title", BorrowedStr("title")),
+    ]));
+
+    let outer = Outer::deserialize(de).unwrap();
+
+    match outer.inner.title {
+        Cow::Borrowed("title") => {}
+        _ => panic!("expected a borrowed string"),
+    }
+}
+
 #[test]
 fn test_lifetimes() {
     #[derive(Deserialize)]
@@ -0,0 +1,73 @@
+use serde::bytes::{ByteBuf, Bytes};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
+
+#[test]
+fn test_bytes() {
+    let bytes = Bytes::new(b"borrowed");
+
+    assert_ser_tokens(&bytes, &[Token::Bytes(b"borrowed")]);
+    assert_de_tokens(&bytes, &[Token::BorrowedBytes(b"borrowed")]);
+    assert_de_tokens(&bytes, &[Token::BorrowedStr("borrowed")]);
+}
+
+#[test]
+fn test_byte_buf() {
+    let buf = ByteBuf::from(&b"owned"[..]);
+
+    assert_tokens(&buf, &[Token::Bytes(b"owned")]);
+    assert_de_tokens(&buf, &[Token::ByteBuf(b"owned")]);
+    assert_de_tokens(&buf, &[Token::Str("owned")]);
+    assert_de_tokens(&buf, &[Token::String("owned")]);
+    assert_de_tokens(
+        &buf,
+        &[
+            Token::Seq { len: Some(5) },
+            Token::U8(b'o'),
+            Token::U8(b'w'),
+            Token::U8(b'n'),
+            Token::U8(b'e'),
+            Token::U8(b'd'),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_derived() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Packet<'a> {
+        #[serde(borrow)]
+        header: &'a Bytes,
+        payload: ByteBuf,
+    }
+
+    assert_tokens(
+        &Packet {
+            header: Bytes::new(b"head"),
+            payload: ByteBuf::from(&b"body"[..]),
+        },
+        &[
+            Token::Struct {
+                name: "Packet",
+                len: 2,
+            },
+            Token::Str("header"),
+            Token::BorrowedBytes(b"head"),
+            Token::Str("payload"),
+            Token::Bytes(b"body"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_accessors() {
+    let mut buf = ByteBuf::from(vec![1, 2, 3]);
+    buf[0] = 9;
+    assert_eq!(&buf[..], [9, 2, 3]);
+    assert_eq!(buf.clone().into_vec(), vec![9, 2, 3]);
+
+    let bytes: &Bytes = std::borrow::Borrow::borrow(&buf);
+    assert_eq!(bytes, Bytes::new(&[9, 2, 3]));
+}
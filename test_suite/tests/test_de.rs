@@ -14,6 +14,7 @@ use fnv::FnvHasher;
 use serde::de::{Deserialize, DeserializeOwned, Deserializer, IntoDeserializer};
 use serde_derive::Deserialize;
 use serde_test::{assert_de_tokens, Configure, Token};
+use std::cmp;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::default::Default;
 use std::ffi::{CStr, CString, OsString};
@@ -837,6 +838,9 @@ fn test_char() {
     test('a', &[Token::Char('a')]);
     test('a', &[Token::Str("a")]);
     test('a', &[Token::String("a")]);
+    test('a', &[Token::U32(0x61)]);
+    test('a', &[Token::U64(0x61)]);
+    test('\u{10FFFF}', &[Token::U32(0x0010_FFFF)]);
 }
 
 #[test]
@@ -1771,22 +1775,23 @@ fn test_boxed_slice() {
 
 #[test]
 fn test_duration() {
+    let struct_tokens = &[
+        Token::Struct {
+            name: "Duration",
+            len: 2,
+        },
+        Token::Str("secs"),
+        Token::U64(1),
+        Token::Str("nanos"),
+        Token::U32(2),
+        Token::StructEnd,
+    ];
+    test(Duration::new(1, 2).compact(), struct_tokens);
+    // Human-readable formats are self-describing, so the struct form written
+    // by older versions still deserializes.
+    test(Duration::new(1, 2).readable(), struct_tokens);
     test(
-        Duration::new(1, 2),
-        &[
-            Token::Struct {
-                name: "Duration",
-                len: 2,
-            },
-            Token::Str("secs"),
-            Token::U64(1),
-            Token::Str("nanos"),
-            Token::U32(2),
-            Token::StructEnd,
-        ],
-    );
-    test(
-        Duration::new(1, 2),
+        Duration::new(1, 2).compact(),
         &[
             Token::Seq { len: Some(2) },
             Token::I64(1),
@@ -1797,23 +1802,34 @@ fn test_duration() {
 }
 
 #[test]
-fn test_system_time() {
+fn test_duration_from_string() {
+    test(Duration::new(1, 2).readable(), &[Token::Str("1.000000002s")]);
+    test(Duration::new(5, 0).readable(), &[Token::Str("5s")]);
     test(
-        UNIX_EPOCH + Duration::new(1, 2),
-        &[
-            Token::Struct {
-                name: "SystemTime",
-                len: 2,
-            },
-            Token::Str("secs_since_epoch"),
-            Token::U64(1),
-            Token::Str("nanos_since_epoch"),
-            Token::U32(2),
-            Token::StructEnd,
-        ],
+        Duration::new(1, 500_000_000).readable(),
+        &[Token::Str("1.5s")],
     );
+}
+
+#[test]
+fn test_system_time() {
+    let struct_tokens = &[
+        Token::Struct {
+            name: "SystemTime",
+            len: 2,
+        },
+        Token::Str("secs_since_epoch"),
+        Token::U64(1),
+        Token::Str("nanos_since_epoch"),
+        Token::U32(2),
+        Token::StructEnd,
+    ];
+    test((UNIX_EPOCH + Duration::new(1, 2)).compact(), struct_tokens);
+    // Human-readable formats are self-describing, so the struct form written
+    // by older versions still deserializes.
+    test((UNIX_EPOCH + Duration::new(1, 2)).readable(), struct_tokens);
     test(
-        UNIX_EPOCH + Duration::new(1, 2),
+        (UNIX_EPOCH + Duration::new(1, 2)).compact(),
         &[
             Token::Seq { len: Some(2) },
             Token::I64(1),
@@ -1823,6 +1839,31 @@ fn test_system_time() {
     );
 }
 
+#[test]
+fn test_system_time_from_string() {
+    test(
+        (UNIX_EPOCH + Duration::new(1_000_000_000, 0)).readable(),
+        &[Token::Str("2001-09-09T01:46:40Z")],
+    );
+    test(
+        (UNIX_EPOCH + Duration::new(1_000_000_000, 2)).readable(),
+        &[Token::Str("2001-09-09T01:46:40.000000002Z")],
+    );
+    // Lowercase separators and an explicit UTC offset are accepted.
+    test(
+        (UNIX_EPOCH + Duration::new(1_000_000_000, 0)).readable(),
+        &[Token::Str("2001-09-09 01:46:40z")],
+    );
+    test(
+        (UNIX_EPOCH + Duration::new(1_000_000_000, 0)).readable(),
+        &[Token::Str("2001-09-09T03:46:40+02:00")],
+    );
+    test(
+        (UNIX_EPOCH + Duration::new(1_000_000_000, 0)).readable(),
+        &[Token::Str("2001-09-08T23:46:40-02:00")],
+    );
+}
+
 #[test]
 fn test_range() {
     test(
@@ -1905,6 +1946,266 @@ fn test_bound() {
     );
 }
 
+#[test]
+fn test_cmp_ordering() {
+    test(
+        cmp::Ordering::Less,
+        &[Token::UnitVariant {
+            name: "Ordering",
+            variant: "Less",
+        }],
+    );
+    test(
+        cmp::Ordering::Equal,
+        &[Token::UnitVariant {
+            name: "Ordering",
+            variant: "Equal",
+        }],
+    );
+    test(
+        cmp::Ordering::Greater,
+        &[Token::UnitVariant {
+            name: "Ordering",
+            variant: "Greater",
+        }],
+    );
+}
+
+#[test]
+fn test_backtrace() {
+    let de = serde::de::value::StrDeserializer::<serde::de::value::Error>::new("anything");
+    let backtrace = std::backtrace::Backtrace::deserialize(de).unwrap();
+    assert_eq!(
+        backtrace.status(),
+        std::backtrace::BacktraceStatus::Disabled
+    );
+}
+
+#[test]
+fn test_instant() {
+    let de = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+        vec![("secs", -1i64), ("nanos", 0)].into_iter(),
+    );
+    let instant = std::time::Instant::deserialize(de).unwrap();
+    assert!(instant <= std::time::Instant::now());
+
+    let de = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+        vec![("secs", 1i64), ("nanos", -1)].into_iter(),
+    );
+    assert!(std::time::Instant::deserialize(de).is_err());
+}
+
+#[test]
+fn test_num_helpers() {
+    use serde::de::value::Error;
+    use serde::de::num;
+
+    let exp = &"a small integer";
+    assert_eq!(num::checked_from_i64::<u8, Error>(1, exp).unwrap(), 1);
+    assert!(num::checked_from_i64::<u8, Error>(-1, exp).is_err());
+    assert!(num::checked_from_i64::<u8, Error>(300, exp).is_err());
+    assert_eq!(num::checked_from_u64::<i8, Error>(127, exp).unwrap(), 127);
+    assert!(num::checked_from_u64::<i8, Error>(128, exp).is_err());
+    assert_eq!(num::checked_from_i128::<u64, Error>(1, exp).unwrap(), 1);
+    assert!(num::checked_from_u128::<u64, Error>(u128::MAX, exp).is_err());
+    assert_eq!(num::checked_from_f64::<u8, Error>(1.0, exp).unwrap(), 1);
+    assert_eq!(num::checked_from_f64::<i8, Error>(-2.0, exp).unwrap(), -2);
+    assert!(num::checked_from_f64::<u8, Error>(1.5, exp).is_err());
+    assert!(num::checked_from_f64::<u8, Error>(-1.0, exp).is_err());
+    assert!(num::checked_from_f64::<u8, Error>(1e300, exp).is_err());
+}
+
+#[test]
+fn test_lazy() {
+    let de = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+        vec![("a", 1u32), ("b", 2)].into_iter(),
+    );
+    let mut lazy = serde::de::Lazy::<BTreeMap<String, u32>>::deserialize(de).unwrap();
+    assert!(!lazy.is_decoded());
+    assert_eq!(lazy.get().unwrap()["a"], 1);
+    assert!(lazy.is_decoded());
+    assert_eq!(lazy.get().unwrap()["b"], 2);
+    let map = lazy.into_inner().unwrap();
+    assert_eq!(map.len(), 2);
+
+    // Decoding errors surface on access, not during the initial capture.
+    let de = serde::de::value::StrDeserializer::<serde::de::value::Error>::new("xyz");
+    let mut lazy = serde::de::Lazy::<u32>::deserialize(de).unwrap();
+    assert!(lazy.get().is_err());
+}
+
+#[test]
+fn test_for_each() {
+    use serde::de::{DeserializeSeed, ForEach, ForEachEntry};
+
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec![1u64, 2, 3].into_iter(),
+    );
+    let mut sum = 0;
+    let count = ForEach::new(|value: u64| sum += value).deserialize(de).unwrap();
+    assert_eq!(sum, 6);
+    assert_eq!(count, 3);
+
+    let de = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+        vec![("a", 1u64), ("b", 2)].into_iter(),
+    );
+    let mut keys = String::new();
+    let mut total = 0;
+    let count = ForEachEntry::new(|key: String, value: u64| {
+        keys.push_str(&key);
+        total += value;
+    })
+    .deserialize(de)
+    .unwrap();
+    assert_eq!(keys, "ab");
+    assert_eq!(total, 3);
+    assert_eq!(count, 2);
+
+    // Element errors propagate out of the seed.
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec!["x"].into_iter(),
+    );
+    assert!(ForEach::new(|_: u64| ()).deserialize(de).is_err());
+}
+
+#[test]
+fn test_schema_deserializer() {
+    use serde::de::schema::{Schema, SchemaDeserializer};
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Rect {
+        width: u64,
+        height: u64,
+    }
+
+    let schema = Schema::Struct {
+        name: "Rect".to_owned(),
+        fields: vec![
+            ("width".to_owned(), Schema::U64),
+            ("height".to_owned(), Schema::U64),
+        ],
+    };
+
+    // The positional stream carries no field names; the schema supplies them.
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec![3u32, 4].into_iter(),
+    );
+    let rect = Rect::deserialize(SchemaDeserializer::new(de, &schema)).unwrap();
+    assert_eq!(rect, Rect { width: 3, height: 4 });
+
+    // The same stream can be read through `deserialize_any` consumers.
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec![3u32, 4].into_iter(),
+    );
+    let map = BTreeMap::<String, u64>::deserialize(SchemaDeserializer::new(de, &schema)).unwrap();
+    assert_eq!(map["width"], 3);
+    assert_eq!(map["height"], 4);
+
+    let schema = Schema::Seq(Box::new(Schema::U64));
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec![1u32, 2, 3].into_iter(),
+    );
+    let seq = Vec::<u64>::deserialize(SchemaDeserializer::new(de, &schema)).unwrap();
+    assert_eq!(seq, [1, 2, 3]);
+}
+
+#[test]
+fn test_seq_iter() {
+    struct Sum(u64);
+
+    impl<'de> Deserialize<'de> for Sum {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct SumVisitor;
+
+            impl<'de> serde::de::Visitor<'de> for SumVisitor {
+                type Value = Sum;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    formatter.write_str("a sequence of integers")
+                }
+
+                fn visit_seq<A>(self, seq: A) -> Result<Sum, A::Error>
+                where
+                    A: serde::de::SeqAccess<'de>,
+                {
+                    serde::de::SeqIter::<_, u64>::new(seq).sum::<Result<u64, A::Error>>().map(Sum)
+                }
+            }
+
+            deserializer.deserialize_seq(SumVisitor)
+        }
+    }
+
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec![1u64, 2, 3].into_iter(),
+    );
+    let sum = Sum::deserialize(de).unwrap();
+    assert_eq!(sum.0, 6);
+}
+
+#[test]
+fn test_boxed_array() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Blob {
+        #[serde(deserialize_with = "serde::de::boxed_array")]
+        data: Box<[u32; 3]>,
+    }
+
+    assert_de_tokens(
+        &Blob {
+            data: Box::new([1, 2, 3]),
+        },
+        &[
+            Token::Struct {
+                name: "Blob",
+                len: 1,
+            },
+            Token::Str("data"),
+            Token::Tuple { len: 3 },
+            Token::U32(1),
+            Token::U32(2),
+            Token::U32(3),
+            Token::TupleEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // Large enough that building the array on the stack would overflow it in
+    // debug builds.
+    const N: usize = 1 << 20;
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        iter::repeat(1u8).take(N),
+    );
+    let big: Box<[u8; N]> = serde::de::boxed_array(de).unwrap();
+    assert_eq!(big[N - 1], 1);
+}
+
+#[test]
+fn test_error_unexpected_capture() {
+    use serde::de::Error as _;
+
+    let err = serde::de::value::Error::invalid_type_of(&true, &"a string");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: boolean `true`, expected a string"
+    );
+
+    let err = serde::de::value::Error::invalid_value_of("xyz", &"a number");
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"xyz\", expected a number"
+    );
+
+    let err = serde::de::value::Error::invalid_type_of(&5u8, &"a string");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `5`, expected a string"
+    );
+}
+
 #[test]
 fn test_path() {
     test(
@@ -1917,22 +2218,122 @@ fn test_path() {
     );
 }
 
+#[test]
+fn test_path_components() {
+    use std::ffi::OsStr;
+    use std::path::Component;
+
+    test(
+        Component::RootDir,
+        &[Token::UnitVariant {
+            name: "Component",
+            variant: "RootDir",
+        }],
+    );
+    test(
+        Component::CurDir,
+        &[Token::UnitVariant {
+            name: "Component",
+            variant: "CurDir",
+        }],
+    );
+    test(
+        Component::ParentDir,
+        &[Token::UnitVariant {
+            name: "Component",
+            variant: "ParentDir",
+        }],
+    );
+    test(
+        Component::Normal(OsStr::new("lib")),
+        &[
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::BorrowedStr("lib"),
+        ],
+    );
+
+    // A full path round trips through Vec<Component>.
+    let components: Vec<Component> = Path::new("/usr/local/lib").components().collect();
+    test(
+        components,
+        &[
+            Token::Seq { len: None },
+            Token::UnitVariant {
+                name: "Component",
+                variant: "RootDir",
+            },
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::BorrowedStr("usr"),
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::BorrowedStr("local"),
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::BorrowedStr("lib"),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[cfg(windows)]
+#[test]
+fn test_path_component_prefix() {
+    use std::path::Component;
+
+    let components: Vec<Component> = Path::new(r"C:\lib").components().collect();
+    test(
+        components,
+        &[
+            Token::Seq { len: None },
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Prefix",
+            },
+            Token::BorrowedStr("C:"),
+            Token::UnitVariant {
+                name: "Component",
+                variant: "RootDir",
+            },
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::BorrowedStr("lib"),
+            Token::SeqEnd,
+        ],
+    );
+}
+
 #[test]
 fn test_path_buf() {
     test(
-        PathBuf::from("/usr/local/lib"),
+        PathBuf::from("/usr/local/lib").compact(),
+        &[Token::Str("/usr/local/lib")],
+    );
+    test(
+        PathBuf::from("/usr/local/lib").readable(),
         &[Token::Str("/usr/local/lib")],
     );
     test(
-        PathBuf::from("/usr/local/lib"),
+        PathBuf::from("/usr/local/lib").readable(),
         &[Token::String("/usr/local/lib")],
     );
     test(
-        PathBuf::from("/usr/local/lib"),
+        PathBuf::from("/usr/local/lib").readable(),
         &[Token::Bytes(b"/usr/local/lib")],
     );
     test(
-        PathBuf::from("/usr/local/lib"),
+        PathBuf::from("/usr/local/lib").readable(),
         &[Token::ByteBuf(b"/usr/local/lib")],
     );
 }
@@ -1940,23 +2341,58 @@ fn test_path_buf() {
 #[test]
 fn test_boxed_path() {
     test(
-        PathBuf::from("/usr/local/lib").into_boxed_path(),
+        PathBuf::from("/usr/local/lib").into_boxed_path().compact(),
         &[Token::Str("/usr/local/lib")],
     );
     test(
-        PathBuf::from("/usr/local/lib").into_boxed_path(),
+        PathBuf::from("/usr/local/lib").into_boxed_path().readable(),
         &[Token::String("/usr/local/lib")],
     );
     test(
-        PathBuf::from("/usr/local/lib").into_boxed_path(),
+        PathBuf::from("/usr/local/lib").into_boxed_path().readable(),
         &[Token::Bytes(b"/usr/local/lib")],
     );
     test(
-        PathBuf::from("/usr/local/lib").into_boxed_path(),
+        PathBuf::from("/usr/local/lib").into_boxed_path().readable(),
         &[Token::ByteBuf(b"/usr/local/lib")],
     );
 }
 
+#[test]
+fn test_rc_path() {
+    test(
+        Rc::<Path>::from(Path::new("/usr/local/lib")).readable(),
+        &[Token::Str("/usr/local/lib")],
+    );
+}
+
+#[test]
+fn test_arc_path() {
+    test(
+        Arc::<Path>::from(Path::new("/usr/local/lib")).readable(),
+        &[Token::Str("/usr/local/lib")],
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_boxed_osstr() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let value = OsString::from_vec(vec![1, 2, 3]).into_boxed_os_str();
+    let tokens = [
+        Token::Enum { name: "OsString" },
+        Token::Str("Unix"),
+        Token::Seq { len: Some(2) },
+        Token::U8(1),
+        Token::U8(2),
+        Token::U8(3),
+        Token::SeqEnd,
+    ];
+
+    assert_de_tokens(&value.compact(), &tokens);
+}
+
 #[test]
 fn test_cstring() {
     test(CString::new("abc").unwrap(), &[Token::Bytes(b"abc")]);
@@ -2004,6 +2440,30 @@ fn test_wrapping() {
     test(Wrapping(1usize), &[Token::U64(1)]);
 }
 
+#[test]
+fn test_saturating() {
+    test(std::num::Saturating(1usize), &[Token::U32(1)]);
+    test(std::num::Saturating(1usize), &[Token::U64(1)]);
+}
+
+#[test]
+fn test_once_cell() {
+    test(std::cell::OnceCell::<bool>::new(), &[Token::None]);
+
+    let cell = std::cell::OnceCell::new();
+    cell.set(true).unwrap();
+    test(cell, &[Token::Some, Token::Bool(true)]);
+}
+
+#[test]
+fn test_once_lock() {
+    test(std::sync::OnceLock::<bool>::new(), &[Token::None]);
+
+    let lock = std::sync::OnceLock::new();
+    lock.set(true).unwrap();
+    test(lock, &[Token::Some, Token::Bool(true)]);
+}
+
 #[test]
 fn test_rc_dst() {
     test(Rc::<str>::from("s"), &[Token::Str("s")]);
@@ -2223,10 +2683,35 @@ fn test_osstring() {
         Token::SeqEnd,
     ];
 
-    assert_de_tokens(&value, &tokens);
+    assert_de_tokens(&value.clone().compact(), &tokens);
+    // Human-readable formats are self-describing, so the tagged platform
+    // encoding written by older versions still deserializes.
+    assert_de_tokens(&value.readable(), &tokens);
     assert_de_tokens_ignore(&tokens);
 }
 
+#[cfg(unix)]
+#[test]
+fn test_osstring_portable() {
+    use std::os::unix::ffi::OsStringExt;
+
+    test(OsString::from("abc").readable(), &[Token::Str("abc")]);
+    test(
+        OsString::from_vec(b"ab\xC3".to_vec()).readable(),
+        &[Token::Bytes(b"ab\xC3")],
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_pathbuf() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let value = PathBuf::from(OsStr::from_bytes(b"/tmp/\xC3").to_os_string());
+    test(value.readable(), &[Token::Bytes(b"/tmp/\xC3")]);
+}
+
 #[cfg(windows)]
 #[test]
 fn test_osstring() {
@@ -2243,7 +2728,8 @@ fn test_osstring() {
         Token::SeqEnd,
     ];
 
-    assert_de_tokens(&value, &tokens);
+    assert_de_tokens(&value.clone().compact(), &tokens);
+    assert_de_tokens(&value.readable(), &tokens);
     assert_de_tokens_ignore(&tokens);
 }
 
@@ -2288,3 +2774,263 @@ fn test_atomics() {
         test(AtomicU64::load, 8589934592u64);
     }
 }
+
+#[test]
+fn test_lenient_vec() {
+    let de = serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(
+        vec![1i32, 300, 3].into_iter(),
+    );
+    let v = serde::de::LenientVec::<u8>::deserialize(de).unwrap();
+    assert_eq!(v.elements(), [1, 3]);
+    assert_eq!(v.errors().len(), 1);
+}
+
+#[test]
+fn test_lenient_map() {
+    let de = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+        vec![("a", 1i32), ("b", 300), ("c", 3)].into_iter(),
+    );
+    let m = serde::de::LenientMap::<String, u8>::deserialize(de).unwrap();
+    assert_eq!(m.entries().len(), 2);
+    assert_eq!(m.entries()["a"], 1);
+    assert_eq!(m.entries()["c"], 3);
+    assert_eq!(m.errors().len(), 1);
+}
+
+#[test]
+fn test_non_self_describing() {
+    use serde::de::value::{Error, SeqDeserializer};
+
+    let de = macros::NonSelfDescribing(SeqDeserializer::<_, Error>::new(
+        vec![1i32, 2, 3].into_iter(),
+    ));
+    assert!(!de.is_self_describing());
+    let v = Vec::<i32>::deserialize(de).unwrap();
+    assert_eq!(v, [1, 2, 3]);
+
+    // Untagged enums buffer through deserialize_any and must be rejected.
+    #[derive(Deserialize, Debug)]
+    #[serde(untagged)]
+    enum Untagged {
+        Int(i32),
+    }
+
+    let de = macros::NonSelfDescribing(<i32 as IntoDeserializer>::into_deserializer(1));
+    let err = Untagged::deserialize(de).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "deserialize_any is not supported by non-self-describing formats",
+    );
+}
+
+#[test]
+fn test_chunked_visits() {
+    use serde::de::value::Error;
+    use serde::de::{BytesChunkAccess, Error as _, StrChunkAccess, Visitor};
+    use std::fmt;
+
+    struct Chunks<'a>(std::slice::Iter<'a, &'a str>);
+
+    impl<'de, 'a> StrChunkAccess<'de> for Chunks<'a> {
+        type Error = Error;
+
+        fn next_chunk(&mut self) -> Result<Option<&str>, Error> {
+            Ok(self.0.next().copied())
+        }
+    }
+
+    impl<'de, 'a> BytesChunkAccess<'de> for Chunks<'a> {
+        type Error = Error;
+
+        fn next_chunk(&mut self) -> Result<Option<&[u8]>, Error> {
+            Ok(self.0.next().map(|chunk| chunk.as_bytes()))
+        }
+    }
+
+    // The default implementations accumulate into one owned value.
+    struct Owned;
+
+    impl<'de> Visitor<'de> for Owned {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<String, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(v.to_owned())
+        }
+    }
+
+    let string = Owned
+        .visit_str_chunked(Chunks(["stream", "ed in ", "pieces"].iter()))
+        .unwrap();
+    assert_eq!(string, "streamed in pieces");
+
+    // A streaming visitor consumes the chunks without collecting them.
+    struct ByteCount;
+
+    impl<'de> Visitor<'de> for ByteCount {
+        type Value = usize;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a byte array")
+        }
+
+        fn visit_bytes_chunked<A>(self, mut chunks: A) -> Result<usize, A::Error>
+        where
+            A: BytesChunkAccess<'de>,
+        {
+            let mut total = 0;
+            while let Some(chunk) = chunks.next_chunk()? {
+                total += chunk.len();
+            }
+            Ok(total)
+        }
+    }
+
+    let total = ByteCount
+        .visit_bytes_chunked(Chunks(["ab", "cde"].iter()))
+        .unwrap();
+    assert_eq!(total, 5);
+}
+
+#[test]
+fn test_size_limit() {
+    use serde::de::value::{Error, SeqAccessDeserializer, SeqDeserializer};
+    use serde::de::{DeserializeSeed, SeqAccess, SizeLimit};
+
+    // Claims an absurd length for a three-element sequence.
+    struct HugeHint<A>(A);
+
+    impl<'de, A> SeqAccess<'de> for HugeHint<A>
+    where
+        A: SeqAccess<'de>,
+    {
+        type Error = A::Error;
+
+        fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+        where
+            T: DeserializeSeed<'de>,
+        {
+            self.0.next_element_seed(seed)
+        }
+
+        fn size_hint(&self) -> Option<usize> {
+            Some(usize::MAX)
+        }
+    }
+
+    let deserialize = || {
+        let access = HugeHint(SeqDeserializer::<_, Error>::new(vec![1u64, 2, 3].into_iter()));
+        Vec::<u64>::deserialize(SeqAccessDeserializer::new(access)).unwrap()
+    };
+
+    // The default 1 MiB budget caps the preallocation at 131072 u64s.
+    let vec = deserialize();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.capacity(), 1024 * 1024 / 8);
+
+    let previous = SizeLimit::set_max_preallocation_bytes(1024);
+    let vec = deserialize();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.capacity(), 1024 / 8);
+    SizeLimit::set_max_preallocation_bytes(previous);
+}
+
+#[test]
+fn test_no_size_hint() {
+    use serde::de::value::{Error, MapAccessDeserializer, MapDeserializer, SeqAccessDeserializer, SeqDeserializer};
+
+    let access = SeqDeserializer::<_, Error>::new(vec![1i32, 2, 3].into_iter());
+    let v = Vec::<i32>::deserialize(SeqAccessDeserializer::new(macros::NoSizeHint(access))).unwrap();
+    assert_eq!(v, [1, 2, 3]);
+
+    let access = MapDeserializer::<_, Error>::new(vec![("a", 1i32), ("b", 2)].into_iter());
+    let m =
+        BTreeMap::<String, i32>::deserialize(MapAccessDeserializer::new(macros::NoSizeHint(access)))
+            .unwrap();
+    assert_eq!(m.len(), 2);
+    assert_eq!(m["a"], 1);
+    assert_eq!(m["b"], 2);
+}
+
+#[test]
+fn test_bulk_seq_access() {
+    use serde::de::value::{Error, SeqAccessDeserializer};
+    use serde::de::{DeserializeSeed, IntoDeserializer, SeqAccess};
+    use std::cell::Cell;
+
+    // Overrides the bulk methods the way a binary format serving contiguous
+    // primitive runs would, recording that they were chosen over the
+    // element-by-element path.
+    struct Run<'a> {
+        data: std::vec::IntoIter<u32>,
+        bulk: &'a Cell<bool>,
+    }
+
+    impl<'de, 'a> SeqAccess<'de> for Run<'a> {
+        type Error = Error;
+
+        fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+        where
+            T: DeserializeSeed<'de>,
+        {
+            match self.data.next() {
+                Some(v) => seed.deserialize(v.into_deserializer()).map(Some),
+                None => Ok(None),
+            }
+        }
+
+        fn next_elements<T>(&mut self, out: &mut Vec<T>) -> Result<(), Error>
+        where
+            T: Deserialize<'de>,
+        {
+            self.bulk.set(true);
+            for v in self.data.by_ref() {
+                out.push(T::deserialize(v.into_deserializer())?);
+            }
+            Ok(())
+        }
+
+        fn next_slice<T>(&mut self, buf: &mut [T]) -> Result<usize, Error>
+        where
+            T: Deserialize<'de>,
+        {
+            self.bulk.set(true);
+            let mut filled = 0;
+            for dest in buf.iter_mut() {
+                match self.data.next() {
+                    Some(v) => *dest = T::deserialize(v.into_deserializer())?,
+                    None => break,
+                }
+                filled += 1;
+            }
+            Ok(filled)
+        }
+    }
+
+    let bulk = Cell::new(false);
+    let run = Run {
+        data: vec![1, 2, 3].into_iter(),
+        bulk: &bulk,
+    };
+    let vec = Vec::<u32>::deserialize(SeqAccessDeserializer::new(run)).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+    assert!(bulk.get());
+
+    // deserialize_in_place overwrites existing elements through next_slice and
+    // appends the rest through next_elements.
+    bulk.set(false);
+    let mut place = vec![9u32, 9];
+    let run = Run {
+        data: vec![4, 5, 6].into_iter(),
+        bulk: &bulk,
+    };
+    Vec::deserialize_in_place(SeqAccessDeserializer::new(run), &mut place).unwrap();
+    assert_eq!(place, [4, 5, 6]);
+    assert!(bulk.get());
+}
@@ -7,7 +7,7 @@
 
 use serde::de::{Deserialize, IntoDeserializer};
 use serde_derive::Deserialize;
-use serde_test::{assert_de_tokens_error, Token};
+use serde_test::{assert_de_tokens_error, Compact, Token};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::{CStr, CString};
 use std::num::{
@@ -1151,6 +1151,27 @@ fn test_unknown_field() {
     );
 }
 
+#[test]
+fn test_unknown_field_typo_suggestion() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Struct {
+        nickname: u8,
+        location: u8,
+    }
+
+    assert_de_tokens_error::<Struct>(
+        &[
+            Token::Struct {
+                name: "Struct",
+                len: 2,
+            },
+            Token::Str("nicknam"),
+        ],
+        "unknown field `nicknam`, expected `nickname` or `location`, did you mean `nickname`?",
+    );
+}
+
 #[test]
 fn test_skipped_field_is_unknown() {
     assert_de_tokens_error::<StructDenyUnknown>(
@@ -1318,6 +1339,25 @@ fn test_string_from_unit() {
     );
 }
 
+#[test]
+fn test_char_from_invalid_code_point() {
+    // A surrogate is not a valid Unicode scalar value.
+    assert_de_tokens_error::<char>(
+        &[Token::U32(0xD800)],
+        "invalid value: integer `55296`, expected a character",
+    );
+
+    assert_de_tokens_error::<char>(
+        &[Token::U64(0x0011_0000)],
+        "invalid value: integer `1114112`, expected a character",
+    );
+
+    assert_de_tokens_error::<char>(
+        &[Token::U64(u64::MAX)],
+        "invalid value: integer `18446744073709551615`, expected a character",
+    );
+}
+
 #[test]
 fn test_btreeset_from_unit() {
     assert_de_tokens_error::<BTreeSet<isize>>(
@@ -1456,7 +1496,7 @@ fn test_wrapping_overflow() {
 
 #[test]
 fn test_duration_overflow_seq() {
-    assert_de_tokens_error::<Duration>(
+    assert_de_tokens_error::<Compact<Duration>>(
         &[
             Token::Seq { len: Some(2) },
             Token::U64(u64::max_value()),
@@ -1469,7 +1509,7 @@ fn test_duration_overflow_seq() {
 
 #[test]
 fn test_duration_overflow_struct() {
-    assert_de_tokens_error::<Duration>(
+    assert_de_tokens_error::<Compact<Duration>>(
         &[
             Token::Struct {
                 name: "Duration",
@@ -1487,7 +1527,7 @@ fn test_duration_overflow_struct() {
 
 #[test]
 fn test_systemtime_overflow_seq() {
-    assert_de_tokens_error::<SystemTime>(
+    assert_de_tokens_error::<Compact<SystemTime>>(
         &[
             Token::Seq { len: Some(2) },
             Token::U64(u64::max_value()),
@@ -1500,7 +1540,7 @@ fn test_systemtime_overflow_seq() {
 
 #[test]
 fn test_systemtime_overflow_struct() {
-    assert_de_tokens_error::<SystemTime>(
+    assert_de_tokens_error::<Compact<SystemTime>>(
         &[
             Token::Struct {
                 name: "SystemTime",
@@ -1519,7 +1559,7 @@ fn test_systemtime_overflow_struct() {
 #[cfg(systemtime_checked_add)]
 #[test]
 fn test_systemtime_overflow() {
-    assert_de_tokens_error::<SystemTime>(
+    assert_de_tokens_error::<Compact<SystemTime>>(
         &[
             Token::Seq { len: Some(2) },
             Token::U64(u64::max_value()),
@@ -1559,3 +1599,106 @@ fn test_never_type() {
         "cannot deserialize `!`",
     );
 }
+
+#[cfg(unix)]
+#[test]
+fn test_path_component_prefix_on_unix() {
+    assert_de_tokens_error::<std::path::Component>(
+        &[
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Prefix",
+            },
+            Token::BorrowedStr("C:"),
+        ],
+        "invalid value: string \"C:\", expected a Windows path prefix",
+    );
+}
+
+mod error_kind {
+    use super::*;
+    use serde::de::value::MapDeserializer;
+    use serde::de::{Error as _, ErrorKind};
+    use std::fmt;
+
+    // An error type that stores the structured kind alongside the message,
+    // the way a format's error type would.
+    #[derive(Debug)]
+    struct KindError {
+        message: String,
+        kind: Option<ErrorKind>,
+    }
+
+    impl fmt::Display for KindError {
+        fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(&self.message)
+        }
+    }
+
+    impl std::error::Error for KindError {}
+
+    impl serde::de::Error for KindError {
+        fn custom<T: fmt::Display>(msg: T) -> Self {
+            KindError {
+                message: msg.to_string(),
+                kind: None,
+            }
+        }
+
+        fn from_kind(kind: ErrorKind) -> Self {
+            KindError {
+                message: kind.to_string(),
+                kind: Some(kind),
+            }
+        }
+
+        fn kind(&self) -> Option<&ErrorKind> {
+            self.kind.as_ref()
+        }
+    }
+
+    #[test]
+    fn test_missing_field_kind() {
+        let de = MapDeserializer::<_, KindError>::new(std::iter::empty::<(&str, i32)>());
+        let err = Struct::deserialize(de).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `a`");
+        assert_eq!(err.kind(), Some(&ErrorKind::MissingField { field: "a" }));
+    }
+
+    #[test]
+    fn test_unknown_field_kind() {
+        let de = MapDeserializer::<_, KindError>::new(vec![("unrecognized", 1i32)].into_iter());
+        let err = StructDenyUnknown::deserialize(de).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown field `unrecognized`, expected `a`"
+        );
+        assert_eq!(
+            err.kind(),
+            Some(&ErrorKind::UnknownField {
+                field: String::from("unrecognized"),
+                expected: &["a"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_type_kind() {
+        let err = u32::deserialize(IntoDeserializer::<KindError>::into_deserializer("x"))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid type: string \"x\", expected u32");
+        assert_eq!(
+            err.kind(),
+            Some(&ErrorKind::InvalidType {
+                unexpected: String::from("string \"x\""),
+                expected: String::from("u32"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_custom_has_no_kind() {
+        let err = KindError::custom("something else");
+        assert_eq!(err.kind(), None);
+    }
+}
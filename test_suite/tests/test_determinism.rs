@@ -0,0 +1,86 @@
+//! Helpers for asserting that serialization is deterministic.
+//!
+//! serde_test is maintained out of tree, so the assertion helpers live here
+//! alongside the tests that exercise them. A value is serialized twice (for
+//! the closure-based variant, rebuilt in between, so e.g. differently
+//! populated map states are covered) and the two event streams must match.
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde::ser::Serialize;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+mod trace;
+use crate::trace::trace;
+
+/// Serializes the value twice and asserts the event streams are identical.
+fn assert_deterministic<T>(value: &T)
+where
+    T: Serialize,
+{
+    assert_deterministic_build(|| value);
+}
+
+/// Builds and serializes a value twice and asserts the event streams are
+/// identical, so construction-order effects (for example map insertion order)
+/// are part of what is being checked.
+fn assert_deterministic_build<T, F>(build: F)
+where
+    T: Serialize,
+    F: Fn() -> T,
+{
+    let first = trace(&build());
+    let second = trace(&build());
+    assert_eq!(
+        first, second,
+        "serialization of the value is not deterministic"
+    );
+}
+
+#[test]
+fn test_primitives_deterministic() {
+    assert_deterministic(&true);
+    assert_deterministic(&-5i32);
+    assert_deterministic(&"hello");
+    assert_deterministic(&Some(1u8));
+}
+
+#[test]
+fn test_btreemap_deterministic_across_insertion_orders() {
+    // The map is rebuilt with a different insertion order on every call; a
+    // BTreeMap must serialize identically regardless.
+    let orders = std::cell::Cell::new(0u32);
+    assert_deterministic_build(|| {
+        let mut map = BTreeMap::new();
+        if orders.replace(orders.get() + 1) % 2 == 0 {
+            for i in 0..10u32 {
+                map.insert(i, i * i);
+            }
+        } else {
+            for i in (0..10u32).rev() {
+                map.insert(i, i * i);
+            }
+        }
+        map
+    });
+}
+
+#[test]
+fn test_struct_deterministic() {
+    #[derive(Serialize)]
+    struct Nested {
+        values: Vec<u32>,
+        tags: BTreeMap<String, String>,
+    }
+
+    assert_deterministic_build(|| Nested {
+        values: vec![1, 2, 3],
+        tags: {
+            let mut tags = BTreeMap::new();
+            tags.insert("b".to_owned(), "2".to_owned());
+            tags.insert("a".to_owned(), "1".to_owned());
+            tags
+        },
+    });
+}
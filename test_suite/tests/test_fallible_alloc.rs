@@ -0,0 +1,87 @@
+// The test suite enables serde's "fallible-alloc" feature, so every growing
+// collection below is routed through the try_reserve code paths. Allocation
+// failure itself cannot be provoked portably from a test; what these tests
+// pin down is that the fallible paths are exercised and behave identically to
+// the infallible ones when allocation succeeds.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use serde_test::{assert_de_tokens, Token};
+
+#[test]
+fn test_vec() {
+    assert_de_tokens(
+        &vec![1u32, 2, 3],
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U32(1),
+            Token::U32(2),
+            Token::U32(3),
+            Token::SeqEnd,
+        ],
+    );
+    // A missing size hint grows the collection element by element.
+    assert_de_tokens(
+        &vec![1u32, 2, 3],
+        &[
+            Token::Seq { len: None },
+            Token::U32(1),
+            Token::U32(2),
+            Token::U32(3),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_vec_deque() {
+    assert_de_tokens(
+        &VecDeque::from(vec![1u32, 2, 3]),
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U32(1),
+            Token::U32(2),
+            Token::U32(3),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_string() {
+    assert_de_tokens(&String::from("abc"), &[Token::Str("abc")]);
+    assert_de_tokens(&String::from("abc"), &[Token::String("abc")]);
+    assert_de_tokens(&String::from("abc"), &[Token::Bytes(b"abc")]);
+}
+
+#[test]
+fn test_hash_map() {
+    let mut map = HashMap::new();
+    map.insert('a', 1u32);
+    map.insert('b', 2);
+    assert_de_tokens(
+        &map,
+        &[
+            Token::Map { len: Some(2) },
+            Token::Char('a'),
+            Token::U32(1),
+            Token::Char('b'),
+            Token::U32(2),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_hash_set() {
+    let set: HashSet<u32> = [1, 2, 3].iter().copied().collect();
+    assert_de_tokens(
+        &set,
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U32(1),
+            Token::U32(2),
+            Token::U32(3),
+            Token::SeqEnd,
+        ],
+    );
+}
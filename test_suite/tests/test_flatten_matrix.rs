@@ -0,0 +1,428 @@
+//! A systematic matrix of `#[serde(flatten)]` combined with every enum
+//! representation, defaults, skips, and borrows. These interactions exercise
+//! the buffering machinery in serde's private::de module, which is where most
+//! flatten regressions show up.
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Inner {
+    x: i32,
+}
+
+#[test]
+fn test_flatten_externally_tagged_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Repr {
+        A { x: i32 },
+        B { y: i32 },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        tag_along: u32,
+        #[serde(flatten)]
+        repr: Repr,
+    }
+
+    assert_tokens(
+        &Outer {
+            tag_along: 1,
+            repr: Repr::B { y: 2 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("tag_along"),
+            Token::U32(1),
+            Token::Str("B"),
+            Token::Struct { name: "B", len: 1 },
+            Token::Str("y"),
+            Token::I32(2),
+            Token::StructEnd,
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_internally_tagged_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "kind")]
+    enum Repr {
+        A { x: i32 },
+        B { y: i32 },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        tag_along: u32,
+        #[serde(flatten)]
+        repr: Repr,
+    }
+
+    assert_tokens(
+        &Outer {
+            tag_along: 1,
+            repr: Repr::A { x: 2 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("tag_along"),
+            Token::U32(1),
+            Token::Str("kind"),
+            Token::Str("A"),
+            Token::Str("x"),
+            Token::I32(2),
+            Token::MapEnd,
+        ],
+    );
+
+    // The tag is allowed to show up after other fields.
+    assert_de_tokens(
+        &Outer {
+            tag_along: 1,
+            repr: Repr::B { y: 3 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("y"),
+            Token::I32(3),
+            Token::Str("tag_along"),
+            Token::U32(1),
+            Token::Str("kind"),
+            Token::Str("B"),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_adjacently_tagged_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "kind", content = "payload")]
+    enum Repr {
+        A(Inner),
+        B,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        tag_along: u32,
+        #[serde(flatten)]
+        repr: Repr,
+    }
+
+    assert_tokens(
+        &Outer {
+            tag_along: 1,
+            repr: Repr::A(Inner { x: 2 }),
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("tag_along"),
+            Token::U32(1),
+            Token::Str("kind"),
+            Token::UnitVariant {
+                name: "Repr",
+                variant: "A",
+            },
+            Token::Str("payload"),
+            Token::Struct {
+                name: "Inner",
+                len: 1,
+            },
+            Token::Str("x"),
+            Token::I32(2),
+            Token::StructEnd,
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_untagged_enum() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(untagged)]
+    enum Repr {
+        A { x: i32 },
+        B { y: i32 },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        tag_along: u32,
+        #[serde(flatten)]
+        repr: Repr,
+    }
+
+    assert_tokens(
+        &Outer {
+            tag_along: 1,
+            repr: Repr::B { y: 2 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("tag_along"),
+            Token::U32(1),
+            Token::Str("y"),
+            Token::I32(2),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_with_defaulted_sibling() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(untagged)]
+    enum Repr {
+        A { x: i32 },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        #[serde(default)]
+        tag_along: u32,
+        #[serde(flatten)]
+        repr: Repr,
+    }
+
+    assert_de_tokens(
+        &Outer {
+            tag_along: 0,
+            repr: Repr::A { x: 2 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("x"),
+            Token::I32(2),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_struct_with_defaulted_field() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Flat {
+        a: i32,
+        #[serde(default)]
+        b: i32,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        #[serde(flatten)]
+        flat: Flat,
+    }
+
+    assert_de_tokens(
+        &Outer {
+            flat: Flat { a: 1, b: 0 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_with_skipped_fields() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Flat {
+        a: i32,
+        #[serde(skip)]
+        hidden: i32,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer {
+        #[serde(skip_serializing)]
+        #[serde(default)]
+        cached: u32,
+        #[serde(flatten)]
+        flat: Flat,
+    }
+
+    assert_tokens(
+        &Outer {
+            cached: 0,
+            flat: Flat { a: 1, hidden: 0 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_with_borrowed_fields() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Flat<'a> {
+        #[serde(borrow)]
+        name: &'a str,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer<'a> {
+        tag_along: u32,
+        #[serde(flatten, borrow)]
+        flat: Flat<'a>,
+    }
+
+    assert_tokens(
+        &Outer {
+            tag_along: 1,
+            flat: Flat { name: "static str" },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("tag_along"),
+            Token::U32(1),
+            Token::Str("name"),
+            Token::BorrowedStr("static str"),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_untagged_enum_with_borrows_and_defaults() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(untagged)]
+    enum Repr<'a> {
+        Named {
+            #[serde(borrow)]
+            name: &'a str,
+            #[serde(default)]
+            weight: u32,
+        },
+        Anonymous {
+            id: u64,
+        },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Outer<'a> {
+        #[serde(flatten, borrow)]
+        repr: Repr<'a>,
+    }
+
+    assert_de_tokens(
+        &Outer {
+            repr: Repr::Named {
+                name: "zero",
+                weight: 0,
+            },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("name"),
+            Token::BorrowedStr("zero"),
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Outer {
+            repr: Repr::Anonymous { id: 7 },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("id"),
+            Token::U64(7),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_collect_custom_map() {
+    use serde::de::flatten::FlattenCollect;
+    use serde::de::MapAccess;
+
+    #[derive(PartialEq, Debug)]
+    struct OrderedExtras(Vec<(String, u32)>);
+
+    impl<'de> FlattenCollect<'de> for OrderedExtras {
+        fn flatten_collect<A>(mut map: A) -> Result<Self, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut entries = Vec::new();
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(OrderedExtras(entries))
+        }
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Record {
+        id: u64,
+        #[serde(flatten, deserialize_with = "serde::de::flatten::collect")]
+        extra: OrderedExtras,
+    }
+
+    // The custom collection receives the unclaimed entries in input order.
+    assert_de_tokens(
+        &Record {
+            id: 7,
+            extra: OrderedExtras(vec![("b".to_owned(), 2), ("a".to_owned(), 1)]),
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("b"),
+            Token::U32(2),
+            Token::Str("id"),
+            Token::U64(7),
+            Token::Str("a"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_collect_vec_preserves_order_and_duplicates() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Packet {
+        id: u64,
+        #[serde(flatten, deserialize_with = "serde::de::flatten::collect")]
+        headers: Vec<(String, u32)>,
+    }
+
+    // Unlike a map target, Vec<(K, V)> keeps the input order and does not
+    // collapse repeated keys.
+    assert_de_tokens(
+        &Packet {
+            id: 7,
+            headers: vec![
+                ("b".to_owned(), 2),
+                ("a".to_owned(), 1),
+                ("b".to_owned(), 3),
+            ],
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("b"),
+            Token::U32(2),
+            Token::Str("id"),
+            Token::U64(7),
+            Token::Str("a"),
+            Token::U32(1),
+            Token::Str("b"),
+            Token::U32(3),
+            Token::MapEnd,
+        ],
+    );
+}
@@ -5,6 +5,7 @@
 #![deny(warnings)]
 #![allow(
     unknown_lints,
+    dead_code,
     mixed_script_confusables,
     clippy::derive_partial_eq_without_eq,
     clippy::extra_unused_type_parameters,
@@ -718,6 +719,16 @@ fn test_gen() {
         flat: T,
     }
 
+    // The bound is placed on the flattened field type as a whole, so a
+    // wrapper that is serializable for any T can be flattened even when T
+    // itself is not.
+    #[derive(Serialize, Deserialize)]
+    struct FlattenUnboundedGeneric<T> {
+        #[serde(flatten)]
+        flat: FlatMarker<T>,
+    }
+    assert::<FlattenUnboundedGeneric<X>>();
+
     // https://github.com/serde-rs/serde/issues/1804
     #[derive(Serialize, Deserialize)]
     enum Message {
@@ -735,6 +746,13 @@ fn test_gen() {
         y: u16,
     }
 
+    #[derive(Serialize)]
+    #[serde(transparent)]
+    #[repr(packed)]
+    struct PackedTransparent {
+        t: u16,
+    }
+
     macro_rules! deriving {
         ($field:ty) => {
             #[derive(Deserialize)]
@@ -799,6 +817,22 @@ mod both_x {
     pub use super::{de_x as deserialize, ser_x as serialize};
 }
 
+// Implements Serialize and Deserialize for any T, like a map of metadata that
+// is keyed by a marker type.
+pub struct FlatMarker<T>(PhantomData<T>);
+
+impl<T> Serialize for FlatMarker<T> {
+    fn serialize<S: Serializer>(&self, _: S) -> StdResult<S::Ok, S::Error> {
+        unimplemented!()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for FlatMarker<T> {
+    fn deserialize<D: Deserializer<'de>>(_: D) -> StdResult<Self, D::Error> {
+        unimplemented!()
+    }
+}
+
 impl SerializeWith for X {
     fn serialize_with<S: Serializer>(_: &Self, _: S) -> StdResult<S::Ok, S::Error> {
         unimplemented!()
@@ -0,0 +1,87 @@
+use serde::de::{Error as _, Incremental};
+use std::fmt;
+
+#[derive(Debug)]
+enum LineError {
+    Incomplete,
+    Message(String),
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LineError::Incomplete => formatter.write_str("unexpected end of input"),
+            LineError::Message(msg) => formatter.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for LineError {}
+
+impl serde::de::Error for LineError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        LineError::Message(msg.to_string())
+    }
+
+    fn is_incomplete(&self) -> bool {
+        matches!(self, LineError::Incomplete)
+    }
+}
+
+fn parse_line(input: &[u8]) -> Result<(u32, usize), LineError> {
+    let line_end = match input.iter().position(|b| *b == b'\n') {
+        Some(position) => position,
+        None => return Err(LineError::Incomplete),
+    };
+    let line = std::str::from_utf8(&input[..line_end]).map_err(LineError::custom)?;
+    let value = line.parse().map_err(LineError::custom)?;
+    Ok((value, line_end + 1))
+}
+
+#[test]
+fn test_incremental_needs_more_data() {
+    let mut incremental = Incremental::new();
+    assert!(incremental.is_empty());
+
+    incremental.feed(b"12");
+    assert!(incremental.next_value(parse_line).unwrap().is_none());
+    assert_eq!(incremental.buffer(), b"12");
+
+    incremental.feed(b"3\n");
+    assert_eq!(incremental.next_value(parse_line).unwrap(), Some(123));
+    assert!(incremental.is_empty());
+}
+
+#[test]
+fn test_incremental_consecutive_values() {
+    let mut incremental = Incremental::new();
+    incremental.feed(b"1\n2\n3");
+
+    assert_eq!(incremental.next_value(parse_line).unwrap(), Some(1));
+    assert_eq!(incremental.next_value(parse_line).unwrap(), Some(2));
+
+    // The trailing partial value stays buffered until its newline arrives.
+    assert!(incremental.next_value(parse_line).unwrap().is_none());
+    incremental.feed(b"\n");
+    assert_eq!(incremental.next_value(parse_line).unwrap(), Some(3));
+}
+
+#[test]
+fn test_incremental_hard_error() {
+    let mut incremental = Incremental::new();
+    incremental.feed(b"botched\n");
+
+    // A complete but malformed value is a real error, not a request for
+    // more input.
+    let err = incremental.next_value(parse_line).unwrap_err();
+    assert!(!err.is_incomplete());
+
+    // The failed input is left in place for the caller to inspect.
+    assert_eq!(incremental.buffer(), b"botched\n");
+}
+
+#[test]
+fn test_default_is_incomplete() {
+    let err = serde::de::value::Error::custom("unexpected end of input");
+    assert!(!err.is_incomplete());
+}
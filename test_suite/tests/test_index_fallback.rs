@@ -0,0 +1,379 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde::de::value::Error;
+use serde::de::{self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess};
+use serde::ser::{Impossible, Serialize, SerializeStructVariant, Serializer};
+use serde::forward_to_deserialize_any;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(tag = "t", index_fallback)]
+enum InternallyTagged {
+    A { x: u32 },
+    B { y: u32 },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(untagged, index_fallback)]
+enum Untagged {
+    A { x: u32 },
+    B { y: u32 },
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(tag = "t")]
+enum NoFallback {
+    A { x: u32 },
+}
+
+/// A serializer for a format that does not describe its own structure. It
+/// records serialization as a flat list of textual events and refuses the
+/// map-based encoding that the internally tagged representation relies on.
+struct CompactSerializer<'a> {
+    events: &'a mut Vec<String>,
+}
+
+impl<'a> CompactSerializer<'a> {
+    fn unsupported(self) -> Error {
+        de::Error::custom("unsupported in a non-self-describing format")
+    }
+}
+
+impl<'a> Serializer for CompactSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.events.push(format!("bool({})", v));
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.events.push(format!("i64({})", v));
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.events.push(format!("u64({})", v));
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.events.push(format!("f64({})", v));
+        Ok(())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        self.events
+            .push(format!("unit_variant({}, {})", name, variant_index));
+        Ok(())
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.events
+            .push(format!("newtype_variant({}, {})", name, variant_index));
+        value.serialize(CompactSerializer {
+            events: self.events,
+        })
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        self.events
+            .push(format!("struct_variant({}, {})", name, variant_index));
+        Ok(self)
+    }
+
+    fn is_self_describing(&self) -> bool {
+        false
+    }
+}
+
+impl<'a> SerializeStructVariant for CompactSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.events.push(format!("field({})", key));
+        value.serialize(CompactSerializer {
+            events: self.events,
+        })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A deserializer for the same format: an enum is nothing but a variant index
+/// followed by the field values in order. `deserialize_any` is an error, as in
+/// any format that does not record the structure of the data.
+struct CompactEnum {
+    variant_index: u32,
+    values: Vec<u32>,
+}
+
+impl<'de> de::Deserializer<'de> for CompactEnum {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "deserialize_any is not supported in a non-self-describing format",
+        ))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+
+    fn is_self_describing(&self) -> bool {
+        false
+    }
+}
+
+impl<'de> EnumAccess<'de> for CompactEnum {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let index = self.variant_index;
+        let variant = seed.deserialize(index.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for CompactEnum {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.values[0].into_deserializer())
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(de::value::SeqDeserializer::new(self.values.into_iter()))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(de::value::SeqDeserializer::new(self.values.into_iter()))
+    }
+}
+
+#[test]
+fn test_self_describing_representation_unchanged() {
+    // On self-describing formats the attribute is inert and the usual
+    // representations apply.
+    assert_tokens(
+        &InternallyTagged::A { x: 1 },
+        &[
+            Token::Struct {
+                name: "InternallyTagged",
+                len: 2,
+            },
+            Token::Str("t"),
+            Token::Str("A"),
+            Token::Str("x"),
+            Token::U32(1),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Untagged::B { y: 2 },
+        &[
+            Token::Struct {
+                name: "Untagged",
+                len: 1,
+            },
+            Token::Str("y"),
+            Token::U32(2),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_fallback_serialization() {
+    let mut events = Vec::new();
+    InternallyTagged::A { x: 1 }
+        .serialize(CompactSerializer {
+            events: &mut events,
+        })
+        .unwrap();
+    assert_eq!(
+        events,
+        [
+            "struct_variant(InternallyTagged, 0)",
+            "field(x)",
+            "u64(1)"
+        ]
+    );
+
+    let mut events = Vec::new();
+    Untagged::B { y: 2 }
+        .serialize(CompactSerializer {
+            events: &mut events,
+        })
+        .unwrap();
+    assert_eq!(events, ["struct_variant(Untagged, 1)", "field(y)", "u64(2)"]);
+}
+
+#[test]
+fn test_fallback_deserialization() {
+    assert_eq!(
+        InternallyTagged::deserialize(CompactEnum {
+            variant_index: 0,
+            values: vec![1],
+        })
+        .unwrap(),
+        InternallyTagged::A { x: 1 },
+    );
+
+    assert_eq!(
+        Untagged::deserialize(CompactEnum {
+            variant_index: 1,
+            values: vec![2],
+        })
+        .unwrap(),
+        Untagged::B { y: 2 },
+    );
+}
+
+#[test]
+fn test_no_fallback_without_attribute() {
+    // Without the attribute the internally tagged representation still
+    // requires a self-describing format.
+    NoFallback::deserialize(CompactEnum {
+        variant_index: 0,
+        values: vec![1],
+    })
+    .unwrap_err();
+}
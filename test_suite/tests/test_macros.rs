@@ -2059,6 +2059,45 @@ fn test_rename_all_fields() {
     );
 }
 
+#[test]
+fn test_rename_all_fields_independent_directions() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all_fields(serialize = "kebab-case", deserialize = "camelCase"))]
+    enum E {
+        V {
+            a_field: bool,
+        },
+    }
+
+    assert_ser_tokens(
+        &E::V { a_field: true },
+        &[
+            Token::StructVariant {
+                name: "E",
+                variant: "V",
+                len: 1,
+            },
+            Token::Str("a-field"),
+            Token::Bool(true),
+            Token::StructVariantEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &E::V { a_field: true },
+        &[
+            Token::StructVariant {
+                name: "E",
+                variant: "V",
+                len: 1,
+            },
+            Token::Str("aField"),
+            Token::Bool(true),
+            Token::StructVariantEnd,
+        ],
+    );
+}
+
 #[test]
 fn test_untagged_newtype_variant_containing_unit_struct_not_map() {
     #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -2147,3 +2186,142 @@ fn test_packed_struct_can_derive_serialize() {
         t: f32,
     }
 }
+
+#[test]
+fn test_wide_struct_field_matching() {
+    // 16 fields is the point where the generated identifier visitor switches
+    // to length-bucketed matching; the observable behavior must not change.
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Wide {
+        a: u8,
+        bb: u8,
+        cc: u8,
+        ddd: u8,
+        eee: u8,
+        ffff: u8,
+        gggg: u8,
+        hhhhh: u8,
+        iiiii: u8,
+        jjjjjj: u8,
+        kkkkkk: u8,
+        lllllll: u8,
+        mmmmmmm: u8,
+        nnnnnnnn: u8,
+        oooooooo: u8,
+        #[serde(alias = "pp")]
+        ppppppppp: u8,
+    }
+
+    let value = Wide {
+        a: 1,
+        bb: 2,
+        cc: 3,
+        ddd: 4,
+        eee: 5,
+        ffff: 6,
+        gggg: 7,
+        hhhhh: 8,
+        iiiii: 9,
+        jjjjjj: 10,
+        kkkkkk: 11,
+        lllllll: 12,
+        mmmmmmm: 13,
+        nnnnnnnn: 14,
+        oooooooo: 15,
+        ppppppppp: 16,
+    };
+
+    // String keys, including an alias that shares a length with other names,
+    // an unknown key of a known length, and an unknown key of an unknown
+    // length.
+    assert_de_tokens(
+        &value,
+        &[
+            Token::Struct {
+                name: "Wide",
+                len: 16,
+            },
+            Token::Str("a"),
+            Token::U8(1),
+            Token::Str("bb"),
+            Token::U8(2),
+            Token::Str("cc"),
+            Token::U8(3),
+            Token::Str("ddd"),
+            Token::U8(4),
+            Token::Str("eee"),
+            Token::U8(5),
+            Token::Str("ffff"),
+            Token::U8(6),
+            Token::Str("gggg"),
+            Token::U8(7),
+            Token::Str("hhhhh"),
+            Token::U8(8),
+            Token::Str("iiiii"),
+            Token::U8(9),
+            Token::Str("jjjjjj"),
+            Token::U8(10),
+            Token::Str("kkkkkk"),
+            Token::U8(11),
+            Token::Str("lllllll"),
+            Token::U8(12),
+            Token::Str("mmmmmmm"),
+            Token::U8(13),
+            Token::Str("nnnnnnnn"),
+            Token::U8(14),
+            Token::Str("oooooooo"),
+            Token::U8(15),
+            Token::Str("zz"),
+            Token::U8(0),
+            Token::Str("unknown-length-key"),
+            Token::U8(0),
+            Token::Str("pp"),
+            Token::U8(16),
+            Token::StructEnd,
+        ],
+    );
+
+    // Byte-string keys go through the bucketed visit_bytes path.
+    assert_de_tokens(
+        &value,
+        &[
+            Token::Struct {
+                name: "Wide",
+                len: 16,
+            },
+            Token::Bytes(b"a"),
+            Token::U8(1),
+            Token::Bytes(b"bb"),
+            Token::U8(2),
+            Token::Bytes(b"cc"),
+            Token::U8(3),
+            Token::Bytes(b"ddd"),
+            Token::U8(4),
+            Token::Bytes(b"eee"),
+            Token::U8(5),
+            Token::Bytes(b"ffff"),
+            Token::U8(6),
+            Token::Bytes(b"gggg"),
+            Token::U8(7),
+            Token::Bytes(b"hhhhh"),
+            Token::U8(8),
+            Token::Bytes(b"iiiii"),
+            Token::U8(9),
+            Token::Bytes(b"jjjjjj"),
+            Token::U8(10),
+            Token::Bytes(b"kkkkkk"),
+            Token::U8(11),
+            Token::Bytes(b"lllllll"),
+            Token::U8(12),
+            Token::Bytes(b"mmmmmmm"),
+            Token::U8(13),
+            Token::Bytes(b"nnnnnnnn"),
+            Token::U8(14),
+            Token::Bytes(b"oooooooo"),
+            Token::U8(15),
+            Token::Bytes(b"pp"),
+            Token::U8(16),
+            Token::StructEnd,
+        ],
+    );
+}
@@ -0,0 +1,536 @@
+//! Assertions that deserialization streams tokens instead of buffering them.
+//!
+//! serde_test is maintained out of tree, so like the helpers in
+//! `test_assert_with.rs` the instrumentation lives here. Buffering cannot be
+//! observed from serde_test directly, but serde's private `Content` buffer
+//! always drains its input through `deserialize_any`, so a forwarding wrapper
+//! around the token deserializer can count how many values were pulled into a
+//! buffer instead of being streamed to the target type.
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+use serde_derive::Deserialize;
+use serde_test::{assert_de_tokens, Token};
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    static BUFFERED_VALUES: Cell<usize> = Cell::new(0);
+}
+
+/// Asserts that `tokens` deserialize to `expected` without any value being
+/// buffered along the way.
+///
+/// Use this to lock in streaming behavior that a type relies on, for example
+/// that a struct is not silently routed through the buffering code paths that
+/// `flatten` and the self-describing enum representations use.
+#[track_caller]
+pub fn assert_de_tokens_no_buffering<'de, T>(expected: &T, tokens: &'de [Token])
+where
+    T: Deserialize<'de> + PartialEq + fmt::Debug,
+{
+    let (actual, buffered) = de_tokens_counting_buffered::<T>(tokens);
+    assert_eq!(actual, *expected);
+    assert_eq!(
+        buffered, 0,
+        "deserialization buffered {} value(s) instead of streaming",
+        buffered,
+    );
+}
+
+/// Deserializes a value from `tokens` and additionally returns how many
+/// values were drained into a buffer through `deserialize_any`.
+#[track_caller]
+pub fn de_tokens_counting_buffered<'de, T>(tokens: &'de [Token]) -> (T, usize)
+where
+    T: Deserialize<'de>,
+{
+    BUFFERED_VALUES.with(|count| count.set(0));
+    let holder = Extract(Cell::new(None));
+    assert_de_tokens(&holder, tokens);
+    let buffered = BUFFERED_VALUES.with(Cell::get);
+    match holder.0.into_inner() {
+        Some(value) => (value, buffered),
+        None => panic!("deserialized value was not captured"),
+    }
+}
+
+/// Smuggles the deserialized value out of `assert_de_tokens` the same way as
+/// `test_assert_with.rs`, wrapping the deserializer in [`Counting`] first.
+struct Extract<T>(Cell<Option<T>>);
+
+impl<T> PartialEq for Extract<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if let Some(value) = self.0.take() {
+            other.0.set(Some(value));
+        }
+        true
+    }
+}
+
+impl<T> fmt::Debug for Extract<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Extract")
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Extract<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(Counting(deserializer)).map(|value| Extract(Cell::new(Some(value))))
+    }
+}
+
+/// Forwards every `Deserializer` method to the wrapped deserializer,
+/// incrementing the buffered-value counter on `deserialize_any` and keeping
+/// nested sequences, maps, and enums wrapped so nothing escapes the count.
+struct Counting<D>(D);
+
+macro_rules! forward_counting {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.0.$method(CountingVisitor(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, D> Deserializer<'de> for Counting<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        BUFFERED_VALUES.with(|count| count.set(count.get() + 1));
+        self.0.deserialize_any(CountingVisitor(visitor))
+    }
+
+    forward_counting! {
+        deserialize_bool
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+        deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit
+        deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_unit_struct(name, CountingVisitor(visitor))
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0
+            .deserialize_newtype_struct(name, CountingVisitor(visitor))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, CountingVisitor(visitor))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0
+            .deserialize_tuple_struct(name, len, CountingVisitor(visitor))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0
+            .deserialize_struct(name, fields, CountingVisitor(visitor))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0
+            .deserialize_enum(name, variants, CountingVisitor(visitor))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+struct CountingVisitor<V>(V);
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.0.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V> Visitor<'de> for CountingVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool: bool,
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+        visit_str: &str,
+        visit_borrowed_str: &'de str,
+        visit_string: String,
+        visit_bytes: &[u8],
+        visit_borrowed_bytes: &'de [u8],
+        visit_byte_buf: Vec<u8>,
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.0.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.visit_some(Counting(deserializer))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.0.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.visit_newtype_struct(Counting(deserializer))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.0.visit_seq(CountingSeqAccess(seq))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.0.visit_map(CountingMapAccess(map))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        self.0.visit_enum(CountingEnumAccess(data))
+    }
+}
+
+struct CountingSeed<S>(S);
+
+impl<'de, S> DeserializeSeed<'de> for CountingSeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.deserialize(Counting(deserializer))
+    }
+}
+
+struct CountingSeqAccess<A>(A);
+
+impl<'de, A> SeqAccess<'de> for CountingSeqAccess<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(CountingSeed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+struct CountingMapAccess<A>(A);
+
+impl<'de, A> MapAccess<'de> for CountingMapAccess<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        self.0.next_key_seed(CountingSeed(seed))
+    }
+
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        self.0.next_value_seed(CountingSeed(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+struct CountingEnumAccess<A>(A);
+
+impl<'de, A> EnumAccess<'de> for CountingEnumAccess<A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = CountingVariantAccess<A::Variant>;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let (value, variant) = self.0.variant_seed(CountingSeed(seed))?;
+        Ok((value, CountingVariantAccess(variant)))
+    }
+}
+
+struct CountingVariantAccess<A>(A);
+
+impl<'de, A> VariantAccess<'de> for CountingVariantAccess<A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        self.0.newtype_variant_seed(CountingSeed(seed))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.tuple_variant(len, CountingVisitor(visitor))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.struct_variant(fields, CountingVisitor(visitor))
+    }
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Plain {
+    id: u32,
+    name: String,
+}
+
+#[test]
+fn test_plain_struct_streams() {
+    assert_de_tokens_no_buffering(
+        &Plain {
+            id: 1,
+            name: "one".to_owned(),
+        },
+        &[
+            Token::Struct {
+                name: "Plain",
+                len: 2,
+            },
+            Token::Str("id"),
+            Token::U32(1),
+            Token::Str("name"),
+            Token::Str("one"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_externally_tagged_enum_streams() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum External {
+        Pair { a: u8, b: u8 },
+    }
+
+    assert_de_tokens_no_buffering(
+        &External::Pair { a: 1, b: 2 },
+        &[
+            Token::StructVariant {
+                name: "External",
+                variant: "Pair",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::U8(1),
+            Token::Str("b"),
+            Token::U8(2),
+            Token::StructVariantEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_flatten_buffers() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Outer {
+        extra: bool,
+        #[serde(flatten)]
+        inner: Plain,
+    }
+
+    let (value, buffered) = de_tokens_counting_buffered::<Outer>(&[
+        Token::Map { len: None },
+        Token::Str("extra"),
+        Token::Bool(true),
+        Token::Str("id"),
+        Token::U32(7),
+        Token::Str("name"),
+        Token::Str("seven"),
+        Token::MapEnd,
+    ]);
+    assert_eq!(
+        value,
+        Outer {
+            extra: true,
+            inner: Plain {
+                id: 7,
+                name: "seven".to_owned(),
+            },
+        },
+    );
+    assert!(buffered > 0, "flatten is expected to buffer");
+}
+
+#[test]
+#[should_panic(expected = "instead of streaming")]
+fn test_no_buffering_assertion_catches_flatten() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Outer {
+        #[serde(flatten)]
+        inner: Plain,
+    }
+
+    assert_de_tokens_no_buffering(
+        &Outer {
+            inner: Plain {
+                id: 1,
+                name: "one".to_owned(),
+            },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("id"),
+            Token::U32(1),
+            Token::Str("name"),
+            Token::Str("one"),
+            Token::MapEnd,
+        ],
+    );
+}
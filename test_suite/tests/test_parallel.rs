@@ -0,0 +1,133 @@
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use serde::ser::parallel::ParallelSlice;
+use serde::{Serialize, Serializer};
+use serde_test::{assert_ser_tokens, assert_ser_tokens_error, Configure, Token};
+
+// Large enough to take the parallel path; the threshold inside the helpers
+// is 2048 elements.
+const LARGE_LEN: usize = 3000;
+
+#[test]
+fn test_parallel_slice_matches_sequential() {
+    let values: Vec<u64> = (0..LARGE_LEN as u64).collect();
+    let mut tokens = vec![Token::Seq {
+        len: Some(LARGE_LEN),
+    }];
+    for i in 0..LARGE_LEN as u64 {
+        tokens.push(Token::U64(i));
+    }
+    tokens.push(Token::SeqEnd);
+    assert_ser_tokens(&ParallelSlice(&values).compact(), &tokens);
+}
+
+#[test]
+fn test_short_slice_falls_back_to_sequential() {
+    let values = [1u64, 2, 3];
+    assert_ser_tokens(
+        &ParallelSlice(&values),
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U64(1),
+            Token::U64(2),
+            Token::U64(3),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_parallel_slice_inside_struct() {
+    struct Record<'a> {
+        samples: &'a [u64],
+    }
+
+    impl<'a> Serialize for Record<'a> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            use serde::ser::SerializeStruct;
+
+            let mut state = serializer.serialize_struct("Record", 1)?;
+            state.serialize_field("samples", &ParallelSlice(self.samples))?;
+            state.end()
+        }
+    }
+
+    let samples: Vec<u64> = (0..LARGE_LEN as u64).collect();
+    let mut tokens = vec![
+        Token::Struct {
+            name: "Record",
+            len: 1,
+        },
+        Token::Str("samples"),
+        Token::Seq {
+            len: Some(LARGE_LEN),
+        },
+    ];
+    for i in 0..LARGE_LEN as u64 {
+        tokens.push(Token::U64(i));
+    }
+    tokens.push(Token::SeqEnd);
+    tokens.push(Token::StructEnd);
+    assert_ser_tokens(&Record { samples: &samples }.compact(), &tokens);
+}
+
+#[test]
+fn test_parallel_error_propagation() {
+    struct Bomb;
+
+    impl Serialize for Bomb {
+        fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            use serde::ser::Error;
+
+            Err(S::Error::custom("bomb element"))
+        }
+    }
+
+    // The failure happens on a worker thread while pre-encoding, before the
+    // outer serializer sees any of the sequence.
+    let bombs: Vec<Bomb> = (0..LARGE_LEN).map(|_| Bomb).collect();
+    assert_ser_tokens_error(&ParallelSlice(&bombs).compact(), &[], "bomb element");
+}
+
+#[test]
+fn test_parallel_slice_readability() {
+    use std::time::Duration;
+
+    // Duration encodes differently in human-readable and compact formats;
+    // the pre-encoding step must follow the target serializer's choice.
+    let values: Vec<Duration> = (0..LARGE_LEN).map(|_| Duration::new(1, 2)).collect();
+
+    let mut readable_tokens = vec![Token::Seq {
+        len: Some(LARGE_LEN),
+    }];
+    for _ in 0..LARGE_LEN {
+        readable_tokens.push(Token::Str("1.000000002s"));
+    }
+    readable_tokens.push(Token::SeqEnd);
+    assert_ser_tokens(&ParallelSlice(&values).readable(), &readable_tokens);
+
+    let mut compact_tokens = vec![Token::Seq {
+        len: Some(LARGE_LEN),
+    }];
+    for _ in 0..LARGE_LEN {
+        compact_tokens.extend([
+            Token::Struct {
+                name: "Duration",
+                len: 2,
+            },
+            Token::Str("secs"),
+            Token::U64(1),
+            Token::Str("nanos"),
+            Token::U32(2),
+            Token::StructEnd,
+        ]);
+    }
+    compact_tokens.push(Token::SeqEnd);
+    assert_ser_tokens(&ParallelSlice(&values).compact(), &compact_tokens);
+}
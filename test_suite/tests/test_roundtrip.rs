@@ -1,4 +1,6 @@
+use serde_derive::{Deserialize, Serialize};
 use serde_test::{assert_tokens, Configure, Token};
+use std::collections::BTreeMap;
 use std::net;
 
 #[macro_use]
@@ -21,6 +23,50 @@ fn ip_addr_roundtrip() {
     );
 }
 
+#[test]
+fn derived_roundtrip() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        id: u64,
+        name: String,
+        tags: Vec<String>,
+        extra: Option<BTreeMap<String, i32>>,
+    }
+
+    macros::roundtrip(&Record {
+        id: 1,
+        name: "first".to_owned(),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        extra: None,
+    });
+    macros::roundtrip(&Record {
+        id: 2,
+        name: String::new(),
+        tags: Vec::new(),
+        extra: Some(btreemap!["k".to_owned() => -1]),
+    });
+}
+
+#[test]
+fn generated_roundtrip() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Value {
+        Unit,
+        Int(i64),
+        Pair(bool, char),
+        Nested { values: Vec<Value> },
+    }
+
+    macros::roundtrip_values((0i64..32).map(|i| match i % 4 {
+        0 => Value::Unit,
+        1 => Value::Int(i * i),
+        2 => Value::Pair(i % 8 == 2, char::from(b'a' + i as u8)),
+        _ => Value::Nested {
+            values: vec![Value::Unit, Value::Int(-i)],
+        },
+    }));
+}
+
 #[test]
 fn socket_addr_roundtrip() {
     assert_tokens(
@@ -0,0 +1,146 @@
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Seeded, Serializer};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_ser_tokens, Token};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A toy string interner shared through `Rc` so that cloning the seed for
+/// each deserialization still accumulates into the same table.
+#[derive(Clone, Default)]
+struct Interner {
+    strings: Rc<RefCell<Vec<String>>>,
+}
+
+impl Interner {
+    fn intern(&self, s: &str) -> usize {
+        let mut strings = self.strings.borrow_mut();
+        match strings.iter().position(|existing| existing == s) {
+            Some(index) => index,
+            None => {
+                strings.push(s.to_owned());
+                strings.len() - 1
+            }
+        }
+    }
+
+    fn strings(&self) -> Vec<String> {
+        self.strings.borrow().clone()
+    }
+}
+
+fn intern_str<'de, D>(state: &Interner, deserializer: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(state.intern(&s))
+}
+
+fn resolve_str<S>(state: &Interner, index: &usize, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&state.strings.borrow()[*index])
+}
+
+fn missing_group() -> usize {
+    usize::MAX
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(seed = "Interner")]
+struct Record {
+    #[serde(seed_with = "intern_str", serialize_seed_with = "resolve_str")]
+    name: usize,
+    #[serde(
+        seed_with = "intern_str",
+        serialize_seed_with = "resolve_str",
+        default = "missing_group"
+    )]
+    group: usize,
+}
+
+#[test]
+fn test_seed_map() {
+    let interner = Interner::default();
+
+    let de = MapDeserializer::<_, Error>::new(
+        vec![("name", "alpha"), ("group", "beta")].into_iter(),
+    );
+    let record = DeserializeSeed::deserialize(interner.clone(), de).unwrap();
+    assert_eq!(record, Record { name: 0, group: 1 });
+    assert_eq!(interner.strings(), ["alpha", "beta"]);
+}
+
+#[test]
+fn test_seed_interning_deduplicates() {
+    let interner = Interner::default();
+
+    let de = MapDeserializer::<_, Error>::new(
+        vec![("name", "alpha"), ("group", "alpha")].into_iter(),
+    );
+    let record = DeserializeSeed::deserialize(interner.clone(), de).unwrap();
+    assert_eq!(record, Record { name: 0, group: 0 });
+    assert_eq!(interner.strings(), ["alpha"]);
+}
+
+#[test]
+fn test_seed_seq() {
+    let interner = Interner::default();
+
+    let de = SeqDeserializer::<_, Error>::new(vec!["alpha", "beta"].into_iter());
+    let record = DeserializeSeed::deserialize(interner.clone(), de).unwrap();
+    assert_eq!(record, Record { name: 0, group: 1 });
+    assert_eq!(interner.strings(), ["alpha", "beta"]);
+}
+
+#[test]
+fn test_seed_field_default() {
+    let interner = Interner::default();
+
+    let de = MapDeserializer::<_, Error>::new(vec![("name", "alpha")].into_iter());
+    let record = DeserializeSeed::deserialize(interner.clone(), de).unwrap();
+    assert_eq!(
+        record,
+        Record {
+            name: 0,
+            group: usize::MAX,
+        }
+    );
+    assert_eq!(interner.strings(), ["alpha"]);
+}
+
+#[test]
+fn test_seed_missing_field() {
+    let interner = Interner::default();
+
+    let de = MapDeserializer::<_, Error>::new(vec![("group", "beta")].into_iter());
+    let err = DeserializeSeed::deserialize(interner, de).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `name`");
+}
+
+#[test]
+fn test_seed_serialize() {
+    let interner = Interner::default();
+    let record = Record {
+        name: interner.intern("alpha"),
+        group: interner.intern("beta"),
+    };
+
+    assert_ser_tokens(
+        &Seeded::new(&record, &interner),
+        &[
+            Token::Struct {
+                name: "Record",
+                len: 2,
+            },
+            Token::Str("name"),
+            Token::Str("alpha"),
+            Token::Str("group"),
+            Token::Str("beta"),
+            Token::StructEnd,
+        ],
+    );
+}
@@ -9,6 +9,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::CString;
 use std::net;
 use std::num::Wrapping;
+use std::cmp::Ordering;
 use std::ops::Bound;
 use std::path::{Path, PathBuf};
 use std::rc::{Rc, Weak as RcWeak};
@@ -310,6 +311,302 @@ fn test_hashmap() {
     );
 }
 
+#[test]
+fn test_hashmap_unordered() {
+    macros::assert_ser_tokens_unordered(
+        &hashmap![1 => 2, 3 => 4, 5 => 6],
+        &[Token::Map { len: Some(3) }],
+        &[
+            &[Token::I32(1), Token::I32(2)],
+            &[Token::I32(3), Token::I32(4)],
+            &[Token::I32(5), Token::I32(6)],
+        ],
+        &[Token::MapEnd],
+    );
+    macros::assert_ser_tokens_unordered(
+        &hashmap!["a" => vec![1, 2], "b" => vec![3]],
+        &[Token::Map { len: Some(2) }],
+        &[
+            &[
+                Token::Str("a"),
+                Token::Seq { len: Some(2) },
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+            &[
+                Token::Str("b"),
+                Token::Seq { len: Some(1) },
+                Token::I32(3),
+                Token::SeqEnd,
+            ],
+        ],
+        &[Token::MapEnd],
+    );
+}
+
+#[test]
+fn test_integer128_serialize() {
+    use serde::de::value::Error;
+    use serde::ser::{Error as _, Impossible, Serialize, Serializer};
+    use std::num::{NonZeroI128, NonZeroU128};
+
+    // `serde_test` has no 128-bit tokens, so exercise the serialize paths
+    // with a serializer that accepts nothing else.
+    #[derive(Debug, PartialEq)]
+    enum Int128 {
+        Signed(i128),
+        Unsigned(u128),
+    }
+
+    struct Int128Serializer;
+
+    macro_rules! unexpected {
+        ($($name:ident: $ty:ty,)*) => {
+            $(
+                fn $name(self, _: $ty) -> Result<Int128, Error> {
+                    Err(Error::custom("expected i128 or u128"))
+                }
+            )*
+        };
+    }
+
+    impl Serializer for Int128Serializer {
+        type Ok = Int128;
+        type Error = Error;
+
+        type SerializeSeq = Impossible<Int128, Error>;
+        type SerializeTuple = Impossible<Int128, Error>;
+        type SerializeTupleStruct = Impossible<Int128, Error>;
+        type SerializeTupleVariant = Impossible<Int128, Error>;
+        type SerializeMap = Impossible<Int128, Error>;
+        type SerializeStruct = Impossible<Int128, Error>;
+        type SerializeStructVariant = Impossible<Int128, Error>;
+
+        fn serialize_i128(self, v: i128) -> Result<Int128, Error> {
+            Ok(Int128::Signed(v))
+        }
+
+        fn serialize_u128(self, v: u128) -> Result<Int128, Error> {
+            Ok(Int128::Unsigned(v))
+        }
+
+        unexpected! {
+            serialize_bool: bool,
+            serialize_i8: i8,
+            serialize_i16: i16,
+            serialize_i32: i32,
+            serialize_i64: i64,
+            serialize_u8: u8,
+            serialize_u16: u16,
+            serialize_u32: u32,
+            serialize_u64: u64,
+            serialize_f32: f32,
+            serialize_f64: f64,
+            serialize_char: char,
+            serialize_str: &str,
+            serialize_bytes: &[u8],
+        }
+
+        fn serialize_none(self) -> Result<Int128, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_some<T>(self, _: &T) -> Result<Int128, Error>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_unit(self) -> Result<Int128, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_unit_struct(self, _: &'static str) -> Result<Int128, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+        ) -> Result<Int128, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Int128, Error>
+        where
+            T: ?Sized + Serialize,
+        {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<Int128, Error>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStruct, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Error> {
+            Err(Error::custom("expected i128 or u128"))
+        }
+    }
+
+    assert_eq!(
+        i128::MIN.serialize(Int128Serializer).unwrap(),
+        Int128::Signed(i128::MIN),
+    );
+    assert_eq!(
+        i128::MAX.serialize(Int128Serializer).unwrap(),
+        Int128::Signed(i128::MAX),
+    );
+    assert_eq!(
+        u128::MAX.serialize(Int128Serializer).unwrap(),
+        Int128::Unsigned(u128::MAX),
+    );
+    assert_eq!(
+        NonZeroI128::new(-1).unwrap().serialize(Int128Serializer).unwrap(),
+        Int128::Signed(-1),
+    );
+    assert_eq!(
+        NonZeroU128::new(1).unwrap().serialize(Int128Serializer).unwrap(),
+        Int128::Unsigned(1),
+    );
+    assert_eq!(
+        Wrapping(1i128).serialize(Int128Serializer).unwrap(),
+        Int128::Signed(1),
+    );
+}
+
+#[test]
+fn test_hashset_unordered() {
+    macros::assert_ser_tokens_unordered(
+        &hashset![1, 2, 3],
+        &[Token::Seq { len: Some(3) }],
+        &[&[Token::I32(1)], &[Token::I32(2)], &[Token::I32(3)]],
+        &[Token::SeqEnd],
+    );
+}
+
+#[test]
+fn test_ser_tokens_diff() {
+    macros::assert_ser_tokens_diff(
+        &Struct { a: 1, b: 2, c: 3 },
+        &[
+            Token::Struct {
+                name: "Struct",
+                len: 3,
+            },
+            Token::Str("a"),
+            Token::I32(1),
+            Token::Str("b"),
+            Token::I32(2),
+            Token::Str("c"),
+            Token::I32(3),
+            Token::StructEnd,
+        ],
+    );
+
+    let diff = macros::token_diff(
+        &[
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+        ],
+        &[
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(9),
+            Token::SeqEnd,
+        ],
+    );
+    let lines: Vec<&str> = diff.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[1].starts_with("    0 "));
+    assert!(lines[2].contains("    I32(1)"));
+    assert!(lines[3].starts_with("!"));
+    assert!(lines[3].contains("I32(2)"));
+    assert!(lines[3].contains("I32(9)"));
+    assert!(lines[4].contains("SeqEnd"));
+}
+
+#[test]
+fn test_capabilities() {
+    use serde::de::value::{Error, UnitDeserializer};
+    use serde::ser::Capabilities;
+    use serde::Deserializer;
+
+    // The defaults describe a format like JSON.
+    let caps = Capabilities::default();
+    assert!(!caps.raw_bytes());
+    assert!(!caps.non_string_keys());
+    assert!(!caps.preserves_integer_width());
+    assert!(!caps.native_enums());
+
+    let caps = caps.with_raw_bytes(true).with_preserves_integer_width(true);
+    assert!(caps.raw_bytes());
+    assert!(caps.preserves_integer_width());
+    assert!(!caps.non_string_keys());
+
+    // Deserializers report capabilities too, defaulting to the same profile.
+    let de = UnitDeserializer::<Error>::new();
+    assert_eq!(de.capabilities(), Capabilities::default());
+}
+
 #[test]
 fn test_unit_struct() {
     assert_ser_tokens(&UnitStruct, &[Token::UnitStruct { name: "UnitStruct" }]);
@@ -428,9 +725,9 @@ fn test_boxed_slice() {
 }
 
 #[test]
-fn test_duration() {
+fn test_duration_compact() {
     assert_ser_tokens(
-        &Duration::new(1, 2),
+        &Duration::new(1, 2).compact(),
         &[
             Token::Struct {
                 name: "Duration",
@@ -446,10 +743,20 @@ fn test_duration() {
 }
 
 #[test]
-fn test_system_time() {
+fn test_duration_readable() {
+    assert_ser_tokens(&Duration::new(1, 2).readable(), &[Token::Str("1.000000002s")]);
+    assert_ser_tokens(&Duration::new(5, 0).readable(), &[Token::Str("5s")]);
+    assert_ser_tokens(
+        &Duration::new(1, 500_000_000).readable(),
+        &[Token::Str("1.500000000s")],
+    );
+}
+
+#[test]
+fn test_system_time_compact() {
     let system_time = UNIX_EPOCH + Duration::new(1, 200);
     assert_ser_tokens(
-        &system_time,
+        &system_time.compact(),
         &[
             Token::Struct {
                 name: "SystemTime",
@@ -464,6 +771,27 @@ fn test_system_time() {
     );
 }
 
+#[test]
+fn test_system_time_readable() {
+    let system_time = UNIX_EPOCH + Duration::new(1_000_000_000, 0);
+    assert_ser_tokens(
+        &system_time.readable(),
+        &[Token::Str("2001-09-09T01:46:40Z")],
+    );
+
+    let system_time = UNIX_EPOCH + Duration::new(1_000_000_000, 2);
+    assert_ser_tokens(
+        &system_time.readable(),
+        &[Token::Str("2001-09-09T01:46:40.000000002Z")],
+    );
+
+    let system_time = UNIX_EPOCH + Duration::new(0, 0);
+    assert_ser_tokens(
+        &system_time.readable(),
+        &[Token::Str("1970-01-01T00:00:00Z")],
+    );
+}
+
 #[test]
 fn test_range() {
     assert_ser_tokens(
@@ -528,6 +856,373 @@ fn test_bound() {
     );
 }
 
+#[test]
+fn test_ordering() {
+    assert_ser_tokens(
+        &Ordering::Less,
+        &[Token::UnitVariant {
+            name: "Ordering",
+            variant: "Less",
+        }],
+    );
+    assert_ser_tokens(
+        &Ordering::Equal,
+        &[Token::UnitVariant {
+            name: "Ordering",
+            variant: "Equal",
+        }],
+    );
+    assert_ser_tokens(
+        &Ordering::Greater,
+        &[Token::UnitVariant {
+            name: "Ordering",
+            variant: "Greater",
+        }],
+    );
+}
+
+#[test]
+fn test_field_filter() {
+    use serde::ser::FieldFilter;
+
+    #[derive(Serialize)]
+    struct Inner {
+        x: u32,
+        y: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        a: Inner,
+        b: u32,
+    }
+
+    let value = Outer {
+        a: Inner { x: 1, y: 2 },
+        b: 3,
+    };
+
+    let filter = FieldFilter::include(["a.x"]);
+    assert_ser_tokens(
+        &filter.apply(&value),
+        &[
+            Token::Struct {
+                name: "Outer",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::Struct {
+                name: "Inner",
+                len: 2,
+            },
+            Token::Str("x"),
+            Token::U32(1),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    let filter = FieldFilter::exclude(["a.y"]);
+    assert_ser_tokens(
+        &filter.apply(&value),
+        &[
+            Token::Struct {
+                name: "Outer",
+                len: 2,
+            },
+            Token::Str("a"),
+            Token::Struct {
+                name: "Inner",
+                len: 2,
+            },
+            Token::Str("x"),
+            Token::U32(1),
+            Token::StructEnd,
+            Token::Str("b"),
+            Token::U32(3),
+            Token::StructEnd,
+        ],
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert("name".to_owned(), 1u32);
+    map.insert("secret".to_owned(), 2);
+    let filter = FieldFilter::exclude(["secret"]);
+    assert_ser_tokens(
+        &filter.apply(&map),
+        &[
+            Token::Map { len: None },
+            Token::Str("name"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_budget() {
+    use serde::ser::Budget;
+
+    #[derive(Serialize)]
+    struct Node {
+        label: String,
+        children: Vec<Node>,
+    }
+
+    let tree = Node {
+        label: "root".to_owned(),
+        children: vec![Node {
+            label: "leaf".to_owned(),
+            children: Vec::new(),
+        }],
+    };
+
+    // Within budget, serialization is unchanged.
+    let budget = Budget::new()
+        .max_depth(4)
+        .max_elements(16)
+        .max_string_bytes(64);
+    assert_ser_tokens(
+        &budget.apply(&tree),
+        &[
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+            Token::Str("label"),
+            Token::Str("root"),
+            Token::Str("children"),
+            Token::Seq { len: Some(1) },
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+            Token::Str("label"),
+            Token::Str("leaf"),
+            Token::Str("children"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // The nested struct sits at depth 3: outer struct, children seq, inner
+    // struct.
+    let budget = Budget::new().max_depth(2);
+    assert_ser_tokens_error(
+        &budget.apply(&tree),
+        &[
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+            Token::Str("label"),
+            Token::Str("root"),
+            Token::Str("children"),
+            Token::Seq { len: Some(1) },
+        ],
+        "nesting depth budget exceeded",
+    );
+
+    // Two fields of the outer struct, one element of the children seq, and
+    // the third spent on the inner struct's first field.
+    let budget = Budget::new().max_elements(3);
+    assert_ser_tokens_error(
+        &budget.apply(&tree),
+        &[
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+            Token::Str("label"),
+            Token::Str("root"),
+            Token::Str("children"),
+            Token::Seq { len: Some(1) },
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+        ],
+        "element budget exceeded",
+    );
+
+    // "root" fits, "root" + "leaf" does not.
+    let budget = Budget::new().max_string_bytes(7);
+    assert_ser_tokens_error(
+        &budget.apply(&tree),
+        &[
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+            Token::Str("label"),
+            Token::Str("root"),
+            Token::Str("children"),
+            Token::Seq { len: Some(1) },
+            Token::Struct {
+                name: "Node",
+                len: 2,
+            },
+            Token::Str("label"),
+        ],
+        "string byte budget exceeded",
+    );
+}
+
+#[test]
+fn test_serializer_provided_defaults() {
+    use serde::ser::{Impossible, Serializer};
+    use serde::Serialize as _;
+
+    // A serializer that implements only the required subset of `Serializer`,
+    // relying on the provided defaults for everything else.
+    struct Minimal;
+
+    impl Serializer for Minimal {
+        type Ok = String;
+        type Error = serde::de::value::Error;
+
+        type SerializeSeq = Impossible<String, Self::Error>;
+        type SerializeTuple = Impossible<String, Self::Error>;
+        type SerializeTupleStruct = Impossible<String, Self::Error>;
+        type SerializeTupleVariant = Impossible<String, Self::Error>;
+        type SerializeMap = Impossible<String, Self::Error>;
+        type SerializeStruct = Impossible<String, Self::Error>;
+        type SerializeStructVariant = Impossible<String, Self::Error>;
+
+        fn serialize_bool(self, v: bool) -> Result<String, Self::Error> {
+            Ok(v.to_string())
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<String, Self::Error> {
+            Ok(v.to_string())
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<String, Self::Error> {
+            Ok(v.to_string())
+        }
+
+        fn serialize_f64(self, v: f64) -> Result<String, Self::Error> {
+            Ok(v.to_string())
+        }
+
+        fn serialize_str(self, v: &str) -> Result<String, Self::Error> {
+            Ok(v.to_owned())
+        }
+
+        fn serialize_bytes(self, _v: &[u8]) -> Result<String, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_none(self) -> Result<String, Self::Error> {
+            Ok("none".to_owned())
+        }
+
+        fn serialize_some<T>(self, value: &T) -> Result<String, Self::Error>
+        where
+            T: ?Sized + serde::Serialize,
+        {
+            value.serialize(Minimal)
+        }
+
+        fn serialize_unit(self) -> Result<String, Self::Error> {
+            Ok("unit".to_owned())
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            variant: &'static str,
+        ) -> Result<String, Self::Error> {
+            Ok(variant.to_owned())
+        }
+
+        fn serialize_newtype_variant<T>(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            value: &T,
+        ) -> Result<String, Self::Error>
+        where
+            T: ?Sized + serde::Serialize,
+        {
+            value.serialize(Minimal)
+        }
+
+        fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStruct, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStructVariant, Self::Error> {
+            Err(serde::ser::Error::custom("unsupported"))
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Millimeters(u8);
+
+    // Narrow integers default to the wide equivalents.
+    assert_eq!(5i8.serialize(Minimal).unwrap(), "5");
+    assert_eq!(5u16.serialize(Minimal).unwrap(), "5");
+    assert_eq!(1.5f32.serialize(Minimal).unwrap(), "1.5");
+    // Characters default to single element strings.
+    assert_eq!('x'.serialize(Minimal).unwrap(), "x");
+    // Unit structs default to unit, newtype structs to their contents.
+    assert_eq!(std::marker::PhantomData::<u8>.serialize(Minimal).unwrap(), "unit");
+    assert_eq!(Millimeters(8).serialize(Minimal).unwrap(), "8");
+}
+
+#[test]
+fn test_backtrace() {
+    let backtrace = std::backtrace::Backtrace::disabled();
+    let expected: &'static str = Box::leak(backtrace.to_string().into_boxed_str());
+    assert_ser_tokens(&backtrace, &[Token::Str(expected)]);
+}
+
 #[test]
 fn test_path() {
     assert_ser_tokens(
@@ -536,6 +1231,53 @@ fn test_path() {
     );
 }
 
+#[test]
+fn test_path_components() {
+    assert_ser_tokens(
+        &Path::new("/usr/local/lib").components(),
+        &[
+            Token::Seq { len: None },
+            Token::UnitVariant {
+                name: "Component",
+                variant: "RootDir",
+            },
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::Str("usr"),
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::Str("local"),
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::Str("lib"),
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_ser_tokens(
+        &Path::new("../lib").components(),
+        &[
+            Token::Seq { len: None },
+            Token::UnitVariant {
+                name: "Component",
+                variant: "ParentDir",
+            },
+            Token::NewtypeVariant {
+                name: "Component",
+                variant: "Normal",
+            },
+            Token::Str("lib"),
+            Token::SeqEnd,
+        ],
+    );
+}
+
 #[test]
 fn test_path_buf() {
     assert_ser_tokens(
@@ -571,6 +1313,12 @@ fn test_rc_weak_none() {
     assert_ser_tokens(&RcWeak::<bool>::new(), &[Token::None]);
 }
 
+#[test]
+fn test_rc_weak_dangling() {
+    let weak = Rc::downgrade(&Rc::new(true));
+    assert_ser_tokens(&weak, &[Token::None]);
+}
+
 #[test]
 fn test_arc() {
     assert_ser_tokens(&Arc::new(true), &[Token::Bool(true)]);
@@ -587,11 +1335,22 @@ fn test_arc_weak_none() {
     assert_ser_tokens(&ArcWeak::<bool>::new(), &[Token::None]);
 }
 
+#[test]
+fn test_arc_weak_dangling() {
+    let weak = Arc::downgrade(&Arc::new(true));
+    assert_ser_tokens(&weak, &[Token::None]);
+}
+
 #[test]
 fn test_wrapping() {
     assert_ser_tokens(&Wrapping(1usize), &[Token::U64(1)]);
 }
 
+#[test]
+fn test_saturating() {
+    assert_ser_tokens(&std::num::Saturating(1usize), &[Token::U64(1)]);
+}
+
 #[test]
 fn test_rc_dst() {
     assert_ser_tokens(&Rc::<str>::from("s"), &[Token::Str("s")]);
@@ -797,15 +1556,40 @@ fn test_never_result() {
 
 #[test]
 #[cfg(unix)]
-fn test_cannot_serialize_paths() {
+fn test_non_utf8_path() {
     use std::ffi::OsStr;
     use std::os::unix::ffi::OsStrExt;
 
-    assert_ser_tokens_error(
-        &Path::new(OsStr::from_bytes(b"Hello \xF0\x90\x80World")),
-        &[],
-        "path contains invalid UTF-8 characters",
+    let path = Path::new(OsStr::from_bytes(b"/tmp/\xC3"));
+    assert_ser_tokens(
+        &path.compact(),
+        &[
+            Token::Enum { name: "OsString" },
+            Token::Str("Unix"),
+            Token::Seq { len: Some(6) },
+            Token::U8(b'/'),
+            Token::U8(b't'),
+            Token::U8(b'm'),
+            Token::U8(b'p'),
+            Token::U8(b'/'),
+            Token::U8(0xC3),
+            Token::SeqEnd,
+        ],
     );
+    assert_ser_tokens(&path.readable(), &[Token::Bytes(b"/tmp/\xC3")]);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_os_string_readable() {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let os_string = OsString::from("abc");
+    assert_ser_tokens(&os_string.readable(), &[Token::Str("abc")]);
+
+    let os_string = OsString::from_vec(b"ab\xC3".to_vec());
+    assert_ser_tokens(&os_string.readable(), &[Token::Bytes(b"ab\xC3")]);
 }
 
 #[test]
@@ -881,3 +1665,154 @@ fn test_rwlock_dst() {
         ],
     );
 }
+
+#[test]
+fn test_once_cell() {
+    let cell = std::cell::OnceCell::<u32>::new();
+    assert_ser_tokens(&cell, &[Token::None]);
+    cell.set(1).unwrap();
+    assert_ser_tokens(&cell, &[Token::Some, Token::U32(1)]);
+}
+
+#[test]
+fn test_once_lock() {
+    let lock = std::sync::OnceLock::<u32>::new();
+    assert_ser_tokens(&lock, &[Token::None]);
+    lock.set(1).unwrap();
+    assert_ser_tokens(&lock, &[Token::Some, Token::U32(1)]);
+}
+
+#[test]
+fn test_structural_hash() {
+    use serde::ser::hash;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    fn digest<T>(value: &T) -> u64
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        let mut hasher = DefaultHasher::new();
+        hash(value, &mut hasher).unwrap();
+        hasher.finish()
+    }
+
+    #[derive(Serialize)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Renamed {
+        x: u32,
+        y: u32,
+    }
+
+    // Equal serialized content hashes equally; container names do not
+    // participate.
+    assert_eq!(
+        digest(&Point { x: 1, y: 2 }),
+        digest(&Renamed { x: 1, y: 2 }),
+    );
+    assert_ne!(digest(&Point { x: 1, y: 2 }), digest(&Point { x: 2, y: 1 }));
+
+    // Integers hash by value regardless of width.
+    assert_eq!(digest(&7u8), digest(&7u64));
+    assert_eq!(digest(&-7i8), digest(&-7i64));
+    assert_ne!(digest(&7u64), digest(&7i64));
+
+    // Newtype structs are transparent.
+    #[derive(Serialize)]
+    struct Wrapper(u32);
+    assert_eq!(digest(&Wrapper(7)), digest(&7u32));
+
+    // Different data model constructs are domain separated.
+    assert_ne!(digest(""), digest::<[u32]>(&[]));
+    assert_ne!(digest(&()), digest(&Option::<u32>::None));
+
+    // Variant names participate.
+    #[derive(Serialize)]
+    enum E {
+        A(u32),
+        B(u32),
+    }
+    assert_ne!(digest(&E::A(1)), digest(&E::B(1)));
+}
+
+#[test]
+fn test_structural_diff() {
+    use serde::ser::{diff, diff_first};
+
+    #[derive(Serialize)]
+    struct Inner {
+        x: u32,
+        y: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        a: Inner,
+        b: String,
+    }
+
+    let left = Outer {
+        a: Inner {
+            x: 1,
+            y: vec![1, 2, 3],
+        },
+        b: "alpha".to_owned(),
+    };
+    let right = Outer {
+        a: Inner {
+            x: 1,
+            y: vec![1, 9, 3],
+        },
+        b: "beta".to_owned(),
+    };
+
+    let differences = diff(&left, &right).unwrap();
+    assert_eq!(differences.len(), 2);
+    assert_eq!(differences[0].path(), "a.y.1");
+    assert_eq!(differences[0].left(), "integer `2`");
+    assert_eq!(differences[0].right(), "integer `9`");
+    assert_eq!(differences[1].path(), "b");
+    assert_eq!(differences[1].to_string(), "`b`: string \"alpha\" != string \"beta\"");
+
+    let first = diff_first(&left, &right).unwrap().unwrap();
+    assert_eq!(first.path(), "a.y.1");
+
+    // Identical serialized content, regardless of container names and
+    // integer widths.
+    #[derive(Serialize)]
+    struct Renamed {
+        x: u64,
+        y: Vec<u64>,
+    }
+    assert!(diff_first(
+        &Inner {
+            x: 1,
+            y: vec![2, 3]
+        },
+        &Renamed { x: 1, y: vec![2, 3] },
+    )
+    .unwrap()
+    .is_none());
+
+    // A length mismatch is reported at the collection itself.
+    let differences = diff(&vec![1u32, 2], &vec![1u32, 2, 3]).unwrap();
+    assert_eq!(differences.len(), 1);
+    assert_eq!(differences[0].path(), "");
+    assert_eq!(
+        differences[0].to_string(),
+        "sequence of 2 elements != sequence of 3 elements",
+    );
+
+    // Map keys become path segments.
+    let mut left = BTreeMap::new();
+    left.insert("k".to_owned(), 1u32);
+    let mut right = BTreeMap::new();
+    right.insert("k".to_owned(), 2u32);
+    let first = diff_first(&left, &right).unwrap().unwrap();
+    assert_eq!(first.path(), "k");
+}
@@ -0,0 +1,193 @@
+use serde::de::value::{Error as ValueError, SeqAccessDeserializer};
+use serde::de::{Deserializer, IntoDeserializer, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use serde::rc::{shared, SharedScope};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+use std::rc::Rc;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Doc {
+    #[serde(with = "serde::rc::shared")]
+    head: Rc<String>,
+    #[serde(with = "serde::rc::shared")]
+    tail: Rc<String>,
+}
+
+// Deserializer for one "Shared" entry driven through its seq representation:
+// the id followed by `None` for a back-reference or `Some(value)` otherwise.
+struct SharedEntry {
+    id: u64,
+    value: Option<&'static str>,
+    state: u8,
+}
+
+fn shared_entry(id: u64, value: Option<&'static str>) -> SeqAccessDeserializer<SharedEntry> {
+    SeqAccessDeserializer::new(SharedEntry {
+        id,
+        value,
+        state: 0,
+    })
+}
+
+impl<'de> SeqAccess<'de> for SharedEntry {
+    type Error = ValueError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        self.state += 1;
+        match self.state {
+            1 => seed.deserialize(self.id.into_deserializer()).map(Some),
+            2 => seed
+                .deserialize(OptionDeserializer(self.value))
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+struct OptionDeserializer(Option<&'static str>);
+
+impl<'de> Deserializer<'de> for OptionDeserializer {
+    type Error = ValueError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Some(s) => visitor.visit_some(s.into_deserializer()),
+            None => visitor.visit_none(),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier ignored_any
+    }
+}
+
+#[test]
+fn test_shared_tokens() {
+    let node = Rc::new(String::from("node"));
+    let doc = Doc {
+        head: Rc::clone(&node),
+        tail: node,
+    };
+
+    let _scope = SharedScope::new();
+    let tokens = &[
+        Token::Struct {
+            name: "Doc",
+            len: 2,
+        },
+        Token::Str("head"),
+        Token::Struct {
+            name: "Shared",
+            len: 2,
+        },
+        Token::Str("id"),
+        Token::U64(0),
+        Token::Str("value"),
+        Token::Some,
+        Token::Str("node"),
+        Token::StructEnd,
+        Token::Str("tail"),
+        Token::Struct {
+            name: "Shared",
+            len: 2,
+        },
+        Token::Str("id"),
+        Token::U64(0),
+        Token::Str("value"),
+        Token::None,
+        Token::StructEnd,
+        Token::StructEnd,
+    ];
+    assert_ser_tokens(&doc, tokens);
+    assert_de_tokens(&doc, tokens);
+}
+
+#[test]
+fn test_no_scope() {
+    let node = Rc::new(String::from("node"));
+    let doc = Doc {
+        head: Rc::clone(&node),
+        tail: node,
+    };
+
+    // Without an active scope every occurrence is written in full.
+    assert_ser_tokens(
+        &doc,
+        &[
+            Token::Struct {
+                name: "Doc",
+                len: 2,
+            },
+            Token::Str("head"),
+            Token::Struct {
+                name: "Shared",
+                len: 2,
+            },
+            Token::Str("id"),
+            Token::U64(0),
+            Token::Str("value"),
+            Token::Some,
+            Token::Str("node"),
+            Token::StructEnd,
+            Token::Str("tail"),
+            Token::Struct {
+                name: "Shared",
+                len: 2,
+            },
+            Token::Str("id"),
+            Token::U64(0),
+            Token::Str("value"),
+            Token::Some,
+            Token::Str("node"),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_identity_preserved() {
+    let _scope = SharedScope::new();
+
+    let first: Rc<String> = shared::deserialize(shared_entry(0, Some("node"))).unwrap();
+    let second: Rc<String> = shared::deserialize(shared_entry(0, None)).unwrap();
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(*second, "node");
+}
+
+#[test]
+fn test_unresolved_back_reference() {
+    let _scope = SharedScope::new();
+
+    let err = shared::deserialize::<Rc<String>, _>(shared_entry(7, None)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unresolved shared pointer back-reference 7"
+    );
+}
+
+#[test]
+fn test_scopes_nest() {
+    let outer_scope = SharedScope::new();
+    let first: Rc<String> = shared::deserialize(shared_entry(0, Some("node"))).unwrap();
+
+    {
+        // The inner scope starts empty, so the back-reference is unresolved.
+        let _inner_scope = SharedScope::new();
+        shared::deserialize::<Rc<String>, _>(shared_entry(0, None)).unwrap_err();
+    }
+
+    // Dropping the inner scope restores the outer registry.
+    let second: Rc<String> = shared::deserialize(shared_entry(0, None)).unwrap();
+    assert!(Rc::ptr_eq(&first, &second));
+    drop(outer_scope);
+}
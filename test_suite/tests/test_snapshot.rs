@@ -0,0 +1,89 @@
+//! Golden-file assertions for serialized token streams.
+//!
+//! serde_test is maintained out of tree, so the snapshot helper lives here
+//! alongside the tests that exercise it. A value's event stream is written to
+//! `tests/snapshots/<name>.tokens` the first time a test runs (or whenever
+//! `UPDATE_SNAPSHOTS` is set in the environment) and compared against that
+//! file on subsequent runs, so large types are protected against accidental
+//! wire-format changes without hand-maintaining huge `Token` arrays.
+
+use serde::ser::Serialize;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+mod trace;
+use crate::trace::trace;
+
+/// Asserts that the value's serialization matches the named snapshot file,
+/// creating or regenerating the file when `UPDATE_SNAPSHOTS` is set or the
+/// file does not exist yet.
+fn assert_snapshot<T>(name: &str, value: &T)
+where
+    T: Serialize,
+{
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.tokens", name));
+    let actual = trace(value).join("\n") + "\n";
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        expected, actual,
+        "serialization of `{}` does not match the snapshot in {}; \
+         rerun with UPDATE_SNAPSHOTS=1 if the change is intended",
+        name,
+        path.display(),
+    );
+}
+
+#[derive(Serialize)]
+struct Server {
+    hostname: String,
+    port: u16,
+    tags: BTreeMap<String, String>,
+    replicas: Vec<Replica>,
+}
+
+#[derive(Serialize)]
+struct Replica {
+    region: String,
+    weight: Option<u32>,
+}
+
+#[test]
+fn test_struct_snapshot() {
+    let mut tags = BTreeMap::new();
+    tags.insert("env".to_owned(), "prod".to_owned());
+    tags.insert("team".to_owned(), "storage".to_owned());
+    let server = Server {
+        hostname: "db-1".to_owned(),
+        port: 5432,
+        tags,
+        replicas: vec![
+            Replica {
+                region: "eu-west".to_owned(),
+                weight: Some(10),
+            },
+            Replica {
+                region: "us-east".to_owned(),
+                weight: None,
+            },
+        ],
+    };
+    assert_snapshot("server", &server);
+}
+
+#[test]
+fn test_seq_snapshot() {
+    assert_snapshot("fibonacci", &[1u32, 1, 2, 3, 5, 8, 13]);
+}
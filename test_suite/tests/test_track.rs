@@ -0,0 +1,106 @@
+use serde::de::track;
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer, StrDeserializer};
+use serde::de::{Deserializer, IntoDeserializer};
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    #[allow(dead_code)]
+    servers: Vec<Server>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Server {
+    #[allow(dead_code)]
+    port: u16,
+}
+
+/// Lets a value deserializer stand in for a nested value inside
+/// `MapDeserializer` and `SeqDeserializer`.
+struct Nested<D>(D);
+
+impl<'de, D> IntoDeserializer<'de, Error> for Nested<D>
+where
+    D: Deserializer<'de, Error = Error>,
+{
+    type Deserializer = D;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self.0
+    }
+}
+
+#[test]
+fn test_nested_field_path() {
+    let de = MapDeserializer::<_, Error>::new(IntoIterator::into_iter([(
+        "servers",
+        Nested(SeqDeserializer::new(IntoIterator::into_iter([
+            Nested(MapDeserializer::new(IntoIterator::into_iter([(
+                "port", 80_u32,
+            )]))),
+            Nested(MapDeserializer::new(IntoIterator::into_iter([(
+                "port", 70_000_u32,
+            )]))),
+        ]))),
+    )]));
+
+    let err = track::deserialize::<_, Config>(de).unwrap_err();
+    assert_eq!(err.path(), "servers[1].port");
+    assert!(err.to_string().starts_with("servers[1].port: "));
+}
+
+#[test]
+fn test_map_key_path() {
+    let de = MapDeserializer::<_, Error>::new(IntoIterator::into_iter([
+        ("one", 1_u32),
+        ("two", 70_000_u32),
+    ]));
+
+    let err = track::deserialize::<_, BTreeMap<String, u16>>(de).unwrap_err();
+    assert_eq!(err.path(), "two");
+}
+
+#[test]
+fn test_sequence_index_path() {
+    let de = SeqDeserializer::<_, Error>::new(IntoIterator::into_iter([0_u32, 1, 70_000]));
+
+    let err = track::deserialize::<_, Vec<u16>>(de).unwrap_err();
+    assert_eq!(err.path(), "[2]");
+}
+
+#[test]
+fn test_missing_field_path() {
+    let de = MapDeserializer::<_, Error>::new(IntoIterator::into_iter([(
+        "servers",
+        Nested(SeqDeserializer::new(IntoIterator::into_iter([Nested(
+            MapDeserializer::new(IntoIterator::into_iter(Vec::<(&str, u32)>::new())),
+        )]))),
+    )]));
+
+    let err = track::deserialize::<_, Config>(de).unwrap_err();
+    assert_eq!(err.path(), "servers[0]");
+    assert_eq!(err.inner().to_string(), "missing field `port`");
+}
+
+#[test]
+fn test_top_level_error() {
+    let de = StrDeserializer::<Error>::new("not a number");
+
+    let err = track::deserialize::<_, u32>(de).unwrap_err();
+    assert_eq!(err.path(), "");
+    assert_eq!(err.to_string(), err.into_inner().to_string());
+}
+
+#[test]
+fn test_success_is_transparent() {
+    let de = MapDeserializer::<_, Error>::new(IntoIterator::into_iter([(
+        "servers",
+        Nested(SeqDeserializer::new(IntoIterator::into_iter([Nested(
+            MapDeserializer::new(IntoIterator::into_iter([("port", 80_u32)])),
+        )]))),
+    )]));
+
+    let config = track::deserialize::<_, Config>(de).unwrap();
+    assert_eq!(config.servers[0].port, 80);
+}
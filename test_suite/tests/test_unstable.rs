@@ -1,4 +1,5 @@
 #![deny(warnings)]
+#![cfg_attr(feature = "unstable", feature(f16))]
 #![allow(clippy::derive_partial_eq_without_eq)]
 
 // This test target is convoluted with the actual #[test] in a separate file to
@@ -1,9 +1,9 @@
 #![allow(clippy::derive_partial_eq_without_eq, clippy::similar_names)]
 
-use serde::de::value::{self, MapAccessDeserializer};
+use serde::de::value::{self, BufferedValue, MapAccessDeserializer};
 use serde::de::{Deserialize, Deserializer, IntoDeserializer, MapAccess, Visitor};
-use serde_derive::Deserialize;
-use serde_test::{assert_de_tokens, Token};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
 use std::fmt;
 
 #[test]
@@ -93,3 +93,316 @@ fn test_map_access_to_enum() {
         ],
     );
 }
+
+#[test]
+fn test_to_deserializer_struct() {
+    #[derive(Serialize)]
+    struct Config {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Endpoint {
+        host: String,
+        port: u16,
+    }
+
+    let config = Config {
+        host: "localhost".to_owned(),
+        port: 8080,
+    };
+    let endpoint = Endpoint::deserialize(value::to_deserializer(&config).unwrap()).unwrap();
+    assert_eq!(
+        endpoint,
+        Endpoint {
+            host: "localhost".to_owned(),
+            port: 8080,
+        }
+    );
+}
+
+#[test]
+fn test_to_deserializer_enum() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum E {
+        Unit,
+        Newtype(u32),
+        Tuple(u8, u8),
+        Struct { a: u32 },
+    }
+
+    for value in [E::Unit, E::Newtype(1), E::Tuple(1, 2), E::Struct { a: 3 }] {
+        let roundtripped = E::deserialize(value::to_deserializer(&value).unwrap()).unwrap();
+        assert_eq!(value, roundtripped);
+    }
+}
+
+#[test]
+fn test_to_deserializer_collections() {
+    let map = {
+        let mut map = std::collections::BTreeMap::new();
+        map.insert("a".to_owned(), vec![1u32, 2, 3]);
+        map
+    };
+    let roundtripped: std::collections::BTreeMap<String, Vec<u32>> =
+        Deserialize::deserialize(value::to_deserializer(&map).unwrap()).unwrap();
+    assert_eq!(map, roundtripped);
+
+    let option = Some('x');
+    assert_eq!(
+        option,
+        Deserialize::deserialize(value::to_deserializer(&option).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn test_buffered_value_replay() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Endpoint {
+        host: String,
+        port: u16,
+    }
+
+    let de = value::MapDeserializer::<_, value::Error>::new(
+        vec![("host", "localhost"), ("port", "8080")].into_iter(),
+    );
+    let buffered = BufferedValue::deserialize(de).unwrap();
+
+    // The same captured input can be replayed any number of times.
+    let map: std::collections::BTreeMap<String, String> = Deserialize::deserialize(
+        IntoDeserializer::<value::Error>::into_deserializer(buffered.clone()),
+    )
+    .unwrap();
+    assert_eq!(map["host"], "localhost");
+
+    let err =
+        Endpoint::deserialize(IntoDeserializer::<value::Error>::into_deserializer(buffered))
+            .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"8080\", expected u16",
+    );
+}
+
+#[test]
+fn test_buffered_value_as_str() {
+    let buffered =
+        BufferedValue::deserialize(IntoDeserializer::<value::Error>::into_deserializer("x"))
+            .unwrap();
+    assert_eq!(buffered.as_str(), Some("x"));
+
+    let buffered =
+        BufferedValue::deserialize(IntoDeserializer::<value::Error>::into_deserializer(0u8))
+            .unwrap();
+    assert_eq!(buffered.as_str(), None);
+}
+
+#[test]
+fn test_buffered_value_from_serialize() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Endpoint {
+        host: String,
+        port: u16,
+        labels: Vec<String>,
+    }
+
+    let endpoint = Endpoint {
+        host: "localhost".to_owned(),
+        port: 8080,
+        labels: vec!["a".to_owned()],
+    };
+
+    // Capture the Serialize output and replay it without going through a
+    // concrete data format.
+    let buffered = BufferedValue::from_serialize(&endpoint).unwrap();
+    let roundtripped = Endpoint::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+        buffered.clone(),
+    ))
+    .unwrap();
+    assert_eq!(endpoint, roundtripped);
+
+    // The captured form is also inspectable and reserializable.
+    let map: std::collections::BTreeMap<String, serde::de::value::BufferedValue> =
+        Deserialize::deserialize(IntoDeserializer::<value::Error>::into_deserializer(buffered))
+            .unwrap();
+    assert_eq!(map["host"].as_str(), Some("localhost"));
+}
+
+#[test]
+fn test_buffered_value_from_serialize_enum() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Point,
+        Circle(u32),
+        Rect { w: u32, h: u32 },
+    }
+
+    for shape in [Shape::Point, Shape::Circle(3), Shape::Rect { w: 4, h: 5 }] {
+        let buffered = BufferedValue::from_serialize(&shape).unwrap();
+        let roundtripped =
+            Shape::deserialize(IntoDeserializer::<value::Error>::into_deserializer(buffered))
+                .unwrap();
+        assert_eq!(shape, roundtripped);
+    }
+}
+
+#[test]
+fn test_buffered_value_reserialize() {
+    let de = value::MapDeserializer::<_, value::Error>::new(
+        vec![("a", vec![1u32, 2]), ("b", vec![3])].into_iter(),
+    );
+    let buffered = BufferedValue::deserialize(de).unwrap();
+
+    assert_ser_tokens(
+        &buffered,
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("a"),
+            Token::Seq { len: Some(2) },
+            Token::U32(1),
+            Token::U32(2),
+            Token::SeqEnd,
+            Token::Str("b"),
+            Token::Seq { len: Some(1) },
+            Token::U32(3),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_struct_deserializer() {
+    use serde::de::value::StructDeserializer;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Config {
+        name: String,
+        retries: u32,
+        verbose: bool,
+    }
+
+    let fields = vec![
+        ("name", BufferedValue::from_serialize("alpha").unwrap()),
+        ("retries", BufferedValue::from_serialize(&3u32).unwrap()),
+        ("verbose", BufferedValue::from_serialize(&true).unwrap()),
+    ];
+    let config =
+        Config::deserialize(StructDeserializer::<_, value::Error>::new(fields.into_iter()))
+            .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "alpha".to_owned(),
+            retries: 3,
+            verbose: true,
+        },
+    );
+
+    // Tuple structs read the field values in order.
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Pair(u32, u32);
+
+    let fields = vec![("a", 1u32), ("b", 2u32)];
+    let pair = Pair::deserialize(StructDeserializer::<_, value::Error>::new(fields.into_iter()))
+        .unwrap();
+    assert_eq!(pair, Pair(1, 2));
+}
+
+#[test]
+fn test_enum_deserializer() {
+    use serde::de::value::EnumDeserializer;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Shape {
+        Point,
+        Circle(u32),
+        Rect { w: u32, h: u32 },
+        Line(u32, u32),
+    }
+
+    let point =
+        Shape::deserialize(EnumDeserializer::<u32, value::Error>::new("Point", None)).unwrap();
+    assert_eq!(point, Shape::Point);
+
+    let circle =
+        Shape::deserialize(EnumDeserializer::<_, value::Error>::new("Circle", Some(7u32)))
+            .unwrap();
+    assert_eq!(circle, Shape::Circle(7));
+
+    let line = Shape::deserialize(EnumDeserializer::<_, value::Error>::new(
+        "Line",
+        Some(vec![1u32, 2]),
+    ))
+    .unwrap();
+    assert_eq!(line, Shape::Line(1, 2));
+
+    let fields = BufferedValue::from_serialize(&{
+        let mut map = std::collections::BTreeMap::new();
+        map.insert("w", 3u32);
+        map.insert("h", 4);
+        map
+    })
+    .unwrap();
+    let rect = Shape::deserialize(EnumDeserializer::<_, value::Error>::new("Rect", Some(fields)))
+        .unwrap();
+    assert_eq!(rect, Shape::Rect { w: 3, h: 4 });
+
+    // A payload handed to a unit variant is a type error, not a panic.
+    let err = Shape::deserialize(EnumDeserializer::<_, value::Error>::new("Point", Some(1u32)))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: newtype variant, expected unit variant",
+    );
+}
+
+#[test]
+fn test_option_into_deserializer() {
+    let some = Option::<u32>::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+        Some(5u32),
+    ))
+    .unwrap();
+    assert_eq!(some, Some(5));
+
+    let none = Option::<u32>::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+        None::<u32>,
+    ))
+    .unwrap();
+    assert_eq!(none, None);
+
+    // The inner value keeps its own IntoDeserializer behavior.
+    let nested = Option::<Vec<u32>>::deserialize(
+        IntoDeserializer::<value::Error>::into_deserializer(Some(vec![1u32, 2])),
+    )
+    .unwrap();
+    assert_eq!(nested, Some(vec![1, 2]));
+}
+
+#[test]
+fn test_tuple_into_deserializer() {
+    // Elements of distinct types each use their own IntoDeserializer impl.
+    let value = <(u32, String, bool)>::deserialize(
+        IntoDeserializer::<value::Error>::into_deserializer((1u32, "two", true)),
+    )
+    .unwrap();
+    assert_eq!(value, (1, String::from("two"), true));
+
+    // Tuple structs read the elements in order.
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Pair(u32, u32);
+
+    let pair = Pair::deserialize(IntoDeserializer::<value::Error>::into_deserializer((
+        1u32, 2u32,
+    )))
+    .unwrap();
+    assert_eq!(pair, Pair(1, 2));
+
+    // Consuming fewer elements than the tuple holds is an error.
+    let err = <(u32,)>::deserialize(IntoDeserializer::<value::Error>::into_deserializer((
+        1u32, 2u32,
+    )))
+    .unwrap_err();
+    assert_eq!(err.to_string(), "invalid length 1, expected 2 elements in sequence");
+}
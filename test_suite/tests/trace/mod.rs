@@ -0,0 +1,289 @@
+//! A serializer that records the serialization of a value as a flat list of
+//! textual events, for tests that compare or store token streams.
+
+#![allow(dead_code)]
+
+use serde::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+use std::fmt::Write;
+
+/// Serializes the value into a flat list of events.
+pub fn trace<T>(value: &T) -> Vec<String>
+where
+    T: Serialize,
+{
+    let mut events = Vec::new();
+    value.serialize(Tracer(&mut events)).unwrap();
+    events
+}
+
+pub struct Tracer<'a>(&'a mut Vec<String>);
+
+impl<'a> Tracer<'a> {
+    fn event(self, event: String) -> Result<(), serde::de::value::Error> {
+        self.0.push(event);
+        Ok(())
+    }
+}
+
+impl<'a> Serializer for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        self.event(format!("bool({})", v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
+        self.event(format!("i64({})", v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
+        self.event(format!("u64({})", v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
+        self.event(format!("f64({})", v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        self.event(format!("str({})", v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        let mut event = String::from("bytes(");
+        for byte in v {
+            write!(event, "{:02x}", byte).unwrap();
+        }
+        event.push(')');
+        self.event(event)
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.event(String::from("none"))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.0.push(String::from("some"));
+        value.serialize(Tracer(self.0))
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        self.event(String::from("unit"))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        self.event(format!("unit_variant({}::{})", name, variant))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.0.push(format!("newtype_variant({}::{})", name, variant));
+        value.serialize(Tracer(self.0))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Self::Error> {
+        self.0.push(String::from("seq"));
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, Self::Error> {
+        self.0.push(format!("tuple({})", len));
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        self.0.push(format!("tuple_struct({})", name));
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        self.0.push(format!("tuple_variant({}::{})", name, variant));
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, Self::Error> {
+        self.0.push(String::from("map"));
+        Ok(self)
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        self.0.push(format!("struct({})", name));
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        self.0.push(format!("struct_variant({}::{})", name, variant));
+        Ok(self)
+    }
+}
+
+impl<'a> SerializeSeq for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
+
+impl<'a> SerializeMap for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(Tracer(self.0))
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStruct for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.0.push(format!("field({})", key));
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTuple for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTupleStruct for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTupleVariant for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStructVariant for Tracer<'a> {
+    type Ok = ();
+    type Error = serde::de::value::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.0.push(format!("field({})", key));
+        value.serialize(Tracer(self.0))
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.0.push(String::from("end"));
+        Ok(())
+    }
+}
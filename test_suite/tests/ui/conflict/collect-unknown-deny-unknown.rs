@@ -0,0 +1,11 @@
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct S {
+    #[serde(collect_unknown)]
+    extra: BTreeMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+struct S {
+    #[serde(collect_unknown)]
+    extra: BTreeMap<String, String>,
+    #[serde(flatten)]
+    nested: BTreeMap<String, String>,
+}
+
+fn main() {}
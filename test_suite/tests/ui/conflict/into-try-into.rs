@@ -1,9 +1,9 @@
 use serde_derive::Serialize;
 
 #[derive(Serialize)]
-#[serde(tag = "type")]
-enum E {
-    Tuple(u8, u8),
+#[serde(into = "u64", try_into = "u64")]
+struct S {
+    a: u8,
 }
 
 fn main() {}
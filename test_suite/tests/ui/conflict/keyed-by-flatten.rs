@@ -0,0 +1,14 @@
+use serde_derive::Deserialize;
+
+#[derive(Deserialize)]
+struct Item {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct S {
+    #[serde(keyed_by = "id", flatten)]
+    items: Vec<Item>,
+}
+
+fn main() {}
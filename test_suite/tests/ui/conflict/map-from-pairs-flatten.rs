@@ -0,0 +1,10 @@
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+struct S {
+    #[serde(map_from_pairs, flatten)]
+    headers: BTreeMap<String, u32>,
+}
+
+fn main() {}
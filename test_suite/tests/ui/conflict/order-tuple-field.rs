@@ -0,0 +1,6 @@
+use serde_derive::Deserialize;
+
+#[derive(Deserialize)]
+struct S(#[serde(order = 0)] u8, u8);
+
+fn main() {}
@@ -23,3 +23,11 @@ fn test_raw_identifiers() {
         ],
     );
 }
+
+#[test]
+fn test_f16() {
+    // serde_test has no native f16 token, so this exercises the default
+    // widening behavior: serialize_f16 forwards to serialize_f32 and
+    // deserialize_f16 forwards to deserialize_f32.
+    assert_tokens(&1.5f16, &[Token::F32(1.5)]);
+}